@@ -111,25 +111,8 @@ impl ArtifactManager {
         data: &[u8],
         description: &str,
     ) -> Result<ValidationArtifact> {
-        // Create directory: .hox/artifacts/{change-id}/
-        let change_dir = self.base_dir.join(change_id);
-        fs::create_dir_all(&change_dir).await.map_err(|e| {
-            HoxError::Io(format!(
-                "Failed to create artifact directory {}: {}",
-                change_dir.display(),
-                e
-            ))
-        })?;
-
-        // Generate filename: {timestamp}-{type}.{ext}
-        let timestamp = Utc::now().format("%Y%m%d-%H%M%S");
-        let filename = format!(
-            "{}-{}.{}",
-            timestamp,
-            artifact_type,
-            artifact_type.extension()
-        );
-        let file_path = change_dir.join(&filename);
+        let relative_path = self.new_artifact_path(change_id, &artifact_type).await?;
+        let file_path = self.base_dir.join(&relative_path);
 
         // Write data to file
         fs::write(&file_path, data).await.map_err(|e| {
@@ -143,7 +126,6 @@ impl ArtifactManager {
         let size_bytes = data.len() as u64;
 
         // Return metadata
-        let relative_path = PathBuf::from(change_id).join(&filename);
         Ok(ValidationArtifact {
             artifact_type: artifact_type.clone(),
             path: relative_path,
@@ -154,6 +136,36 @@ impl ArtifactManager {
         })
     }
 
+    /// Allocate a path for a new artifact, for writers that stream to disk
+    ///
+    /// Creates `.hox/artifacts/{change-id}/` and returns the new file's path
+    /// relative to [`base_dir`](Self::base_dir), named `{timestamp}-{type}.{ext}`.
+    pub async fn new_artifact_path(
+        &self,
+        change_id: &str,
+        artifact_type: &ArtifactType,
+    ) -> Result<PathBuf> {
+        // Create directory: .hox/artifacts/{change-id}/
+        let change_dir = self.base_dir.join(change_id);
+        fs::create_dir_all(&change_dir).await.map_err(|e| {
+            HoxError::Io(format!(
+                "Failed to create artifact directory {}: {}",
+                change_dir.display(),
+                e
+            ))
+        })?;
+
+        // Generate filename: {timestamp}-{type}.{ext}
+        let timestamp = Utc::now().format("%Y%m%d-%H%M%S");
+        let filename = format!(
+            "{}-{}.{}",
+            timestamp,
+            artifact_type,
+            artifact_type.extension()
+        );
+        Ok(PathBuf::from(change_id).join(filename))
+    }
+
     /// List all artifacts for a change
     pub async fn list_artifacts(&self, change_id: &str) -> Result<Vec<ValidationArtifact>> {
         let change_dir = self.base_dir.join(change_id);
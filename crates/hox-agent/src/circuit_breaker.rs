@@ -23,7 +23,8 @@ pub enum CircuitState {
 ///
 /// - **Closed**: Normal operation, all requests allowed
 /// - **Open**: Too many failures, reject requests immediately
-/// - **HalfOpen**: After timeout, allow one request to test recovery
+/// - **HalfOpen**: After the cooldown, allow one probe request at a time; close
+///   after `success_threshold` consecutive successes, reopen on any failure
 ///
 /// # Example
 ///
@@ -43,8 +44,49 @@ pub enum CircuitState {
 pub struct CircuitBreaker {
     failure_count: AtomicU32,
     last_failure: AtomicU64, // Unix timestamp millis
-    threshold: u32,
-    timeout: Duration,
+    /// Consecutive successful probes while half-open
+    probe_successes: AtomicU32,
+    /// Start of the in-flight half-open probe (Unix millis, 0 = none)
+    probe_started: AtomicU64,
+    config: CircuitBreakerConfig,
+}
+
+/// Thresholds controlling when a [`CircuitBreaker`] opens and closes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures before the circuit opens
+    pub failure_threshold: usize,
+    /// Time the circuit stays open before allowing a probe
+    pub cooldown: Duration,
+    /// Consecutive successful probes needed to close the circuit again
+    pub success_threshold: usize,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        // Conservative defaults: 3 failures, 60 second cooldown, 1 probe
+        Self {
+            failure_threshold: 3,
+            cooldown: Duration::from_secs(60),
+            success_threshold: 1,
+        }
+    }
+}
+
+/// Snapshot of a circuit breaker for display
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerStatus {
+    pub state: CircuitState,
+    /// Time until a probe is allowed (zero unless open)
+    pub remaining_cooldown: Duration,
+    pub failure_count: u32,
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
 }
 
 impl CircuitBreaker {
@@ -55,63 +97,96 @@ impl CircuitBreaker {
     /// * `threshold` - Number of consecutive failures before opening circuit
     /// * `timeout_secs` - Seconds to wait before attempting recovery (half-open state)
     pub fn new(threshold: u32, timeout_secs: u64) -> Self {
+        Self::with_config(CircuitBreakerConfig {
+            failure_threshold: threshold as usize,
+            cooldown: Duration::from_secs(timeout_secs),
+            ..Default::default()
+        })
+    }
+
+    /// Create a circuit breaker with explicit thresholds
+    pub fn with_config(config: CircuitBreakerConfig) -> Self {
         Self {
             failure_count: AtomicU32::new(0),
             last_failure: AtomicU64::new(0),
-            threshold,
-            timeout: Duration::from_secs(timeout_secs),
+            probe_successes: AtomicU32::new(0),
+            probe_started: AtomicU64::new(0),
+            config,
         }
     }
 
+    /// Get the configured thresholds
+    pub fn config(&self) -> &CircuitBreakerConfig {
+        &self.config
+    }
+
     /// Get current circuit state
     pub fn state(&self) -> CircuitState {
         let failures = self.failure_count.load(Ordering::Relaxed);
 
-        if failures < self.threshold {
+        if (failures as usize) < self.config.failure_threshold {
             return CircuitState::Closed;
         }
 
-        // Circuit is open - check if timeout has elapsed
+        // Circuit is open - check if cooldown has elapsed
         let last_failure = self.last_failure.load(Ordering::Relaxed);
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as u64;
-
-        let elapsed = now.saturating_sub(last_failure);
+        let elapsed = now_millis().saturating_sub(last_failure);
 
-        if elapsed >= self.timeout.as_millis() as u64 {
+        if elapsed >= self.config.cooldown.as_millis() as u64 {
             CircuitState::HalfOpen
         } else {
             CircuitState::Open
         }
     }
 
-    /// Record a successful operation (resets failure count)
+    /// Record a successful operation
+    ///
+    /// Resets the failure count when closed. While half-open, the circuit
+    /// closes after `success_threshold` consecutive successful probes.
     pub fn record_success(&self) {
+        if self.state() == CircuitState::HalfOpen {
+            self.probe_started.store(0, Ordering::Relaxed);
+            let successes = self.probe_successes.fetch_add(1, Ordering::Relaxed) + 1;
+            if (successes as usize) < self.config.success_threshold {
+                return;
+            }
+        }
+
         self.failure_count.store(0, Ordering::Relaxed);
+        self.probe_successes.store(0, Ordering::Relaxed);
     }
 
     /// Record a failed operation
+    ///
+    /// A failed half-open probe reopens the circuit for another cooldown.
     pub fn record_failure(&self) {
         self.failure_count.fetch_add(1, Ordering::Relaxed);
-
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as u64;
-
-        self.last_failure.store(now, Ordering::Relaxed);
+        self.last_failure.store(now_millis(), Ordering::Relaxed);
+        self.probe_successes.store(0, Ordering::Relaxed);
+        self.probe_started.store(0, Ordering::Relaxed);
     }
 
     /// Check if a request can be executed
     ///
-    /// Returns `true` if circuit is closed or half-open (testing recovery)
+    /// Returns `true` if circuit is closed, or if it is half-open and no other
+    /// probe is in flight. A probe that never reports back is abandoned after
+    /// one cooldown so the circuit cannot get stuck.
     /// Returns `false` if circuit is open (too many failures)
     pub fn can_execute(&self) -> bool {
         match self.state() {
             CircuitState::Closed => true,
-            CircuitState::HalfOpen => true,
+            CircuitState::HalfOpen => {
+                let now = now_millis();
+                let started = self.probe_started.load(Ordering::Relaxed);
+                let abandoned = started != 0
+                    && now.saturating_sub(started) >= self.config.cooldown.as_millis() as u64;
+
+                (started == 0 || abandoned)
+                    && self
+                        .probe_started
+                        .compare_exchange(started, now, Ordering::Relaxed, Ordering::Relaxed)
+                        .is_ok()
+            }
             CircuitState::Open => false,
         }
     }
@@ -126,24 +201,27 @@ impl CircuitBreaker {
         match self.state() {
             CircuitState::Open => {
                 let last_failure = self.last_failure.load(Ordering::Relaxed);
-                let now = SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap()
-                    .as_millis() as u64;
-
-                let elapsed = now.saturating_sub(last_failure);
-                (self.timeout.as_millis() as u64).saturating_sub(elapsed)
+                let elapsed = now_millis().saturating_sub(last_failure);
+                (self.config.cooldown.as_millis() as u64).saturating_sub(elapsed)
             }
             // Closed and HalfOpen states can execute immediately - no retry delay
             _ => 0,
         }
     }
+
+    /// Current state and remaining cooldown, for dashboards
+    pub fn status(&self) -> CircuitBreakerStatus {
+        CircuitBreakerStatus {
+            state: self.state(),
+            remaining_cooldown: Duration::from_millis(self.time_until_retry()),
+            failure_count: self.failure_count(),
+        }
+    }
 }
 
 impl Default for CircuitBreaker {
     fn default() -> Self {
-        // Conservative defaults: 3 failures, 60 second timeout
-        Self::new(3, 60)
+        Self::with_config(CircuitBreakerConfig::default())
     }
 }
 
@@ -225,4 +303,75 @@ mod tests {
         assert!(time_remaining > 0);
         assert!(time_remaining <= 2000);
     }
+
+    fn probing_config(success_threshold: usize) -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            failure_threshold: 2,
+            cooldown: Duration::from_millis(200),
+            success_threshold,
+        }
+    }
+
+    #[test]
+    fn test_half_open_allows_single_probe() {
+        let cb = CircuitBreaker::with_config(probing_config(1));
+        cb.record_failure();
+        cb.record_failure();
+
+        sleep(Duration::from_millis(250));
+        assert!(cb.can_execute());
+        // Second request while the probe is in flight is rejected
+        assert!(!cb.can_execute());
+        assert_eq!(cb.state(), CircuitState::HalfOpen);
+    }
+
+    #[test]
+    fn test_half_open_needs_success_threshold() {
+        let cb = CircuitBreaker::with_config(probing_config(2));
+        cb.record_failure();
+        cb.record_failure();
+        sleep(Duration::from_millis(250));
+
+        assert!(cb.can_execute());
+        cb.record_success();
+        assert_eq!(cb.state(), CircuitState::HalfOpen);
+
+        assert!(cb.can_execute());
+        cb.record_success();
+        assert_eq!(cb.state(), CircuitState::Closed);
+        assert_eq!(cb.failure_count(), 0);
+    }
+
+    #[test]
+    fn test_failed_probe_reopens() {
+        let cb = CircuitBreaker::with_config(probing_config(2));
+        cb.record_failure();
+        cb.record_failure();
+        sleep(Duration::from_millis(250));
+
+        assert!(cb.can_execute());
+        cb.record_success();
+        assert!(cb.can_execute());
+        cb.record_failure();
+
+        assert_eq!(cb.state(), CircuitState::Open);
+        assert!(!cb.can_execute());
+    }
+
+    #[test]
+    fn test_status() {
+        let cb = CircuitBreaker::with_config(CircuitBreakerConfig {
+            failure_threshold: 1,
+            cooldown: Duration::from_secs(30),
+            success_threshold: 1,
+        });
+        assert_eq!(cb.status().state, CircuitState::Closed);
+        assert_eq!(cb.status().remaining_cooldown, Duration::ZERO);
+
+        cb.record_failure();
+        let status = cb.status();
+        assert_eq!(status.state, CircuitState::Open);
+        assert!(status.remaining_cooldown > Duration::from_secs(29));
+        assert_eq!(status.failure_count, 1);
+    }
 }
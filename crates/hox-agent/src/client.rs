@@ -5,8 +5,9 @@
 //! JJ metadata and backpressure signals.
 
 use crate::auth;
-use crate::circuit_breaker::CircuitBreaker;
-use crate::types::{AgentResponse, AgentResult, AnthropicMessage, AnthropicRequest, AnthropicResponse, Model, ToolCall};
+use crate::circuit_breaker::{CircuitBreaker, CircuitBreakerStatus};
+use crate::pricing::{self, PricingTable};
+use crate::types::{AgentResponse, AgentResult, AnthropicMessage, AnthropicRequest, AnthropicResponse, Model, ToolCall, Usage};
 use chrono::Utc;
 use hox_core::{HoxError, Result};
 use serde_json::json;
@@ -29,11 +30,17 @@ fn get_circuit_breaker() -> &'static CircuitBreaker {
     CIRCUIT_BREAKER.get_or_init(CircuitBreaker::default)
 }
 
+/// Status of the circuit breaker shared by all agent spawns
+pub fn circuit_breaker_status() -> CircuitBreakerStatus {
+    get_circuit_breaker().status()
+}
+
 /// Agent client for Anthropic API interactions
 #[derive(Debug, Clone)]
 pub struct AgentClient {
     model: Model,
     max_tokens: usize,
+    pricing: Option<PricingTable>,
 }
 
 impl AgentClient {
@@ -42,6 +49,7 @@ impl AgentClient {
         Self {
             model,
             max_tokens: DEFAULT_MAX_TOKENS,
+            pricing: None,
         }
     }
 
@@ -51,6 +59,17 @@ impl AgentClient {
         self
     }
 
+    /// Estimate costs with a custom pricing table instead of list prices
+    pub fn with_pricing(mut self, pricing: PricingTable) -> Self {
+        self.pricing = Some(pricing);
+        self
+    }
+
+    /// Estimated cost in USD of token usage for this client's model
+    pub fn estimate_cost_usd(&self, usage: &Usage) -> f64 {
+        pricing::estimate_cost_usd(self.pricing.as_ref(), self.model, usage)
+    }
+
     /// Spawn a fresh agent with the given prompt
     pub async fn spawn(&self, prompt: &str, iteration: usize) -> Result<AgentResult> {
         spawn_agent(prompt, iteration, self.model, self.max_tokens).await
@@ -406,6 +425,7 @@ pub async fn spawn_agent(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::pricing::ModelPricing;
 
     #[tokio::test]
     async fn test_spawn_agent_no_auth() {
@@ -422,4 +442,23 @@ mod tests {
         assert_eq!(client.model, Model::Opus);
         assert_eq!(client.max_tokens, 8000);
     }
+
+    #[test]
+    fn test_agent_client_pricing_override() {
+        let usage = Usage {
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+        };
+        let client = AgentClient::new(Model::Sonnet);
+        assert!((client.estimate_cost_usd(&usage) - 4.5).abs() < 1e-9);
+
+        let client = client.with_pricing(PricingTable::default().with_rate(
+            Model::Sonnet,
+            ModelPricing {
+                input_per_mtok: 1.0,
+                output_per_mtok: 5.0,
+            },
+        ));
+        assert!((client.estimate_cost_usd(&usage) - 1.5).abs() < 1e-9);
+    }
 }
@@ -544,7 +544,6 @@ fn validate_command(command: &str) -> Result<()> {
         .split(&['|', '&', ';'][..])
         .next()
         .unwrap_or("")
-        .trim()
         .split_whitespace()
         .next()
         .unwrap_or("");
@@ -910,6 +909,7 @@ Some text after
     }
 
     #[tokio::test]
+    #[allow(clippy::await_holding_lock)]
     async fn test_execute_tools_write_file() {
         use crate::types::ToolCall;
 
@@ -21,6 +21,7 @@ mod auth;
 mod circuit_breaker;
 mod client;
 mod file_executor;
+mod pricing;
 mod promise;
 mod types;
 
@@ -29,12 +30,15 @@ pub use artifact_manager::{
     ValidationArtifact,
 };
 pub use auth::get_auth_token;
-pub use circuit_breaker::{CircuitBreaker, CircuitState};
-pub use client::{spawn_agent, AgentClient};
+pub use circuit_breaker::{
+    CircuitBreaker, CircuitBreakerConfig, CircuitBreakerStatus, CircuitState,
+};
+pub use client::{circuit_breaker_status, spawn_agent, AgentClient};
 pub use file_executor::{
     execute_file_operations, execute_file_operations_with_config, execute_tools,
     file_operation_instructions, validate_path, validate_path_with_config, ExecutionResult,
     FileOperation,
 };
+pub use pricing::{ModelPricing, PricingTable};
 pub use promise::CompletionPromise;
 pub use types::*;
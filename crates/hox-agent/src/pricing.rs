@@ -0,0 +1,151 @@
+//! Token pricing for cost estimation
+//!
+//! Defaults to Anthropic list prices. Negotiated rates can be supplied as a
+//! JSON file mapping model names to per-million-token rates:
+//!
+//! ```json
+//! { "sonnet": { "input_per_mtok": 2.5, "output_per_mtok": 12.0 } }
+//! ```
+
+use hox_core::{HoxError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::types::{Model, Usage};
+
+/// USD rates per million tokens for one model
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPricing {
+    pub input_per_mtok: f64,
+    pub output_per_mtok: f64,
+}
+
+impl ModelPricing {
+    /// Estimated cost in USD of the given token usage
+    pub fn cost_usd(&self, usage: &Usage) -> f64 {
+        (usage.input_tokens as f64 * self.input_per_mtok
+            + usage.output_tokens as f64 * self.output_per_mtok)
+            / 1_000_000.0
+    }
+}
+
+/// Per-model token rates used to estimate costs
+///
+/// Models missing from the table fall back to [`Model::default_pricing`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PricingTable {
+    rates: HashMap<Model, ModelPricing>,
+}
+
+impl PricingTable {
+    /// Table with list prices for every model
+    pub fn defaults() -> Self {
+        let rates = [Model::Opus, Model::Sonnet, Model::Haiku]
+            .into_iter()
+            .map(|model| (model, model.default_pricing()))
+            .collect();
+        Self { rates }
+    }
+
+    /// Override the rates for one model
+    pub fn with_rate(mut self, model: Model, pricing: ModelPricing) -> Self {
+        self.rates.insert(model, pricing);
+        self
+    }
+
+    /// Load a table from a JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            HoxError::Io(format!(
+                "Failed to read pricing file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        serde_json::from_str(&content).map_err(|e| {
+            HoxError::Other(format!(
+                "Failed to parse pricing file {}: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+
+    /// Rates for a model
+    pub fn rate(&self, model: Model) -> ModelPricing {
+        self.rates
+            .get(&model)
+            .copied()
+            .unwrap_or_else(|| model.default_pricing())
+    }
+
+    /// Estimated cost in USD of a model's token usage
+    pub fn cost_usd(&self, model: Model, usage: &Usage) -> f64 {
+        self.rate(model).cost_usd(usage)
+    }
+}
+
+/// Estimated cost in USD under an optional pricing table, defaulting to list prices
+pub(crate) fn estimate_cost_usd(
+    pricing: Option<&PricingTable>,
+    model: Model,
+    usage: &Usage,
+) -> f64 {
+    match pricing {
+        Some(table) => table.cost_usd(model, usage),
+        None => model.default_pricing().cost_usd(usage),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage() -> Usage {
+        Usage {
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+        }
+    }
+
+    #[test]
+    fn test_default_pricing() {
+        let table = PricingTable::defaults();
+
+        assert!((table.cost_usd(Model::Sonnet, &usage()) - 4.5).abs() < 1e-9);
+        assert!((table.cost_usd(Model::Opus, &usage()) - 22.5).abs() < 1e-9);
+        assert!((table.cost_usd(Model::Haiku, &usage()) - 1.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_load_partial_table() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("pricing.json");
+        std::fs::write(
+            &path,
+            r#"{ "sonnet": { "input_per_mtok": 2.0, "output_per_mtok": 10.0 } }"#,
+        )
+        .unwrap();
+
+        let table = PricingTable::load(&path).unwrap();
+
+        assert!((table.cost_usd(Model::Sonnet, &usage()) - 3.0).abs() < 1e-9);
+        // Missing models fall back to list prices
+        assert_eq!(table.rate(Model::Opus), Model::Opus.default_pricing());
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_model() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("pricing.json");
+        std::fs::write(
+            &path,
+            r#"{ "gpt": { "input_per_mtok": 1.0, "output_per_mtok": 1.0 } }"#,
+        )
+        .unwrap();
+
+        assert!(PricingTable::load(&path).is_err());
+    }
+}
@@ -3,8 +3,10 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::pricing::{self, ModelPricing, PricingTable};
+
 /// Claude model variants
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Model {
     Opus,
@@ -22,6 +24,19 @@ impl Model {
             Model::Haiku => "claude-haiku-3-5-20250929",
         }
     }
+
+    /// Anthropic list price per million tokens
+    pub fn default_pricing(&self) -> ModelPricing {
+        let (input_per_mtok, output_per_mtok) = match self {
+            Model::Opus => (15.0, 75.0),
+            Model::Sonnet => (3.0, 15.0),
+            Model::Haiku => (0.8, 4.0),
+        };
+        ModelPricing {
+            input_per_mtok,
+            output_per_mtok,
+        }
+    }
 }
 
 impl std::fmt::Display for Model {
@@ -172,8 +187,21 @@ pub struct LoopConfig {
     pub backpressure_enabled: bool,
     /// Maximum tokens for agent responses
     pub max_tokens: usize,
-    /// Budget cap per agent invocation in USD. None = no limit.
+    /// Budget cap for the whole loop in USD, estimated from token usage.
+    /// None = no limit.
     pub max_budget_usd: Option<f64>,
+    /// Times an iteration that failed for infrastructure reasons (API errors,
+    /// circuit breaker trips, timeouts) is retried without consuming the
+    /// iteration budget. 0 = fail the loop on the first transient error.
+    #[serde(default = "default_transient_retry")]
+    pub transient_retry: usize,
+    /// Token rates for cost estimates and budget checks. None = list prices.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<PricingTable>,
+}
+
+fn default_transient_retry() -> usize {
+    2
 }
 
 impl Default for LoopConfig {
@@ -184,10 +212,19 @@ impl Default for LoopConfig {
             backpressure_enabled: true,
             max_tokens: 16000,
             max_budget_usd: None,
+            transient_retry: default_transient_retry(),
+            pricing: None,
         }
     }
 }
 
+impl LoopConfig {
+    /// Estimated cost in USD of token usage under the active pricing table
+    pub fn estimate_cost_usd(&self, usage: &Usage) -> f64 {
+        pricing::estimate_cost_usd(self.pricing.as_ref(), self.model, usage)
+    }
+}
+
 /// Result from running a complete loop
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LoopResult {
@@ -203,10 +240,59 @@ pub struct LoopResult {
     pub files_modified: Vec<String>,
     /// Total token usage
     pub total_usage: Usage,
+    /// Estimated cost of `total_usage` in USD
+    #[serde(default)]
+    pub total_cost_usd: f64,
     /// Reason for stopping
     pub stop_reason: StopReason,
 }
 
+impl LoopResult {
+    /// One-line machine-readable summary for wrapping scripts
+    pub fn summary(&self) -> LoopSummary {
+        let mut files: Vec<&String> = self
+            .files_created
+            .iter()
+            .chain(&self.files_modified)
+            .collect();
+        files.sort();
+        files.dedup();
+
+        LoopSummary {
+            success: self.success,
+            iterations: self.iterations,
+            stop_reason: self.stop_reason.clone(),
+            files_changed: files.len(),
+            cost_usd: self.total_cost_usd,
+        }
+    }
+}
+
+/// Machine-readable outcome of a loop run (`hox loop start --summary-json`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoopSummary {
+    pub success: bool,
+    pub iterations: usize,
+    pub stop_reason: StopReason,
+    /// Distinct files created or modified
+    pub files_changed: usize,
+    /// Estimated cost in USD
+    pub cost_usd: f64,
+}
+
+impl LoopSummary {
+    /// Summary of a loop that failed before producing a result
+    pub fn failed(error: impl std::fmt::Display) -> Self {
+        Self {
+            success: false,
+            iterations: 0,
+            stop_reason: StopReason::Error(error.to_string()),
+            files_changed: 0,
+            cost_usd: 0.0,
+        }
+    }
+}
+
 /// Why the loop stopped
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum StopReason {
@@ -224,6 +310,10 @@ pub enum StopReason {
     Error(String),
     /// User cancelled
     Cancelled,
+    /// An iteration kept failing for infrastructure reasons after all retries
+    TransientRetriesExhausted,
+    /// Another iteration would exceed `max_budget_usd`
+    BudgetExceeded,
 }
 
 /// Tool call from Anthropic tool_use API
@@ -298,6 +388,9 @@ pub struct ExternalLoopResult {
     pub usage: Option<Usage>,
     /// Stop signal if present ("\[DONE\]", "promise", etc.)
     pub stop_signal: Option<String>,
+    /// Backpressure results from this iteration (`None` if checks were skipped)
+    #[serde(default)]
+    pub backpressure: Option<BackpressureResult>,
 }
 
 #[cfg(test)]
@@ -371,5 +464,57 @@ mod tests {
         assert_eq!(config.model, Model::Sonnet);
         assert!(config.backpressure_enabled);
         assert_eq!(config.max_budget_usd, None);
+        assert_eq!(config.transient_retry, 2);
+    }
+
+    #[test]
+    fn test_loop_config_pricing_override() {
+        let usage = Usage {
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+        };
+        let mut config = LoopConfig::default();
+        assert!((config.estimate_cost_usd(&usage) - 4.5).abs() < 1e-9);
+
+        config.pricing = Some(PricingTable::defaults().with_rate(
+            Model::Sonnet,
+            ModelPricing {
+                input_per_mtok: 1.0,
+                output_per_mtok: 5.0,
+            },
+        ));
+        assert!((config.estimate_cost_usd(&usage) - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_loop_summary_json() {
+        let result = LoopResult {
+            iterations: 3,
+            success: true,
+            final_status: BackpressureResult::all_pass(),
+            files_created: vec!["src/new.rs".into()],
+            files_modified: vec!["src/lib.rs".into(), "src/new.rs".into()],
+            total_usage: Usage {
+                input_tokens: 1_000_000,
+                output_tokens: 100_000,
+            },
+            total_cost_usd: 4.5,
+            stop_reason: StopReason::AllChecksPassed,
+        };
+
+        let line = serde_json::to_string(&result.summary()).unwrap();
+        assert!(!line.contains('\n'));
+
+        let parsed: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed["success"], true);
+        assert_eq!(parsed["iterations"], 3);
+        assert_eq!(parsed["stop_reason"], "AllChecksPassed");
+        assert_eq!(parsed["files_changed"], 2);
+        assert!((parsed["cost_usd"].as_f64().unwrap() - 4.5).abs() < 1e-9);
+
+        let line = serde_json::to_string(&LoopSummary::failed("workspace missing")).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed["success"], false);
+        assert_eq!(parsed["stop_reason"]["Error"], "workspace missing");
     }
 }
@@ -6,7 +6,7 @@ use hox_core::HoxError;
 use std::ffi::OsStr;
 use std::sync::Arc;
 use std::time::Duration;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 /// Configuration for browser launch
 #[derive(Debug, Clone)]
@@ -21,6 +21,9 @@ pub struct BrowserConfig {
     pub user_agent: Option<String>,
     /// Navigation timeout in seconds
     pub timeout_seconds: u64,
+    /// Attach to a running browser instead of launching one: a DevTools
+    /// WebSocket URL (`ws://...`) or a remote debugging port (`9222`)
+    pub attach: Option<String>,
 }
 
 impl Default for BrowserConfig {
@@ -31,6 +34,25 @@ impl Default for BrowserConfig {
             window_height: 1080,
             user_agent: None,
             timeout_seconds: 30,
+            attach: None,
+        }
+    }
+}
+
+/// Where to attach to an already-running browser
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AttachTarget {
+    /// Remote debugging port on localhost
+    Port(u16),
+    /// DevTools WebSocket URL
+    WebSocket(String),
+}
+
+impl AttachTarget {
+    fn parse(target: &str) -> Self {
+        match target.trim().parse() {
+            Ok(port) => AttachTarget::Port(port),
+            Err(_) => AttachTarget::WebSocket(target.trim().to_string()),
         }
     }
 }
@@ -44,6 +66,8 @@ pub struct BrowserSession {
     tab: Arc<Tab>,
     /// Configuration
     config: BrowserConfig,
+    /// Attached to a browser we did not launch (close detaches instead of killing it)
+    attached: bool,
 }
 
 impl BrowserSession {
@@ -64,7 +88,17 @@ impl BrowserSession {
     }
 
     /// Launch browser with custom configuration
+    ///
+    /// If `config.attach` is set, attaches to that browser instead.
     pub async fn launch_with_config(config: BrowserConfig) -> Result<Self> {
+        if let Some(target) = &config.attach {
+            let ws_url = match AttachTarget::parse(target) {
+                AttachTarget::Port(port) => Self::debugger_url(port).await?,
+                AttachTarget::WebSocket(url) => url,
+            };
+            return Self::attach(&ws_url, config);
+        }
+
         info!(
             "Launching browser (headless: {}, size: {}x{})",
             config.headless, config.window_width, config.window_height
@@ -100,17 +134,60 @@ impl BrowserSession {
             browser,
             tab,
             config,
+            attached: false,
         })
     }
 
-    /// Connect to an existing browser instance
+    /// Connect to an existing browser over its DevTools WebSocket URL
+    ///
+    /// The browser is not closed when the session is; only the tab opened by
+    /// the session is.
+    ///
+    /// # Arguments
+    /// * `ws_url` - Browser WebSocket URL (`webSocketDebuggerUrl` from `/json/version`)
+    pub async fn connect(ws_url: &str) -> Result<Self> {
+        Self::attach(ws_url, BrowserConfig::default())
+    }
+
+    /// Connect to an existing browser started with `--remote-debugging-port`
     ///
     /// # Arguments
     /// * `port` - Chrome DevTools Protocol port (typically 9222)
-    pub async fn connect(port: u16) -> Result<Self> {
-        info!("Connecting to existing browser on port {}", port);
+    pub async fn connect_port(port: u16) -> Result<Self> {
+        let ws_url = Self::debugger_url(port).await?;
+        Self::attach(&ws_url, BrowserConfig::default())
+    }
+
+    /// Look up the browser WebSocket URL on a local debugging port
+    async fn debugger_url(port: u16) -> Result<String> {
+        let version_url = format!("http://127.0.0.1:{}/json/version", port);
+        debug!("Discovering DevTools endpoint at {}", version_url);
+
+        let version: serde_json::Value = reqwest::get(&version_url)
+            .await
+            .map_err(|e| {
+                HoxError::Browser(format!("Failed to reach browser on port {}: {}", port, e))
+            })?
+            .json()
+            .await
+            .map_err(|e| HoxError::Browser(format!("Invalid DevTools response: {}", e)))?;
+
+        version["webSocketDebuggerUrl"]
+            .as_str()
+            .map(String::from)
+            .ok_or_else(|| {
+                HoxError::Browser(format!(
+                    "Browser on port {} did not report a webSocketDebuggerUrl",
+                    port
+                ))
+            })
+    }
+
+    /// Attach to a running browser and open a tab for this session
+    fn attach(ws_url: &str, config: BrowserConfig) -> Result<Self> {
+        info!("Connecting to existing browser at {}", ws_url);
 
-        let browser = Browser::connect(format!("http://127.0.0.1:{}", port))
+        let browser = Browser::connect(ws_url.to_string())
             .map_err(|e| HoxError::Browser(format!("Failed to connect to browser: {}", e)))?;
 
         let tab = browser
@@ -122,7 +199,8 @@ impl BrowserSession {
         Ok(Self {
             browser,
             tab,
-            config: BrowserConfig::default(),
+            config,
+            attached: true,
         })
     }
 
@@ -221,9 +299,24 @@ impl BrowserSession {
         &self.tab
     }
 
+    /// Whether this session is attached to a browser it did not launch
+    pub fn is_attached(&self) -> bool {
+        self.attached
+    }
+
     /// Close the browser session
+    ///
+    /// A launched browser is shut down. An attached browser keeps running;
+    /// only the tab this session opened is closed.
     pub async fn close(self) -> Result<()> {
-        info!("Closing browser session");
+        if self.attached {
+            info!("Detaching from browser");
+            if let Err(e) = self.tab.close(false) {
+                warn!("Failed to close session tab: {}", e);
+            }
+        } else {
+            info!("Closing browser session");
+        }
         // Browser will be dropped and cleaned up automatically
         Ok(())
     }
@@ -246,6 +339,16 @@ mod tests {
         assert_eq!(config.window_width, 1920);
         assert_eq!(config.window_height, 1080);
         assert_eq!(config.timeout_seconds, 30);
+        assert!(config.attach.is_none());
+    }
+
+    #[test]
+    fn test_attach_target_parse() {
+        assert_eq!(AttachTarget::parse("9222"), AttachTarget::Port(9222));
+        assert_eq!(
+            AttachTarget::parse("ws://127.0.0.1:9222/devtools/browser/abc"),
+            AttachTarget::WebSocket("ws://127.0.0.1:9222/devtools/browser/abc".to_string())
+        );
     }
 
     #[test]
@@ -256,6 +359,7 @@ mod tests {
             window_height: 768,
             user_agent: Some("CustomAgent/1.0".to_string()),
             timeout_seconds: 60,
+            attach: None,
         };
 
         assert!(!config.headless);
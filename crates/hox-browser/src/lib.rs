@@ -8,6 +8,7 @@
 //! - **Browser Management**: Launch and control Chrome/Chromium browsers
 //! - **Screenshot Capture**: Full-page and element-specific screenshots
 //! - **Visual Verification**: Element existence checks, text validation, attribute verification
+//! - **Visual Diff**: Pixel comparison of screenshots against a baseline
 //! - **Artifact Storage**: Integration with `hox-agent` artifact system
 //!
 //! # Example
@@ -60,7 +61,8 @@
 //!
 //! - Chrome or Chromium browser installed
 //! - For headless operation, no additional setup required
-//! - For connecting to existing browser: `chrome --remote-debugging-port=9222`
+//! - For connecting to existing browser: `chrome --remote-debugging-port=9222`, then
+//!   `BrowserSession::connect_port(9222)` or `BrowserConfig { attach: Some("9222".into()), .. }`
 //!
 //! # Architecture
 //!
@@ -69,20 +71,27 @@
 //! - [`browser`]: Browser lifecycle and session management
 //! - [`screenshot`]: Screenshot capture with artifact storage
 //! - [`verification`]: Visual verification and element checking
+//! - [`visual_diff`]: Screenshot comparison against baselines
 //! - [`error`]: Error types for browser operations
 
 pub mod browser;
 pub mod error;
+mod png;
 pub mod screenshot;
 pub mod verification;
+pub mod visual_diff;
 
 // Re-export commonly used types
 pub use browser::{BrowserConfig, BrowserSession};
 pub use error::{BrowserError, Result};
-pub use screenshot::{capture_element, capture_full_page, capture_screenshot, ScreenshotOptions};
+pub use screenshot::{
+    capture_element, capture_full_page, capture_full_page_streaming, capture_screenshot,
+    ScreenshotOptions,
+};
 pub use verification::{
     verify_attribute, verify_element, verify_elements, verify_text, ElementAttribute, VisualCheck,
 };
+pub use visual_diff::{compare_screenshots, compare_screenshots_to_artifact, DiffResult};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,211 @@
+//! PNG decoding and encoding for screenshot comparison
+//!
+//! Thin wrapper over the `png` crate that converts any PNG browsers produce
+//! into 8-bit RGBA, with size limits applied before decoding untrusted files.
+
+use crate::error::Result;
+use hox_core::HoxError;
+use png::{BitDepth, ColorType, Compression, Decoder, Encoder, Limits, Transformations};
+
+/// Largest width or height accepted on decode (Chrome's screenshot limit)
+const MAX_DIMENSION: u32 = 16_384;
+
+/// Largest decoded RGBA buffer accepted on decode
+const MAX_DECODED_BYTES: usize = 256 * 1024 * 1024;
+
+/// An 8-bit RGBA image
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    /// Row-major RGBA pixels, 4 bytes each
+    pub pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    #[cfg(test)]
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * self.width + x) * 4) as usize;
+        [
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ]
+    }
+}
+
+fn invalid(msg: impl std::fmt::Display) -> HoxError {
+    HoxError::Browser(format!("Invalid PNG: {}", msg))
+}
+
+/// Decode a PNG into RGBA pixels
+pub(crate) fn decode(data: &[u8]) -> Result<RgbaImage> {
+    let mut decoder = Decoder::new_with_limits(
+        data,
+        Limits {
+            bytes: MAX_DECODED_BYTES,
+        },
+    );
+    decoder.set_transformations(Transformations::normalize_to_color8());
+
+    let (width, height) = decoder.read_header_info().map_err(invalid)?.size();
+    if width == 0 || height == 0 || width > MAX_DIMENSION || height > MAX_DIMENSION {
+        return Err(invalid(format!("unsupported size {}x{}", width, height)));
+    }
+    let decoded_len = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|n| n.checked_mul(4))
+        .filter(|&n| n <= MAX_DECODED_BYTES)
+        .ok_or_else(|| invalid(format!("image too large ({}x{})", width, height)))?;
+
+    let mut reader = decoder.read_info().map_err(invalid)?;
+    // RGBA is the widest 8-bit output, so this fits whenever `decoded_len` does
+    let mut buffer = vec![0u8; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut buffer).map_err(invalid)?;
+    buffer.truncate(frame.buffer_size());
+
+    let pixels = match frame.color_type {
+        ColorType::Rgba => buffer,
+        ColorType::Rgb => buffer
+            .chunks_exact(3)
+            .flat_map(|px| [px[0], px[1], px[2], 255])
+            .collect(),
+        ColorType::GrayscaleAlpha => buffer
+            .chunks_exact(2)
+            .flat_map(|px| [px[0], px[0], px[0], px[1]])
+            .collect(),
+        ColorType::Grayscale => buffer.iter().flat_map(|&v| [v, v, v, 255]).collect(),
+        ColorType::Indexed => return Err(invalid("palette was not expanded")),
+    };
+    if pixels.len() != decoded_len {
+        return Err(invalid("image data too short"));
+    }
+
+    Ok(RgbaImage {
+        width,
+        height,
+        pixels,
+    })
+}
+
+/// Encode an RGBA image as PNG
+pub(crate) fn encode(image: &RgbaImage) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut encoder = Encoder::new(&mut out, image.width, image.height);
+    encoder.set_color(ColorType::Rgba);
+    encoder.set_depth(BitDepth::Eight);
+    encoder.set_compression(Compression::Fast);
+
+    let mut writer = encoder.write_header().map_err(encode_error)?;
+    writer
+        .write_image_data(&image.pixels)
+        .map_err(encode_error)?;
+    writer.finish().map_err(encode_error)?;
+    Ok(out)
+}
+
+fn encode_error(e: png::EncodingError) -> HoxError {
+    HoxError::Browser(format!("Failed to encode PNG: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gradient(width: u32, height: u32) -> RgbaImage {
+        let pixels = (0..width * height)
+            .flat_map(|i| [(i * 7) as u8, (i * 13) as u8, (i * 29) as u8, 255 - i as u8])
+            .collect();
+        RgbaImage {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// A PNG with only a signature and an IHDR claiming `width`x`height` RGBA
+    fn header_only(width: u32, height: u32) -> Vec<u8> {
+        let mut body = Vec::with_capacity(13);
+        body.extend_from_slice(&width.to_be_bytes());
+        body.extend_from_slice(&height.to_be_bytes());
+        body.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        png.extend_from_slice(b"IHDR");
+        png.extend_from_slice(&body);
+        let mut crc = crc32fast::Hasher::new();
+        crc.update(b"IHDR");
+        crc.update(&body);
+        png.extend_from_slice(&crc.finalize().to_be_bytes());
+        png
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let image = gradient(5, 4);
+        assert_eq!(decode(&encode(&image).unwrap()).unwrap(), image);
+    }
+
+    #[test]
+    fn test_decode_expands_to_rgba() {
+        let encode_as = |color: ColorType, data: &[u8], palette: Option<&[u8]>| {
+            let mut out = Vec::new();
+            let mut encoder = Encoder::new(&mut out, 2, 1);
+            encoder.set_color(color);
+            encoder.set_depth(BitDepth::Eight);
+            if let Some(palette) = palette {
+                encoder.set_palette(palette.to_vec());
+                encoder.set_trns(vec![255, 0]);
+            }
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(data).unwrap();
+            writer.finish().unwrap();
+            out
+        };
+
+        let gray = decode(&encode_as(ColorType::Grayscale, &[10, 200], None)).unwrap();
+        assert_eq!(gray.pixels, [10, 10, 10, 255, 200, 200, 200, 255]);
+
+        let rgb = decode(&encode_as(ColorType::Rgb, &[1, 2, 3, 4, 5, 6], None)).unwrap();
+        assert_eq!(rgb.pixels, [1, 2, 3, 255, 4, 5, 6, 255]);
+
+        let palette = [9, 8, 7, 6, 5, 4];
+        let indexed = decode(&encode_as(ColorType::Indexed, &[0, 1], Some(&palette))).unwrap();
+        assert_eq!(indexed.pixels, [9, 8, 7, 255, 6, 5, 4, 0]);
+    }
+
+    #[test]
+    fn test_decode_rejects_garbage() {
+        assert!(decode(b"not a png").is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_oversized_header() {
+        // Headers claiming huge images are rejected before any allocation
+        for (width, height) in [(u32::MAX, u32::MAX), (MAX_DIMENSION + 1, 1)] {
+            let err = decode(&header_only(width, height)).unwrap_err().to_string();
+            assert!(err.contains(&format!("{}x{}", width, height)), "{}", err);
+        }
+        assert!(decode(&header_only(0, 1)).is_err());
+
+        // Within the per-side limit but past the total byte limit
+        let png = header_only(MAX_DIMENSION, MAX_DIMENSION);
+        assert!(decode(&png).unwrap_err().to_string().contains("too large"));
+    }
+
+    #[test]
+    fn test_decode_rejects_truncated_chunk_length() {
+        let mut png = encode(&gradient(2, 2)).unwrap();
+        // Set the IHDR length to u32::MAX
+        png[8..12].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(decode(&png).is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_truncated_image_data() {
+        let png = encode(&gradient(8, 8)).unwrap();
+        assert!(decode(&png[..png.len() - 20]).is_err());
+    }
+}
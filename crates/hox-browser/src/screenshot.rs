@@ -2,11 +2,19 @@
 
 use crate::browser::BrowserSession;
 use crate::error::Result;
-use headless_chrome::protocol::cdp::Page::CaptureScreenshotFormatOption;
+use base64::engine::general_purpose::STANDARD;
+use base64::read::DecoderReader;
+use chrono::Utc;
+use headless_chrome::protocol::cdp::Page::{self, CaptureScreenshotFormatOption};
 use hox_agent::{ArtifactManager, ArtifactType, ValidationArtifact};
 use hox_core::HoxError;
+use std::io::{BufWriter, Write};
+use std::path::Path;
 use tracing::{debug, info};
 
+/// Buffer size used when streaming decoded screenshots to disk
+const STREAM_BUFFER_SIZE: usize = 64 * 1024;
+
 /// Screenshot capture options
 #[derive(Debug, Clone)]
 pub struct ScreenshotOptions {
@@ -181,6 +189,92 @@ pub async fn capture_full_page(
     .await
 }
 
+/// Full-page screenshot written straight to an artifact file
+///
+/// Unlike [`capture_full_page`], the CDP base64 payload is decoded in chunks
+/// while writing, so the decoded image is never held in memory. Use this for
+/// very tall pages.
+pub async fn capture_full_page_streaming(
+    session: &BrowserSession,
+    artifact_manager: &ArtifactManager,
+    change_id: &str,
+    name: &str,
+) -> Result<ValidationArtifact> {
+    info!(
+        "Capturing streamed full page screenshot '{}' for change {}",
+        name, change_id
+    );
+
+    let encoded = session
+        .tab()
+        .call_method(Page::CaptureScreenshot {
+            format: Some(CaptureScreenshotFormatOption::Png),
+            quality: None,
+            clip: None,
+            from_surface: Some(true),
+            capture_beyond_viewport: Some(true),
+            optimize_for_speed: None,
+        })
+        .map_err(|e| HoxError::Browser(format!("CDP capture failed: {}", e)))?
+        .data;
+
+    let relative_path = artifact_manager
+        .new_artifact_path(change_id, &ArtifactType::Screenshot)
+        .await?;
+    let path = artifact_manager.base_dir().join(&relative_path);
+    let size_bytes = tokio::task::spawn_blocking(move || write_base64_to_file(&encoded, &path))
+        .await
+        .map_err(|e| HoxError::Browser(format!("Screenshot writer panicked: {}", e)))??;
+
+    info!(
+        "Screenshot stored: {} ({} bytes)",
+        relative_path.display(),
+        size_bytes
+    );
+
+    Ok(ValidationArtifact {
+        artifact_type: ArtifactType::Screenshot,
+        path: relative_path,
+        mime_type: ArtifactType::Screenshot.mime_type().to_string(),
+        size_bytes,
+        created_at: Utc::now(),
+        description: format!("Full page screenshot ({})", name),
+    })
+}
+
+/// Decode base64 into a file in fixed-size chunks, returning the decoded size
+///
+/// Blocking; run it on a blocking thread from async code.
+fn write_base64_to_file(encoded: &str, path: &Path) -> Result<u64> {
+    let file = std::fs::File::create(path).map_err(|e| {
+        HoxError::Io(format!(
+            "Failed to create artifact {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    let size = decode_base64_into(encoded, file).map_err(|e| {
+        HoxError::Browser(format!(
+            "Failed to write screenshot {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    Ok(size)
+}
+
+/// Decode base64 into `sink`, handing it at most [`STREAM_BUFFER_SIZE`] bytes per write
+fn decode_base64_into<W: Write>(encoded: &str, sink: W) -> std::io::Result<u64> {
+    let mut writer = BufWriter::with_capacity(STREAM_BUFFER_SIZE, sink);
+    let mut decoder = DecoderReader::new(encoded.as_bytes(), &STANDARD);
+
+    let size = std::io::copy(&mut decoder, &mut writer)?;
+    writer.flush()?;
+    Ok(size)
+}
+
 /// Convenience function for element screenshot
 ///
 /// # Arguments
@@ -209,6 +303,7 @@ pub async fn capture_element(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use base64::Engine;
 
     #[test]
     fn test_screenshot_options_default() {
@@ -225,6 +320,64 @@ mod tests {
         assert!(options.full_page);
     }
 
+    #[test]
+    fn test_write_base64_to_file() {
+        // Mocked CDP capture payload spanning several stream buffers
+        let image: Vec<u8> = (0..4 * STREAM_BUFFER_SIZE + 17)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let encoded = STANDARD.encode(&image);
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("page.png");
+
+        let size = write_base64_to_file(&encoded, &path).unwrap();
+        assert_eq!(size, image.len() as u64);
+        assert_eq!(std::fs::read(&path).unwrap(), image);
+
+        assert!(write_base64_to_file("not base64!", &dir.path().join("bad.png")).is_err());
+    }
+
+    /// Records every write so the test can check how much is handed over at once
+    #[derive(Default)]
+    struct RecordingWriter {
+        written: Vec<u8>,
+        largest_write: usize,
+    }
+
+    impl Write for RecordingWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.largest_write = self.largest_write.max(buf.len());
+            self.written.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_decode_base64_writes_bounded_chunks() {
+        // Synthetic 16 MiB "tall page" as a mocked CDP capture payload
+        let image: Vec<u8> = (0..16 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+        let encoded = STANDARD.encode(&image);
+
+        let mut recorder = RecordingWriter::default();
+        let size = decode_base64_into(&encoded, &mut recorder).unwrap();
+
+        assert_eq!(size, image.len() as u64);
+        assert!(
+            recorder.written == image,
+            "decoded bytes differ from the image"
+        );
+        assert!(
+            recorder.largest_write <= STREAM_BUFFER_SIZE,
+            "largest single write was {} bytes",
+            recorder.largest_write
+        );
+    }
+
     #[test]
     fn test_screenshot_options_element() {
         let options = ScreenshotOptions::element("#main");
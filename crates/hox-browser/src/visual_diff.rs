@@ -0,0 +1,276 @@
+//! Visual diff of screenshots against a baseline
+//!
+//! Compares two PNG screenshots pixel by pixel and renders a diff image with
+//! the differing pixels highlighted in red over a dimmed copy of the baseline.
+
+use crate::error::Result;
+use crate::png::{self, RgbaImage};
+use hox_agent::{ArtifactManager, ArtifactType};
+use hox_core::HoxError;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+/// Highlight color for differing pixels
+const DIFF_COLOR: [u8; 4] = [255, 0, 0, 255];
+
+/// Result of comparing a screenshot against its baseline
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffResult {
+    /// Number of pixels whose RGBA values differ
+    pub pixels_differing: u64,
+    /// Total pixels compared
+    pub total_pixels: u64,
+    /// Fraction of differing pixels (0.0 - 1.0)
+    pub ratio: f64,
+    /// Whether `ratio` is within the requested threshold
+    pub within_threshold: bool,
+    /// Highlighted diff image, written only when pixels differ
+    pub diff_image_path: Option<PathBuf>,
+}
+
+/// Compare a screenshot against a baseline
+///
+/// `threshold` is the largest acceptable fraction of differing pixels. When
+/// any pixels differ, a diff image is written next to `current` as
+/// `{stem}.diff.png`. Screenshots of different sizes are an error.
+pub async fn compare_screenshots(
+    baseline: &Path,
+    current: &Path,
+    threshold: f64,
+) -> Result<DiffResult> {
+    let (mut result, diff_image) = diff_files(baseline, current, threshold).await?;
+
+    if let Some(image) = diff_image {
+        let stem = current
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "screenshot".to_string());
+        let path = current.with_file_name(format!("{}.diff.png", stem));
+        tokio::fs::write(&path, png::encode(&image)?)
+            .await
+            .map_err(|e| {
+                HoxError::Io(format!(
+                    "Failed to write diff image {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+        info!("Diff image written: {}", path.display());
+        result.diff_image_path = Some(path);
+    }
+
+    Ok(result)
+}
+
+/// Compare a screenshot against a baseline, storing the diff image as an artifact
+///
+/// Like [`compare_screenshots`], but the diff image is stored for `change_id`
+/// through the artifact manager. `diff_image_path` is the artifact's absolute path.
+pub async fn compare_screenshots_to_artifact(
+    baseline: &Path,
+    current: &Path,
+    threshold: f64,
+    artifact_manager: &ArtifactManager,
+    change_id: &str,
+) -> Result<DiffResult> {
+    let (mut result, diff_image) = diff_files(baseline, current, threshold).await?;
+
+    if let Some(image) = diff_image {
+        let artifact = artifact_manager
+            .store_artifact(
+                change_id,
+                ArtifactType::Screenshot,
+                &png::encode(&image)?,
+                &format!(
+                    "Visual diff of {} against {} ({:.2}% differing)",
+                    current.display(),
+                    baseline.display(),
+                    result.ratio * 100.0
+                ),
+            )
+            .await?;
+        info!("Diff image stored: {}", artifact.path.display());
+        result.diff_image_path = Some(artifact.absolute_path(artifact_manager.base_dir()));
+    }
+
+    Ok(result)
+}
+
+/// Load and compare two screenshot files
+async fn diff_files(
+    baseline: &Path,
+    current: &Path,
+    threshold: f64,
+) -> Result<(DiffResult, Option<RgbaImage>)> {
+    let baseline_image = load_png(baseline).await?;
+    let current_image = load_png(current).await?;
+    let (result, diff_image) = diff_images(&baseline_image, &current_image, threshold)?;
+
+    debug!(
+        "Compared {} to {}: {}/{} pixels differ",
+        current.display(),
+        baseline.display(),
+        result.pixels_differing,
+        result.total_pixels
+    );
+    Ok((result, diff_image))
+}
+
+async fn load_png(path: &Path) -> Result<RgbaImage> {
+    let data = tokio::fs::read(path).await.map_err(|e| {
+        HoxError::Io(format!(
+            "Failed to read screenshot {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    png::decode(&data)
+        .map_err(|e| HoxError::Browser(format!("Failed to decode {}: {}", path.display(), e)))
+}
+
+/// Compare two images, returning the diff image when any pixels differ
+fn diff_images(
+    baseline: &RgbaImage,
+    current: &RgbaImage,
+    threshold: f64,
+) -> Result<(DiffResult, Option<RgbaImage>)> {
+    if (baseline.width, baseline.height) != (current.width, current.height) {
+        return Err(HoxError::Browser(format!(
+            "Screenshot size mismatch: baseline is {}x{}, current is {}x{}",
+            baseline.width, baseline.height, current.width, current.height
+        )));
+    }
+
+    let mut diff = Vec::with_capacity(baseline.pixels.len());
+    let mut pixels_differing = 0u64;
+    for (old, new) in baseline
+        .pixels
+        .chunks_exact(4)
+        .zip(current.pixels.chunks_exact(4))
+    {
+        if old == new {
+            // Dimmed grayscale of the baseline keeps the layout recognisable
+            let luma = (old[0] as u32 * 299 + old[1] as u32 * 587 + old[2] as u32 * 114) / 1000;
+            let dimmed = (128 + luma / 2) as u8;
+            diff.extend_from_slice(&[dimmed, dimmed, dimmed, 255]);
+        } else {
+            pixels_differing += 1;
+            diff.extend_from_slice(&DIFF_COLOR);
+        }
+    }
+
+    let total_pixels = baseline.width as u64 * baseline.height as u64;
+    let ratio = if total_pixels == 0 {
+        0.0
+    } else {
+        pixels_differing as f64 / total_pixels as f64
+    };
+
+    let diff_image = (pixels_differing > 0).then_some(RgbaImage {
+        width: baseline.width,
+        height: baseline.height,
+        pixels: diff,
+    });
+
+    Ok((
+        DiffResult {
+            pixels_differing,
+            total_pixels,
+            ratio,
+            within_threshold: ratio <= threshold,
+            diff_image_path: None,
+        },
+        diff_image,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn solid(width: u32, height: u32, rgba: [u8; 4]) -> RgbaImage {
+        RgbaImage {
+            width,
+            height,
+            pixels: rgba.repeat((width * height) as usize),
+        }
+    }
+
+    fn write(dir: &TempDir, name: &str, image: &RgbaImage) -> PathBuf {
+        let path = dir.path().join(name);
+        std::fs::write(&path, png::encode(image).unwrap()).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn test_identical_screenshots() {
+        let dir = TempDir::new().unwrap();
+        let image = solid(4, 4, [10, 20, 30, 255]);
+        let baseline = write(&dir, "baseline.png", &image);
+        let current = write(&dir, "current.png", &image);
+
+        let result = compare_screenshots(&baseline, &current, 0.0).await.unwrap();
+
+        assert_eq!(result.pixels_differing, 0);
+        assert_eq!(result.total_pixels, 16);
+        assert!(result.within_threshold);
+        assert!(result.diff_image_path.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_differing_pixel_writes_diff_image() {
+        let dir = TempDir::new().unwrap();
+        let image = solid(4, 5, [10, 20, 30, 255]);
+        let mut changed = image.clone();
+        changed.pixels[4..8].copy_from_slice(&[200, 20, 30, 255]);
+        let baseline = write(&dir, "baseline.png", &image);
+        let current = write(&dir, "current.png", &changed);
+
+        let result = compare_screenshots(&baseline, &current, 0.01)
+            .await
+            .unwrap();
+
+        assert_eq!(result.pixels_differing, 1);
+        assert_eq!(result.total_pixels, 20);
+        assert_eq!(result.ratio, 0.05);
+        assert!(!result.within_threshold);
+
+        let diff_path = result.diff_image_path.unwrap();
+        assert_eq!(diff_path, dir.path().join("current.diff.png"));
+        let diff = png::decode(&std::fs::read(diff_path).unwrap()).unwrap();
+        assert_eq!(diff.pixel(1, 0), DIFF_COLOR);
+        assert_ne!(diff.pixel(0, 0), DIFF_COLOR);
+    }
+
+    #[tokio::test]
+    async fn test_diff_stored_as_artifact() {
+        let dir = TempDir::new().unwrap();
+        let baseline = write(&dir, "baseline.png", &solid(2, 2, [0, 0, 0, 255]));
+        let current = write(&dir, "current.png", &solid(2, 2, [255, 255, 255, 255]));
+        let manager = ArtifactManager::new(dir.path().join(".hox"));
+
+        let result = compare_screenshots_to_artifact(&baseline, &current, 1.0, &manager, "abc")
+            .await
+            .unwrap();
+
+        assert_eq!(result.ratio, 1.0);
+        assert!(result.within_threshold);
+        let diff_path = result.diff_image_path.unwrap();
+        assert!(diff_path.starts_with(manager.base_dir().join("abc")));
+        assert!(diff_path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_size_mismatch_is_error() {
+        let dir = TempDir::new().unwrap();
+        let baseline = write(&dir, "baseline.png", &solid(4, 4, [0, 0, 0, 255]));
+        let current = write(&dir, "current.png", &solid(4, 3, [0, 0, 0, 255]));
+
+        let err = compare_screenshots(&baseline, &current, 0.0)
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("size mismatch"));
+    }
+}
@@ -6,22 +6,29 @@
 //!   hox status                  Show orchestration status
 //!   hox patterns list           List learned patterns
 //!   hox patterns propose `<file>` Propose a new pattern
+//!   hox patterns dedup          List clusters of similar patterns
 //!   hox validate `<change>`     Run validation on a change
 
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand, ValueEnum};
-use hox_agent::{BackpressureResult, ExternalLoopState, LoopConfig, Model};
+use futures::StreamExt;
+use hox_agent::{BackpressureResult, LoopConfig, LoopSummary, Model, PricingTable};
 use hox_core::{DelegationStrategy, HandoffContext, HoxConfig, OrchestratorId, Task};
-use hox_evolution::{builtin_patterns, PatternStore};
-use hox_jj::{BookmarkManager, JjCommand, JjExecutor, MetadataManager, RevsetQueries};
+use hox_evolution::{builtin_patterns, PatternStore, ReviewGate};
+use hox_jj::{
+    BookmarkManager, ChangeIdResolver, JjCommand, JjExecutor, MetadataManager, RevsetQueries,
+};
 use hox_orchestrator::{
-    create_initial_state, load_state, run_external_iteration, save_state, ExternalIterationConfig,
-    Orchestrator, OrchestratorConfig, PhaseManager,
+    advance_state, create_initial_state, load_state, request_stop, run_external_iteration,
+    save_state, stop_file_path, watch_stop_file, ExternalIterationConfig, Orchestrator,
+    OrchestratorConfig, PhaseManager,
 };
 use hox_planning::{cli_tool_prd, example_prd, PrdDecomposer, ProjectRequirementsDocument};
 use hox_validation::{ByzantineConsensus, ConsensusConfig, Validator, ValidatorConfig};
 use std::path::PathBuf;
-use tracing::{info, Level};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::{info, warn, Level};
 use tracing_subscriber::FmtSubscriber;
 
 #[derive(Parser)]
@@ -60,7 +67,8 @@ enum Commands {
     /// Run orchestration on a plan
     Orchestrate {
         /// Plan description or file
-        plan: String,
+        #[arg(required_unless_present = "resume")]
+        plan: Option<String>,
 
         /// Number of orchestrators to spawn
         #[arg(short = 'n', long, default_value = "1")]
@@ -73,10 +81,22 @@ enum Commands {
         /// Enable hierarchical delegation (spawn child orchestrators for epics)
         #[arg(long)]
         delegate: bool,
+
+        /// Resume a previously started orchestrator from its saved state
+        #[arg(long, value_name = "ID", conflicts_with = "plan")]
+        resume: Option<OrchestratorId>,
     },
 
     /// Show orchestration status
-    Status,
+    Status {
+        /// Output the status as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Also show the tasks assigned to and ready for this agent
+        #[arg(long)]
+        agent: Option<String>,
+    },
 
     /// Pattern management
     Patterns {
@@ -92,12 +112,32 @@ enum Commands {
         /// Number of validators (3f+1 for f faulty)
         #[arg(short = 'n', long, default_value = "4")]
         validators: usize,
+
+        /// Explain the consensus outcome (votes, threshold, quorum, outliers)
+        #[arg(long, alias = "consensus-detail")]
+        detail: bool,
+
+        /// Per-validator vote weights, in validator order (e.g. 2,1,1,1; default 1 each)
+        #[arg(long, value_delimiter = ',')]
+        weights: Vec<f64>,
+
+        /// Cap on any single validator's weight; heavier weights are clamped
+        #[arg(long, default_value_t = ConsensusConfig::default().max_weight)]
+        max_weight: f64,
     },
 
     /// Query changes using Hox metadata
     Query {
         /// Revset query
         revset: String,
+
+        /// Maximum number of changes to show
+        #[arg(long)]
+        limit: Option<usize>,
+
+        /// Number of matching changes to skip
+        #[arg(long, default_value = "0")]
+        offset: usize,
     },
 
     /// Set Hox metadata on current change
@@ -127,10 +167,18 @@ enum Commands {
 
     /// Launch 3D visualization in browser
     Viz {
-        /// Port to serve on
+        /// Host or IP address to bind to
+        #[arg(long, default_value = "0.0.0.0")]
+        host: String,
+
+        /// Port to serve on (0 picks a free port)
         #[arg(short, long, default_value = "7070")]
         port: u16,
 
+        /// Host to show in the printed URL, e.g. for remote deployments
+        #[arg(long)]
+        url_host: Option<String>,
+
         /// Refresh interval in milliseconds
         #[arg(short, long, default_value = "500")]
         refresh: u64,
@@ -155,6 +203,17 @@ enum Commands {
         max_oplog: usize,
     },
 
+    /// Export the orchestration graph (agents, phases, change ancestry)
+    Graph {
+        /// Output format
+        #[arg(long, value_enum, default_value = "dot")]
+        format: GraphFormat,
+
+        /// Write to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
     /// Manage bookmarks for task assignments
     Bookmark {
         #[command(subcommand)]
@@ -178,13 +237,119 @@ enum Commands {
         /// Remove agent workspace after rollback
         #[arg(long)]
         remove_workspace: bool,
+
+        /// List named checkpoints recorded before destructive DAG operations
+        #[arg(long, conflicts_with_all = ["agent", "operation", "count", "checkpoint"])]
+        list_checkpoints: bool,
+
+        /// Restore to the most recent checkpoint with this label
+        #[arg(long, value_name = "LABEL", conflicts_with_all = ["agent", "operation", "count"])]
+        checkpoint: Option<String>,
     },
 
     /// DAG manipulation commands for task restructuring
     Dag {
+        /// Preview parallelize/absorb/split/squash without rewriting history
+        #[arg(long, global = true)]
+        preview: bool,
+
         #[command(subcommand)]
         action: DagCommands,
     },
+
+    /// Package .hox state and a task metadata snapshot into a bundle
+    Export {
+        /// Bundle path (tar archive)
+        bundle: PathBuf,
+    },
+
+    /// Restore a bundle created by `hox export` into a repository
+    Import {
+        /// Bundle path (tar archive)
+        bundle: PathBuf,
+
+        /// Repository path (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        path: PathBuf,
+    },
+
+    /// Agent diagnostics
+    Agent {
+        #[command(subcommand)]
+        action: AgentCommands,
+    },
+
+    /// Agent telemetry metrics
+    Metrics {
+        #[command(subcommand)]
+        action: MetricsCommands,
+    },
+
+    /// Agent workspace management
+    Workspace {
+        #[command(subcommand)]
+        action: WorkspaceCommands,
+    },
+}
+
+/// Workspace subcommands
+#[derive(Subcommand)]
+enum WorkspaceCommands {
+    /// List jj workspaces and whether they hold work
+    List,
+
+    /// Remove abandoned or long-unmodified workspaces that hox created
+    Prune {
+        /// Hours without any file modified before a workspace is stale
+        #[arg(long, default_value = "24")]
+        older_than_hours: u64,
+
+        /// Show what would be removed without removing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// Metrics subcommands
+#[derive(Subcommand)]
+enum MetricsCommands {
+    /// Print aggregated metrics for scraping or inspection
+    Export {
+        /// Output format
+        #[arg(long, value_enum, default_value = "prometheus")]
+        format: MetricsFormat,
+
+        /// Metrics file (defaults to .hox/metrics/agents.jsonl)
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+}
+
+/// Orchestration graph export format
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum GraphFormat {
+    Dot,
+}
+
+/// Metrics export format
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum MetricsFormat {
+    Prometheus,
+    Json,
+}
+
+/// Agent diagnostic subcommands
+#[derive(Subcommand)]
+enum AgentCommands {
+    /// Show an agent's assigned changes, their history, and activity
+    Logs {
+        /// Agent name
+        agent: String,
+
+        /// Evolution log entries to show per change
+        #[arg(short = 'n', long, default_value = "5")]
+        evolog: usize,
+    },
 }
 
 /// DAG manipulation subcommands
@@ -200,6 +365,10 @@ enum DagCommands {
     Absorb {
         /// Optional paths to absorb (all changes if omitted)
         paths: Vec<String>,
+
+        /// Show which change each hunk would move to, without absorbing
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Split a change into multiple changes by file groups
@@ -209,6 +378,10 @@ enum DagCommands {
 
         /// Files for the split (creates sibling with these files)
         files: Vec<String>,
+
+        /// Split hunks instead of whole files (`path:start-end`, repeatable)
+        #[arg(long = "hunk", value_name = "PATH:START-END", conflicts_with = "files")]
+        hunks: Vec<String>,
     },
 
     /// Squash a change into its parent
@@ -231,6 +404,19 @@ enum DagCommands {
         paths: Vec<String>,
     },
 
+    /// Move a change onto a new parent
+    Rebase {
+        /// Change ID to rebase
+        change_id: String,
+
+        /// New parent change
+        destination: String,
+
+        /// Insert between the destination and this change instead
+        #[arg(long)]
+        insert_before: Option<String>,
+    },
+
     /// Duplicate a change for speculative execution
     Duplicate {
         /// Change ID to duplicate
@@ -251,6 +437,28 @@ enum DagCommands {
     Evolog {
         /// Change ID to show evolution for
         change_id: String,
+
+        /// Output entries as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Only show entries recorded after this operation ID
+        #[arg(long, value_name = "OP")]
+        since: Option<String>,
+    },
+
+    /// Show evolution history interleaved across changes, ordered by time
+    Timeline {
+        /// Revset of changes to include
+        revset: String,
+
+        /// Output entries as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Only show entries recorded after this operation ID
+        #[arg(long, value_name = "OP")]
+        since: Option<String>,
     },
 
     /// Clean up redundant parent relationships
@@ -320,6 +528,22 @@ enum LoopCommands {
         /// Disable backpressure checks (tests/lints/builds)
         #[arg(long)]
         no_backpressure: bool,
+
+        /// Retries for iterations that fail on API/infrastructure errors
+        #[arg(long, default_value = "2")]
+        transient_retries: usize,
+
+        /// JSON file of per-model token rates for cost estimates (list prices if omitted)
+        #[arg(long)]
+        pricing: Option<PathBuf>,
+
+        /// Stop before an iteration that would push the estimated cost past this many USD
+        #[arg(long)]
+        max_budget_usd: Option<f64>,
+
+        /// Print a one-line JSON summary as the last stdout line (other output goes to stderr)
+        #[arg(long)]
+        summary_json: bool,
     },
 
     /// Show loop status for a task
@@ -328,12 +552,38 @@ enum LoopCommands {
         change_id: String,
     },
 
-    /// Stop a running loop (marks task as blocked)
+    /// Stop a running loop, killing in-flight checks, and mark its task as blocked
     Stop {
         /// JJ change ID
         change_id: String,
     },
 
+    /// Run iterations locally, resuming from and updating an external state file
+    Resume {
+        /// JJ change ID of the task to work on
+        change_id: String,
+
+        /// State file to resume from and update in place (created if missing)
+        #[arg(long)]
+        state_file: PathBuf,
+
+        /// Stop once the state reaches this iteration (0 for unlimited)
+        #[arg(short = 'n', long, default_value = "20")]
+        max_iterations: usize,
+
+        /// Model to use (opus, sonnet, haiku)
+        #[arg(short, long, default_value = "sonnet")]
+        model: CliModel,
+
+        /// Disable backpressure checks (tests/lints/builds)
+        #[arg(long)]
+        no_backpressure: bool,
+
+        /// Maximum tokens for agent response
+        #[arg(long, default_value = "16000")]
+        max_tokens: usize,
+    },
+
     /// Run single external iteration (bash-orchestratable mode)
     External {
         /// JJ change ID of the task to work on
@@ -407,6 +657,13 @@ enum PatternCommands {
 
     /// Show builtin patterns
     Builtin,
+
+    /// List clusters of near-duplicate patterns for review
+    Dedup {
+        /// Similarity threshold 0.0-1.0 (default: evolution.similarity_threshold)
+        #[arg(long)]
+        threshold: Option<f64>,
+    },
 }
 
 #[tokio::main]
@@ -419,9 +676,11 @@ async fn main() -> Result<()> {
     } else {
         Level::INFO
     };
+    // Logs go to stderr so stdout stays machine-readable (`--summary-json`)
     let subscriber = FmtSubscriber::builder()
         .with_max_level(level)
         .with_target(false)
+        .with_writer(std::io::stderr)
         .finish();
     tracing::subscriber::set_global_default(subscriber)?;
 
@@ -437,11 +696,28 @@ async fn main() -> Result<()> {
             orchestrators,
             max_agents,
             delegate,
-        } => cmd_orchestrate(plan, orchestrators, max_agents, delegate).await,
-        Commands::Status => cmd_status().await,
+            resume,
+        } => match resume {
+            Some(id) => cmd_orchestrate_resume(id, max_agents, delegate).await,
+            None => {
+                let plan = plan.expect("clap requires plan unless --resume is given");
+                cmd_orchestrate(plan, orchestrators, max_agents, delegate).await
+            }
+        },
+        Commands::Status { json, agent } => cmd_status(json, agent).await,
         Commands::Patterns { action } => cmd_patterns(action).await,
-        Commands::Validate { change, validators } => cmd_validate(change, validators).await,
-        Commands::Query { revset } => cmd_query(revset).await,
+        Commands::Validate {
+            change,
+            validators,
+            detail,
+            weights,
+            max_weight,
+        } => cmd_validate(change, validators, detail, weights, max_weight).await,
+        Commands::Query {
+            revset,
+            limit,
+            offset,
+        } => cmd_query(revset, limit, offset).await,
         Commands::Set {
             priority,
             status,
@@ -450,20 +726,40 @@ async fn main() -> Result<()> {
         } => cmd_set(priority, status, agent, orchestrator).await,
         Commands::Loop { action } => cmd_loop(action).await,
         Commands::Viz {
+            host,
             port,
+            url_host,
             refresh,
             max_oplog,
             no_open,
-        } => cmd_viz(port, refresh, max_oplog, no_open).await,
+        } => cmd_viz(host, port, url_host, refresh, max_oplog, no_open).await,
         Commands::Dashboard { refresh, max_oplog } => cmd_dashboard(refresh, max_oplog).await,
+        Commands::Graph { format, output } => cmd_graph(format, output).await,
         Commands::Bookmark { action } => cmd_bookmark(action).await,
         Commands::Rollback {
             agent,
             operation,
             count,
             remove_workspace,
-        } => cmd_rollback(agent, operation, count, remove_workspace).await,
-        Commands::Dag { action } => cmd_dag(action).await,
+            list_checkpoints,
+            checkpoint,
+        } => {
+            cmd_rollback(
+                agent,
+                operation,
+                count,
+                remove_workspace,
+                list_checkpoints,
+                checkpoint,
+            )
+            .await
+        }
+        Commands::Dag { preview, action } => cmd_dag(action, preview).await,
+        Commands::Export { bundle } => cmd_export(bundle).await,
+        Commands::Import { bundle, path } => cmd_import(bundle, path).await,
+        Commands::Agent { action } => cmd_agent(action).await,
+        Commands::Metrics { action } => cmd_metrics(action).await,
+        Commands::Workspace { action } => cmd_workspace(action).await,
     }
 }
 
@@ -547,6 +843,14 @@ async fn cmd_init(
 
     // If we have a PRD, save it and show decomposition
     if let Some(doc) = prd_doc {
+        if let Err(problems) = doc.validate() {
+            println!("\nPRD has {} problem(s):", problems.len());
+            for problem in &problems {
+                println!("  - {}", problem);
+            }
+            anyhow::bail!("Invalid PRD; fix the problems above and re-run hox init");
+        }
+
         // Save PRD to .hox/prd.json
         let prd_path = hox_dir.join("prd.json");
         tokio::fs::write(&prd_path, serde_json::to_string_pretty(&doc)?).await?;
@@ -569,6 +873,7 @@ async fn cmd_init(
                 "priority": t.priority,
                 "phase": t.phase,
                 "status": t.status,
+                "dependencies": t.dependencies,
             })).collect::<Vec<_>>(),
         });
 
@@ -640,68 +945,104 @@ async fn cmd_orchestrate(
     Ok(())
 }
 
-async fn cmd_status() -> Result<()> {
+async fn cmd_orchestrate_resume(
+    id: OrchestratorId,
+    max_agents: usize,
+    delegate: bool,
+) -> Result<()> {
+    let jj = JjCommand::detect()
+        .await
+        .context("Not in a JJ repository")?;
+
+    let mut config =
+        OrchestratorConfig::new(id.clone(), jj.repo_root()).with_max_agents(max_agents);
+    if delegate {
+        config = config.with_delegation_strategy(DelegationStrategy::PhasePerChild);
+    }
+
+    let mut orchestrator = Orchestrator::resume(config, jj).await?;
+
+    match orchestrator.phases().current_phase() {
+        Some(phase) => println!(
+            "Resumed orchestrator {} at phase {} ({})",
+            id, phase.number, phase.name
+        ),
+        None => println!("Resumed orchestrator {} with all phases completed", id),
+    }
+
+    if delegate {
+        orchestrator.run_with_delegation().await?;
+        println!("Orchestration completed");
+    } else {
+        println!("Use 'hox status' to check progress");
+    }
+
+    Ok(())
+}
+
+async fn cmd_status(json: bool, agent: Option<String>) -> Result<()> {
     let jj = JjCommand::detect()
         .await
         .context("Not in a JJ repository")?;
     let queries = RevsetQueries::new(jj);
+    let mut report = queries.status_report().await?;
+    if let Some(agent) = agent {
+        report.agent = Some(queries.agent_workload(&agent).await?);
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
 
     println!("Hox Status");
     println!("==========");
 
-    // Find orchestrators - try bookmark query first, fallback to description search
-    let orchestrators = match queries.all_orchestrators_by_bookmark().await {
-        Ok(orcks) => orcks,
-        Err(_) => {
-            queries
-                .query("description(glob:\"Orchestrator: O-*\")")
-                .await?
-        }
-    };
-    println!("\nOrchestrators: {}", orchestrators.len());
-
-    // Find in-progress tasks
-    let in_progress = queries.by_status("in_progress").await?;
-    println!("In Progress: {}", in_progress.len());
-
-    // Find blocked tasks
-    let blocked = queries.by_status("blocked").await?;
-    println!("Blocked: {}", blocked.len());
-
-    // Find parallelizable tasks (Phase 6 power query)
-    let parallelizable = queries.parallelizable_tasks().await?;
+    println!("\nOrchestrators: {}", report.orchestrators);
+    println!("In Progress: {}", report.in_progress);
+    println!("Blocked: {}", report.blocked);
     println!(
         "Parallelizable (independent heads): {}",
-        parallelizable.len()
+        report.parallelizable
     );
-
-    // Find empty/abandoned changes (Phase 6 power query)
-    let empty = queries.empty_changes().await?;
-    if !empty.is_empty() {
-        println!("Empty/Abandoned: {}", empty.len());
+    if report.empty > 0 {
+        println!("Empty/Abandoned: {}", report.empty);
     }
 
-    // Find conflicts
-    let conflicts = queries.conflicts().await?;
-    if !conflicts.is_empty() {
-        println!("\nConflicts: {}", conflicts.len());
-        for c in &conflicts {
-            println!("  - {}", c);
-
-            // Show what blocks this conflict (Phase 6 power query)
-            let blockers = queries.blocking_conflicts(c).await.unwrap_or_default();
-            if !blockers.is_empty() {
-                println!("    Blocked by: {} conflicting ancestor(s)", blockers.len());
+    if !report.conflicts.is_empty() {
+        println!("\nConflicts: {}", report.conflicts.len());
+        for conflict in &report.conflicts {
+            println!("  - {}", conflict.change_id);
+            if !conflict.blocking_conflicts.is_empty() {
+                println!(
+                    "    Blocked by: {} conflicting ancestor(s)",
+                    conflict.blocking_conflicts.len()
+                );
             }
         }
     }
 
+    if let Some(workload) = &report.agent {
+        println!(
+            "\nAgent {}: {} assigned, {} ready",
+            workload.agent,
+            workload.assigned.len(),
+            workload.ready.len()
+        );
+        for change_id in &workload.ready {
+            println!("  - {}", change_id);
+        }
+    }
+
     Ok(())
 }
 
 async fn cmd_patterns(action: PatternCommands) -> Result<()> {
     let hox_dir = PathBuf::from(".hox");
-    let mut store = PatternStore::new(hox_dir.join("patterns"));
+    let config = HoxConfig::load_or_default(std::path::Path::new("."))?;
+    let similarity_threshold = config.evolution.similarity_threshold;
+    let mut store =
+        PatternStore::new(hox_dir.join("patterns")).with_similarity_threshold(similarity_threshold);
     store.load().await?;
 
     match action {
@@ -717,6 +1058,7 @@ async fn cmd_patterns(action: PatternCommands) -> Result<()> {
                 return Ok(());
             }
 
+            let gate = ReviewGate::new();
             println!("Patterns:");
             for p in patterns {
                 println!(
@@ -726,13 +1068,20 @@ async fn cmd_patterns(action: PatternCommands) -> Result<()> {
                     if p.approved { "approved" } else { "pending" }
                 );
                 println!("    Category: {}", p.category);
-                println!("    Success: {:.0}%", p.success_rate * 100.0);
+                println!(
+                    "    Success: {:.0}% ({} uses)",
+                    p.success_rate * 100.0,
+                    p.usage_count
+                );
+                if gate.needs_rereview(p) {
+                    println!("    Success rate has dropped; needs re-review");
+                }
             }
         }
 
         PatternCommands::Propose { file } => {
             let content = tokio::fs::read_to_string(&file).await?;
-            let pattern: hox_evolution::Pattern = serde_json::from_str(&content)?;
+            let pattern = hox_evolution::Pattern::from_json(&content)?;
 
             store.propose(pattern.clone()).await?;
             println!("Proposed pattern: {} ({})", pattern.name, pattern.id);
@@ -753,64 +1102,212 @@ async fn cmd_patterns(action: PatternCommands) -> Result<()> {
                 println!("  Content: {}", p.content);
             }
         }
+
+        PatternCommands::Dedup { threshold } => {
+            let threshold = threshold.unwrap_or(similarity_threshold);
+            let clusters = store.similar_clusters(threshold);
+
+            if clusters.is_empty() {
+                println!(
+                    "No similar patterns found (threshold {:.0}%)",
+                    threshold * 100.0
+                );
+                return Ok(());
+            }
+
+            println!(
+                "{} cluster(s) of similar patterns (threshold {:.0}%):",
+                clusters.len(),
+                threshold * 100.0
+            );
+            for (i, cluster) in clusters.iter().enumerate() {
+                println!("\nCluster {}:", i + 1);
+                let first = cluster[0];
+                for p in cluster {
+                    println!(
+                        "  {} - {} ({}, {:.0}% similar)",
+                        p.id,
+                        p.name,
+                        if p.approved { "approved" } else { "pending" },
+                        first.similarity(p) * 100.0
+                    );
+                    println!("    When: {}", p.when);
+                }
+            }
+        }
     }
 
     Ok(())
 }
 
-async fn cmd_validate(change: String, validator_count: usize) -> Result<()> {
+async fn cmd_validate(
+    change: String,
+    validator_count: usize,
+    detail: bool,
+    weights: Vec<f64>,
+    max_weight: f64,
+) -> Result<()> {
     info!("Validating change: {}", change);
 
+    if weights.len() > validator_count {
+        anyhow::bail!(
+            "Got {} weights for {} validators",
+            weights.len(),
+            validator_count
+        );
+    }
+    if let Some(w) = weights.iter().find(|w| !w.is_finite() || **w < 0.0) {
+        anyhow::bail!("Invalid validator weight: {}", w);
+    }
+    if !max_weight.is_finite() || max_weight <= 0.0 {
+        anyhow::bail!("Invalid --max-weight: {}", max_weight);
+    }
+    if let Some(w) = weights.iter().find(|w| **w > max_weight) {
+        warn!(
+            "Validator weight {} exceeds --max-weight {}; it will count as {}",
+            w, max_weight, max_weight
+        );
+    }
+    let weight_of = |i: usize| weights.get(i).copied().unwrap_or(1.0);
+
     let config = ConsensusConfig {
         fault_tolerance: (validator_count - 1) / 3,
         threshold: 0.75,
+        max_weight,
+        ..Default::default()
     };
+    let vote_timeout = config.vote_timeout;
 
     let mut consensus = ByzantineConsensus::new(config);
 
-    // Run validators
-    for i in 0..validator_count {
-        let validator_config = ValidatorConfig::default();
-        let validator = Validator::new(validator_config);
+    // Run validators concurrently; consensus stops waiting once the outcome is decided
+    let votes = futures::stream::iter(0..validator_count)
+        .map(|i| {
+            let change = change.clone();
+            async move {
+                let validator = Validator::new(ValidatorConfig::default());
+                match validator.validate(&change).await {
+                    Ok(report) => {
+                        println!(
+                            "Validator {}: {:?} (score: {:.2})",
+                            i + 1,
+                            report.result,
+                            report.score
+                        );
+                        let mut dimensions: Vec<_> = report.dimensions.iter().collect();
+                        dimensions.sort_by(|a, b| a.0.cmp(b.0));
+                        if !dimensions.is_empty() {
+                            let breakdown: Vec<String> = dimensions
+                                .iter()
+                                .map(|(name, score)| format!("{} {:.2}", name, score))
+                                .collect();
+                            println!("  {}", breakdown.join(", "));
+                        }
+                        Some(hox_validation::Vote {
+                            validator_id: validator.id().to_string(),
+                            change_id: change,
+                            result: report.result.clone(),
+                            score: report.score,
+                            report,
+                            weight: weight_of(i),
+                        })
+                    }
+                    Err(e) => {
+                        warn!("Validator {} failed: {}", i + 1, e);
+                        None
+                    }
+                }
+            }
+        })
+        .buffer_unordered(validator_count.max(1))
+        .filter_map(futures::future::ready);
 
-        let report = validator.validate(&change).await?;
+    // Check consensus
+    let result = consensus
+        .collect_votes(&change, validator_count, votes, vote_timeout)
+        .await;
+    println!("\nConsensus: {:?}", result);
+
+    if detail {
+        let explanation = consensus.explain(&change);
+        println!("\nConsensus detail:");
         println!(
-            "Validator {}: {:?} (score: {:.2})",
-            i + 1,
-            report.result,
-            report.score
+            "  Votes: {} pass, {} fail, {} partial ({} total)",
+            explanation.pass_votes,
+            explanation.fail_votes,
+            explanation.partial_votes,
+            explanation.total
         );
-
-        consensus.add_vote(hox_validation::Vote {
-            validator_id: validator.id().to_string(),
-            change_id: change.clone(),
-            result: report.result.clone(),
-            score: report.score,
-            report,
-        });
+        println!(
+            "  Agreement: {:.2} for {:?} (threshold {:.2}, has {:.2} of {:.2} weight, needs {:.2})",
+            explanation.agreement,
+            explanation.leading,
+            explanation.threshold,
+            explanation.leading_weight,
+            explanation.total_weight,
+            explanation.weight_needed
+        );
+        let quorum_status = if explanation.quorum_met {
+            "met"
+        } else {
+            "not met"
+        };
+        println!(
+            "  Quorum: {} validators for f={} ({})",
+            explanation.quorum, explanation.fault_tolerance, quorum_status
+        );
+        if explanation.outliers.is_empty() {
+            println!("  Outliers: none");
+        } else {
+            println!("  Outliers: {}", explanation.outliers.join(", "));
+        }
     }
 
-    // Check consensus
-    let result = consensus.reach_consensus(&change);
-    println!("\nConsensus: {:?}", result);
-
     Ok(())
 }
 
-async fn cmd_query(revset: String) -> Result<()> {
+async fn cmd_query(revset: String, limit: Option<usize>, offset: usize) -> Result<()> {
     let jj = JjCommand::detect()
         .await
         .context("Not in a JJ repository")?;
     let queries = RevsetQueries::new(jj);
 
-    let changes = queries.query(&revset).await?;
+    let (changes, total) = match limit {
+        Some(limit) => (
+            queries.query_paginated(&revset, offset, limit).await?,
+            queries.count(&revset).await?,
+        ),
+        None if offset > 0 => {
+            let total = queries.count(&revset).await?;
+            (
+                queries
+                    .query_paginated(&revset, offset, total.saturating_sub(offset))
+                    .await?,
+                total,
+            )
+        }
+        None => {
+            let changes = queries.query(&revset).await?;
+            let total = changes.len();
+            (changes, total)
+        }
+    };
 
     if changes.is_empty() {
         println!("No changes match: {}", revset);
         return Ok(());
     }
 
-    println!("Matching changes ({}):", changes.len());
+    if changes.len() == total {
+        println!("Matching changes ({}):", total);
+    } else {
+        println!(
+            "Matching changes ({}-{} of {}):",
+            offset + 1,
+            offset + changes.len(),
+            total
+        );
+    }
     for c in changes {
         println!("  {}", c);
     }
@@ -860,7 +1357,41 @@ async fn cmd_set(
     Ok(())
 }
 
+/// Watch for `hox loop stop` on `change_id`, setting `cancel` when it is requested
+async fn watch_loop_stop(
+    jj: &JjCommand,
+    change_id: &str,
+    cancel: &Arc<AtomicBool>,
+) -> tokio::task::JoinHandle<()> {
+    let full_change_id = ChangeIdResolver::new(jj.clone())
+        .normalize_change_id(change_id)
+        .await
+        .unwrap_or_else(|_| change_id.to_string());
+    let stop_file = stop_file_path(&jj.repo_root().join(".hox"), &full_change_id);
+    watch_stop_file(stop_file, Arc::clone(cancel))
+}
+
 async fn cmd_loop(action: LoopCommands) -> Result<()> {
+    let summary_json = matches!(
+        action,
+        LoopCommands::Start {
+            summary_json: true,
+            ..
+        }
+    );
+
+    let result = run_loop_command(action).await;
+    if let Err(e) = &result {
+        // Wrapping scripts still get a summary line when the loop fails
+        if summary_json {
+            let summary = LoopSummary::failed(format!("{:#}", e));
+            println!("{}", serde_json::to_string(&summary)?);
+        }
+    }
+    result
+}
+
+async fn run_loop_command(action: LoopCommands) -> Result<()> {
     let jj = JjCommand::detect()
         .await
         .context("Not in a JJ repository")?;
@@ -871,7 +1402,22 @@ async fn cmd_loop(action: LoopCommands) -> Result<()> {
             max_iterations,
             model,
             no_backpressure,
+            transient_retries,
+            pricing,
+            max_budget_usd,
+            summary_json,
         } => {
+            // Keep stdout clean for the JSON summary when requested
+            macro_rules! report {
+                ($($arg:tt)*) => {
+                    if summary_json {
+                        eprintln!($($arg)*)
+                    } else {
+                        println!($($arg)*)
+                    }
+                };
+            }
+
             info!(
                 "Starting loop on {} with model {:?}, max {} iterations",
                 change_id, model, max_iterations
@@ -894,18 +1440,24 @@ async fn cmd_loop(action: LoopCommands) -> Result<()> {
                 model: model.into(),
                 backpressure_enabled: !no_backpressure,
                 max_tokens: 16000,
-                max_budget_usd: None,
+                max_budget_usd,
+                transient_retry: transient_retries,
+                pricing: pricing
+                    .as_deref()
+                    .map(PricingTable::load)
+                    .transpose()
+                    .context("Failed to load pricing table")?,
             };
 
             // Create and run orchestrator
             let orch_config = OrchestratorConfig::new(OrchestratorId::root(), jj.repo_root());
             let mut orchestrator = Orchestrator::with_executor(orch_config, jj).await?;
 
-            println!("Starting Ralph-style loop...");
-            println!("  Task: {}", task.description.lines().next().unwrap_or(""));
-            println!("  Model: {:?}", model);
-            println!("  Max iterations: {}", max_iterations);
-            println!(
+            report!("Starting Ralph-style loop...");
+            report!("  Task: {}", task.description.lines().next().unwrap_or(""));
+            report!("  Model: {:?}", model);
+            report!("  Max iterations: {}", max_iterations);
+            report!(
                 "  Backpressure: {}",
                 if no_backpressure {
                     "disabled"
@@ -913,33 +1465,49 @@ async fn cmd_loop(action: LoopCommands) -> Result<()> {
                     "enabled"
                 }
             );
-            println!();
-
-            let result = orchestrator.run_loop(task, Some(config)).await?;
-
-            println!();
-            println!("Loop completed!");
-            println!("  Iterations: {}", result.iterations);
-            println!("  Success: {}", result.success);
-            println!("  Stop reason: {:?}", result.stop_reason);
-            println!("  Files created: {}", result.files_created.len());
-            println!("  Files modified: {}", result.files_modified.len());
-            println!(
+            report!();
+
+            // Ctrl-C stops the loop gracefully, killing in-flight backpressure checks
+            let cancel = orchestrator.loop_cancellation_token();
+            let interrupt_watcher = tokio::spawn(async move {
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    warn!("Interrupt received, stopping loop");
+                    cancel.store(true, Ordering::SeqCst);
+                }
+            });
+            let result = orchestrator.run_loop(task, Some(config)).await;
+            interrupt_watcher.abort();
+            let result = result?;
+
+            report!();
+            report!("Loop completed!");
+            report!("  Iterations: {}", result.iterations);
+            report!("  Success: {}", result.success);
+            report!("  Stop reason: {:?}", result.stop_reason);
+            report!("  Files created: {}", result.files_created.len());
+            report!("  Files modified: {}", result.files_modified.len());
+            report!(
                 "  Tokens used: {} input, {} output",
-                result.total_usage.input_tokens, result.total_usage.output_tokens
+                result.total_usage.input_tokens,
+                result.total_usage.output_tokens
             );
+            report!("  Estimated cost: ${:.4}", result.total_cost_usd);
 
             if !result.success {
-                println!();
-                println!("Final backpressure status:");
+                report!();
+                report!("Final backpressure status:");
                 for check in &result.final_status.checks {
-                    println!(
+                    report!(
                         "  {}: {}",
                         check.name,
                         if check.passed { "PASSED" } else { "FAILED" }
                     );
                 }
             }
+
+            if summary_json {
+                println!("{}", serde_json::to_string(&result.summary())?);
+            }
         }
 
         LoopCommands::Status { change_id } => {
@@ -977,13 +1545,21 @@ async fn cmd_loop(action: LoopCommands) -> Result<()> {
         }
 
         LoopCommands::Stop { change_id } => {
+            let full_change_id = ChangeIdResolver::new(jj.clone())
+                .normalize_change_id(&change_id)
+                .await?;
+            request_stop(&jj.repo_root().join(".hox"), &full_change_id).await?;
+
             let manager = MetadataManager::new(jj.clone());
             let mut metadata = manager.read(&change_id).await?;
 
             metadata.status = Some(hox_core::TaskStatus::Blocked);
             manager.set(&change_id, &metadata).await?;
 
-            println!("Marked {} as blocked (loop stopped)", change_id);
+            println!(
+                "Asked the running loop on {} to stop and marked it blocked",
+                change_id
+            );
         }
 
         LoopCommands::External {
@@ -1025,10 +1601,12 @@ async fn cmd_loop(action: LoopCommands) -> Result<()> {
             // Get backpressure from state or create initial
             let backpressure = state
                 .backpressure
+                .clone()
                 .unwrap_or_else(BackpressureResult::all_pass);
 
             // Next iteration number
             let iteration = state.iteration + 1;
+            let cancel = Arc::new(AtomicBool::new(false));
 
             // Build iteration config
             let config = ExternalIterationConfig {
@@ -1041,10 +1619,14 @@ async fn cmd_loop(action: LoopCommands) -> Result<()> {
                 max_tokens,
                 workspace_path: jj.repo_root().to_path_buf(),
                 run_backpressure: !no_backpressure,
+                cancel: &cancel,
             };
 
             // Run single iteration
-            let result = run_external_iteration(&config, &jj).await?;
+            let stop_watcher = watch_loop_stop(&jj, &change_id, &cancel).await;
+            let result = run_external_iteration(&config, &jj).await;
+            stop_watcher.abort();
+            let result = result?;
 
             // Output result as JSON to stdout
             let json = serde_json::to_string_pretty(&result)?;
@@ -1052,30 +1634,137 @@ async fn cmd_loop(action: LoopCommands) -> Result<()> {
 
             // Save updated state if requested
             if let Some(output_path) = output_state {
-                let new_state = ExternalLoopState {
-                    change_id: task.change_id.clone(),
-                    iteration,
-                    context: result.context.clone(),
-                    backpressure: Some(BackpressureResult::all_pass()),
-                    files_touched: {
-                        let mut files = state.files_touched.clone();
-                        files.extend(result.files_created.clone());
-                        files.extend(result.files_modified.clone());
-                        files
-                    },
+                save_state(&advance_state(&state, &result), &output_path).await?;
+            }
+        }
+
+        LoopCommands::Resume {
+            change_id,
+            state_file,
+            max_iterations,
+            model,
+            no_backpressure,
+            max_tokens,
+        } => {
+            let output = jj
+                .exec(&["log", "-r", &change_id, "-T", "description", "--no-graph"])
+                .await?;
+
+            if !output.success {
+                anyhow::bail!("Failed to get change description: {}", output.stderr);
+            }
+
+            let task = Task::new(&change_id, output.stdout.trim());
+
+            let mut state = if state_file.exists() {
+                load_state(&state_file).await?
+            } else {
+                let state = create_initial_state(jj.clone(), &task).await?;
+                save_state(&state, &state_file).await?;
+                state
+            };
+
+            if state.change_id != change_id {
+                anyhow::bail!(
+                    "State file {:?} belongs to change {}, not {}",
+                    state_file,
+                    state.change_id,
+                    change_id
+                );
+            }
+
+            println!(
+                "Resuming loop for {} at iteration {}",
+                change_id,
+                state.iteration + 1
+            );
+
+            let cancel = Arc::new(AtomicBool::new(false));
+            let stop_watcher = watch_loop_stop(&jj, &change_id, &cancel).await;
+
+            loop {
+                if max_iterations > 0 && state.iteration >= max_iterations {
+                    println!("Reached max iterations ({})", max_iterations);
+                    break;
+                }
+
+                let context: HandoffContext = serde_json::from_value(state.context.clone())
+                    .context("Failed to deserialize context from state")?;
+                let backpressure = state
+                    .backpressure
+                    .clone()
+                    .unwrap_or_else(BackpressureResult::all_pass);
+
+                let config = ExternalIterationConfig {
+                    task: &task,
+                    context: &context,
+                    backpressure: &backpressure,
+                    iteration: state.iteration + 1,
+                    max_iterations,
+                    model: model.into(),
+                    max_tokens,
+                    workspace_path: jj.repo_root().to_path_buf(),
+                    run_backpressure: !no_backpressure,
+                    cancel: &cancel,
                 };
 
-                save_state(&new_state, &output_path).await?;
+                // The state file always holds the last completed iteration
+                let result = tokio::select! {
+                    result = run_external_iteration(&config, &jj) => result?,
+                    _ = tokio::signal::ctrl_c() => {
+                        save_state(&state, &state_file).await?;
+                        println!(
+                            "\nInterrupted; state saved at iteration {} in {:?}",
+                            state.iteration, state_file
+                        );
+                        return Ok(());
+                    }
+                };
+
+                state = advance_state(&state, &result);
+                save_state(&state, &state_file).await?;
+
+                println!(
+                    "Iteration {}: {} file(s) changed, checks {}",
+                    result.iteration,
+                    result.files_created.len() + result.files_modified.len(),
+                    if result.success { "passed" } else { "failed" }
+                );
+
+                if cancel.load(Ordering::SeqCst) {
+                    println!("Stopped by hox loop stop");
+                    break;
+                }
+                if let Some(signal) = &result.stop_signal {
+                    println!("Stop signal detected ({})", signal);
+                    break;
+                }
+                if result.success && !no_backpressure {
+                    println!("All checks passed");
+                    break;
+                }
             }
+
+            stop_watcher.abort();
+            println!("State saved to {:?}", state_file);
         }
     }
 
     Ok(())
 }
 
-async fn cmd_viz(port: u16, refresh_ms: u64, max_oplog: usize, no_open: bool) -> Result<()> {
+async fn cmd_viz(
+    bind_host: String,
+    port: u16,
+    print_url_host: Option<String>,
+    refresh_ms: u64,
+    max_oplog: usize,
+    no_open: bool,
+) -> Result<()> {
     let config = hox_viz::VizConfig {
+        bind_host,
         port,
+        print_url_host,
         refresh_ms,
         max_oplog,
         open_browser: !no_open,
@@ -1100,6 +1789,33 @@ async fn cmd_dashboard(refresh_ms: u64, max_oplog: usize) -> Result<()> {
     Ok(())
 }
 
+async fn cmd_graph(format: GraphFormat, output: Option<PathBuf>) -> Result<()> {
+    let source = hox_dashboard::JjDataSource::new(hox_dashboard::DashboardConfig::default());
+    let state = source.fetch_state().await?;
+
+    let change_ids: Vec<String> = state
+        .agents
+        .iter()
+        .filter_map(|agent| agent.change_id.clone())
+        .collect();
+    let dag = hox_dashboard::fetch_change_graph(&change_ids).await?;
+
+    let rendered = match format {
+        GraphFormat::Dot => hox_dashboard::to_dot(&state, &dag),
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, rendered)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!("Wrote graph to {}", path.display());
+        }
+        None => print!("{}", rendered),
+    }
+
+    Ok(())
+}
+
 async fn cmd_bookmark(action: BookmarkCommands) -> Result<()> {
     let jj = JjCommand::detect()
         .await
@@ -1187,13 +1903,51 @@ async fn cmd_rollback(
     operation: Option<String>,
     count: Option<usize>,
     remove_workspace: bool,
+    list_checkpoints: bool,
+    checkpoint: Option<String>,
 ) -> Result<()> {
-    use hox_orchestrator::RecoveryManager;
-
     let jj = JjCommand::detect()
         .await
         .context("Not in a JJ repository")?;
-    let recovery_manager = RecoveryManager::new(jj.clone(), jj.repo_root().to_path_buf());
+    let recovery_manager = recovery_manager(&jj)?;
+
+    if list_checkpoints {
+        let checkpoints = recovery_manager.list_checkpoints().await?;
+        if checkpoints.is_empty() {
+            println!("No checkpoints recorded");
+            return Ok(());
+        }
+
+        println!("Checkpoints (oldest first):");
+        for point in checkpoints {
+            println!(
+                "  {} - {} ({})",
+                point.label.as_deref().unwrap_or("-"),
+                point.operation_id,
+                point.created_at.format("%Y-%m-%d %H:%M:%S")
+            );
+        }
+        return Ok(());
+    }
+
+    if let Some(label) = checkpoint {
+        let point = recovery_manager
+            .find_checkpoint(&label)
+            .await?
+            .with_context(|| format!("No checkpoint labeled '{}'", label))?;
+
+        info!("Restoring to checkpoint {} ({})", label, point.operation_id);
+        println!(
+            "Restoring to checkpoint {} ({})...",
+            label, point.operation_id
+        );
+
+        let result = recovery_manager.restore_from(&point).await?;
+
+        println!("Restore complete:");
+        println!("  Operations undone: {}", result.operations_undone);
+        return Ok(());
+    }
 
     // Determine rollback mode
     match (agent, operation, count) {
@@ -1262,6 +2016,8 @@ async fn cmd_rollback(
             println!("  hox rollback --operation <op-id>        # Restore to specific operation");
             println!("  hox rollback --count <n>                # Undo last N operations");
             println!("  hox rollback --agent <name> --operation <op-id> # Rollback agent work");
+            println!("  hox rollback --list-checkpoints         # List named checkpoints");
+            println!("  hox rollback --checkpoint <label>       # Restore to a checkpoint");
         }
 
         // Invalid combinations (e.g., agent without operation, count with agent, etc.)
@@ -1273,17 +2029,77 @@ async fn cmd_rollback(
     Ok(())
 }
 
-async fn cmd_dag(action: DagCommands) -> Result<()> {
+/// Recovery manager for the repo, keeping the configured number of checkpoints
+fn recovery_manager(jj: &JjCommand) -> Result<hox_orchestrator::RecoveryManager<JjCommand>> {
+    let config = HoxConfig::load_or_default(jj.repo_root())?;
+    Ok(
+        hox_orchestrator::RecoveryManager::new(jj.clone(), jj.repo_root().to_path_buf())
+            .with_max_checkpoints(config.recovery.max_checkpoints),
+    )
+}
+
+/// Record a checkpoint before a destructive DAG operation
+async fn checkpoint_before(jj: &JjCommand, label: &str) -> Result<()> {
+    let point = recovery_manager(jj)?.checkpoint(label).await?;
+    println!(
+        "Checkpoint '{}' saved at {} (hox rollback --checkpoint {})",
+        label, point.operation_id, label
+    );
+    Ok(())
+}
+
+async fn cmd_dag(action: DagCommands, preview: bool) -> Result<()> {
     use hox_jj::DagOperations;
 
     let jj = JjCommand::detect()
         .await
         .context("Not in a JJ repository")?;
-    let dag_ops = DagOperations::new(jj);
+    let dag_ops = DagOperations::new(jj.clone()).with_dry_run(preview);
+
+    if preview
+        && matches!(
+            action,
+            DagCommands::Duplicate { .. }
+                | DagCommands::Backout { .. }
+                | DagCommands::SimplifyParents { .. }
+        )
+    {
+        anyhow::bail!(
+            "--preview is only supported for parallelize, absorb, split, squash and rebase"
+        );
+    }
 
     match action {
+        DagCommands::Parallelize { revset } if preview => {
+            let result = dag_ops.parallelize(&revset).await?;
+
+            println!("Parallelize preview (nothing applied):");
+            println!("  Changes to restructure: {}", result.changes_restructured);
+            println!("  Clean: {}", result.clean);
+
+            if !result.conflicts.is_empty() {
+                println!("  Files that would conflict:");
+                for file in &result.conflicts {
+                    println!("    - {}", file);
+                }
+            }
+        }
+
         DagCommands::Parallelize { revset } => {
             info!("Parallelizing changes in revset: {}", revset);
+
+            let feasibility = dag_ops.can_parallelize(&revset).await?;
+            if !feasibility.feasible {
+                println!(
+                    "Warning: {} changes touch overlapping files and will likely conflict:",
+                    feasibility.changes_checked
+                );
+                for file in &feasibility.overlapping_files {
+                    println!("    - {}", file);
+                }
+            }
+
+            checkpoint_before(&jj, "parallelize").await?;
             println!("Parallelizing changes: {}", revset);
 
             let result = dag_ops.parallelize(&revset).await?;
@@ -1300,16 +2116,40 @@ async fn cmd_dag(action: DagCommands) -> Result<()> {
             }
         }
 
-        DagCommands::Absorb { paths } => {
-            info!("Absorbing changes");
-            println!("Absorbing changes into ancestor commits...");
-
+        DagCommands::Absorb { paths, dry_run } => {
             let paths_refs: Option<Vec<&str>> = if paths.is_empty() {
                 None
             } else {
                 Some(paths.iter().map(|s| s.as_str()).collect())
             };
 
+            if dry_run || preview {
+                info!("Previewing absorb");
+                let plans = dag_ops.absorb_preview(paths_refs.as_deref()).await?;
+
+                if plans.is_empty() {
+                    println!("No working copy changes to absorb");
+                    return Ok(());
+                }
+
+                println!("Absorb preview ({} hunks):", plans.len());
+                for plan in &plans {
+                    println!(
+                        "  {} {} -> {}",
+                        plan.path,
+                        plan.hunk_summary,
+                        plan.target_change
+                            .as_deref()
+                            .unwrap_or("(stays in working copy)")
+                    );
+                }
+                return Ok(());
+            }
+
+            info!("Absorbing changes");
+            checkpoint_before(&jj, "absorb").await?;
+            println!("Absorbing changes into ancestor commits...");
+
             let result = dag_ops.absorb(paths_refs.as_deref()).await?;
 
             println!("Absorb complete:");
@@ -1324,16 +2164,34 @@ async fn cmd_dag(action: DagCommands) -> Result<()> {
             }
         }
 
-        DagCommands::Split { change_id, files } => {
-            info!("Splitting change {} by files", change_id);
+        DagCommands::Split {
+            change_id,
+            files,
+            hunks,
+        } => {
             println!("Splitting change {}...", change_id);
 
-            if files.is_empty() {
-                anyhow::bail!("No files provided for split. Specify at least one file.");
-            }
+            let result = if !hunks.is_empty() {
+                info!("Splitting change {} by hunks", change_id);
+                let selections = hunks
+                    .iter()
+                    .map(|h| parse_hunk_selection(h))
+                    .collect::<Result<Vec<_>>>()?;
+                dag_ops.split_by_hunks(&change_id, selections).await?
+            } else {
+                info!("Splitting change {} by files", change_id);
+                if files.is_empty() {
+                    anyhow::bail!("No files provided for split. Specify at least one file.");
+                }
 
-            let file_groups = vec![files];
-            let result = dag_ops.split_by_files(&change_id, &file_groups).await?;
+                let file_groups = vec![files];
+                dag_ops.split_by_files(&change_id, &file_groups).await?
+            };
+
+            if result.dry_run {
+                println!("Split preview: selection is valid, nothing applied");
+                return Ok(());
+            }
 
             println!("Split complete:");
             println!("  New changes created: {}", result.new_changes.len());
@@ -1348,15 +2206,25 @@ async fn cmd_dag(action: DagCommands) -> Result<()> {
 
         DagCommands::Squash { change_id } => {
             info!("Squashing change {}", change_id);
+            if !preview {
+                checkpoint_before(&jj, &format!("squash-{}", change_id)).await?;
+            }
             println!("Squashing change {} into parent...", change_id);
 
             dag_ops.squash(&change_id).await?;
 
-            println!("Squash complete: {} folded into parent", change_id);
+            if preview {
+                println!("Squash preview: {} would be folded into parent", change_id);
+            } else {
+                println!("Squash complete: {} folded into parent", change_id);
+            }
         }
 
         DagCommands::SquashInto { from, into, paths } => {
             info!("Squashing from {} into {}", from, into);
+            if !preview {
+                checkpoint_before(&jj, &format!("squash-{}", from)).await?;
+            }
             println!("Squashing from {} into {}...", from, into);
 
             let paths_refs: Option<Vec<&str>> = if paths.is_empty() {
@@ -1369,7 +2237,9 @@ async fn cmd_dag(action: DagCommands) -> Result<()> {
                 .squash_into(&from, &into, paths_refs.as_deref())
                 .await?;
 
-            if let Some(path_list) = paths_refs {
+            if preview {
+                println!("Squash preview: nothing applied");
+            } else if let Some(path_list) = paths_refs {
                 println!(
                     "Squash complete: moved {} files from {} to {}",
                     path_list.len(),
@@ -1384,6 +2254,31 @@ async fn cmd_dag(action: DagCommands) -> Result<()> {
             }
         }
 
+        DagCommands::Rebase {
+            change_id,
+            destination,
+            insert_before,
+        } => {
+            info!("Rebasing {} onto {}", change_id, destination);
+            println!("Rebasing {} onto {}...", change_id, destination);
+
+            let result = dag_ops
+                .rebase(&change_id, &destination, insert_before.as_deref())
+                .await?;
+
+            if result.dry_run {
+                println!("Rebase preview: {} would be moved", change_id);
+            } else {
+                println!("Rebase complete:");
+                println!("  Change ID: {}", result.change_id);
+                println!("  Conflicted: {}", result.conflicted);
+            }
+
+            if let Some(before) = insert_before {
+                println!("  Inserted before: {}", before);
+            }
+        }
+
         DagCommands::Duplicate {
             change_id,
             destination,
@@ -1419,13 +2314,26 @@ async fn cmd_dag(action: DagCommands) -> Result<()> {
             );
         }
 
-        DagCommands::Evolog { change_id } => {
+        DagCommands::Evolog {
+            change_id,
+            json,
+            since,
+        } => {
             info!("Getting evolution log for {}", change_id);
+
+            let entries = match since.as_deref() {
+                Some(op_id) => dag_ops.evolution_log_since(&change_id, op_id).await?,
+                None => dag_ops.evolution_log(&change_id).await?,
+            };
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+                return Ok(());
+            }
+
             println!("Evolution log for {}:", change_id);
             println!();
 
-            let entries = dag_ops.evolution_log(&change_id).await?;
-
             if entries.is_empty() {
                 println!("No evolution history found");
                 return Ok(());
@@ -1438,6 +2346,38 @@ async fn cmd_dag(action: DagCommands) -> Result<()> {
             }
         }
 
+        DagCommands::Timeline {
+            revset,
+            json,
+            since,
+        } => {
+            info!("Building evolution timeline for revset: {}", revset);
+
+            let change_ids = RevsetQueries::new(jj).query(&revset).await?;
+            let timeline = dag_ops.timeline(&change_ids, since.as_deref()).await?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&timeline)?);
+                return Ok(());
+            }
+
+            println!("Timeline for {} ({} changes):", revset, change_ids.len());
+            println!();
+
+            if timeline.is_empty() {
+                println!("No evolution history found");
+                return Ok(());
+            }
+
+            for item in &timeline {
+                println!(
+                    "  {} {} {}",
+                    item.entry.timestamp, item.change_id, item.entry.commit_id
+                );
+                println!("     {}", item.entry.description);
+            }
+        }
+
         DagCommands::SimplifyParents { change_id } => {
             info!("Simplifying parents for {}", change_id);
             println!("Simplifying parent relationships for {}...", change_id);
@@ -1450,3 +2390,227 @@ async fn cmd_dag(action: DagCommands) -> Result<()> {
 
     Ok(())
 }
+
+async fn cmd_export(bundle: PathBuf) -> Result<()> {
+    let jj = JjCommand::detect()
+        .await
+        .context("Not in a JJ repository")?;
+    let hox_dir = jj.repo_root().join(".hox");
+
+    if !hox_dir.exists() {
+        anyhow::bail!("No .hox directory found. Run 'hox init' first.");
+    }
+
+    let snapshot = hox_orchestrator::snapshot_metadata(jj.clone()).await?;
+    let entries = hox_orchestrator::export_bundle(&hox_dir, &snapshot, &bundle).await?;
+
+    println!("Exported bundle to {:?}", bundle);
+    for entry in &entries {
+        println!("  {}", entry);
+    }
+    println!("Metadata snapshot: {} task(s)", snapshot.len());
+
+    Ok(())
+}
+
+async fn cmd_import(bundle: PathBuf, path: PathBuf) -> Result<()> {
+    let hox_dir = path.join(".hox");
+    let members = hox_orchestrator::import_bundle(&bundle, &hox_dir).await?;
+
+    println!("Imported {} file(s) into {:?}", members.len(), hox_dir);
+    println!(
+        "Task metadata snapshot saved to .hox/{}",
+        hox_orchestrator::METADATA_SNAPSHOT_FILE
+    );
+
+    Ok(())
+}
+
+async fn cmd_metrics(action: MetricsCommands) -> Result<()> {
+    match action {
+        MetricsCommands::Export { format, file } => {
+            let file = match file {
+                Some(file) => file,
+                None => {
+                    let jj = JjCommand::detect()
+                        .await
+                        .context("Not in a JJ repository")?;
+                    jj.repo_root()
+                        .join(".hox")
+                        .join("metrics")
+                        .join(hox_metrics::METRICS_FILE)
+                }
+            };
+
+            let snapshot = hox_metrics::MetricsStorage::append_file(file)
+                .snapshot()
+                .await?;
+
+            match format {
+                MetricsFormat::Prometheus => print!("{}", snapshot.to_prometheus()),
+                MetricsFormat::Json => println!("{}", serde_json::to_string_pretty(&snapshot)?),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn cmd_agent(action: AgentCommands) -> Result<()> {
+    let jj = JjCommand::detect()
+        .await
+        .context("Not in a JJ repository")?;
+
+    match action {
+        AgentCommands::Logs { agent, evolog } => {
+            let logs = hox_orchestrator::collect_agent_logs(jj, &agent, evolog).await?;
+
+            println!("Agent {}", logs.agent);
+            println!("==========");
+
+            if logs.changes.is_empty() {
+                println!("\nNo assigned changes");
+            } else {
+                println!("\nAssigned changes ({}):", logs.changes.len());
+                for change in &logs.changes {
+                    let status = change
+                        .metadata
+                        .status
+                        .map(|s| s.to_string())
+                        .unwrap_or_else(|| "unknown".to_string());
+                    println!(
+                        "\n  {} -> {} [{}]",
+                        change.task_id, change.change_id, status
+                    );
+                    println!("    {}", change.title);
+
+                    for entry in &change.evolog {
+                        println!(
+                            "      {} {} {}",
+                            entry.timestamp, entry.commit_id, entry.description
+                        );
+                    }
+                }
+            }
+
+            if logs.activity.is_empty() {
+                println!("\nNo activity log entries");
+            } else {
+                println!("\nActivity:");
+                for section in &logs.activity {
+                    println!();
+                    for line in section.lines() {
+                        println!("  {}", line);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse a `path:start-end` hunk selection for `hox dag split --hunk`
+fn parse_hunk_selection(spec: &str) -> Result<hox_jj::HunkSelection> {
+    let (path, range) = spec
+        .rsplit_once(':')
+        .ok_or_else(|| anyhow::anyhow!("Invalid hunk '{}': expected PATH:START-END", spec))?;
+    let (start, end) = range.split_once('-').unwrap_or((range, range));
+
+    Ok(hox_jj::HunkSelection::new(
+        path,
+        start
+            .parse()
+            .with_context(|| format!("Invalid start line in '{}'", spec))?,
+        end.parse()
+            .with_context(|| format!("Invalid end line in '{}'", spec))?,
+    ))
+}
+
+async fn cmd_workspace(action: WorkspaceCommands) -> Result<()> {
+    use hox_orchestrator::WorkspaceManager;
+    use std::time::{Duration, SystemTime};
+
+    let jj = JjCommand::detect()
+        .await
+        .context("Not in a JJ repository")?;
+    let mut workspace_manager = WorkspaceManager::new(jj);
+
+    match action {
+        WorkspaceCommands::List => {
+            let workspaces = workspace_manager.list_workspaces().await?;
+            let now = SystemTime::now();
+
+            println!("Workspaces ({}):", workspaces.len());
+            for ws in workspaces {
+                let age = match ws.last_modified {
+                    Some(modified) => {
+                        let hours =
+                            now.duration_since(modified).unwrap_or_default().as_secs() / 3600;
+                        format!("modified {}h ago", hours)
+                    }
+                    None => "missing directory".to_string(),
+                };
+                let path = if ws.path.as_os_str().is_empty() {
+                    "unknown root".to_string()
+                } else {
+                    ws.path.display().to_string()
+                };
+                let owner = if ws.managed {
+                    ""
+                } else {
+                    ", not created by hox"
+                };
+                println!(
+                    "  {} {} [{}] {} ({}{})",
+                    ws.name,
+                    ws.change_id.as_deref().unwrap_or("-"),
+                    if ws.idle { "idle" } else { "busy" },
+                    path,
+                    age,
+                    owner
+                );
+            }
+        }
+
+        WorkspaceCommands::Prune {
+            older_than_hours,
+            dry_run,
+        } => {
+            let older_than = Duration::from_secs(older_than_hours * 3600);
+
+            if dry_run {
+                let now = SystemTime::now();
+                let stale: Vec<_> = workspace_manager
+                    .list_workspaces()
+                    .await?
+                    .into_iter()
+                    .filter(|ws| ws.is_stale(older_than, now))
+                    .collect();
+
+                if stale.is_empty() {
+                    println!("No stale workspaces");
+                    return Ok(());
+                }
+
+                println!("Would prune {} workspaces:", stale.len());
+                for ws in stale {
+                    println!("  {}", ws.name);
+                }
+                return Ok(());
+            }
+
+            let pruned = workspace_manager.prune_stale(older_than).await?;
+            if pruned.is_empty() {
+                println!("No stale workspaces");
+            } else {
+                println!("Pruned {} workspaces:", pruned.len());
+                for name in pruned {
+                    println!("  {}", name);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
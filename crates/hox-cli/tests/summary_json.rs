@@ -0,0 +1,49 @@
+//! `hox loop start --summary-json` keeps stdout machine-readable
+
+#![cfg(unix)]
+
+use std::os::unix::fs::PermissionsExt;
+use std::process::Command;
+
+/// A `jj` that knows its root and the change description and fails everything else
+const FAKE_JJ: &str = r#"#!/bin/sh
+case "$1" in
+  root) pwd ;;
+  log) echo "Add a greeting" ;;
+  *) echo "Error: not supported by the test jj" >&2; exit 1 ;;
+esac
+"#;
+
+#[test]
+fn test_summary_json_is_the_only_stdout() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let bin = dir.path().join("bin");
+    std::fs::create_dir(&bin).unwrap();
+    let jj = bin.join("jj");
+    std::fs::write(&jj, FAKE_JJ).unwrap();
+    std::fs::set_permissions(&jj, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let path = std::env::join_paths(
+        std::iter::once(bin).chain(std::env::split_paths(&std::env::var_os("PATH").unwrap())),
+    )
+    .unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_hox"))
+        .args(["--verbose", "loop", "start", "qpvuntsm", "--summary-json"])
+        .current_dir(dir.path())
+        .env("PATH", path)
+        .env_remove("ANTHROPIC_API_KEY")
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    // Logs went to stderr
+    assert!(stderr.contains("Starting loop on qpvuntsm"), "{}", stderr);
+
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1, "stdout: {}", stdout);
+    let summary: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+    assert_eq!(summary["success"], false);
+    assert!(summary["stop_reason"]["Error"].is_string());
+    assert!(!output.status.success());
+}
@@ -4,7 +4,7 @@
 //! including protected files, loop defaults, backpressure checks, and model configuration.
 
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::Result;
 
@@ -28,6 +28,14 @@ pub struct HoxConfig {
     /// Model selection
     #[serde(default)]
     pub models: ModelConfig,
+
+    /// Recovery checkpoint settings
+    #[serde(default)]
+    pub recovery: RecoveryConfig,
+
+    /// Pattern learning settings
+    #[serde(default)]
+    pub evolution: EvolutionConfig,
 }
 
 /// Default loop execution parameters
@@ -68,6 +76,163 @@ pub struct SlowCheck {
     pub every_n_iterations: usize,
 }
 
+/// Explicit backpressure check commands
+///
+/// Loaded from `.hox/checks.toml` in the repo root. Entries are merged over
+/// auto-detected checks by name: a new name adds a check, a detected name
+/// overrides only the fields it sets, and `enabled = false` drops the check.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChecksConfig {
+    /// Configured checks (`[[check]]` tables)
+    #[serde(default, rename = "check")]
+    pub checks: Vec<CheckDefinition>,
+
+    /// Run every check inside this container (`[container]` table)
+    #[serde(default)]
+    pub container: Option<ContainerConfig>,
+
+    /// Scope test checks to the packages holding files touched this iteration
+    ///
+    /// This trades completeness for speed: tests of untouched packages,
+    /// including dependents of the touched ones, do not run, so regressions
+    /// there go unnoticed. Checks that cannot be scoped run the full suite.
+    #[serde(default)]
+    pub scoped_tests: bool,
+}
+
+/// A single configured check, or an override of a detected one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CheckDefinition {
+    /// Check name (e.g. "build", "test"); matches detected checks of the same name
+    pub name: String,
+
+    /// Program to execute (required when the check is not auto-detected)
+    #[serde(default)]
+    pub program: Option<String>,
+
+    /// Program arguments
+    #[serde(default)]
+    pub args: Option<Vec<String>>,
+
+    /// Timeout in seconds (backpressure default if unset)
+    #[serde(default, alias = "timeout")]
+    pub timeout_secs: Option<u64>,
+
+    /// Whether failures are fed back to the agent
+    #[serde(default)]
+    pub severity: Option<CheckSeverity>,
+
+    /// Shorthand for `severity = "breaking"`; `severity` wins if both are set
+    #[serde(default)]
+    pub breaking: Option<bool>,
+
+    /// Directory to run in, relative to the repo root (absolute paths and `..` are rejected)
+    #[serde(default)]
+    pub working_dir: Option<PathBuf>,
+
+    /// Set to `false` to disable a detected check
+    #[serde(default = "default_check_enabled")]
+    pub enabled: bool,
+}
+
+/// Severity of a configured check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckSeverity {
+    /// Failures are fed back to the agent as errors to fix
+    Breaking,
+    /// Failures are reported but do not steer the agent
+    Warning,
+}
+
+/// Container runtime that backpressure checks run inside
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerConfig {
+    /// Runtime program (e.g. "docker", "podman")
+    #[serde(default = "default_container_runtime")]
+    pub runtime: String,
+
+    /// Image to run checks in
+    pub image: String,
+
+    /// Path the workspace is mounted at inside the container
+    #[serde(default = "default_container_mount")]
+    pub mount_path: String,
+
+    /// Extra arguments for `<runtime> run`, placed before the image
+    #[serde(default)]
+    pub run_args: Vec<String>,
+}
+
+/// User-defined command hooks
+///
+/// Loaded from `.hox/hooks.toml` in the repo root (`[[hook]]` tables).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HooksConfig {
+    /// Configured hooks, run in file order
+    #[serde(default, rename = "hook")]
+    pub hooks: Vec<HookDefinition>,
+}
+
+/// An external command run at a lifecycle event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HookDefinition {
+    /// Hook name used in logs and results
+    pub name: String,
+
+    /// Program to execute
+    pub program: String,
+
+    /// Program arguments
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Lifecycle event the hook runs on
+    pub run_on: HookEvent,
+
+    /// Whether a failure aborts the pipeline instead of being logged
+    #[serde(default)]
+    pub required: bool,
+
+    /// Timeout in seconds (hook default if unset)
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+
+    /// Ordering priority; lower runs first
+    #[serde(default)]
+    pub priority: i32,
+
+    /// Names of hooks this one must run after
+    #[serde(default)]
+    pub after: Vec<String>,
+}
+
+/// Lifecycle events that hooks can run on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HookEvent {
+    /// After an agent iteration's tools have run
+    PostTools,
+    /// Before agent work is merged
+    PreIntegrate,
+    /// After agent work is merged and conflicts are resolved
+    PostIntegrate,
+}
+
+impl std::fmt::Display for HookEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            HookEvent::PostTools => "post-tools",
+            HookEvent::PreIntegrate => "pre-integrate",
+            HookEvent::PostIntegrate => "post-integrate",
+        })
+    }
+}
+
 /// Model configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelConfig {
@@ -80,6 +245,28 @@ pub struct ModelConfig {
     pub api_key_env: String,
 }
 
+/// Default number of recovery checkpoints kept before the oldest are pruned
+pub const DEFAULT_MAX_CHECKPOINTS: usize = 50;
+
+/// Recovery checkpoint configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryConfig {
+    /// Checkpoints kept in `.jj/hox-recovery.jsonl` before the oldest are pruned
+    #[serde(default = "default_max_checkpoints")]
+    pub max_checkpoints: usize,
+}
+
+/// Default Jaccard similarity at which two patterns are considered duplicates
+pub const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.8;
+
+/// Pattern learning configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvolutionConfig {
+    /// Jaccard similarity (0.0 to 1.0) at which two patterns count as duplicates
+    #[serde(default = "default_similarity_threshold")]
+    pub similarity_threshold: f64,
+}
+
 /// Supported programming languages
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Language {
@@ -107,10 +294,30 @@ fn default_fast_checks() -> Vec<String> {
     ]
 }
 
+fn default_check_enabled() -> bool {
+    true
+}
+
+fn default_container_runtime() -> String {
+    "docker".to_string()
+}
+
+fn default_container_mount() -> String {
+    "/workspace".to_string()
+}
+
 fn default_max_iterations() -> usize {
     20
 }
 
+fn default_max_checkpoints() -> usize {
+    DEFAULT_MAX_CHECKPOINTS
+}
+
+fn default_similarity_threshold() -> f64 {
+    DEFAULT_SIMILARITY_THRESHOLD
+}
+
 fn default_model() -> String {
     "claude-sonnet-4".to_string()
 }
@@ -126,14 +333,28 @@ impl HoxConfig {
 
         if config_path.exists() {
             let content = std::fs::read_to_string(&config_path)?;
-            Ok(toml::from_str(&content).map_err(|e| {
+            let config: Self = toml::from_str(&content).map_err(|e| {
                 crate::HoxError::Other(format!("Failed to parse config file: {}", e))
-            })?)
+            })?;
+            config.validate()?;
+            Ok(config)
         } else {
             Ok(Self::default())
         }
     }
 
+    /// Reject values outside their documented ranges
+    fn validate(&self) -> Result<()> {
+        let threshold = self.evolution.similarity_threshold;
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(crate::HoxError::Other(format!(
+                "evolution.similarity_threshold must be between 0.0 and 1.0, got {}",
+                threshold
+            )));
+        }
+        Ok(())
+    }
+
     /// Write default configuration to `.hox/config.toml`
     pub fn write_default(repo_root: &Path) -> Result<()> {
         let config_dir = repo_root.join(".hox");
@@ -192,6 +413,38 @@ impl HoxConfig {
     }
 }
 
+impl ChecksConfig {
+    /// Load checks from `.hox/checks.toml`, or `None` if the file does not exist
+    pub fn load(repo_root: &Path) -> Result<Option<Self>> {
+        let config_path = repo_root.join(".hox/checks.toml");
+
+        if !config_path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&config_path)?;
+        toml::from_str(&content).map(Some).map_err(|e| {
+            crate::HoxError::Other(format!("Failed to parse {}: {}", config_path.display(), e))
+        })
+    }
+}
+
+impl HooksConfig {
+    /// Load hooks from `.hox/hooks.toml`, or `None` if the file does not exist
+    pub fn load(repo_root: &Path) -> Result<Option<Self>> {
+        let config_path = repo_root.join(".hox/hooks.toml");
+
+        if !config_path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&config_path)?;
+        toml::from_str(&content).map(Some).map_err(|e| {
+            crate::HoxError::Other(format!("Failed to parse {}: {}", config_path.display(), e))
+        })
+    }
+}
+
 impl Default for HoxConfig {
     fn default() -> Self {
         Self {
@@ -199,6 +452,8 @@ impl Default for HoxConfig {
             loop_defaults: LoopDefaults::default(),
             backpressure: BackpressureConfig::default(),
             models: ModelConfig::default(),
+            recovery: RecoveryConfig::default(),
+            evolution: EvolutionConfig::default(),
         }
     }
 }
@@ -222,6 +477,22 @@ impl Default for BackpressureConfig {
     }
 }
 
+impl Default for RecoveryConfig {
+    fn default() -> Self {
+        Self {
+            max_checkpoints: default_max_checkpoints(),
+        }
+    }
+}
+
+impl Default for EvolutionConfig {
+    fn default() -> Self {
+        Self {
+            similarity_threshold: default_similarity_threshold(),
+        }
+    }
+}
+
 impl Default for ModelConfig {
     fn default() -> Self {
         Self {
@@ -230,3 +501,32 @@ impl Default for ModelConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn load_with(config: &str) -> Result<HoxConfig> {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join(".hox")).unwrap();
+        std::fs::write(dir.path().join(".hox/config.toml"), config).unwrap();
+        HoxConfig::load_or_default(dir.path())
+    }
+
+    #[test]
+    fn test_similarity_threshold_range() {
+        let config = load_with("[evolution]\nsimilarity_threshold = 0.6\n").unwrap();
+        assert_eq!(config.evolution.similarity_threshold, 0.6);
+        assert_eq!(
+            HoxConfig::default().evolution.similarity_threshold,
+            DEFAULT_SIMILARITY_THRESHOLD
+        );
+
+        for value in ["1.5", "-0.1", "nan"] {
+            let err =
+                load_with(&format!("[evolution]\nsimilarity_threshold = {}\n", value)).unwrap_err();
+            assert!(err.to_string().contains("between 0.0 and 1.0"), "{}", err);
+        }
+    }
+}
@@ -116,5 +116,29 @@ pub enum HoxError {
     Other(String),
 }
 
+impl HoxError {
+    /// Whether the error comes from infrastructure (API availability, rate limits,
+    /// an open circuit breaker, network timeouts) rather than from the work itself
+    ///
+    /// API errors count only for 429 and 5xx responses and for requests that
+    /// never got a response; bad requests and unparseable responses do not.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            HoxError::ApiLimit(_) => true,
+            HoxError::Api(msg) => is_transient_api_message(msg),
+            _ => false,
+        }
+    }
+}
+
+/// Classify an API error by the message the agent client builds for it
+fn is_transient_api_message(msg: &str) -> bool {
+    if let Some(rest) = msg.strip_prefix("Anthropic API error ") {
+        let status: String = rest.chars().take_while(char::is_ascii_digit).collect();
+        return matches!(status.parse::<u16>(), Ok(429) | Ok(500..=599));
+    }
+    msg.starts_with("Failed to send request") || msg.contains("timed out")
+}
+
 /// Result type alias using HoxError
 pub type Result<T> = std::result::Result<T, HoxError>;
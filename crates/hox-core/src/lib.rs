@@ -21,6 +21,10 @@ mod error;
 pub mod fail_open;
 mod types;
 
-pub use config::{BackpressureConfig, HoxConfig, Language, LoopDefaults, ModelConfig, SlowCheck};
+pub use config::{
+    BackpressureConfig, CheckDefinition, CheckSeverity, ChecksConfig, ContainerConfig,
+    EvolutionConfig, HookDefinition, HookEvent, HooksConfig, HoxConfig, Language, LoopDefaults,
+    ModelConfig, RecoveryConfig, SlowCheck, DEFAULT_MAX_CHECKPOINTS, DEFAULT_SIMILARITY_THRESHOLD,
+};
 pub use error::{HoxError, Result};
 pub use types::*;
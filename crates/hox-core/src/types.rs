@@ -326,14 +326,14 @@ impl Task {
 }
 
 /// Status of a single backpressure check (for metadata tracking)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CheckStatusEntry {
     pub name: String,
     pub passed: bool,
 }
 
 /// Backpressure status for loop tracking
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct BackpressureStatus {
     pub checks: Vec<CheckStatusEntry>,
     pub last_errors: Vec<String>,
@@ -347,7 +347,7 @@ impl BackpressureStatus {
 }
 
 /// Handoff context for agent state preservation
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct HandoffContext {
     /// What the agent is currently focused on
     pub current_focus: String,
@@ -367,6 +367,121 @@ pub struct HandoffContext {
     pub backpressure_status: Option<BackpressureStatus>,
 }
 
+/// Opening line of a serialized [`HandoffContext`] block
+pub const HANDOFF_BLOCK_START: &str = "--- hox-handoff ---";
+
+/// Closing line of a serialized [`HandoffContext`] block
+pub const HANDOFF_BLOCK_END: &str = "--- end hox-handoff ---";
+
+impl HandoffContext {
+    /// Serialize into a delimited block for a change description
+    ///
+    /// Each value is written as one `key: value` line with backslashes and
+    /// line breaks escaped, so no value can end the block early.
+    pub fn to_description_block(&self) -> String {
+        let mut block = format!("{}\n", HANDOFF_BLOCK_START);
+        let mut field = |key: &str, value: &str| {
+            block.push_str(key);
+            block.push_str(": ");
+            block.push_str(&escape_block_value(value));
+            block.push('\n');
+        };
+
+        field("focus", &self.current_focus);
+        for (key, items) in [
+            ("progress", &self.progress),
+            ("next", &self.next_steps),
+            ("blocker", &self.blockers),
+            ("file", &self.files_touched),
+            ("decision", &self.decisions),
+        ] {
+            for item in items {
+                field(key, item);
+            }
+        }
+        if let Some(iteration) = self.loop_iteration {
+            field("iteration", &iteration.to_string());
+        }
+        if let Some(status) = &self.backpressure_status {
+            // Serializing plain structs cannot fail
+            field(
+                "backpressure",
+                &serde_json::to_string(status).unwrap_or_default(),
+            );
+        }
+
+        block.push_str(HANDOFF_BLOCK_END);
+        block.push('\n');
+        block
+    }
+
+    /// Parse the first block written by [`to_description_block`](Self::to_description_block)
+    ///
+    /// Returns `None` if `text` has no complete block. Unknown keys are ignored.
+    pub fn from_description_block(text: &str) -> Option<Self> {
+        let mut lines = text
+            .lines()
+            .skip_while(|line| line.trim_end() != HANDOFF_BLOCK_START);
+        lines.next()?;
+
+        let mut context = Self::default();
+        for line in lines {
+            if line.trim_end() == HANDOFF_BLOCK_END {
+                return Some(context);
+            }
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = unescape_block_value(value.strip_prefix(' ').unwrap_or(value));
+
+            match key {
+                "focus" => context.current_focus = value,
+                "progress" => context.progress.push(value),
+                "next" => context.next_steps.push(value),
+                "blocker" => context.blockers.push(value),
+                "file" => context.files_touched.push(value),
+                "decision" => context.decisions.push(value),
+                "iteration" => context.loop_iteration = value.parse().ok(),
+                "backpressure" => context.backpressure_status = serde_json::from_str(&value).ok(),
+                _ => {}
+            }
+        }
+
+        None
+    }
+}
+
+fn escape_block_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape_block_value(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
 /// Agent telemetry data
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AgentTelemetry {
@@ -545,6 +660,7 @@ pub enum OrchestratorMessage {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_orchestrator_id_parsing() {
@@ -577,4 +693,91 @@ mod tests {
         let score = weights.calculate(1.0, 1.0, 1.0, 1.0);
         assert!((score - 1.0).abs() < 0.001);
     }
+
+    #[test]
+    fn test_handoff_block_round_trip() {
+        let context = HandoffContext {
+            current_focus: "Parser: error paths".to_string(),
+            progress: vec![
+                "Line one\nline two".to_string(),
+                HANDOFF_BLOCK_END.to_string(),
+            ],
+            next_steps: vec!["Handle C:\\paths".to_string()],
+            blockers: Vec::new(),
+            files_touched: vec!["src/parser.rs".to_string()],
+            decisions: vec!["  keep whitespace  ".to_string()],
+            loop_iteration: Some(3),
+            backpressure_status: Some(BackpressureStatus {
+                checks: vec![CheckStatusEntry {
+                    name: "test".to_string(),
+                    passed: false,
+                }],
+                last_errors: vec!["assertion failed\n  left: 1".to_string()],
+            }),
+        };
+
+        let description = format!("Task title\n\n{}", context.to_description_block());
+
+        assert_eq!(
+            HandoffContext::from_description_block(&description),
+            Some(context)
+        );
+    }
+
+    #[test]
+    fn test_handoff_block_requires_end_delimiter() {
+        let truncated = format!("{}\nfocus: partial\n", HANDOFF_BLOCK_START);
+        assert_eq!(HandoffContext::from_description_block(&truncated), None);
+        assert_eq!(HandoffContext::from_description_block("no block"), None);
+    }
+
+    fn arb_backpressure() -> impl Strategy<Value = BackpressureStatus> {
+        (
+            prop::collection::vec(
+                (any::<String>(), any::<bool>())
+                    .prop_map(|(name, passed)| CheckStatusEntry { name, passed }),
+                0..3,
+            ),
+            prop::collection::vec(any::<String>(), 0..3),
+        )
+            .prop_map(|(checks, last_errors)| BackpressureStatus {
+                checks,
+                last_errors,
+            })
+    }
+
+    fn arb_handoff_context() -> impl Strategy<Value = HandoffContext> {
+        let items = || prop::collection::vec(any::<String>(), 0..4);
+        (
+            any::<String>(),
+            (items(), items(), items(), items(), items()),
+            any::<Option<usize>>(),
+            prop::option::of(arb_backpressure()),
+        )
+            .prop_map(
+                |(
+                    current_focus,
+                    (progress, next_steps, blockers, files_touched, decisions),
+                    loop_iteration,
+                    backpressure_status,
+                )| HandoffContext {
+                    current_focus,
+                    progress,
+                    next_steps,
+                    blockers,
+                    files_touched,
+                    decisions,
+                    loop_iteration,
+                    backpressure_status,
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn prop_handoff_block_round_trip(context in arb_handoff_context()) {
+            let block = context.to_description_block();
+            prop_assert_eq!(HandoffContext::from_description_block(&block), Some(context));
+        }
+    }
 }
@@ -2,7 +2,9 @@
 //!
 //! The `App` struct holds the dashboard state and handles refresh cycles.
 
-use crate::{DashboardConfig, DashboardState, JjDataSource, Result};
+use crate::{
+    AgentFilter, AgentStatus, DashboardConfig, DashboardState, JjDataSource, JjOplogEntry, Result,
+};
 use std::time::{Duration, Instant};
 
 /// Main application state
@@ -19,6 +21,10 @@ pub struct App {
     pub last_refresh: Instant,
     /// Current tab selection
     pub selected_tab: TabSelection,
+    /// Active agents table filter
+    pub filter: Option<AgentFilter>,
+    /// Whether keystrokes are being typed into the filter
+    pub filter_input: bool,
 }
 
 /// Tab selection for multi-panel views
@@ -43,6 +49,8 @@ impl App {
             should_quit: false,
             last_refresh: Instant::now(),
             selected_tab: TabSelection::Overview,
+            filter: None,
+            filter_input: false,
         }
     }
 
@@ -53,12 +61,76 @@ impl App {
         Ok(())
     }
 
+    /// Append a live-tailed operation, keeping at most `max_oplog_entries`
+    ///
+    /// Operations already loaded by a refresh are ignored.
+    pub fn push_oplog_entry(&mut self, entry: JjOplogEntry) {
+        let oplog = &mut self.state.oplog;
+        // Refreshes and the watcher may render IDs at different lengths
+        if oplog
+            .iter()
+            .any(|e| e.id.starts_with(&entry.id) || entry.id.starts_with(&e.id))
+        {
+            return;
+        }
+
+        oplog.push(entry);
+        if oplog.len() > self.config.max_oplog_entries {
+            let excess = oplog.len() - self.config.max_oplog_entries;
+            oplog.drain(..excess);
+        }
+    }
+
     /// Check if refresh interval has elapsed
     pub fn should_refresh(&self) -> bool {
         let elapsed = self.last_refresh.elapsed();
         elapsed >= Duration::from_millis(self.config.refresh_ms)
     }
 
+    /// Start typing a filter query (`/`)
+    pub fn start_filter_input(&mut self) {
+        self.filter_input = true;
+        self.filter.get_or_insert_with(AgentFilter::default);
+    }
+
+    /// Append a character to the filter query
+    pub fn push_filter_char(&mut self, c: char) {
+        self.filter
+            .get_or_insert_with(AgentFilter::default)
+            .query
+            .push(c);
+    }
+
+    /// Remove the last character of the filter query
+    pub fn pop_filter_char(&mut self) {
+        if let Some(filter) = &mut self.filter {
+            filter.query.pop();
+        }
+    }
+
+    /// Stop typing, keeping the filter applied
+    pub fn finish_filter_input(&mut self) {
+        self.filter_input = false;
+        if self.filter.as_ref().is_some_and(AgentFilter::is_empty) {
+            self.filter = None;
+        }
+    }
+
+    /// Cycle the status filter through every `AgentStatus`, then off
+    pub fn cycle_status_filter(&mut self) {
+        let filter = self.filter.get_or_insert_with(AgentFilter::default);
+        filter.status = AgentStatus::next_filter(filter.status);
+        if filter.is_empty() {
+            self.filter = None;
+        }
+    }
+
+    /// Clear the filter and leave input mode
+    pub fn clear_filter(&mut self) {
+        self.filter = None;
+        self.filter_input = false;
+    }
+
     /// Move to next tab
     pub fn next_tab(&mut self) {
         self.selected_tab = match self.selected_tab {
@@ -118,8 +190,10 @@ mod tests {
 
     #[test]
     fn test_should_refresh() {
-        let mut config = DashboardConfig::default();
-        config.refresh_ms = 100; // 100ms refresh
+        let config = DashboardConfig {
+            refresh_ms: 100, // 100ms refresh
+            ..Default::default()
+        };
         let app = App::new(config);
 
         // Just created, should not refresh yet
@@ -129,4 +203,78 @@ mod tests {
         std::thread::sleep(Duration::from_millis(110));
         assert!(app.should_refresh());
     }
+
+    fn entry(id: &str) -> JjOplogEntry {
+        JjOplogEntry {
+            id: id.to_string(),
+            timestamp: chrono::Utc::now(),
+            description: "new empty commit".to_string(),
+            agent_id: None,
+            op_type: crate::JjOpType::New,
+            tags: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_push_oplog_entry_caps_and_dedupes() {
+        let config = DashboardConfig {
+            max_oplog_entries: 2,
+            ..Default::default()
+        };
+        let mut app = App::new(config);
+
+        app.push_oplog_entry(entry("aaa111"));
+        app.push_oplog_entry(entry("bbb222"));
+        app.push_oplog_entry(entry("bbb2"));
+        app.push_oplog_entry(entry("ccc333"));
+
+        let ids: Vec<_> = app.state.oplog.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["bbb222", "ccc333"]);
+    }
+
+    #[test]
+    fn test_filter_input() {
+        let mut app = App::new(DashboardConfig::default());
+
+        app.start_filter_input();
+        assert!(app.filter_input);
+        for c in "authx".chars() {
+            app.push_filter_char(c);
+        }
+        app.pop_filter_char();
+        app.finish_filter_input();
+
+        assert!(!app.filter_input);
+        assert_eq!(app.filter.as_ref().unwrap().query, "auth");
+
+        app.clear_filter();
+        assert!(app.filter.is_none());
+
+        // An empty query is dropped
+        app.start_filter_input();
+        app.finish_filter_input();
+        assert!(app.filter.is_none());
+    }
+
+    #[test]
+    fn test_cycle_status_filter() {
+        let mut app = App::new(DashboardConfig::default());
+
+        app.cycle_status_filter();
+        assert_eq!(
+            app.filter.as_ref().unwrap().status,
+            Some(AgentStatus::Pending)
+        );
+
+        for _ in 0..4 {
+            app.cycle_status_filter();
+        }
+        assert_eq!(
+            app.filter.as_ref().unwrap().status,
+            Some(AgentStatus::Blocked)
+        );
+
+        app.cycle_status_filter();
+        assert!(app.filter.is_none());
+    }
 }
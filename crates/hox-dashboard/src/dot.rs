@@ -0,0 +1,164 @@
+//! Graphviz DOT export of the orchestration graph
+//!
+//! Uses the same agents and phases as `AgentGraphWidget`, plus the change
+//! ancestry from JJ, so the graph can be rendered for documentation:
+//!
+//! ```bash
+//! hox graph --format dot | dot -Tsvg > orchestration.svg
+//! ```
+
+use crate::{jj_source::ChangeNode, AgentNode, AgentStatus, DashboardState};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+/// Render the orchestration as a DOT digraph
+///
+/// Agents are grouped into one cluster per phase and filled by status. Edges
+/// point from each change to its parents; changes in `dag` that no agent
+/// works on are drawn as plain intermediate nodes.
+pub fn to_dot(state: &DashboardState, dag: &[ChangeNode]) -> String {
+    let agents_by_change: HashMap<&str, &AgentNode> = state
+        .agents
+        .iter()
+        .filter_map(|agent| Some((agent.change_id.as_deref()?, agent)))
+        .collect();
+
+    let mut phases: BTreeMap<usize, Vec<&AgentNode>> = BTreeMap::new();
+    for agent in &state.agents {
+        phases.entry(agent.phase).or_default().push(agent);
+    }
+
+    let mut out = String::new();
+    out.push_str("digraph orchestration {\n");
+    out.push_str("  rankdir=BT;\n");
+    out.push_str("  node [shape=box, style=\"rounded,filled\", fontname=\"monospace\"];\n");
+
+    for (phase, agents) in &phases {
+        let _ = writeln!(out, "  subgraph cluster_phase_{} {{", phase);
+        let _ = writeln!(out, "    label=\"Phase {}\";", phase);
+        for agent in agents {
+            let _ = writeln!(
+                out,
+                "    {} [label=\"{}\\n{}\\n{:?}\", fillcolor=\"{}\"];",
+                quote(&node_id(agent)),
+                escape(&agent.name),
+                escape(agent.change_id.as_deref().unwrap_or("no change")),
+                agent.status,
+                status_fill(agent.status)
+            );
+        }
+        out.push_str("  }\n");
+    }
+
+    for change in dag {
+        if !agents_by_change.contains_key(change.change_id.as_str()) {
+            let _ = writeln!(
+                out,
+                "  {} [label=\"{}\\n{}\", fillcolor=\"white\"];",
+                quote(&change.change_id),
+                escape(&change.change_id),
+                escape(&change.description)
+            );
+        }
+    }
+
+    let node_for = |change_id: &str| {
+        agents_by_change
+            .get(change_id)
+            .map_or_else(|| change_id.to_string(), |agent| node_id(agent))
+    };
+    for change in dag {
+        for parent in &change.parents {
+            if dag.iter().any(|c| &c.change_id == parent) {
+                let _ = writeln!(
+                    out,
+                    "  {} -> {};",
+                    quote(&node_for(&change.change_id)),
+                    quote(&node_for(parent))
+                );
+            }
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// DOT node ID for an agent (its change ID, or the agent ID without one)
+fn node_id(agent: &AgentNode) -> String {
+    agent.change_id.clone().unwrap_or_else(|| agent.id.clone())
+}
+
+/// Graphviz fill color matching the dashboard's status colors
+fn status_fill(status: AgentStatus) -> &'static str {
+    match status.color_name() {
+        "yellow" => "gold",
+        "green" => "palegreen",
+        "red" => "salmon",
+        "magenta" => "plum",
+        _ => "lightgray",
+    }
+}
+
+fn quote(id: &str) -> String {
+    format!("\"{}\"", escape(id))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(id: &str, phase: usize, status: AgentStatus, change_id: &str) -> AgentNode {
+        let mut agent = AgentNode::new(id, id, phase);
+        agent.status = status;
+        agent.change_id = Some(change_id.to_string());
+        agent
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let state = DashboardState {
+            agents: vec![
+                agent("agent-1", 0, AgentStatus::Completed, "aaa"),
+                agent("agent-2", 1, AgentStatus::Failed, "ccc"),
+            ],
+            ..Default::default()
+        };
+        let dag = vec![
+            ChangeNode {
+                change_id: "aaa".to_string(),
+                description: "contracts".to_string(),
+                parents: vec!["root".to_string()],
+            },
+            ChangeNode {
+                change_id: "bbb".to_string(),
+                description: "say \"hi\"".to_string(),
+                parents: vec!["aaa".to_string()],
+            },
+            ChangeNode {
+                change_id: "ccc".to_string(),
+                description: "impl".to_string(),
+                parents: vec!["bbb".to_string()],
+            },
+        ];
+
+        let dot = to_dot(&state, &dag);
+
+        assert!(dot.starts_with("digraph orchestration {"));
+        assert!(dot.contains("subgraph cluster_phase_0"));
+        assert!(dot.contains("subgraph cluster_phase_1"));
+        assert!(
+            dot.contains("\"aaa\" [label=\"agent-1\\naaa\\nCompleted\", fillcolor=\"palegreen\"]")
+        );
+        assert!(dot.contains("fillcolor=\"salmon\""));
+        assert!(dot.contains("\"bbb\" [label=\"bbb\\nsay \\\"hi\\\"\", fillcolor=\"white\"]"));
+        assert!(dot.contains("\"ccc\" -> \"bbb\";"));
+        assert!(dot.contains("\"bbb\" -> \"aaa\";"));
+        // Parents outside the walked DAG are not drawn
+        assert!(!dot.contains("\"root\""));
+    }
+}
@@ -55,6 +55,21 @@ pub fn is_prev_tab_event(key: KeyEvent) -> bool {
         || (key.code == KeyCode::Tab && key.modifiers.contains(KeyModifiers::SHIFT))
 }
 
+/// Check if a key event starts filter input (/)
+pub fn is_filter_event(key: KeyEvent) -> bool {
+    key.code == KeyCode::Char('/')
+}
+
+/// Check if a key event cycles the agent status filter (s)
+pub fn is_status_filter_event(key: KeyEvent) -> bool {
+    matches!(key.code, KeyCode::Char('s') | KeyCode::Char('S'))
+}
+
+/// Check if a key event clears the filter (Esc)
+pub fn is_clear_filter_event(key: KeyEvent) -> bool {
+    key.code == KeyCode::Esc
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,4 +109,17 @@ mod tests {
         let backtab = KeyEvent::new(KeyCode::BackTab, KeyModifiers::NONE);
         assert!(is_prev_tab_event(backtab));
     }
+
+    #[test]
+    fn test_filter_events() {
+        let slash = KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE);
+        assert!(is_filter_event(slash));
+
+        let s = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::NONE);
+        assert!(is_status_filter_event(s));
+
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(is_clear_filter_event(esc));
+        assert!(!is_filter_event(esc));
+    }
 }
@@ -29,7 +29,9 @@ use crate::{
     OrchestrationSession, PhaseProgress, PhaseStatus, Result,
 };
 use chrono::{DateTime, Utc};
+use futures::stream::{self, Stream, StreamExt};
 use hox_core::HoxError;
+use hox_jj::{JjCommand, JjExecutor, OpLogEvent, OpLogWatcher, RevsetBuilder};
 use std::collections::HashMap;
 use tokio::process::Command;
 
@@ -58,6 +60,17 @@ pub struct CommitWithTrailers {
     pub trailers: HashMap<String, String>,
 }
 
+/// A change in the orchestration DAG with its parents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeNode {
+    /// Change ID (short form)
+    pub change_id: String,
+    /// First line of the description
+    pub description: String,
+    /// Parent change IDs (short form)
+    pub parents: Vec<String>,
+}
+
 /// JJ data source for live oplog and state tracking
 pub struct JjDataSource {
     config: DashboardConfig,
@@ -113,6 +126,23 @@ impl JjDataSource {
         })
     }
 
+    /// Stream new JJ operations as they appear
+    ///
+    /// Wraps hox-jj's `OpLogWatcher`, so each operation is emitted once,
+    /// shortly after it is recorded. The stream ends if no JJ repository is
+    /// found or the watcher stops.
+    pub fn tail(&self) -> impl Stream<Item = JjOplogEntry> {
+        stream::once(async {
+            let executor = JjCommand::detect().await?;
+            tail_watcher(OpLogWatcher::new(executor)).await
+        })
+        .filter_map(|tail| async move {
+            tail.map_err(|e| tracing::warn!("JJ oplog tail unavailable: {}", e))
+                .ok()
+        })
+        .flatten()
+    }
+
     /// Get current JJ bookmark
     pub async fn current_bookmark(&self) -> Option<String> {
         let output = Command::new("jj")
@@ -136,6 +166,106 @@ impl JjDataSource {
     }
 }
 
+/// Start a watcher and stream its new operations
+async fn tail_watcher<E: JjExecutor + 'static>(
+    watcher: OpLogWatcher<E>,
+) -> Result<impl Stream<Item = JjOplogEntry>> {
+    Ok(operation_stream(watcher.watch().await?))
+}
+
+/// Map watcher events to oplog entries, dropping lifecycle events
+fn operation_stream(
+    receiver: tokio::sync::mpsc::Receiver<OpLogEvent>,
+) -> impl Stream<Item = JjOplogEntry> {
+    stream::unfold(receiver, |mut receiver| async move {
+        let event = receiver.recv().await?;
+        Some((event, receiver))
+    })
+    .filter_map(|event| async move {
+        match event {
+            OpLogEvent::NewOperation {
+                operation_id,
+                description,
+                ..
+            } => Some(oplog_entry(operation_id, Utc::now(), description)),
+            OpLogEvent::Error(e) => {
+                tracing::debug!("JJ oplog tail error: {}", e);
+                None
+            }
+            OpLogEvent::Started | OpLogEvent::Stopped => None,
+        }
+    })
+}
+
+/// Length of the change IDs the dashboard prints and matches on
+const CHANGE_ID_LEN: usize = 12;
+
+/// Revset for the DAG connecting `change_ids`
+///
+/// Each ID is wrapped in `present()` so a change that no longer exists drops
+/// out instead of failing the query. IDs that are not valid revset symbols
+/// are skipped. `None` if no ID is usable.
+fn change_graph_revset(change_ids: &[String]) -> Option<String> {
+    change_ids
+        .iter()
+        .filter_map(|id| RevsetBuilder::change_id(id).ok())
+        .map(RevsetBuilder::present)
+        .reduce(RevsetBuilder::or)
+        .map(|ids| ids.connected().build())
+}
+
+/// Walk the change DAG connecting the given changes
+///
+/// Uses the `connected()` revset, so changes between two agents' changes
+/// are included too. Change IDs are printed at [`CHANGE_ID_LEN`] characters,
+/// the same length agents' change IDs are recorded at.
+pub async fn fetch_change_graph(change_ids: &[String]) -> Result<Vec<ChangeNode>> {
+    let Some(revset) = change_graph_revset(change_ids) else {
+        return Ok(Vec::new());
+    };
+
+    let template = format!(
+        r#"change_id.short({len}) ++ "\t" ++ parents.map(|p| p.change_id().short({len})).join(",") ++ "\t" ++ description.first_line() ++ "\n""#,
+        len = CHANGE_ID_LEN
+    );
+    let output = Command::new("jj")
+        .args(["log", "--no-graph", "-r", &revset, "-T", &template])
+        .output()
+        .await
+        .map_err(|e| HoxError::JjCommand(format!("Failed to execute jj: {}", e)))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(HoxError::JjCommand(format!("jj log failed: {}", stderr)));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout.lines().filter_map(parse_change_node).collect())
+}
+
+/// Parse a `change_id<TAB>parent,parent<TAB>description` line
+fn parse_change_node(line: &str) -> Option<ChangeNode> {
+    let mut fields = line.splitn(3, '\t');
+    let change_id = fields.next()?.trim();
+    if change_id.is_empty() {
+        return None;
+    }
+    let parents = fields
+        .next()
+        .unwrap_or("")
+        .split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(String::from)
+        .collect();
+
+    Some(ChangeNode {
+        change_id: change_id.to_string(),
+        description: fields.next().unwrap_or("").to_string(),
+        parents,
+    })
+}
+
 /// Fetch recent JJ operation log entries
 pub async fn fetch_oplog(limit: usize) -> Result<Vec<JjOplogEntry>> {
     let output = Command::new("jj")
@@ -191,7 +321,10 @@ pub async fn fetch_oplog(limit: usize) -> Result<Vec<JjOplogEntry>> {
 pub async fn fetch_commits_with_trailers(limit: usize) -> Result<Vec<CommitWithTrailers>> {
     // Template outputs: change_id|description_first_line|trailer1=value1,trailer2=value2
     // We use trailers().map() to format each trailer as key=value
-    let template = r#"change_id.short() ++ "|" ++ description.first_line() ++ "|" ++ trailers.map(|t| t.key() ++ "=" ++ t.value()).join(",") ++ "\n""#;
+    let template = format!(
+        r#"change_id.short({}) ++ "|" ++ description.first_line() ++ "|" ++ trailers.map(|t| t.key() ++ "=" ++ t.value()).join(",") ++ "\n""#,
+        CHANGE_ID_LEN
+    );
 
     let output = Command::new("jj")
         .args([
@@ -200,7 +333,7 @@ pub async fn fetch_commits_with_trailers(limit: usize) -> Result<Vec<CommitWithT
             "-n",
             &limit.to_string(),
             "-T",
-            template,
+            &template,
         ])
         .output()
         .await
@@ -368,6 +501,11 @@ fn parse_oplog_line(line: &str) -> Result<JjOplogEntry> {
             })?
             .with_timezone(&Utc);
 
+    Ok(oplog_entry(id, timestamp, description))
+}
+
+/// Build an oplog entry, inferring agent, operation type, and tags
+fn oplog_entry(id: String, timestamp: DateTime<Utc>, description: String) -> JjOplogEntry {
     // Extract agent ID if present
     let agent_id = extract_agent_id(&description);
 
@@ -377,14 +515,14 @@ fn parse_oplog_line(line: &str) -> Result<JjOplogEntry> {
     // Extract any tags/metadata from description
     let tags = extract_tags(&description);
 
-    Ok(JjOplogEntry {
+    JjOplogEntry {
         id,
         timestamp,
         description,
         agent_id,
         op_type,
         tags,
-    })
+    }
 }
 
 /// Extract agent ID from operation description
@@ -476,13 +614,13 @@ fn extract_agents_from_oplog(oplog: &[JjOplogEntry]) -> Vec<AgentNode> {
                     // Description updates indicate progress
                     agent.tool_calls += 1;
                 }
-                JjOpType::Commit | JjOpType::Squash => {
-                    // Commits often indicate completion
-                    if entry.description.contains("complete") || entry.description.contains("done")
-                    {
-                        agent.status = AgentStatus::Completed;
-                        agent.progress = 1.0;
-                    }
+                // Commits often indicate completion
+                JjOpType::Commit | JjOpType::Squash
+                    if entry.description.contains("complete")
+                        || entry.description.contains("done") =>
+                {
+                    agent.status = AgentStatus::Completed;
+                    agent.progress = 1.0;
                 }
                 // Other operation types (Snapshot, Restore, Rebase, etc.) don't affect agent status
                 _ => {}
@@ -521,14 +659,14 @@ fn extract_agents_from_oplog(oplog: &[JjOplogEntry]) -> Vec<AgentNode> {
 }
 
 /// Extract change ID from description
+///
+/// jj change IDs are written in the letters `k`-`z`; only words of exactly
+/// [`CHANGE_ID_LEN`] such letters count, so ordinary words don't match.
 fn extract_change_id(description: &str) -> Option<String> {
-    // Look for patterns like "abc123" (7-char hex-ish IDs)
-    for word in description.split_whitespace() {
-        if word.len() == 7 && word.chars().all(|c| c.is_ascii_alphanumeric()) {
-            return Some(word.to_string());
-        }
-    }
-    None
+    description
+        .split_whitespace()
+        .find(|word| word.len() == CHANGE_ID_LEN && word.chars().all(|c| matches!(c, 'k'..='z')))
+        .map(String::from)
 }
 
 // Default score for operations that don't fit standard categories
@@ -718,10 +856,28 @@ mod tests {
     #[test]
     fn test_extract_change_id() {
         assert_eq!(
-            extract_change_id("commit abc123d completed"),
-            Some("abc123d".to_string())
+            extract_change_id("commit qpvuntsmwlqt completed"),
+            Some("qpvuntsmwlqt".to_string())
         );
         assert_eq!(extract_change_id("no change id here"), None);
+        // Words and hex commit IDs are not change IDs
+        assert_eq!(extract_change_id("agent started working"), None);
+        assert_eq!(extract_change_id("commit abc123d456ef done"), None);
+    }
+
+    #[test]
+    fn test_change_graph_revset() {
+        let ids = vec![
+            "qpvuntsmwlqt".to_string(),
+            "bad id)".to_string(),
+            "zsuskulnrvyr".to_string(),
+        ];
+        assert_eq!(
+            change_graph_revset(&ids).unwrap(),
+            "connected(present(qpvuntsmwlqt) | present(zsuskulnrvyr))"
+        );
+        assert_eq!(change_graph_revset(&[]), None);
+        assert_eq!(change_graph_revset(&["x y".to_string()]), None);
     }
 
     #[test]
@@ -826,4 +982,54 @@ mod tests {
         assert_eq!(agent2.status, AgentStatus::Completed);
         assert_eq!(agent2.progress, 1.0); // Completed agents have 100% progress
     }
+
+    #[test]
+    fn test_parse_change_node() {
+        let node = parse_change_node("abc\tdef,ghi\tMerge work").unwrap();
+        assert_eq!(node.change_id, "abc");
+        assert_eq!(node.parents, vec!["def", "ghi"]);
+        assert_eq!(node.description, "Merge work");
+
+        let root = parse_change_node("zzz\t\t").unwrap();
+        assert!(root.parents.is_empty());
+        assert!(parse_change_node("").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tail_watcher_maps_operations() {
+        use hox_jj::{JjOutput, MockJjExecutor, OpLogWatcherConfig};
+        use std::time::Duration;
+
+        let dir = tempfile::tempdir().unwrap();
+        let cursor_file = dir.path().join("cursor");
+        std::fs::write(&cursor_file, "op-0").unwrap();
+
+        let ok = |stdout: &str| JjOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            success: true,
+            code: Some(0),
+        };
+        let executor = MockJjExecutor::new()
+            .with_response(
+                "op log -n 10 -T operation_id ++ \"\\t\" ++ description ++ \"\\n\" --no-graph",
+                ok("op-2\tsquash commits\nop-1\tnew empty commit\nop-0\tinit\n"),
+            )
+            .with_response(
+                "op log -n 1 -T operation_id ++ \"\\t\" ++ description --no-graph",
+                ok("op-2\tsquash commits"),
+            );
+        let watcher = OpLogWatcher::new(executor).with_config(OpLogWatcherConfig {
+            poll_interval: Duration::from_millis(10),
+            cursor_file: Some(cursor_file),
+            ..Default::default()
+        });
+
+        let entries: Vec<_> = tail_watcher(watcher).await.unwrap().take(2).collect().await;
+
+        assert_eq!(entries[0].id, "op-1");
+        assert_eq!(entries[0].op_type, JjOpType::New);
+        assert_eq!(entries[1].id, "op-2");
+        assert_eq!(entries[1].op_type, JjOpType::Squash);
+    }
 }
@@ -42,17 +42,19 @@
 mod state;
 
 pub use state::{
-    AgentNode, AgentStatus, DashboardConfig, DashboardState, GlobalMetrics, JjOpType, JjOplogEntry,
-    OrchestrationSession, PhaseProgress, PhaseStatus,
+    AgentFilter, AgentNode, AgentStatus, DashboardConfig, DashboardState, GlobalMetrics, JjOpType,
+    JjOplogEntry, OrchestrationSession, PhaseProgress, PhaseStatus,
 };
 
 // Phase 1a: Error types
 mod error;
 
+pub use dot::to_dot;
 pub use error::{DashboardError, Result};
-pub use jj_source::JjDataSource;
+pub use jj_source::{fetch_change_graph, ChangeNode, JjDataSource};
 
 // Phase 1 modules (to be implemented by parallel agents)
+mod dot;
 mod jj_source;
 mod widgets; // Agent 1b: Ratatui widgets ✓ // Agent 1c: JJ oplog data source
 
@@ -5,10 +5,13 @@
 use crate::{
     app::App,
     event::{
-        self, is_next_tab_event, is_prev_tab_event, is_quit_event, is_refresh_event, AppEvent,
+        self, is_clear_filter_event, is_filter_event, is_next_tab_event, is_prev_tab_event,
+        is_quit_event, is_refresh_event, is_status_filter_event, AppEvent,
     },
     terminal, ui, DashboardConfig, Result,
 };
+use crossterm::event::{KeyCode, KeyModifiers};
+use futures::{FutureExt, StreamExt};
 use std::time::Duration;
 
 /// Main entry point for running the dashboard
@@ -28,8 +31,16 @@ pub async fn run(config: DashboardConfig) -> Result<()> {
         // Continue anyway - empty state is valid
     }
 
+    // New operations arrive here between refreshes
+    let mut oplog_tail = Box::pin(app.data_source.tail());
+
     // Main event loop
     loop {
+        // Apply any operations recorded since the last frame
+        while let Some(Some(entry)) = oplog_tail.next().now_or_never() {
+            app.push_oplog_entry(entry);
+        }
+
         // Draw current state
         terminal.draw(|frame| ui::draw(frame, &app))?;
 
@@ -44,6 +55,18 @@ pub async fn run(config: DashboardConfig) -> Result<()> {
         // Poll for events with a short timeout
         let timeout = Duration::from_millis(100);
         match event::poll_event(timeout)? {
+            Some(AppEvent::Key(key)) if app.filter_input => {
+                // Typing a filter: keys edit the query instead of triggering commands
+                match key.code {
+                    KeyCode::Esc => app.clear_filter(),
+                    KeyCode::Enter => app.finish_filter_input(),
+                    KeyCode::Backspace => app.pop_filter_char(),
+                    KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.push_filter_char(c)
+                    }
+                    _ => {}
+                }
+            }
             Some(AppEvent::Key(key)) => {
                 if is_quit_event(key) {
                     break;
@@ -55,6 +78,12 @@ pub async fn run(config: DashboardConfig) -> Result<()> {
                     app.next_tab();
                 } else if is_prev_tab_event(key) {
                     app.prev_tab();
+                } else if is_filter_event(key) {
+                    app.start_filter_input();
+                } else if is_status_filter_event(key) {
+                    app.cycle_status_filter();
+                } else if is_clear_filter_event(key) {
+                    app.clear_filter();
                 }
             }
             Some(AppEvent::Resize(_, _)) => {
@@ -170,6 +170,59 @@ impl AgentStatus {
     }
 }
 
+impl AgentStatus {
+    /// Next status filter in the hotkey cycle (`None` shows every status)
+    pub fn next_filter(current: Option<Self>) -> Option<Self> {
+        match current {
+            None => Some(Self::Pending),
+            Some(Self::Pending) => Some(Self::Running),
+            Some(Self::Running) => Some(Self::Completed),
+            Some(Self::Completed) => Some(Self::Failed),
+            Some(Self::Failed) => Some(Self::Blocked),
+            Some(Self::Blocked) => None,
+        }
+    }
+}
+
+/// Filter applied to the agents table
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgentFilter {
+    /// Case-insensitive text matched against agent ID, name, and task
+    pub query: String,
+    /// Only show agents with this status
+    pub status: Option<AgentStatus>,
+}
+
+impl AgentFilter {
+    /// Whether an agent passes the filter
+    pub fn matches(&self, agent: &AgentNode) -> bool {
+        if self.status.is_some_and(|status| status != agent.status) {
+            return false;
+        }
+
+        let query = self.query.to_lowercase();
+        query.is_empty()
+            || [&agent.id, &agent.name, &agent.task]
+                .iter()
+                .any(|field| field.to_lowercase().contains(&query))
+    }
+
+    /// Whether the filter lets every agent through
+    pub fn is_empty(&self) -> bool {
+        self.query.is_empty() && self.status.is_none()
+    }
+
+    /// Short description for the table title
+    pub fn describe(&self) -> String {
+        match (self.query.is_empty(), self.status) {
+            (true, None) => String::new(),
+            (false, None) => format!("/{}", self.query),
+            (true, Some(status)) => format!("{:?}", status),
+            (false, Some(status)) => format!("/{} {:?}", self.query, status),
+        }
+    }
+}
+
 /// Parsed JJ operation log entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JjOplogEntry {
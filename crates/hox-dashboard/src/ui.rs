@@ -70,7 +70,11 @@ fn render_header(frame: &mut Frame, area: Rect, app: &App) {
         Span::styled("[r]", Style::default().fg(Color::Yellow)),
         Span::raw("efresh "),
         Span::styled("[Tab]", Style::default().fg(Color::Yellow)),
-        Span::raw(" switch"),
+        Span::raw(" switch "),
+        Span::styled("[/]", Style::default().fg(Color::Yellow)),
+        Span::raw("filter "),
+        Span::styled("[s]", Style::default().fg(Color::Yellow)),
+        Span::raw("tatus"),
     ])])
     .block(Block::default().borders(Borders::ALL))
     .alignment(Alignment::Right);
@@ -103,7 +107,9 @@ fn render_overview(frame: &mut Frame, area: Rect, app: &App) {
 
     // Agents table
     frame.render_widget(
-        WidgetAdapter::new(|area, buf| AgentTableWidget::render(&app.state, area, buf)),
+        WidgetAdapter::new(|area, buf| {
+            AgentTableWidget::render(&app.state, app.filter.as_ref(), area, buf)
+        }),
         chunks[2],
     );
 
@@ -139,7 +145,9 @@ fn render_agents(frame: &mut Frame, area: Rect, app: &App) {
 
     // Full agents table with more detail
     frame.render_widget(
-        WidgetAdapter::new(|area, buf| AgentTableWidget::render_detailed(&app.state, area, buf)),
+        WidgetAdapter::new(|area, buf| {
+            AgentTableWidget::render_detailed(&app.state, app.filter.as_ref(), area, buf)
+        }),
         chunks[1],
     );
 }
@@ -3,7 +3,7 @@
 //! Displays a table of active agents with their progress and status.
 
 use super::status_color_from_name;
-use crate::{AgentNode, DashboardState};
+use crate::{AgentFilter, AgentNode, DashboardState};
 use ratatui::{
     prelude::*,
     widgets::{Block, Borders, Cell, Row, Table},
@@ -12,18 +12,28 @@ use ratatui::{
 pub struct AgentTableWidget;
 
 impl AgentTableWidget {
-    /// Render the agents table
-    pub fn render(state: &DashboardState, area: Rect, buf: &mut Buffer) {
+    /// Render the agents table, showing only agents that pass `filter`
+    pub fn render(
+        state: &DashboardState,
+        filter: Option<&AgentFilter>,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let (agents, hidden) = Self::visible_agents(state, filter);
         let block = Block::default()
-            .title(" ACTIVE AGENTS ")
+            .title(Self::title("ACTIVE AGENTS", filter, hidden))
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Cyan));
 
         let inner = block.inner(area);
         block.render(area, buf);
 
-        if state.agents.is_empty() {
-            let empty_msg = "No active agents";
+        if agents.is_empty() {
+            let empty_msg = if hidden > 0 {
+                "No agents match the filter"
+            } else {
+                "No active agents"
+            };
             buf.set_string(
                 inner.x + 1,
                 inner.y,
@@ -57,11 +67,7 @@ impl AgentTableWidget {
         );
 
         // Create data rows
-        let rows: Vec<Row> = state
-            .agents
-            .iter()
-            .map(|agent| Self::create_agent_row(agent))
-            .collect();
+        let rows: Vec<Row> = agents.into_iter().map(Self::create_agent_row).collect();
 
         // Render table
         let table = Table::new(rows, widths).header(header).column_spacing(1);
@@ -69,6 +75,30 @@ impl AgentTableWidget {
         Widget::render(table, inner, buf);
     }
 
+    /// Agents passing the filter, and how many were hidden
+    fn visible_agents<'a>(
+        state: &'a DashboardState,
+        filter: Option<&AgentFilter>,
+    ) -> (Vec<&'a AgentNode>, usize) {
+        let agents: Vec<&AgentNode> = state
+            .agents
+            .iter()
+            .filter(|agent| filter.is_none_or(|f| f.matches(agent)))
+            .collect();
+        let hidden = state.agents.len() - agents.len();
+        (agents, hidden)
+    }
+
+    /// Table title, including the active filter and hidden row count
+    fn title(base: &str, filter: Option<&AgentFilter>, hidden: usize) -> String {
+        match filter.map(AgentFilter::describe) {
+            Some(desc) if !desc.is_empty() || hidden > 0 => {
+                format!(" {} [{}] {} hidden ", base, desc, hidden)
+            }
+            _ => format!(" {} ", base),
+        }
+    }
+
     /// Create a table row for an agent
     fn create_agent_row(agent: &AgentNode) -> Row<'static> {
         let agent_name = Self::truncate_text(&agent.name, 11);
@@ -123,18 +153,28 @@ impl AgentTableWidget {
         }
     }
 
-    /// Render with detailed view (more columns)
-    pub fn render_detailed(state: &DashboardState, area: Rect, buf: &mut Buffer) {
+    /// Render with detailed view (more columns), showing only agents that pass `filter`
+    pub fn render_detailed(
+        state: &DashboardState,
+        filter: Option<&AgentFilter>,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let (agents, hidden) = Self::visible_agents(state, filter);
         let block = Block::default()
-            .title(" ACTIVE AGENTS (DETAILED) ")
+            .title(Self::title("ACTIVE AGENTS (DETAILED)", filter, hidden))
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Cyan));
 
         let inner = block.inner(area);
         block.render(area, buf);
 
-        if state.agents.is_empty() {
-            let empty_msg = "No active agents";
+        if agents.is_empty() {
+            let empty_msg = if hidden > 0 {
+                "No agents match the filter"
+            } else {
+                "No active agents"
+            };
             buf.set_string(
                 inner.x + 1,
                 inner.y,
@@ -172,11 +212,7 @@ impl AgentTableWidget {
         );
 
         // Create data rows
-        let rows: Vec<Row> = state
-            .agents
-            .iter()
-            .map(|agent| Self::create_detailed_row(agent))
-            .collect();
+        let rows: Vec<Row> = agents.into_iter().map(Self::create_detailed_row).collect();
 
         // Render table
         let table = Table::new(rows, widths).header(header).column_spacing(1);
@@ -270,7 +306,7 @@ mod tests {
         let mut buf = Buffer::empty(Rect::new(0, 0, 80, 24));
         let area = Rect::new(0, 0, 80, 10);
 
-        AgentTableWidget::render(&state, area, &mut buf);
+        AgentTableWidget::render(&state, None, area, &mut buf);
         // Should not panic with empty state
     }
 
@@ -293,7 +329,40 @@ mod tests {
         let mut buf = Buffer::empty(Rect::new(0, 0, 100, 24));
         let area = Rect::new(0, 0, 100, 10);
 
-        AgentTableWidget::render_detailed(&state, area, &mut buf);
+        AgentTableWidget::render_detailed(&state, None, area, &mut buf);
         // Should not panic with detailed rendering
     }
+
+    #[test]
+    fn test_render_with_filter_counts_hidden() {
+        let mut state = DashboardState::default();
+        for (id, status, task) in [
+            ("agent-1", AgentStatus::Running, "Build auth"),
+            ("agent-2", AgentStatus::Failed, "Write docs"),
+            ("agent-3", AgentStatus::Running, "Test auth"),
+        ] {
+            let mut agent = AgentNode::new(id, id, 1);
+            agent.status = status;
+            agent.task = task.to_string();
+            state.agents.push(agent);
+        }
+        let filter = AgentFilter {
+            query: "AUTH".to_string(),
+            status: Some(AgentStatus::Running),
+        };
+
+        let (visible, hidden) = AgentTableWidget::visible_agents(&state, Some(&filter));
+        assert_eq!(visible.len(), 2);
+        assert_eq!(hidden, 1);
+        assert_eq!(
+            AgentTableWidget::title("ACTIVE AGENTS", Some(&filter), hidden),
+            " ACTIVE AGENTS [/AUTH Running] 1 hidden "
+        );
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 80, 10));
+        AgentTableWidget::render(&state, Some(&filter), Rect::new(0, 0, 80, 10), &mut buf);
+        let rendered: String = buf.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(rendered.contains("1 hidden"));
+        assert!(!rendered.contains("agent-2"));
+    }
 }
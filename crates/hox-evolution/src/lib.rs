@@ -15,6 +15,6 @@ mod review;
 
 pub use patterns::{
     builtin_patterns, AgentPerformance, OrchestrationTrace, Pattern, PatternCategory,
-    PatternExtractor, PatternStore, Suggestion, TaskContext,
+    PatternExtractor, PatternStore, Suggestion, TaskContext, DEFAULT_SIMILARITY_THRESHOLD,
 };
 pub use review::{ReviewGate, ReviewResult};
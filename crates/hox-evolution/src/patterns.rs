@@ -1,10 +1,11 @@
 //! Pattern capture and storage
 
 use chrono::{DateTime, Utc};
-use hox_core::{ChangeId, Result};
+pub use hox_core::DEFAULT_SIMILARITY_THRESHOLD;
+use hox_core::{ChangeId, HoxError, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use tracing::{debug, info};
+use std::collections::{HashMap, HashSet};
+use tracing::{debug, info, warn};
 
 /// Trace data from an orchestration run
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +20,9 @@ pub struct OrchestrationTrace {
     pub backpressure_config: Option<String>,
     /// Agent performance metrics
     pub agent_performance: Option<AgentPerformance>,
+    /// IDs of stored patterns that were applied during the run
+    #[serde(default)]
+    pub applied_patterns: Vec<String>,
 }
 
 /// Performance metrics for an agent
@@ -101,6 +105,9 @@ pub struct Pattern {
     pub success_rate: f32,
     /// Number of times this pattern has been used
     pub usage_count: u32,
+    /// Number of those uses that succeeded
+    #[serde(default)]
+    pub success_count: u32,
     /// When this pattern was captured
     pub captured_at: DateTime<Utc>,
     /// Source change where this was learned
@@ -124,6 +131,7 @@ impl Pattern {
             content: String::new(),
             success_rate: 0.0,
             usage_count: 0,
+            success_count: 0,
             captured_at: Utc::now(),
             source_change: None,
             approved: false,
@@ -140,6 +148,23 @@ impl Pattern {
         self
     }
 
+    /// Parse a pattern from its JSON file contents
+    ///
+    /// Patterns saved before `success_count` existed only carry
+    /// `success_rate` and `usage_count`; their success count is derived from
+    /// those so recording new outcomes doesn't reset the rate.
+    pub fn from_json(content: &str) -> Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(content)?;
+        let has_success_count = value.get("success_count").is_some();
+        let mut pattern: Pattern = serde_json::from_value(value)?;
+        if !has_success_count {
+            pattern.success_count = (pattern.success_rate * pattern.usage_count as f32)
+                .round()
+                .clamp(0.0, pattern.usage_count as f32) as u32;
+        }
+        Ok(pattern)
+    }
+
     pub fn with_source(mut self, change_id: ChangeId) -> Self {
         self.source_change = Some(change_id);
         self
@@ -149,12 +174,37 @@ impl Pattern {
         self.approved = true;
     }
 
+    /// Lowercased word tokens of `when` + `content`
+    fn tokens(&self) -> HashSet<String> {
+        self.when
+            .split(|c: char| !c.is_alphanumeric())
+            .chain(self.content.split(|c: char| !c.is_alphanumeric()))
+            .filter(|t| !t.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    }
+
+    /// Jaccard similarity (0.0 to 1.0) of the `when` + `content` token sets
+    pub fn similarity(&self, other: &Pattern) -> f64 {
+        let a = self.tokens();
+        let b = other.tokens();
+        let union = a.union(&b).count();
+        if union == 0 {
+            return 0.0;
+        }
+        a.intersection(&b).count() as f64 / union as f64
+    }
+
+    /// Record the outcome of a run that applied this pattern
+    ///
+    /// Once the pattern has real usage, `success_rate` is the observed
+    /// success ratio rather than the estimate it was captured with.
     pub fn record_usage(&mut self, success: bool) {
         self.usage_count += 1;
-        // Update success rate with exponential moving average
-        let alpha = 0.1;
-        let result = if success { 1.0 } else { 0.0 };
-        self.success_rate = alpha * result + (1.0 - alpha) * self.success_rate;
+        if success {
+            self.success_count += 1;
+        }
+        self.success_rate = self.success_count as f32 / self.usage_count as f32;
     }
 }
 
@@ -163,6 +213,8 @@ pub struct PatternStore {
     patterns: HashMap<String, Pattern>,
     /// Path to the patterns directory (in hox-patterns branch)
     patterns_path: std::path::PathBuf,
+    /// Similarity at which `propose` rejects a near-duplicate of an approved pattern
+    similarity_threshold: f64,
 }
 
 impl PatternStore {
@@ -170,9 +222,15 @@ impl PatternStore {
         Self {
             patterns: HashMap::new(),
             patterns_path: patterns_path.into(),
+            similarity_threshold: DEFAULT_SIMILARITY_THRESHOLD,
         }
     }
 
+    pub fn with_similarity_threshold(mut self, threshold: f64) -> Self {
+        self.similarity_threshold = threshold;
+        self
+    }
+
     /// Load patterns from the hox-patterns branch
     pub async fn load(&mut self) -> Result<()> {
         let path = &self.patterns_path;
@@ -207,8 +265,7 @@ impl PatternStore {
 
     async fn load_pattern_file(&self, path: &std::path::Path) -> Result<Pattern> {
         let content = tokio::fs::read_to_string(path).await?;
-        let pattern: Pattern = serde_json::from_str(&content)?;
-        Ok(pattern)
+        Pattern::from_json(&content)
     }
 
     /// Save a pattern to the store
@@ -235,6 +292,24 @@ impl PatternStore {
         self.patterns.get(id)
     }
 
+    /// Record the outcome of a run that applied a pattern, and persist it
+    pub async fn record_outcome(&mut self, pattern_id: &str, succeeded: bool) -> Result<()> {
+        let pattern = self
+            .patterns
+            .get_mut(pattern_id)
+            .ok_or_else(|| HoxError::Pattern(format!("Pattern not found: {}", pattern_id)))?;
+        pattern.record_usage(succeeded);
+        debug!(
+            "Pattern {} now {:.0}% over {} uses",
+            pattern.name,
+            pattern.success_rate * 100.0,
+            pattern.usage_count
+        );
+
+        let pattern = pattern.clone();
+        self.save(pattern).await
+    }
+
     /// Get patterns by category
     pub fn by_category(&self, category: &PatternCategory) -> Vec<&Pattern> {
         self.patterns
@@ -285,8 +360,84 @@ impl PatternStore {
         output
     }
 
+    /// Patterns (other than `pattern` itself) at or above `threshold` similarity,
+    /// most similar first
+    pub fn find_similar(&self, pattern: &Pattern, threshold: f64) -> Vec<&Pattern> {
+        let mut similar: Vec<(f64, &Pattern)> = self
+            .patterns
+            .values()
+            .filter(|p| p.id != pattern.id)
+            .map(|p| (pattern.similarity(p), p))
+            .filter(|(score, _)| *score >= threshold)
+            .collect();
+        similar.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+        similar.into_iter().map(|(_, p)| p).collect()
+    }
+
+    /// Group patterns into clusters of near-duplicates for review
+    ///
+    /// Clustering is single-linkage: a pattern joins a cluster if it is similar
+    /// to any member. Only clusters with two or more patterns are returned.
+    pub fn similar_clusters(&self, threshold: f64) -> Vec<Vec<&Pattern>> {
+        let mut patterns: Vec<&Pattern> = self.patterns.values().collect();
+        patterns.sort_by(|a, b| a.captured_at.cmp(&b.captured_at).then(a.id.cmp(&b.id)));
+
+        // Union-find over pattern indices
+        let mut parent: Vec<usize> = (0..patterns.len()).collect();
+        fn root(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+
+        for i in 0..patterns.len() {
+            for j in (i + 1)..patterns.len() {
+                if patterns[i].similarity(patterns[j]) >= threshold {
+                    let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+                    parent[b.max(a)] = a.min(b);
+                }
+            }
+        }
+
+        let mut clusters: Vec<Vec<&Pattern>> = Vec::new();
+        let mut cluster_of: HashMap<usize, usize> = HashMap::new();
+        for (i, pattern) in patterns.iter().enumerate() {
+            let r = root(&mut parent, i);
+            let index = *cluster_of.entry(r).or_insert_with(|| {
+                clusters.push(Vec::new());
+                clusters.len() - 1
+            });
+            clusters[index].push(*pattern);
+        }
+
+        clusters.retain(|c| c.len() > 1);
+        clusters
+    }
+
     /// Propose a new pattern (pending approval)
+    ///
+    /// Rejected if an approved pattern is already at or above the store's
+    /// similarity threshold; near-duplicates of pending patterns are only logged.
     pub async fn propose(&mut self, pattern: Pattern) -> Result<()> {
+        let similar = self.find_similar(&pattern, self.similarity_threshold);
+        if let Some(existing) = similar.iter().find(|p| p.approved) {
+            return Err(HoxError::Pattern(format!(
+                "Pattern '{}' duplicates approved pattern '{}' ({}, {:.0}% similar)",
+                pattern.name,
+                existing.name,
+                existing.id,
+                pattern.similarity(existing) * 100.0
+            )));
+        }
+        for existing in &similar {
+            warn!(
+                "Proposed pattern '{}' is similar to pending pattern '{}' ({})",
+                pattern.name, existing.name, existing.id
+            );
+        }
+
         info!("Proposing pattern: {}", pattern.name);
         self.save(pattern).await
     }
@@ -313,6 +464,25 @@ impl PatternExtractor {
         Self { store }
     }
 
+    /// The underlying pattern store
+    pub fn store(&self) -> &PatternStore {
+        &self.store
+    }
+
+    /// Record a completed run's outcome against every pattern it applied
+    ///
+    /// Unknown IDs (e.g. builtin patterns, which are not stored) are skipped.
+    pub async fn record_trace(&mut self, trace: &OrchestrationTrace) -> Result<()> {
+        for id in &trace.applied_patterns {
+            if self.store.get(id).is_none() {
+                debug!("Skipping outcome for unknown pattern {}", id);
+                continue;
+            }
+            self.store.record_outcome(id, trace.success).await?;
+        }
+        Ok(())
+    }
+
     /// Extract patterns from an orchestration trace
     pub fn extract_from_trace(&self, trace: &OrchestrationTrace) -> Vec<Pattern> {
         let mut patterns = Vec::new();
@@ -328,9 +498,9 @@ impl PatternExtractor {
                 ),
             )
             .with_when(format!("Working on {} tasks", trace.task_type))
-            .with_content(format!(
-                "This task type typically converges quickly. Consider using similar decomposition strategies."
-            ));
+            .with_content(
+                "This task type typically converges quickly. Consider using similar decomposition strategies.",
+            );
 
             pattern.success_rate = 0.7;
             patterns.push(pattern);
@@ -478,6 +648,7 @@ mod tests {
             task_type: "rust-feature".to_string(),
             success: true,
             backpressure_config: None,
+            applied_patterns: vec![],
             agent_performance: None,
         };
 
@@ -498,6 +669,7 @@ mod tests {
             task_type: "rust-feature".to_string(),
             success: false,
             backpressure_config: None,
+            applied_patterns: vec![],
             agent_performance: None,
         };
 
@@ -516,6 +688,7 @@ mod tests {
             task_type: "debugging".to_string(),
             success: true,
             backpressure_config: None,
+            applied_patterns: vec![],
             agent_performance: Some(AgentPerformance {
                 agent_id: "agent-123".to_string(),
                 success_rate: 0.85,
@@ -540,6 +713,7 @@ mod tests {
             task_type: "refactor".to_string(),
             success: true,
             backpressure_config: None,
+            applied_patterns: vec![],
             agent_performance: Some(AgentPerformance {
                 agent_id: "agent-456".to_string(),
                 success_rate: 0.9,
@@ -610,4 +784,169 @@ mod tests {
         let suggestions = extractor.suggest(&context);
         assert!(suggestions.is_empty());
     }
+
+    #[test]
+    fn test_similarity_of_similar_and_distinct_patterns() {
+        let a = Pattern::new("A", PatternCategory::Decomposition, "")
+            .with_when("Starting a feature with parallel agents")
+            .with_content("Define shared types first in a contracts phase");
+        let b = Pattern::new("B", PatternCategory::Decomposition, "")
+            .with_when("Starting a feature with parallel agents")
+            .with_content("Define shared types first in a contracts phase!");
+        let c = Pattern::new("C", PatternCategory::ErrorHandling, "")
+            .with_when("A validator times out")
+            .with_content("Retry once, then mark the check as inconclusive");
+
+        assert_eq!(a.similarity(&b), 1.0);
+        assert!(a.similarity(&c) < 0.1);
+        assert_eq!(
+            a.similarity(&Pattern::new("D", PatternCategory::Validation, "")),
+            0.0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_similar_and_clusters() {
+        let dir = tempdir().unwrap();
+        let mut store = PatternStore::new(dir.path());
+
+        let original = Pattern::new("Types First", PatternCategory::Decomposition, "")
+            .with_when("Starting a feature with parallel agents")
+            .with_content("Define all shared types before implementation");
+        let reworded = Pattern::new("Types Up Front", PatternCategory::Decomposition, "")
+            .with_when("Starting a feature with parallel agents")
+            .with_content("Define all shared types before any implementation");
+        let distinct = Pattern::new("Retry Flaky", PatternCategory::ErrorHandling, "")
+            .with_when("A check fails intermittently")
+            .with_content("Rerun the check before reporting failure");
+
+        store.save(original.clone()).await.unwrap();
+        store.save(reworded.clone()).await.unwrap();
+        store.save(distinct.clone()).await.unwrap();
+
+        let similar = store.find_similar(&original, 0.8);
+        assert_eq!(similar.len(), 1);
+        assert_eq!(similar[0].id, reworded.id);
+        assert!(store.find_similar(&distinct, 0.8).is_empty());
+
+        let clusters = store.similar_clusters(0.8);
+        assert_eq!(clusters.len(), 1);
+        let mut ids: Vec<&str> = clusters[0].iter().map(|p| p.id.as_str()).collect();
+        ids.sort();
+        let mut expected = vec![original.id.as_str(), reworded.id.as_str()];
+        expected.sort();
+        assert_eq!(ids, expected);
+    }
+
+    #[tokio::test]
+    async fn test_propose_rejects_duplicate_of_approved() {
+        let dir = tempdir().unwrap();
+        let mut store = PatternStore::new(dir.path()).with_similarity_threshold(0.9);
+
+        let mut approved = Pattern::new("Types First", PatternCategory::Decomposition, "")
+            .with_when("Starting a feature")
+            .with_content("Define shared types first");
+        approved.approve();
+        store.save(approved).await.unwrap();
+
+        let duplicate = Pattern::new("Types First Again", PatternCategory::Decomposition, "")
+            .with_when("Starting a feature")
+            .with_content("Define shared types first");
+        assert!(matches!(
+            store.propose(duplicate).await,
+            Err(HoxError::Pattern(_))
+        ));
+
+        let different = Pattern::new("Integration", PatternCategory::Integration, "")
+            .with_when("Decomposing parallel work")
+            .with_content("Always plan an integration phase");
+        store.propose(different).await.unwrap();
+        assert_eq!(store.pending().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_load_backfills_success_count() {
+        let dir = tempdir().unwrap();
+        let mut pattern = Pattern::new("Legacy", PatternCategory::Validation, "Legacy pattern");
+        pattern.success_rate = 0.75;
+        pattern.usage_count = 8;
+        let mut legacy = serde_json::to_value(&pattern).unwrap();
+        legacy.as_object_mut().unwrap().remove("success_count");
+        std::fs::write(
+            dir.path().join(format!("{}.json", pattern.id)),
+            legacy.to_string(),
+        )
+        .unwrap();
+
+        let mut store = PatternStore::new(dir.path());
+        store.load().await.unwrap();
+        assert_eq!(store.get(&pattern.id).unwrap().success_count, 6);
+
+        store.record_outcome(&pattern.id, true).await.unwrap();
+        let loaded = store.get(&pattern.id).unwrap();
+        assert_eq!(loaded.usage_count, 9);
+        assert!((loaded.success_rate - 7.0 / 9.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_record_usage_computes_rate() {
+        let mut pattern = Pattern::new("Rate", PatternCategory::Validation, "Rate test");
+        pattern.success_rate = 0.7;
+
+        for success in [true, true, false, true] {
+            pattern.record_usage(success);
+        }
+
+        assert_eq!(pattern.usage_count, 4);
+        assert_eq!(pattern.success_count, 3);
+        assert_eq!(pattern.success_rate, 0.75);
+    }
+
+    #[tokio::test]
+    async fn test_record_outcome_persists() {
+        let dir = tempdir().unwrap();
+        let mut store = PatternStore::new(dir.path());
+        let pattern = Pattern::new("Tracked", PatternCategory::Validation, "Tracked pattern");
+        let id = pattern.id.clone();
+        store.save(pattern).await.unwrap();
+
+        store.record_outcome(&id, true).await.unwrap();
+        store.record_outcome(&id, false).await.unwrap();
+        assert!(store.record_outcome("missing", true).await.is_err());
+
+        let mut reloaded = PatternStore::new(dir.path());
+        reloaded.load().await.unwrap();
+        let pattern = reloaded.get(&id).unwrap();
+        assert_eq!(pattern.usage_count, 2);
+        assert_eq!(pattern.success_rate, 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_record_trace_updates_applied_patterns() {
+        let dir = tempdir().unwrap();
+        let mut store = PatternStore::new(dir.path());
+        let applied = Pattern::new("Applied", PatternCategory::Decomposition, "Applied");
+        let untouched = Pattern::new("Untouched", PatternCategory::Decomposition, "Untouched");
+        let (applied_id, untouched_id) = (applied.id.clone(), untouched.id.clone());
+        store.save(applied).await.unwrap();
+        store.save(untouched).await.unwrap();
+
+        let mut extractor = PatternExtractor::new(store);
+        for success in [true, false, false] {
+            let trace = OrchestrationTrace {
+                iterations: 12,
+                task_type: "refactor".to_string(),
+                success,
+                backpressure_config: None,
+                applied_patterns: vec![applied_id.clone(), "builtin".to_string()],
+                agent_performance: None,
+            };
+            extractor.record_trace(&trace).await.unwrap();
+        }
+
+        let applied = extractor.store().get(&applied_id).unwrap();
+        assert_eq!(applied.usage_count, 3);
+        assert!((applied.success_rate - 1.0 / 3.0).abs() < f32::EPSILON);
+        assert_eq!(extractor.store().get(&untouched_id).unwrap().usage_count, 0);
+    }
 }
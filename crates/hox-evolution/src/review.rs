@@ -4,7 +4,7 @@ use hox_core::Result;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
-use crate::patterns::Pattern;
+use crate::patterns::{Pattern, PatternStore};
 
 /// Result of a review
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -25,6 +25,8 @@ pub struct ReviewGate {
     min_success_rate: f32,
     /// Minimum usage count to consider for auto-approval
     min_usage_count: u32,
+    /// Approved patterns whose observed success rate falls below this need re-review
+    rereview_below: f32,
 }
 
 impl ReviewGate {
@@ -33,9 +35,37 @@ impl ReviewGate {
             require_human: true,
             min_success_rate: 0.8,
             min_usage_count: 3,
+            rereview_below: 0.5,
         }
     }
 
+    /// Set the success rate below which approved patterns are flagged for re-review
+    pub fn with_rereview_threshold(mut self, threshold: f32) -> Self {
+        self.rereview_below = threshold;
+        self
+    }
+
+    /// Whether an approved pattern has degraded enough to need re-review
+    ///
+    /// Patterns with fewer than `min_usage_count` recorded uses are not
+    /// flagged, so a single failure right after approval isn't enough.
+    pub fn needs_rereview(&self, pattern: &Pattern) -> bool {
+        pattern.approved
+            && pattern.usage_count >= self.min_usage_count
+            && pattern.success_rate < self.rereview_below
+    }
+
+    /// Approved patterns in the store that need re-review, worst first
+    pub fn rereview_candidates<'a>(&self, store: &'a PatternStore) -> Vec<&'a Pattern> {
+        let mut candidates: Vec<&Pattern> = store
+            .approved()
+            .into_iter()
+            .filter(|p| self.needs_rereview(p))
+            .collect();
+        candidates.sort_by(|a, b| a.success_rate.total_cmp(&b.success_rate));
+        candidates
+    }
+
     /// Allow auto-approval for patterns meeting criteria
     pub fn with_auto_approve(mut self, min_success_rate: f32, min_usage_count: u32) -> Self {
         self.require_human = false;
@@ -208,4 +238,24 @@ mod tests {
         let result = gate.review(&pattern);
         assert_eq!(result, ReviewResult::Approved);
     }
+
+    #[test]
+    fn test_needs_rereview_after_degrading() {
+        let gate = ReviewGate::new().with_rereview_threshold(0.6);
+        let mut pattern = Pattern::new("Degrading", PatternCategory::Validation, "Degrading");
+        pattern.approve();
+
+        pattern.record_usage(false);
+        pattern.record_usage(false);
+        // Not enough uses yet
+        assert!(!gate.needs_rereview(&pattern));
+
+        pattern.record_usage(true);
+        assert!(gate.needs_rereview(&pattern));
+
+        for _ in 0..3 {
+            pattern.record_usage(true);
+        }
+        assert!(!gate.needs_rereview(&pattern));
+    }
 }
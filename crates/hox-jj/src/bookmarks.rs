@@ -81,6 +81,75 @@ impl<E: JjExecutor> BookmarkManager<E> {
         Ok(())
     }
 
+    /// Move an existing bookmark to a new change, keeping its tracking
+    ///
+    /// Executes: `jj bookmark move {name} --to {new_change_id} --allow-backwards`
+    ///
+    /// Use this after a task change is rebased or squashed. Backwards and
+    /// sideways moves are allowed since the new change is rarely a descendant.
+    #[instrument(skip(self))]
+    pub async fn move_bookmark(&self, name: &str, new_change_id: &str) -> Result<BookmarkInfo> {
+        validate_identifier(name, "bookmark")?;
+        validate_identifier(new_change_id, "change_id")?;
+        debug!("Moving bookmark {} -> {}", name, new_change_id);
+
+        let output = self
+            .executor
+            .exec(&[
+                "bookmark",
+                "move",
+                name,
+                "--to",
+                new_change_id,
+                "--allow-backwards",
+            ])
+            .await?;
+
+        if !output.success {
+            return Err(HoxError::JjCommand(format!(
+                "Failed to move bookmark {}: {}",
+                name, output.stderr
+            )));
+        }
+
+        self.get(name).await
+    }
+
+    /// Rename a bookmark
+    ///
+    /// Executes: `jj bookmark rename {old} {new}`
+    #[instrument(skip(self))]
+    pub async fn rename_bookmark(&self, old: &str, new: &str) -> Result<BookmarkInfo> {
+        validate_identifier(old, "bookmark")?;
+        validate_identifier(new, "bookmark")?;
+        debug!("Renaming bookmark {} -> {}", old, new);
+
+        let output = self
+            .executor
+            .exec(&["bookmark", "rename", old, new])
+            .await?;
+
+        if !output.success {
+            return Err(HoxError::JjCommand(format!(
+                "Failed to rename bookmark {}: {}",
+                old, output.stderr
+            )));
+        }
+
+        self.get(new).await
+    }
+
+    /// Look up a single local bookmark by exact name
+    async fn get(&self, name: &str) -> Result<BookmarkInfo> {
+        let mut bookmarks = self.list(Some(name)).await?;
+        // `list --all` also reports remote copies; prefer the local bookmark
+        bookmarks.sort_by_key(|b| b.tracking.is_some());
+        bookmarks
+            .into_iter()
+            .next()
+            .ok_or_else(|| HoxError::JjCommand(format!("Bookmark {} not found", name)))
+    }
+
     /// Delete a bookmark
     ///
     /// Executes: `jj bookmark delete {name}`
@@ -368,6 +437,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -385,6 +455,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -402,6 +473,7 @@ mod tests {
                 stdout: "task/abc123|abc123def456|\nagent/foo/task/xyz|xyz789abc|\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -423,6 +495,7 @@ mod tests {
                 stdout: "agent/agent-42/task/abc123def456|abc123def456ghi789|\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -442,6 +515,7 @@ mod tests {
                     .to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -452,4 +526,70 @@ mod tests {
         assert_eq!(tasks.get("abc123"), Some(&"abc123def456".to_string()));
         assert_eq!(tasks.get("xyz789"), Some(&"xyz789abc123".to_string()));
     }
+
+    const LIST_COMMAND: &str = r#"bookmark list --all -T name ++ "|" ++ change_id ++ "|" ++ if(tracked, remote_name, "") ++ "\n""#;
+
+    fn ok(stdout: &str) -> JjOutput {
+        JjOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            success: true,
+            code: Some(0),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_move_bookmark() {
+        let executor = MockJjExecutor::new()
+            .with_response(
+                "bookmark move agent/agent-42/task/abc123 --to def456 --allow-backwards",
+                ok(""),
+            )
+            .with_response(
+                LIST_COMMAND,
+                ok("agent/agent-42/task/abc123|def456ghi789|origin\nagent/agent-42/task/abc123|def456ghi789|\ntask/abc123|abc123def456|\n"),
+            );
+
+        let manager = BookmarkManager::new(executor);
+        let info = manager
+            .move_bookmark("agent/agent-42/task/abc123", "def456")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            info,
+            BookmarkInfo {
+                name: "agent/agent-42/task/abc123".to_string(),
+                change_id: "def456ghi789".to_string(),
+                tracking: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rename_bookmark() {
+        let executor = MockJjExecutor::new()
+            .with_response("bookmark rename task/abc123 task/renamed", ok(""))
+            .with_response(LIST_COMMAND, ok("task/renamed|abc123def456|\n"));
+
+        let manager = BookmarkManager::new(executor);
+        let info = manager
+            .rename_bookmark("task/abc123", "task/renamed")
+            .await
+            .unwrap();
+
+        assert_eq!(info.name, "task/renamed");
+        assert_eq!(info.change_id, "abc123def456");
+    }
+
+    #[tokio::test]
+    async fn test_move_bookmark_rejects_unsafe_names() {
+        let manager = BookmarkManager::new(MockJjExecutor::new());
+
+        assert!(manager.move_bookmark("task/x; rm", "abc123").await.is_err());
+        assert!(manager
+            .rename_bookmark("task/abc123", "task/$(id)")
+            .await
+            .is_err());
+    }
 }
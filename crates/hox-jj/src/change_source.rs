@@ -0,0 +1,232 @@
+//! Polling sources of repository changes
+//!
+//! [`ChangeSource`] abstracts over what counts as "a change": jj operations
+//! for jj repositories (via [`OpLogWatcher`](crate::OpLogWatcher)) and new
+//! `HEAD` commits for git-only repositories (via [`GitChangeSource`]).
+
+use async_trait::async_trait;
+use hox_core::{HoxError, Result};
+use std::path::PathBuf;
+use std::process::Output;
+use tokio::process::Command;
+use tracing::debug;
+
+/// A change reported by a [`ChangeSource`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeEvent {
+    /// Operation ID (jj) or commit hash (git)
+    pub id: String,
+    pub description: String,
+    /// Paths touched, relative to the repo root (empty if the source does not report them)
+    pub paths: Vec<PathBuf>,
+}
+
+/// A source of repository changes that can be polled
+#[async_trait]
+pub trait ChangeSource: Send {
+    /// Changes since the previous poll, oldest first
+    ///
+    /// The first poll establishes a baseline and reports nothing, unless the
+    /// source resumes from persisted state.
+    async fn poll_changes(&mut self) -> Result<Vec<ChangeEvent>>;
+}
+
+/// Detects new commits on `HEAD` in a git repository
+pub struct GitChangeSource {
+    repo_root: PathBuf,
+    last_head: Option<String>,
+}
+
+impl GitChangeSource {
+    pub fn new(repo_root: impl Into<PathBuf>) -> Self {
+        Self {
+            repo_root: repo_root.into(),
+            last_head: None,
+        }
+    }
+
+    /// Get the repository root
+    pub fn repo_root(&self) -> &PathBuf {
+        &self.repo_root
+    }
+
+    async fn git_output(&self, args: &[&str]) -> Result<Output> {
+        Command::new("git")
+            .arg("-C")
+            .arg(&self.repo_root)
+            .args(args)
+            .output()
+            .await
+            .map_err(|e| HoxError::Io(format!("Failed to run git: {}", e)))
+    }
+
+    async fn git(&self, args: &[&str]) -> Result<String> {
+        let output = self.git_output(args).await?;
+        if !output.status.success() {
+            return Err(git_failure(args, &output));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Current `HEAD` commit, or `None` in a repository without commits
+    ///
+    /// `rev-parse --verify --quiet` exits 1 without output only when `HEAD`
+    /// does not resolve; any other failure (not a repository, git missing,
+    /// a corrupt ref) is an error.
+    async fn head(&self) -> Result<Option<String>> {
+        let args = ["rev-parse", "--verify", "--quiet", "HEAD"];
+        let output = self.git_output(&args).await?;
+
+        if output.status.success() {
+            Ok(Some(
+                String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            ))
+        } else if output.status.code() == Some(1) && output.stderr.is_empty() {
+            Ok(None)
+        } else {
+            Err(git_failure(&args, &output))
+        }
+    }
+
+    /// Commits reachable from `head` but not `since`, oldest first
+    async fn commits_between(&self, since: Option<&str>, head: &str) -> Result<Vec<ChangeEvent>> {
+        let range = match since {
+            Some(since) => format!("{}..{}", since, head),
+            None => head.to_string(),
+        };
+        let stdout = self
+            .git(&[
+                "log",
+                "--reverse",
+                "--format=%x1e%H%x09%s",
+                "--name-only",
+                &range,
+            ])
+            .await?;
+
+        Ok(parse_git_log(&stdout))
+    }
+}
+
+fn git_failure(args: &[&str], output: &Output) -> HoxError {
+    HoxError::Other(format!(
+        "git {} failed: {}",
+        args.first().unwrap_or(&""),
+        String::from_utf8_lossy(&output.stderr).trim()
+    ))
+}
+
+#[async_trait]
+impl ChangeSource for GitChangeSource {
+    async fn poll_changes(&mut self) -> Result<Vec<ChangeEvent>> {
+        let Some(head) = self.head().await? else {
+            return Ok(Vec::new());
+        };
+
+        let changes = match &self.last_head {
+            None => Vec::new(),
+            Some(last) if *last == head => Vec::new(),
+            Some(last) => {
+                debug!("HEAD moved from {} to {}", last, head);
+                self.commits_between(Some(last), &head).await?
+            }
+        };
+
+        self.last_head = Some(head);
+        Ok(changes)
+    }
+}
+
+/// Parse `git log --format=%x1e%H%x09%s --name-only` output
+fn parse_git_log(stdout: &str) -> Vec<ChangeEvent> {
+    stdout
+        .split('\x1e')
+        .filter(|record| !record.trim().is_empty())
+        .map(|record| {
+            let mut lines = record.lines();
+            let header = lines.next().unwrap_or_default();
+            let (id, description) = header.split_once('\t').unwrap_or((header, ""));
+            ChangeEvent {
+                id: id.to_string(),
+                description: description.to_string(),
+                paths: lines
+                    .filter(|line| !line.is_empty())
+                    .map(PathBuf::from)
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=Hox", "-c", "user.email=hox@example.com"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(status.status.success(), "git {:?} failed", args);
+    }
+
+    fn commit(dir: &Path, file: &str, message: &str) {
+        std::fs::write(dir.join(file), message).unwrap();
+        git(dir, &["add", file]);
+        git(dir, &["commit", "-q", "-m", message]);
+    }
+
+    #[test]
+    fn test_parse_git_log() {
+        let events = parse_git_log("\x1eaaa\tFirst\n\na.txt\nb.txt\n\x1ebbb\tSecond\n\nc.txt\n");
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].id, "aaa");
+        assert_eq!(events[0].description, "First");
+        assert_eq!(
+            events[0].paths,
+            vec![PathBuf::from("a.txt"), PathBuf::from("b.txt")]
+        );
+        assert_eq!(events[1].paths, vec![PathBuf::from("c.txt")]);
+    }
+
+    #[tokio::test]
+    async fn test_git_change_source_reports_new_commits() {
+        let dir = TempDir::new().unwrap();
+        git(dir.path(), &["init", "-q"]);
+        let mut source = GitChangeSource::new(dir.path());
+
+        // No commits yet
+        assert!(source.poll_changes().await.unwrap().is_empty());
+
+        commit(dir.path(), "a.txt", "Add a");
+        // First commit becomes the baseline
+        assert!(source.poll_changes().await.unwrap().is_empty());
+
+        commit(dir.path(), "b.txt", "Add b");
+        commit(dir.path(), "c.txt", "Add c");
+        let changes = source.poll_changes().await.unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].description, "Add b");
+        assert_eq!(changes[0].paths, vec![PathBuf::from("b.txt")]);
+        assert_eq!(changes[1].description, "Add c");
+
+        // Nothing new since the last poll
+        assert!(source.poll_changes().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_git_change_source_reports_git_errors() {
+        let dir = TempDir::new().unwrap();
+        let mut source = GitChangeSource::new(dir.path().join("missing"));
+
+        let err = source.poll_changes().await.unwrap_err();
+        assert!(err.to_string().contains("git rev-parse failed"), "{}", err);
+    }
+}
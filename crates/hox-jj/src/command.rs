@@ -1,18 +1,25 @@
 //! JJ command execution abstraction
 
 use async_trait::async_trait;
-use hox_core::{HoxError, Result};
+use hox_core::{ChangeId, HoxError, Result};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Output;
+use std::sync::{Arc, Mutex};
 use tokio::process::Command;
 use tracing::{debug, instrument};
 
+use crate::revsets::parse_change_ids;
+use crate::validate::{validate_identifier, validate_revset};
+
 /// Output from a JJ command
 #[derive(Debug, Clone)]
 pub struct JjOutput {
     pub stdout: String,
     pub stderr: String,
     pub success: bool,
+    /// Exit code, or `None` if the process was killed by a signal
+    pub code: Option<i32>,
 }
 
 impl From<Output> for JjOutput {
@@ -21,6 +28,7 @@ impl From<Output> for JjOutput {
             stdout: String::from_utf8_lossy(&output.stdout).to_string(),
             stderr: String::from_utf8_lossy(&output.stderr).to_string(),
             success: output.status.success(),
+            code: output.status.code(),
         }
     }
 }
@@ -93,11 +101,138 @@ impl JjExecutor for JjCommand {
     }
 }
 
+/// Resolves change ID prefixes and revsets to full change IDs
+///
+/// Change IDs show up both in full and as short prefixes (e.g. the 12-char
+/// form in logs), so compare IDs only after normalizing them. Resolved
+/// prefixes are cached; revsets like `@` that can move are always re-queried.
+pub struct ChangeIdResolver<E: JjExecutor> {
+    executor: E,
+    cache: Mutex<HashMap<String, ChangeId>>,
+}
+
+impl<E: JjExecutor> ChangeIdResolver<E> {
+    pub fn new(executor: E) -> Self {
+        Self {
+            executor,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Resolve a change ID prefix or single-change revset to the full change ID
+    ///
+    /// Executes: `jj log -r {id} -T change_id --no-graph`. An ambiguous prefix
+    /// is reported with all matching change IDs.
+    pub async fn normalize_change_id(&self, id: &str) -> Result<ChangeId> {
+        let id = id.trim();
+        validate_revset(id)?;
+
+        if let Some(full) = self.cache.lock().expect("cache poisoned").get(id) {
+            return Ok(full.clone());
+        }
+
+        let output = self
+            .executor
+            .exec(&["log", "-r", id, "-T", "change_id ++ \"\\n\"", "--no-graph"])
+            .await?;
+
+        if !output.success {
+            if output.stderr.contains("ambiguous") {
+                let candidates = self.prefix_candidates(id).await?;
+                return Err(HoxError::JjCommand(format!(
+                    "Change ID prefix '{}' is ambiguous, candidates: {}",
+                    id,
+                    candidates.join(", ")
+                )));
+            }
+            return Err(HoxError::JjCommand(format!(
+                "Failed to resolve change ID {}: {}",
+                id,
+                output.stderr.trim()
+            )));
+        }
+
+        let mut ids = parse_change_ids(&output);
+        ids.dedup();
+        let full = match ids.len() {
+            0 => return Err(HoxError::JjCommand(format!("No change matches {}", id))),
+            1 => ids.remove(0),
+            n => {
+                return Err(HoxError::JjCommand(format!(
+                    "{} resolves to {} changes, expected one: {}",
+                    id,
+                    n,
+                    ids.join(", ")
+                )))
+            }
+        };
+
+        if full.starts_with(id) {
+            self.cache
+                .lock()
+                .expect("cache poisoned")
+                .insert(id.to_string(), full.clone());
+        }
+
+        Ok(full)
+    }
+
+    /// All change IDs starting with a prefix
+    async fn prefix_candidates(&self, prefix: &str) -> Result<Vec<ChangeId>> {
+        validate_identifier(prefix, "change_id")?;
+        let revset = format!("change_id({})", prefix);
+        let output = self
+            .executor
+            .exec(&[
+                "log",
+                "-r",
+                &revset,
+                "-T",
+                "change_id ++ \"\\n\"",
+                "--no-graph",
+            ])
+            .await?;
+
+        if !output.success {
+            return Err(HoxError::JjCommand(format!(
+                "Failed to list changes matching {}: {}",
+                prefix, output.stderr
+            )));
+        }
+
+        Ok(parse_change_ids(&output))
+    }
+}
+
+/// What a [`MockJjExecutor`] response queue does once every response was returned
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MockExhaustion {
+    /// Keep returning the last queued response
+    #[default]
+    RepeatLast,
+    /// Fail the call, so tests catch unexpected extra invocations
+    Error,
+}
+
+/// Responses registered for a single command
+#[derive(Clone)]
+struct MockResponses {
+    outputs: Vec<JjOutput>,
+    /// Registered via `with_responses`, so subject to [`MockExhaustion`]
+    queued: bool,
+}
+
 /// Mock JJ executor for testing
+///
+/// Every `exec` call is recorded. The record is shared between clones, so a
+/// mock handed to several components (or async tasks) sees every call made
+/// through any of them.
 #[derive(Clone)]
 pub struct MockJjExecutor {
     repo_root: PathBuf,
-    responses: std::collections::HashMap<String, JjOutput>,
+    responses: HashMap<String, MockResponses>,
+    exhaustion: MockExhaustion,
+    calls: Arc<Mutex<Vec<Vec<String>>>>,
 }
 
 impl Default for MockJjExecutor {
@@ -110,24 +245,107 @@ impl MockJjExecutor {
     pub fn new() -> Self {
         Self {
             repo_root: PathBuf::from("/mock/repo"),
-            responses: std::collections::HashMap::new(),
+            responses: HashMap::new(),
+            exhaustion: MockExhaustion::default(),
+            calls: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
+    /// Use a real directory as the repo root (e.g. a temp dir)
+    pub fn with_repo_root(mut self, repo_root: PathBuf) -> Self {
+        self.repo_root = repo_root;
+        self
+    }
+
+    /// Always answer `command` with `output`
     pub fn with_response(mut self, command: &str, output: JjOutput) -> Self {
-        self.responses.insert(command.to_string(), output);
+        self.responses.insert(
+            command.to_string(),
+            MockResponses {
+                outputs: vec![output],
+                queued: false,
+            },
+        );
         self
     }
+
+    /// Answer successive calls of `command` with `outputs` in order
+    ///
+    /// Once the queue runs out the mock follows its [`MockExhaustion`] setting.
+    pub fn with_responses(mut self, command: &str, outputs: Vec<JjOutput>) -> Self {
+        self.responses.insert(
+            command.to_string(),
+            MockResponses {
+                outputs,
+                queued: true,
+            },
+        );
+        self
+    }
+
+    /// Set what exhausted response queues do (defaults to repeating the last one)
+    pub fn with_exhaustion(mut self, exhaustion: MockExhaustion) -> Self {
+        self.exhaustion = exhaustion;
+        self
+    }
+
+    /// Number of times `command` has been executed
+    pub fn call_count(&self, command: &str) -> usize {
+        self.calls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|args| args.join(" ") == command)
+            .count()
+    }
+
+    /// Argument vectors of every `exec` call so far, in call order
+    pub fn recorded_calls(&self) -> Vec<Vec<String>> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    /// Panic unless some `exec` call was made with exactly `args`
+    #[track_caller]
+    pub fn assert_called_with(&self, args: &[&str]) {
+        let calls = self.recorded_calls();
+        assert!(
+            calls.iter().any(|call| call == args),
+            "expected jj call {:?}, recorded calls: {:#?}",
+            args,
+            calls
+        );
+    }
 }
 
 #[async_trait]
 impl JjExecutor for MockJjExecutor {
     async fn exec(&self, args: &[&str]) -> Result<JjOutput> {
         let key = args.join(" ");
-        self.responses
+        let call = {
+            let mut calls = self.calls.lock().unwrap();
+            let previous = calls.iter().filter(|c| c.join(" ") == key).count();
+            calls.push(args.iter().map(|a| a.to_string()).collect());
+            previous
+        };
+
+        let responses = self
+            .responses
             .get(&key)
-            .cloned()
-            .ok_or_else(|| HoxError::JjCommand(format!("No mock response for: {}", key)))
+            .filter(|r| !r.outputs.is_empty())
+            .ok_or_else(|| HoxError::JjCommand(format!("No mock response for: {}", key)))?;
+
+        match responses.outputs.get(call) {
+            Some(output) => Ok(output.clone()),
+            None if responses.queued && self.exhaustion == MockExhaustion::Error => {
+                Err(HoxError::JjCommand(format!(
+                    "Mock responses exhausted for: {} (call {} of {})",
+                    key,
+                    call + 1,
+                    responses.outputs.len()
+                )))
+            }
+            None => Ok(responses.outputs[responses.outputs.len() - 1].clone()),
+        }
     }
 
     fn repo_root(&self) -> &PathBuf {
@@ -147,6 +365,7 @@ mod tests {
                 stdout: "test output".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -154,4 +373,158 @@ mod tests {
         assert!(output.success);
         assert_eq!(output.stdout, "test output");
     }
+
+    #[tokio::test]
+    async fn test_mock_executor_response_queue() {
+        let executor = MockJjExecutor::new()
+            .with_responses(
+                "log -r @",
+                vec![
+                    log_output("before", "", true),
+                    log_output("after", "", true),
+                ],
+            )
+            .with_response("new", log_output("", "", true));
+
+        // Clones share call counts
+        let clone = executor.clone();
+        assert_eq!(
+            clone.exec(&["log", "-r", "@"]).await.unwrap().stdout,
+            "before"
+        );
+        executor.exec(&["new"]).await.unwrap();
+        assert_eq!(
+            executor.exec(&["log", "-r", "@"]).await.unwrap().stdout,
+            "after"
+        );
+        assert_eq!(
+            executor.exec(&["log", "-r", "@"]).await.unwrap().stdout,
+            "after"
+        );
+
+        assert_eq!(executor.call_count("log -r @"), 3);
+        assert_eq!(clone.call_count("new"), 1);
+        assert_eq!(executor.call_count("status"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_mock_executor_exhaustion_error() {
+        let executor = MockJjExecutor::new()
+            .with_exhaustion(MockExhaustion::Error)
+            .with_responses("log", vec![log_output("once", "", true)])
+            .with_response("status", log_output("clean", "", true));
+
+        assert_eq!(executor.exec(&["log"]).await.unwrap().stdout, "once");
+        let err = executor.exec(&["log"]).await.unwrap_err();
+        assert!(err.to_string().contains("exhausted"));
+
+        // Single responses keep repeating regardless
+        executor.exec(&["status"]).await.unwrap();
+        assert_eq!(executor.exec(&["status"]).await.unwrap().stdout, "clean");
+    }
+
+    #[tokio::test]
+    async fn test_mock_executor_records_calls() {
+        let executor = MockJjExecutor::new().with_response("new a b", log_output("", "", true));
+
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let executor = executor.clone();
+                tokio::spawn(async move { executor.exec(&["new", "a", "b"]).await })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+        // Unknown commands are recorded too
+        assert!(executor.exec(&["status"]).await.is_err());
+
+        let calls = executor.recorded_calls();
+        assert_eq!(calls.len(), 5);
+        assert_eq!(calls[4], vec!["status"]);
+        executor.assert_called_with(&["new", "a", "b"]);
+        assert_eq!(executor.call_count("new a b"), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected jj call")]
+    fn test_assert_called_with_panics_when_missing() {
+        MockJjExecutor::new().assert_called_with(&["new"]);
+    }
+
+    fn log_output(stdout: &str, stderr: &str, success: bool) -> JjOutput {
+        JjOutput {
+            stdout: stdout.to_string(),
+            stderr: stderr.to_string(),
+            success,
+            code: Some(if success { 0 } else { 1 }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_normalize_change_id_prefix() {
+        let executor = MockJjExecutor::new()
+            .with_response(
+                r#"log -r qpvuntsm -T change_id ++ "\n" --no-graph"#,
+                log_output("qpvuntsmwlqtkrzmqvzzxwvpvxlwxzxq\n", "", true),
+            )
+            .with_response(
+                r#"log -r @ -T change_id ++ "\n" --no-graph"#,
+                log_output("rlvkpnrzqnoowoytxnquwvuryrwnrmlp\n", "", true),
+            );
+        let resolver = ChangeIdResolver::new(executor);
+
+        let full = resolver.normalize_change_id("qpvuntsm").await.unwrap();
+        assert_eq!(full, "qpvuntsmwlqtkrzmqvzzxwvpvxlwxzxq");
+
+        // A revset resolves to the change it currently points at
+        assert_eq!(
+            resolver.normalize_change_id("@").await.unwrap(),
+            "rlvkpnrzqnoowoytxnquwvuryrwnrmlp"
+        );
+
+        // Prefixes are cached, revsets are not
+        let cache = resolver.cache.lock().unwrap();
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache["qpvuntsm"], "qpvuntsmwlqtkrzmqvzzxwvpvxlwxzxq");
+    }
+
+    #[tokio::test]
+    async fn test_normalize_change_id_ambiguous() {
+        let executor = MockJjExecutor::new()
+            .with_response(
+                r#"log -r qp -T change_id ++ "\n" --no-graph"#,
+                log_output("", "Error: Change ID prefix `qp` is ambiguous\n", false),
+            )
+            .with_response(
+                r#"log -r change_id(qp) -T change_id ++ "\n" --no-graph"#,
+                log_output(
+                    "qpvuntsmwlqtkrzmqvzzxwvpvxlwxzxq\nqpzzkkpqnrsuvwxyzzxwqrstuvwxyzkl\n",
+                    "",
+                    true,
+                ),
+            );
+        let resolver = ChangeIdResolver::new(executor);
+
+        let err = resolver.normalize_change_id("qp").await.unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("ambiguous"));
+        assert!(message.contains("qpvuntsmwlqtkrzmqvzzxwvpvxlwxzxq"));
+        assert!(message.contains("qpzzkkpqnrsuvwxyzzxwqrstuvwxyzkl"));
+    }
+
+    #[tokio::test]
+    async fn test_normalize_change_id_multiple_changes() {
+        let executor = MockJjExecutor::new().with_response(
+            r#"log -r heads(all()) -T change_id ++ "\n" --no-graph"#,
+            log_output("aaa\nbbb\n", "", true),
+        );
+        let resolver = ChangeIdResolver::new(executor);
+
+        let err = resolver
+            .normalize_change_id("heads(all())")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("resolves to 2 changes"));
+    }
 }
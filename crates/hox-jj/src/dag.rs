@@ -8,10 +8,15 @@
 //!
 //! These operations are safe because bookmarks auto-track through DAG rewrites (Phase 1).
 
+use chrono::{DateTime, FixedOffset, NaiveDateTime};
 use hox_core::{HoxError, Result};
+use serde::{Deserialize, Serialize};
 use tracing::{debug, instrument};
 
 use crate::command::JjExecutor;
+use crate::diff::{apply_hunks, parse_git_diff};
+use crate::revsets::parse_change_ids;
+use crate::validate::{validate_path, validate_revset};
 
 /// Result from parallelize operation
 #[derive(Debug, Clone)]
@@ -22,6 +27,19 @@ pub struct ParallelizeResult {
     pub clean: bool,
     /// List of conflicts encountered (empty if clean)
     pub conflicts: Vec<String>,
+    /// True when this is a preview and nothing was rewritten
+    pub dry_run: bool,
+}
+
+/// Prediction of whether `parallelize` can run without conflicts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParallelizeFeasibility {
+    /// True when no two changes in the revset touch the same file
+    pub feasible: bool,
+    /// Number of changes examined
+    pub changes_checked: usize,
+    /// Files modified by more than one change (sorted)
+    pub overlapping_files: Vec<String>,
 }
 
 /// Result from absorb operation
@@ -31,32 +49,173 @@ pub struct AbsorbResult {
     pub hunks_absorbed: usize,
     /// Change IDs affected by absorption
     pub affected_changes: Vec<String>,
+    /// True when this is a preview and nothing was rewritten
+    pub dry_run: bool,
+}
+
+/// Planned destination of a single working-copy hunk, as `jj absorb` would move it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbsorbPlan {
+    /// File the hunk belongs to
+    pub path: String,
+    /// Hunk header, e.g. `@@ -10,2 +10,3 @@`
+    pub hunk_summary: String,
+    /// Change the hunk would be absorbed into (`None` if it stays in the working copy)
+    pub target_change: Option<String>,
 }
 
 /// Result from split operation
 #[derive(Debug, Clone)]
 pub struct SplitResult {
-    /// New change IDs created from the split
+    /// New change IDs created from the split (empty for a preview)
     pub new_changes: Vec<String>,
+    /// True when this is a preview and nothing was rewritten
+    pub dry_run: bool,
 }
 
-/// Entry from evolution log (change history)
+/// Result from rebase operation
 #[derive(Debug, Clone)]
+pub struct RebaseResult {
+    /// Full change ID of the rebased change (unchanged by the rebase itself)
+    pub change_id: String,
+    /// Whether the rebased change ended up conflicted
+    pub conflicted: bool,
+    /// True when this is a preview and nothing was rewritten
+    pub dry_run: bool,
+}
+
+/// A range of lines within a file to move into a split-off change
+///
+/// Lines are 1-based and inclusive, numbered as in the change's version of the
+/// file. Every diff hunk the range touches must lie entirely inside it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HunkSelection {
+    pub path: String,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+impl HunkSelection {
+    pub fn new(path: impl Into<String>, start_line: usize, end_line: usize) -> Self {
+        Self {
+            path: path.into(),
+            start_line,
+            end_line,
+        }
+    }
+}
+
+/// Name of the scripted diff editor used for non-interactive hunk splits
+const HUNK_SPLIT_TOOL: &str = "hox-hunks";
+
+/// Private directory holding the pre-computed file contents for a hunk split
+///
+/// Created with a random name and owner-only permissions so concurrent splits
+/// of the same change never share it and it cannot be pre-planted as a symlink.
+/// `change_id` must already be a validated revset; anything but alphanumerics is
+/// flattened so the prefix stays a single path component.
+fn hunk_split_staging_dir(change_id: &str) -> Result<tempfile::TempDir> {
+    let label = change_id.replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+    Ok(tempfile::Builder::new()
+        .prefix(&format!("hox-split-{}-", label))
+        .tempdir()?)
+}
+
+/// Escape a string for use inside a TOML basic string
+fn toml_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Entry from evolution log (change history)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EvolutionEntry {
     pub commit_id: String,
     pub description: String,
     pub timestamp: String,
 }
 
+/// Evolution log entry tagged with the change it belongs to
+///
+/// Produced by [`DagOperations::timeline`] when interleaving the evolution
+/// logs of several changes into a single history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    pub change_id: String,
+    #[serde(flatten)]
+    pub entry: EvolutionEntry,
+}
+
+/// Parse a JJ-formatted timestamp (e.g. `2025-01-30 12:00:00.000 -08:00`)
+///
+/// Timestamps without an offset are interpreted as UTC.
+fn parse_jj_timestamp(timestamp: &str) -> Option<DateTime<FixedOffset>> {
+    let timestamp = timestamp.trim();
+    DateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S%.f %:z")
+        .or_else(|_| DateTime::parse_from_rfc3339(timestamp))
+        .ok()
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S%.f")
+                .ok()
+                .map(|naive| naive.and_utc().fixed_offset())
+        })
+}
+
+/// Compare two JJ timestamps chronologically, falling back to string order
+fn compare_timestamps(a: &str, b: &str) -> std::cmp::Ordering {
+    match (parse_jj_timestamp(a), parse_jj_timestamp(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
+
+/// Extract the new change IDs reported by `jj split`
+fn parse_created_changes(stdout: &str) -> Vec<String> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            // Look for lines containing change IDs
+            if line.contains("Created") || line.contains("change") {
+                // Extract potential change ID (simplified parsing)
+                line.split_whitespace()
+                    .find(|s| s.len() > 8 && s.chars().all(|c| c.is_alphanumeric()))
+                    .map(String::from)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
 /// DAG manipulation operations for task restructuring
 pub struct DagOperations<E: JjExecutor> {
     executor: E,
+    dry_run: bool,
 }
 
 impl<E: JjExecutor> DagOperations<E> {
     /// Create a new DAG operations manager
     pub fn new(executor: E) -> Self {
-        Self { executor }
+        Self {
+            executor,
+            dry_run: false,
+        }
+    }
+
+    /// Preview mutations instead of applying them
+    ///
+    /// In dry-run mode `parallelize`, `absorb`, `split_by_files`,
+    /// `split_by_hunks`, `squash` and `squash_into` only run read-only queries
+    /// and report what they would do. jj has no `--dry-run` for these
+    /// commands, so the previews come from `can_parallelize`,
+    /// `absorb_preview` and validation of the inputs.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Whether mutations are previewed instead of applied
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
     }
 
     /// Convert sequential changes into parallel siblings
@@ -69,6 +228,16 @@ impl<E: JjExecutor> DagOperations<E> {
     pub async fn parallelize(&self, revset: &str) -> Result<ParallelizeResult> {
         debug!("Parallelizing changes in revset: {}", revset);
 
+        if self.dry_run {
+            let feasibility = self.can_parallelize(revset).await?;
+            return Ok(ParallelizeResult {
+                changes_restructured: feasibility.changes_checked,
+                clean: feasibility.feasible,
+                conflicts: feasibility.overlapping_files,
+                dry_run: true,
+            });
+        }
+
         let output = self.executor.exec(&["parallelize", revset]).await?;
 
         if !output.success {
@@ -98,6 +267,77 @@ impl<E: JjExecutor> DagOperations<E> {
             changes_restructured: 1, // Conservative estimate without querying revset
             clean,
             conflicts,
+            dry_run: false,
+        })
+    }
+
+    /// Predict whether parallelizing a revset would produce conflicts
+    ///
+    /// Executes: `jj log -r {revset}` and `jj diff --name-only -r {change_id}` per change
+    ///
+    /// Changes that modify the same file will conflict once they become siblings,
+    /// so any file touched by two or more changes makes the revset infeasible.
+    #[instrument(skip(self))]
+    pub async fn can_parallelize(&self, revset: &str) -> Result<ParallelizeFeasibility> {
+        debug!("Checking parallelize feasibility for revset: {}", revset);
+
+        let output = self
+            .executor
+            .exec(&[
+                "log",
+                "-r",
+                revset,
+                "-T",
+                "change_id ++ \"\\n\"",
+                "--no-graph",
+            ])
+            .await?;
+
+        if !output.success {
+            return Err(HoxError::JjCommand(format!(
+                "Failed to resolve revset {}: {}",
+                revset, output.stderr
+            )));
+        }
+
+        let change_ids = parse_change_ids(&output);
+        let mut touched: std::collections::BTreeMap<String, usize> =
+            std::collections::BTreeMap::new();
+
+        for change_id in &change_ids {
+            let diff = self
+                .executor
+                .exec(&["diff", "--name-only", "-r", change_id])
+                .await?;
+
+            if !diff.success {
+                return Err(HoxError::JjCommand(format!(
+                    "Failed to list files for {}: {}",
+                    change_id, diff.stderr
+                )));
+            }
+
+            let files: std::collections::BTreeSet<&str> = diff
+                .stdout
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .collect();
+            for file in files {
+                *touched.entry(file.to_string()).or_default() += 1;
+            }
+        }
+
+        let overlapping_files: Vec<String> = touched
+            .into_iter()
+            .filter(|(_, count)| *count > 1)
+            .map(|(file, _)| file)
+            .collect();
+
+        Ok(ParallelizeFeasibility {
+            feasible: overlapping_files.is_empty(),
+            changes_checked: change_ids.len(),
+            overlapping_files,
         })
     }
 
@@ -111,6 +351,23 @@ impl<E: JjExecutor> DagOperations<E> {
     pub async fn absorb(&self, paths: Option<&[&str]>) -> Result<AbsorbResult> {
         debug!("Absorbing changes, paths: {:?}", paths);
 
+        if self.dry_run {
+            let plans = self.absorb_preview(paths).await?;
+            let mut affected_changes: Vec<String> = plans
+                .iter()
+                .filter_map(|plan| plan.target_change.clone())
+                .collect();
+            let hunks_absorbed = affected_changes.len();
+            affected_changes.sort();
+            affected_changes.dedup();
+
+            return Ok(AbsorbResult {
+                hunks_absorbed,
+                affected_changes,
+                dry_run: true,
+            });
+        }
+
         let mut args = vec!["absorb"];
         if let Some(path_list) = paths {
             args.extend(path_list.iter().copied());
@@ -158,9 +415,96 @@ impl<E: JjExecutor> DagOperations<E> {
         Ok(AbsorbResult {
             hunks_absorbed,
             affected_changes,
+            dry_run: false,
         })
     }
 
+    /// Preview where `absorb` would move each working-copy hunk, without mutating anything
+    ///
+    /// Executes: `jj diff --git --context 0 -r @ [paths...]` and, per touched file,
+    /// `jj file annotate -r @- -T {template} {path}`
+    ///
+    /// A hunk is attributed to the single change that last modified every line it
+    /// rewrites. Pure insertions, and hunks whose lines come from several changes,
+    /// are left in the working copy (`target_change: None`), matching `jj absorb`.
+    #[instrument(skip(self))]
+    pub async fn absorb_preview(&self, paths: Option<&[&str]>) -> Result<Vec<AbsorbPlan>> {
+        debug!("Previewing absorb, paths: {:?}", paths);
+
+        let mut args = vec!["diff", "--git", "--context", "0", "-r", "@"];
+        if let Some(path_list) = paths {
+            args.extend(path_list.iter().copied());
+        }
+
+        let output = self.executor.exec(&args).await?;
+
+        if !output.success {
+            return Err(HoxError::JjCommand(format!(
+                "Failed to diff working copy: {}",
+                output.stderr
+            )));
+        }
+
+        let hunks = parse_git_diff(&output.stdout);
+        let mut annotations: std::collections::HashMap<String, Vec<String>> =
+            std::collections::HashMap::new();
+        let mut plans = Vec::with_capacity(hunks.len());
+
+        for hunk in hunks {
+            if !annotations.contains_key(&hunk.path) {
+                let lines = self.annotate_parent(&hunk.path).await?;
+                annotations.insert(hunk.path.clone(), lines);
+            }
+            let line_owners = &annotations[&hunk.path];
+
+            let mut owners = hunk
+                .removed_lines()
+                .into_iter()
+                .map(|line| line_owners.get(line - 1).cloned());
+            let target_change = match owners.next().flatten() {
+                Some(first) if owners.all(|o| o.as_ref() == Some(&first)) => Some(first),
+                _ => None,
+            };
+
+            plans.push(AbsorbPlan {
+                path: hunk.path.clone(),
+                hunk_summary: hunk.header(),
+                target_change,
+            });
+        }
+
+        Ok(plans)
+    }
+
+    /// Change ID that last touched each line of a file in the working-copy parent
+    ///
+    /// Returns an empty list for files that do not exist in the parent.
+    async fn annotate_parent(&self, path: &str) -> Result<Vec<String>> {
+        let output = self
+            .executor
+            .exec(&[
+                "file",
+                "annotate",
+                "-r",
+                "@-",
+                "-T",
+                "commit.change_id() ++ \"\\n\"",
+                path,
+            ])
+            .await?;
+
+        if !output.success {
+            debug!("No annotation for {}: {}", path, output.stderr);
+            return Ok(Vec::new());
+        }
+
+        Ok(output
+            .stdout
+            .lines()
+            .map(|l| l.trim().to_string())
+            .collect())
+    }
+
     /// Split a change into multiple changes by file groups
     ///
     /// Executes: `jj split -r {change_id} --siblings {files...}` for each group
@@ -200,6 +544,14 @@ impl<E: JjExecutor> DagOperations<E> {
             }
         }
 
+        if self.dry_run {
+            debug!("Dry run, would execute: jj {}", args.join(" "));
+            return Ok(SplitResult {
+                new_changes: Vec::new(),
+                dry_run: true,
+            });
+        }
+
         let output = self.executor.exec(&args).await?;
 
         if !output.success {
@@ -209,25 +561,199 @@ impl<E: JjExecutor> DagOperations<E> {
             )));
         }
 
-        // Parse output to extract new change IDs
-        // JJ split outputs the new change IDs
-        let new_changes = output
-            .stdout
-            .lines()
-            .filter_map(|line| {
-                // Look for lines containing change IDs
-                if line.contains("Created") || line.contains("change") {
-                    // Extract potential change ID (simplified parsing)
-                    line.split_whitespace()
-                        .find(|s| s.len() > 8 && s.chars().all(|c| c.is_alphanumeric()))
-                        .map(String::from)
-                } else {
-                    None
+        Ok(SplitResult {
+            new_changes: parse_created_changes(&output.stdout),
+            dry_run: false,
+        })
+    }
+
+    /// Split selected hunks of a change into a new sibling change
+    ///
+    /// Executes: `jj split -r {change_id} --siblings --tool hox-hunks` with a scripted
+    /// diff editor, after reading `jj diff --git --context 0` and `jj file show` per file
+    ///
+    /// `jj split` only selects whole files on the command line, so the selected
+    /// hunks are applied to the parent's version of each file up front and the
+    /// diff editor simply copies those contents into the first change. Selections
+    /// are validated against the change's diff: each must cover at least one hunk,
+    /// and may not cut a hunk in half. A deleted file's diff is a single hunk at
+    /// line 1; selecting it moves the whole deletion into the first change.
+    #[instrument(skip(self))]
+    pub async fn split_by_hunks(
+        &self,
+        change_id: &str,
+        selections: Vec<HunkSelection>,
+    ) -> Result<SplitResult> {
+        debug!(
+            "Splitting change {} by {} hunk selections",
+            change_id,
+            selections.len()
+        );
+
+        validate_revset(change_id)?;
+        if selections.is_empty() {
+            return Err(HoxError::JjCommand(
+                "No hunk selections provided for split".to_string(),
+            ));
+        }
+
+        let mut by_path: std::collections::BTreeMap<&str, Vec<&HunkSelection>> =
+            std::collections::BTreeMap::new();
+        for selection in &selections {
+            validate_path(&selection.path, "hunk selection path")?;
+            if selection.start_line == 0 || selection.start_line > selection.end_line {
+                return Err(HoxError::JjCommand(format!(
+                    "Invalid line range {}-{} for {}",
+                    selection.start_line, selection.end_line, selection.path
+                )));
+            }
+            by_path.entry(&selection.path).or_default().push(selection);
+        }
+
+        let parent = format!("{}-", change_id);
+        let mut staged_files = Vec::with_capacity(by_path.len());
+        let mut deleted_files = Vec::new();
+
+        for (path, file_selections) in by_path {
+            let output = self
+                .executor
+                .exec(&["diff", "--git", "--context", "0", "-r", change_id, path])
+                .await?;
+
+            if !output.success {
+                return Err(HoxError::JjCommand(format!(
+                    "Failed to diff {} in {}: {}",
+                    path, change_id, output.stderr
+                )));
+            }
+
+            let hunks = parse_git_diff(&output.stdout);
+            let mut selected = Vec::new();
+
+            for selection in &file_selections {
+                let mut matched = false;
+                for hunk in &hunks {
+                    let (start, end) = hunk.new_span();
+                    let overlaps = start <= selection.end_line && end >= selection.start_line;
+                    if !overlaps {
+                        continue;
+                    }
+                    if start < selection.start_line || end > selection.end_line {
+                        return Err(HoxError::JjCommand(format!(
+                            "Selection {}:{}-{} splits hunk {}",
+                            path,
+                            selection.start_line,
+                            selection.end_line,
+                            hunk.header()
+                        )));
+                    }
+                    matched = true;
+                    if !selected.contains(&hunk) {
+                        selected.push(hunk);
+                    }
                 }
-            })
-            .collect();
 
-        Ok(SplitResult { new_changes })
+                if !matched {
+                    return Err(HoxError::JjCommand(format!(
+                        "Selection {}:{}-{} does not match any change in {}",
+                        path, selection.start_line, selection.end_line, change_id
+                    )));
+                }
+            }
+
+            // Deleted files have no content to stage; the editor removes them
+            if output
+                .stdout
+                .lines()
+                .any(|line| line.starts_with("deleted file mode"))
+            {
+                deleted_files.push(path.to_string());
+                continue;
+            }
+
+            // Files added by the change have no parent version
+            let base = self
+                .executor
+                .exec(&["file", "show", "-r", &parent, path])
+                .await?;
+            let base = if base.success {
+                base.stdout
+            } else {
+                String::new()
+            };
+
+            staged_files.push((path.to_string(), apply_hunks(&base, &selected)));
+        }
+
+        if self.dry_run {
+            debug!(
+                "Dry run, would split {} files out of {}",
+                staged_files.len() + deleted_files.len(),
+                change_id
+            );
+            return Ok(SplitResult {
+                new_changes: Vec::new(),
+                dry_run: true,
+            });
+        }
+
+        let staging = hunk_split_staging_dir(change_id)?;
+        let staging_dir = staging.path().to_path_buf();
+        for (path, content) in &staged_files {
+            let target = staging_dir.join(path);
+            if let Some(parent_dir) = target.parent() {
+                std::fs::create_dir_all(parent_dir)?;
+            }
+            std::fs::write(&target, content)?;
+        }
+
+        // $right starts as the full change: reset it to the parent ($left),
+        // overlay the staged files ($2), then remove the selected deletions ($3...)
+        // so only selected hunks remain
+        let program = format!("merge-tools.{}.program=\"sh\"", HUNK_SPLIT_TOOL);
+        let mut edit_args = format!(
+            r#"merge-tools.{}.edit-args=["-c", "find \"$1\" -mindepth 1 -delete && cp -R \"$0\"/. \"$1\"/ && cp -R \"$2\"/. \"$1\"/ && r=\"$1\" && shift 2 && for p in \"$@\"; do rm -f -- \"${{r:?}}/${{p:?}}\"; done", "$left", "$right", "{}""#,
+            HUNK_SPLIT_TOOL,
+            toml_escape(&staging_dir.display().to_string())
+        );
+        for path in &deleted_files {
+            edit_args.push_str(&format!(r#", "{}""#, toml_escape(path)));
+        }
+        edit_args.push(']');
+
+        let result = self
+            .executor
+            .exec(&[
+                "split",
+                "-r",
+                change_id,
+                "--siblings",
+                "--tool",
+                HUNK_SPLIT_TOOL,
+                "--config",
+                &program,
+                "--config",
+                &edit_args,
+            ])
+            .await;
+
+        if let Err(e) = staging.close() {
+            debug!("Failed to clean up {}: {}", staging_dir.display(), e);
+        }
+
+        let output = result?;
+
+        if !output.success {
+            return Err(HoxError::JjCommand(format!(
+                "Failed to split change by hunks: {}",
+                output.stderr
+            )));
+        }
+
+        Ok(SplitResult {
+            new_changes: parse_created_changes(&output.stdout),
+            dry_run: false,
+        })
     }
 
     /// Fold a change into its parent
@@ -240,6 +766,11 @@ impl<E: JjExecutor> DagOperations<E> {
     pub async fn squash(&self, change_id: &str) -> Result<()> {
         debug!("Squashing change {} into parent", change_id);
 
+        if self.dry_run {
+            debug!("Dry run, would execute: jj squash -r {}", change_id);
+            return Ok(());
+        }
+
         let output = self.executor.exec(&["squash", "-r", change_id]).await?;
 
         if !output.success {
@@ -276,6 +807,11 @@ impl<E: JjExecutor> DagOperations<E> {
             args.extend(path_list.iter().copied());
         }
 
+        if self.dry_run {
+            debug!("Dry run, would execute: jj {}", args.join(" "));
+            return Ok(());
+        }
+
         let output = self.executor.exec(&args).await?;
 
         if !output.success {
@@ -288,6 +824,77 @@ impl<E: JjExecutor> DagOperations<E> {
         Ok(())
     }
 
+    /// Move a single change onto a new parent
+    ///
+    /// Executes: `jj rebase -r {change_id} -d {destination}`, or
+    /// `jj rebase -r {change_id} -A {destination} -B {insert_before}` to insert
+    /// the change between two others. Descendants of the change stay where
+    /// they were, rebased onto its old parent.
+    #[instrument(skip(self))]
+    pub async fn rebase(
+        &self,
+        change_id: &str,
+        destination: &str,
+        insert_before: Option<&str>,
+    ) -> Result<RebaseResult> {
+        debug!(
+            "Rebasing {} onto {}, insert before: {:?}",
+            change_id, destination, insert_before
+        );
+
+        let mut args = vec!["rebase", "-r", change_id];
+        match insert_before {
+            Some(before) => args.extend(["-A", destination, "-B", before]),
+            None => args.extend(["-d", destination]),
+        }
+
+        if self.dry_run {
+            debug!("Dry run, would execute: jj {}", args.join(" "));
+            return Ok(RebaseResult {
+                change_id: change_id.to_string(),
+                conflicted: false,
+                dry_run: true,
+            });
+        }
+
+        let output = self.executor.exec(&args).await?;
+
+        if !output.success {
+            return Err(HoxError::JjCommand(format!(
+                "Failed to rebase change: {}",
+                output.stderr
+            )));
+        }
+
+        let output = self
+            .executor
+            .exec(&[
+                "log",
+                "-r",
+                change_id,
+                "-T",
+                "change_id ++ \"\\t\" ++ if(conflict, \"conflict\") ++ \"\\n\"",
+                "--no-graph",
+            ])
+            .await?;
+
+        if !output.success {
+            return Err(HoxError::JjCommand(format!(
+                "Failed to read rebased change: {}",
+                output.stderr
+            )));
+        }
+
+        let line = output.stdout.lines().next().unwrap_or_default();
+        let (new_change_id, conflict) = line.split_once('\t').unwrap_or((line, ""));
+
+        Ok(RebaseResult {
+            change_id: new_change_id.trim().to_string(),
+            conflicted: conflict.trim() == "conflict",
+            dry_run: false,
+        })
+    }
+
     /// Duplicate a change for speculative execution
     ///
     /// Executes: `jj duplicate {change_id} [-d {destination}]`
@@ -374,6 +981,31 @@ impl<E: JjExecutor> DagOperations<E> {
         Ok(backout_change_id)
     }
 
+    /// Abandon changes, rebasing their descendants onto their parents
+    ///
+    /// Executes: `jj abandon {change_ids...}`
+    #[instrument(skip(self))]
+    pub async fn abandon(&self, change_ids: &[String]) -> Result<()> {
+        if change_ids.is_empty() {
+            return Ok(());
+        }
+        debug!("Abandoning {} change(s)", change_ids.len());
+
+        let mut args = vec!["abandon"];
+        args.extend(change_ids.iter().map(String::as_str));
+
+        let output = self.executor.exec(&args).await?;
+
+        if !output.success {
+            return Err(HoxError::JjCommand(format!(
+                "Failed to abandon changes: {}",
+                output.stderr
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Get the evolution log for a change (all rewrites, amends)
     ///
     /// Executes: `jj evolog -r {change_id} -T {template} --no-graph`
@@ -423,6 +1055,102 @@ impl<E: JjExecutor> DagOperations<E> {
         Ok(entries)
     }
 
+    /// Get the evolution log for a change, keeping only entries recorded after an operation
+    ///
+    /// Executes: `jj op log --at-op {op_id} -n 1 -T {template} --no-graph` to resolve the
+    /// operation time, then filters [`evolution_log`](Self::evolution_log) by timestamp.
+    #[instrument(skip(self))]
+    pub async fn evolution_log_since(
+        &self,
+        change_id: &str,
+        op_id: &str,
+    ) -> Result<Vec<EvolutionEntry>> {
+        let since = self.operation_timestamp(op_id).await?;
+
+        let entries = self.evolution_log(change_id).await?;
+
+        Ok(entries
+            .into_iter()
+            .filter(|e| compare_timestamps(&e.timestamp, &since).is_gt())
+            .collect())
+    }
+
+    /// Interleave the evolution logs of several changes, ordered by timestamp
+    ///
+    /// Calls [`evolution_log`](Self::evolution_log) for each change and merges
+    /// the results into a single oldest-first history. When `since_op` is given,
+    /// its timestamp is resolved once and entries up to it are dropped. This
+    /// gives a global view of how a group of related tasks evolved.
+    #[instrument(skip(self))]
+    pub async fn timeline(
+        &self,
+        change_ids: &[String],
+        since_op: Option<&str>,
+    ) -> Result<Vec<TimelineEntry>> {
+        debug!("Building timeline for {} changes", change_ids.len());
+
+        let since = match since_op {
+            Some(op_id) => Some(self.operation_timestamp(op_id).await?),
+            None => None,
+        };
+
+        let mut timeline = Vec::new();
+        for change_id in change_ids {
+            let entries = self.evolution_log(change_id).await?;
+            timeline.extend(
+                entries
+                    .into_iter()
+                    .filter(|e| {
+                        since
+                            .as_deref()
+                            .is_none_or(|s| compare_timestamps(&e.timestamp, s).is_gt())
+                    })
+                    .map(|entry| TimelineEntry {
+                        change_id: change_id.clone(),
+                        entry,
+                    }),
+            );
+        }
+
+        // Stable sort keeps per-change evolog order for identical timestamps
+        timeline.sort_by(|a, b| compare_timestamps(&a.entry.timestamp, &b.entry.timestamp));
+
+        Ok(timeline)
+    }
+
+    /// Resolve the start time of an operation
+    async fn operation_timestamp(&self, op_id: &str) -> Result<String> {
+        let output = self
+            .executor
+            .exec(&[
+                "op",
+                "log",
+                "--at-op",
+                op_id,
+                "-n",
+                "1",
+                "-T",
+                "self.time().start() ++ \"\\n\"",
+                "--no-graph",
+            ])
+            .await?;
+
+        if !output.success {
+            return Err(HoxError::JjCommand(format!(
+                "Failed to resolve operation {}: {}",
+                op_id, output.stderr
+            )));
+        }
+
+        output
+            .stdout
+            .lines()
+            .map(str::trim)
+            .find(|l| !l.is_empty())
+            .map(String::from)
+            .ok_or_else(|| HoxError::JjCommand(format!("Operation not found: {}", op_id)))
+    }
+
     /// Clean up redundant parent relationships
     ///
     /// Executes: `jj simplify-parents -r {change_id}`
@@ -462,6 +1190,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -483,6 +1212,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: "Warning: conflict detected in file.rs\n".to_string(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -493,6 +1223,59 @@ mod tests {
         assert_eq!(result.conflicts.len(), 1);
     }
 
+    fn feasibility_executor(a_files: &str, b_files: &str) -> MockJjExecutor {
+        let ok = |stdout: &str| JjOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            success: true,
+            code: Some(0),
+        };
+        MockJjExecutor::new()
+            .with_response(
+                "log -r task-a | task-b -T change_id ++ \"\\n\" --no-graph",
+                ok("aaa\nbbb\n"),
+            )
+            .with_response("diff --name-only -r aaa", ok(a_files))
+            .with_response("diff --name-only -r bbb", ok(b_files))
+    }
+
+    #[tokio::test]
+    async fn test_can_parallelize_overlapping_files() {
+        let executor = feasibility_executor("src/lib.rs\nsrc/a.rs\n", "src/b.rs\nsrc/lib.rs\n");
+
+        let dag_ops = DagOperations::new(executor);
+        let result = dag_ops.can_parallelize("task-a | task-b").await.unwrap();
+
+        assert!(!result.feasible);
+        assert_eq!(result.changes_checked, 2);
+        assert_eq!(result.overlapping_files, vec!["src/lib.rs"]);
+    }
+
+    #[tokio::test]
+    async fn test_parallelize_dry_run() {
+        // No `parallelize` response: executing it would fail the test
+        let executor = feasibility_executor("src/lib.rs\n", "src/lib.rs\n");
+
+        let dag_ops = DagOperations::new(executor).with_dry_run(true);
+        let result = dag_ops.parallelize("task-a | task-b").await.unwrap();
+
+        assert!(result.dry_run);
+        assert_eq!(result.changes_restructured, 2);
+        assert!(!result.clean);
+        assert_eq!(result.conflicts, vec!["src/lib.rs"]);
+    }
+
+    #[tokio::test]
+    async fn test_can_parallelize_disjoint_files() {
+        let executor = feasibility_executor("src/a.rs\n", "src/b.rs\n");
+
+        let dag_ops = DagOperations::new(executor);
+        let result = dag_ops.can_parallelize("task-a | task-b").await.unwrap();
+
+        assert!(result.feasible);
+        assert!(result.overlapping_files.is_empty());
+    }
+
     #[tokio::test]
     async fn test_absorb() {
         let executor = MockJjExecutor::new().with_response(
@@ -501,6 +1284,7 @@ mod tests {
                 stdout: "Absorbed 3 hunks into commit abc123\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -520,6 +1304,7 @@ mod tests {
                 stdout: "Absorbed 5 hunks into commit xyz789\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -532,6 +1317,133 @@ mod tests {
         assert_eq!(result.hunks_absorbed, 5);
     }
 
+    #[tokio::test]
+    async fn test_absorb_preview() {
+        let executor = MockJjExecutor::new()
+            .with_response(
+                "diff --git --context 0 -r @",
+                JjOutput {
+                    stdout: "diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -2,1 +2,1 @@
+-fn one() {}
++fn one() { todo!() }
+@@ -4,2 +4,2 @@
+-fn three() {}
+-fn four() {}
++fn three() { todo!() }
++fn four() { todo!() }
+@@ -5,0 +6,1 @@
++fn five() {}
+"
+                    .to_string(),
+                    stderr: String::new(),
+                    success: true,
+                    code: Some(0),
+                },
+            )
+            .with_response(
+                "file annotate -r @- -T commit.change_id() ++ \"\\n\" src/lib.rs",
+                JjOutput {
+                    stdout: "aaa\nbbb\naaa\nbbb\nccc\n".to_string(),
+                    stderr: String::new(),
+                    success: true,
+                    code: Some(0),
+                },
+            );
+
+        let dag_ops = DagOperations::new(executor);
+        let plans = dag_ops.absorb_preview(None).await.unwrap();
+
+        assert_eq!(plans.len(), 3);
+        assert_eq!(plans[0].path, "src/lib.rs");
+        assert_eq!(plans[0].hunk_summary, "@@ -2,1 +2,1 @@");
+        assert_eq!(plans[0].target_change.as_deref(), Some("bbb"));
+        // Lines 4 and 5 come from different changes: ambiguous, stays in @
+        assert_eq!(plans[1].target_change, None);
+        // Pure insertion has no line to attribute
+        assert_eq!(plans[2].target_change, None);
+    }
+
+    #[tokio::test]
+    async fn test_absorb_dry_run() {
+        let executor = MockJjExecutor::new()
+            .with_response(
+                "diff --git --context 0 -r @",
+                JjOutput {
+                    stdout: "diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,1 +1,1 @@
+-fn one() {}
++fn one() { todo!() }
+@@ -3,1 +3,1 @@
+-fn three() {}
++fn three() { todo!() }
+@@ -4,0 +5,1 @@
++fn five() {}
+"
+                    .to_string(),
+                    stderr: String::new(),
+                    success: true,
+                    code: Some(0),
+                },
+            )
+            .with_response(
+                "file annotate -r @- -T commit.change_id() ++ \"\\n\" src/lib.rs",
+                JjOutput {
+                    stdout: "bbb\nbbb\naaa\nccc\n".to_string(),
+                    stderr: String::new(),
+                    success: true,
+                    code: Some(0),
+                },
+            );
+
+        let dag_ops = DagOperations::new(executor).with_dry_run(true);
+        let result = dag_ops.absorb(None).await.unwrap();
+
+        assert!(result.dry_run);
+        assert_eq!(result.hunks_absorbed, 2);
+        assert_eq!(result.affected_changes, vec!["aaa", "bbb"]);
+    }
+
+    #[tokio::test]
+    async fn test_absorb_preview_new_file() {
+        let executor = MockJjExecutor::new()
+            .with_response(
+                "diff --git --context 0 -r @ new.rs",
+                JjOutput {
+                    stdout: "diff --git a/new.rs b/new.rs
+new file mode 100644
+--- /dev/null
++++ b/new.rs
+@@ -0,0 +1,1 @@
++fn new() {}
+"
+                    .to_string(),
+                    stderr: String::new(),
+                    success: true,
+                    code: Some(0),
+                },
+            )
+            .with_response(
+                "file annotate -r @- -T commit.change_id() ++ \"\\n\" new.rs",
+                JjOutput {
+                    stdout: String::new(),
+                    stderr: "Error: No such path: new.rs".to_string(),
+                    success: false,
+                    code: Some(1),
+                },
+            );
+
+        let dag_ops = DagOperations::new(executor);
+        let plans = dag_ops.absorb_preview(Some(&["new.rs"])).await.unwrap();
+
+        assert_eq!(plans.len(), 1);
+        assert_eq!(plans[0].target_change, None);
+    }
+
     #[tokio::test]
     async fn test_split_by_files() {
         let executor = MockJjExecutor::new().with_response(
@@ -541,6 +1453,7 @@ mod tests {
                     .to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -556,6 +1469,207 @@ mod tests {
         assert_eq!(result.new_changes[1], "ghi789012def");
     }
 
+    const TWO_HUNK_DIFF: &str = "diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -2,1 +2,1 @@
+-fn parse() {}
++fn parse() { parse_v2() }
+@@ -9,0 +10,2 @@
++fn render() {}
++fn render_v2() {}
+";
+
+    fn ok_output(stdout: &str) -> JjOutput {
+        JjOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            success: true,
+            code: Some(0),
+        }
+    }
+
+    /// What [`SplitCapture`] saw while `jj split` ran
+    struct SplitCall {
+        args: Vec<String>,
+        staging: std::path::PathBuf,
+        /// Staged `src/lib.rs`, if one was staged
+        staged: Option<String>,
+    }
+
+    /// Answers `jj split` itself (its staging path is random) and records its
+    /// args, the staging dir and the staged `src/lib.rs` while the split runs
+    #[derive(Clone)]
+    struct SplitCapture {
+        inner: MockJjExecutor,
+        seen: std::sync::Arc<std::sync::Mutex<Option<SplitCall>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl JjExecutor for SplitCapture {
+        async fn exec(&self, args: &[&str]) -> Result<JjOutput> {
+            if args.first() != Some(&"split") {
+                return self.inner.exec(args).await;
+            }
+            let edit_args = args.last().unwrap();
+            let staging = edit_args
+                .split(r#""$right", ""#)
+                .nth(1)
+                .and_then(|rest| rest.split('"').next())
+                .unwrap();
+            let staging = std::path::PathBuf::from(staging);
+            let staged = std::fs::read_to_string(staging.join("src/lib.rs")).ok();
+            *self.seen.lock().unwrap() = Some(SplitCall {
+                args: args.iter().map(|a| a.to_string()).collect(),
+                staging,
+                staged,
+            });
+            Ok(ok_output(
+                "Created new change def456789abc\nCreated new change ghi789012def\n",
+            ))
+        }
+
+        fn repo_root(&self) -> &std::path::PathBuf {
+            self.inner.repo_root()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_split_by_hunks_two_hunks_one_file() {
+        let mock = MockJjExecutor::new()
+            .with_response(
+                "diff --git --context 0 -r abc123 src/lib.rs",
+                ok_output(TWO_HUNK_DIFF),
+            )
+            .with_response(
+                "file show -r abc123- src/lib.rs",
+                ok_output("a\nfn parse() {}\nc\nd\ne\nf\ng\nh\ni\n"),
+            );
+        let executor = SplitCapture {
+            inner: mock,
+            seen: Default::default(),
+        };
+
+        let dag_ops = DagOperations::new(executor.clone());
+        let result = dag_ops
+            .split_by_hunks(
+                "abc123",
+                vec![
+                    HunkSelection::new("src/lib.rs", 2, 2),
+                    HunkSelection::new("src/lib.rs", 10, 11),
+                ],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.new_changes, vec!["def456789abc", "ghi789012def"]);
+        let call = executor.seen.lock().unwrap().take().unwrap();
+        assert!(call
+            .staging
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("hox-split-abc123-"));
+        assert_eq!(
+            call.args,
+            vec![
+                "split".to_string(),
+                "-r".to_string(),
+                "abc123".to_string(),
+                "--siblings".to_string(),
+                "--tool".to_string(),
+                "hox-hunks".to_string(),
+                "--config".to_string(),
+                r#"merge-tools.hox-hunks.program="sh""#.to_string(),
+                "--config".to_string(),
+                format!(
+                    r#"merge-tools.hox-hunks.edit-args=["-c", "find \"$1\" -mindepth 1 -delete && cp -R \"$0\"/. \"$1\"/ && cp -R \"$2\"/. \"$1\"/ && r=\"$1\" && shift 2 && for p in \"$@\"; do rm -f -- \"${{r:?}}/${{p:?}}\"; done", "$left", "$right", "{}"]"#,
+                    call.staging.display()
+                ),
+            ]
+        );
+        let staged = call.staged.unwrap();
+        assert_eq!(staged.lines().nth(1), Some("fn parse() { parse_v2() }"));
+        assert!(!call.staging.exists(), "staging dir should be cleaned up");
+    }
+
+    #[tokio::test]
+    async fn test_split_by_hunks_deleted_file() {
+        let mock = MockJjExecutor::new().with_response(
+            "diff --git --context 0 -r abc123 src/lib.rs",
+            ok_output(
+                "diff --git a/src/lib.rs b/src/lib.rs
+deleted file mode 100644
+--- a/src/lib.rs
++++ /dev/null
+@@ -1,2 +0,0 @@
+-fn parse() {}
+-fn render() {}
+",
+            ),
+        );
+        let executor = SplitCapture {
+            inner: mock,
+            seen: Default::default(),
+        };
+
+        let dag_ops = DagOperations::new(executor.clone());
+        dag_ops
+            .split_by_hunks("abc123", vec![HunkSelection::new("src/lib.rs", 1, 1)])
+            .await
+            .unwrap();
+
+        let call = executor.seen.lock().unwrap().take().unwrap();
+        assert!(call.staged.is_none(), "deleted files are not staged");
+        assert!(call.args.last().unwrap().ends_with(r#", "src/lib.rs"]"#));
+        // The parent version is never read for a deletion
+        assert_eq!(
+            executor.inner.call_count("file show -r abc123- src/lib.rs"),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_split_by_hunks_rejects_unsafe_change_id() {
+        let dag_ops = DagOperations::new(MockJjExecutor::new());
+        let err = dag_ops
+            .split_by_hunks(
+                "abc'; rm -rf /",
+                vec![HunkSelection::new("src/lib.rs", 2, 2)],
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, HoxError::JjRevset(_)));
+
+        // Path-like revsets still stage directly under the temp dir
+        let staging = hunk_split_staging_dir("../abc123/@-").unwrap();
+        assert_eq!(
+            staging.path().parent(),
+            Some(std::env::temp_dir().as_path())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_split_by_hunks_rejects_partial_hunk() {
+        let executor = MockJjExecutor::new().with_response(
+            "diff --git --context 0 -r abc123 src/lib.rs",
+            ok_output(TWO_HUNK_DIFF),
+        );
+
+        let dag_ops = DagOperations::new(executor);
+        let err = dag_ops
+            .split_by_hunks("abc123", vec![HunkSelection::new("src/lib.rs", 10, 10)])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("splits hunk @@ -9,0 +10,2 @@"));
+
+        let err = dag_ops
+            .split_by_hunks("abc123", vec![HunkSelection::new("src/lib.rs", 5, 6)])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("does not match any change"));
+    }
+
     #[tokio::test]
     async fn test_squash() {
         let executor = MockJjExecutor::new().with_response(
@@ -564,6 +1678,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -573,6 +1688,80 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    const REBASED_CHANGE_COMMAND: &str =
+        "log -r abc123 -T change_id ++ \"\\t\" ++ if(conflict, \"conflict\") ++ \"\\n\" --no-graph";
+
+    #[tokio::test]
+    async fn test_rebase() {
+        let executor = MockJjExecutor::new()
+            .with_response(
+                "rebase -r abc123 -d main",
+                JjOutput {
+                    stdout: String::new(),
+                    stderr: "Rebased 1 commits\n".to_string(),
+                    success: true,
+                    code: Some(0),
+                },
+            )
+            .with_response(
+                REBASED_CHANGE_COMMAND,
+                JjOutput {
+                    stdout: "abc123def456\t\n".to_string(),
+                    stderr: String::new(),
+                    success: true,
+                    code: Some(0),
+                },
+            );
+
+        let dag_ops = DagOperations::new(executor);
+        let result = dag_ops.rebase("abc123", "main", None).await.unwrap();
+
+        assert_eq!(result.change_id, "abc123def456");
+        assert!(!result.conflicted);
+        assert!(!result.dry_run);
+    }
+
+    #[tokio::test]
+    async fn test_rebase_insert_before_with_conflict() {
+        let executor = MockJjExecutor::new()
+            .with_response(
+                "rebase -r abc123 -A main -B xyz789",
+                JjOutput {
+                    stdout: String::new(),
+                    stderr: "New conflicts appeared in these commits:\n".to_string(),
+                    success: true,
+                    code: Some(0),
+                },
+            )
+            .with_response(
+                REBASED_CHANGE_COMMAND,
+                JjOutput {
+                    stdout: "abc123def456\tconflict\n".to_string(),
+                    stderr: String::new(),
+                    success: true,
+                    code: Some(0),
+                },
+            );
+
+        let dag_ops = DagOperations::new(executor);
+        let result = dag_ops
+            .rebase("abc123", "main", Some("xyz789"))
+            .await
+            .unwrap();
+
+        assert!(result.conflicted);
+    }
+
+    #[tokio::test]
+    async fn test_rebase_dry_run() {
+        // No responses mocked: a dry run must not execute anything
+        let dag_ops = DagOperations::new(MockJjExecutor::new()).with_dry_run(true);
+        let result = dag_ops.rebase("abc123", "main", None).await.unwrap();
+
+        assert!(result.dry_run);
+        assert_eq!(result.change_id, "abc123");
+    }
+
     #[tokio::test]
     async fn test_squash_into() {
         let executor = MockJjExecutor::new().with_response(
@@ -581,6 +1770,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -598,6 +1788,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -617,6 +1808,7 @@ mod tests {
                 stdout: "Created new change def456789abc\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -634,6 +1826,7 @@ mod tests {
                 stdout: "Created new change ghi012345def\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -643,6 +1836,27 @@ mod tests {
         assert_eq!(new_change_id, "ghi012345def");
     }
 
+    #[tokio::test]
+    async fn test_abandon() {
+        let executor = MockJjExecutor::new().with_response(
+            "abandon def456 ghi789",
+            JjOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+                success: true,
+                code: Some(0),
+            },
+        );
+
+        let dag_ops = DagOperations::new(executor);
+        dag_ops
+            .abandon(&["def456".to_string(), "ghi789".to_string()])
+            .await
+            .unwrap();
+        // Nothing to abandon runs no command
+        dag_ops.abandon(&[]).await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_backout() {
         let executor = MockJjExecutor::new().with_response(
@@ -651,6 +1865,7 @@ mod tests {
                 stdout: "Created backout change def456789abc\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -668,6 +1883,7 @@ mod tests {
                 stdout: "abc123def456\tInitial commit\t2025-01-30 12:00:00\ndef456ghi789\tAmended message\t2025-01-30 12:30:00\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -682,6 +1898,101 @@ mod tests {
         assert_eq!(entries[1].description, "Amended message");
     }
 
+    const EVOLOG_TEMPLATE: &str =
+        r#"commit_id ++ "\t" ++ description.first_line() ++ "\t" ++ committer.timestamp() ++ "\n""#;
+
+    fn evolog_output(stdout: &str) -> JjOutput {
+        JjOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            success: true,
+            code: Some(0),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeline_is_timestamp_ordered() {
+        let executor = MockJjExecutor::new()
+            .with_response(
+                &format!("evolog -r aaa -T {} --no-graph", EVOLOG_TEMPLATE),
+                evolog_output(
+                    "a2\tTask A amended\t2025-01-30 12:30:00.000 +00:00\na1\tTask A\t2025-01-30 12:00:00.000 +00:00\n",
+                ),
+            )
+            .with_response(
+                &format!("evolog -r bbb -T {} --no-graph", EVOLOG_TEMPLATE),
+                evolog_output(
+                    "b2\tTask B amended\t2025-01-30 13:00:00.000 +00:00\nb1\tTask B\t2025-01-30 04:15:00.000 -08:00\n",
+                ),
+            );
+
+        let dag_ops = DagOperations::new(executor);
+        let timeline = dag_ops
+            .timeline(&["aaa".to_string(), "bbb".to_string()], None)
+            .await
+            .unwrap();
+
+        let commits: Vec<&str> = timeline
+            .iter()
+            .map(|t| t.entry.commit_id.as_str())
+            .collect();
+        // b1 is 12:15 UTC once the -08:00 offset is applied
+        assert_eq!(commits, vec!["a1", "b1", "a2", "b2"]);
+        assert_eq!(timeline[0].change_id, "aaa");
+        assert_eq!(timeline[1].change_id, "bbb");
+    }
+
+    #[tokio::test]
+    async fn test_timeline_since_operation() {
+        let executor = MockJjExecutor::new()
+            .with_response(
+                "op log --at-op op123 -n 1 -T self.time().start() ++ \"\\n\" --no-graph",
+                evolog_output("2025-01-30 12:10:00.000 +00:00\n"),
+            )
+            .with_response(
+                &format!("evolog -r aaa -T {} --no-graph", EVOLOG_TEMPLATE),
+                evolog_output(
+                    "a2\tTask A amended\t2025-01-30 12:30:00.000 +00:00\na1\tTask A\t2025-01-30 12:00:00.000 +00:00\n",
+                ),
+            )
+            .with_response(
+                &format!("evolog -r bbb -T {} --no-graph", EVOLOG_TEMPLATE),
+                evolog_output("b1\tTask B\t2025-01-30 12:20:00.000 +00:00\n"),
+            );
+
+        let dag_ops = DagOperations::new(executor);
+        let timeline = dag_ops
+            .timeline(&["aaa".to_string(), "bbb".to_string()], Some("op123"))
+            .await
+            .unwrap();
+
+        let commits: Vec<&str> = timeline
+            .iter()
+            .map(|t| t.entry.commit_id.as_str())
+            .collect();
+        assert_eq!(commits, vec!["b1", "a2"]);
+
+        let since = dag_ops.evolution_log_since("aaa", "op123").await.unwrap();
+        assert_eq!(since.len(), 1);
+        assert_eq!(since[0].commit_id, "a2");
+    }
+
+    #[test]
+    fn test_timeline_entry_json_is_flat() {
+        let entry = TimelineEntry {
+            change_id: "aaa".to_string(),
+            entry: EvolutionEntry {
+                commit_id: "a1".to_string(),
+                description: "Task A".to_string(),
+                timestamp: "2025-01-30 12:00:00.000 +00:00".to_string(),
+            },
+        };
+
+        let json = serde_json::to_value(&entry).unwrap();
+        assert_eq!(json["change_id"], "aaa");
+        assert_eq!(json["commit_id"], "a1");
+    }
+
     #[tokio::test]
     async fn test_simplify_parents() {
         let executor = MockJjExecutor::new().with_response(
@@ -690,6 +2001,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -698,4 +2010,25 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_split_and_squash_dry_run() {
+        // Empty mock: any mutating command would fail
+        let dag_ops = DagOperations::new(MockJjExecutor::new()).with_dry_run(true);
+        assert!(dag_ops.is_dry_run());
+
+        let file_groups = vec![vec!["src/main.rs".to_string()]];
+        let result = dag_ops
+            .split_by_files("abc123", &file_groups)
+            .await
+            .unwrap();
+        assert!(result.dry_run);
+        assert!(result.new_changes.is_empty());
+
+        dag_ops.squash("abc123").await.unwrap();
+        dag_ops
+            .squash_into("abc123", "def456", Some(&["README.md"]))
+            .await
+            .unwrap();
+    }
 }
@@ -0,0 +1,254 @@
+//! Git-format diff parsing for hunk-level DAG operations
+
+/// A single hunk from a git-format diff (`jj diff --git`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DiffHunk {
+    /// File path (new side, or old side for deletions)
+    pub path: String,
+    /// First line of the hunk on the old side (1-based)
+    pub old_start: usize,
+    /// Number of old lines covered by the hunk
+    pub old_lines: usize,
+    /// First line of the hunk on the new side (1-based)
+    pub new_start: usize,
+    /// Number of new lines covered by the hunk
+    pub new_lines: usize,
+    /// Hunk body lines, including their ` `, `-`, `+` prefixes
+    pub body: Vec<String>,
+}
+
+impl DiffHunk {
+    /// The `@@ -a,b +c,d @@` header describing this hunk
+    pub fn header(&self) -> String {
+        format!(
+            "@@ -{},{} +{},{} @@",
+            self.old_start, self.old_lines, self.new_start, self.new_lines
+        )
+    }
+
+    /// Old-side line numbers (1-based) removed or replaced by this hunk
+    pub fn removed_lines(&self) -> Vec<usize> {
+        let mut line = self.old_start;
+        let mut removed = Vec::new();
+        for body_line in &self.body {
+            match body_line.chars().next() {
+                Some('-') => {
+                    removed.push(line);
+                    line += 1;
+                }
+                Some(' ') => line += 1,
+                _ => {}
+            }
+        }
+        removed
+    }
+
+    /// New-side content of the hunk (context and added lines)
+    pub fn new_content(&self) -> impl Iterator<Item = &str> {
+        self.body
+            .iter()
+            .filter(|l| l.starts_with(' ') || l.starts_with('+'))
+            .map(|l| &l[1..])
+    }
+
+    /// Whether the body already holds every line announced by the header
+    fn is_complete(&self) -> bool {
+        let old = self.body.iter().filter(|l| !l.starts_with('+')).count();
+        let new = self.body.iter().filter(|l| !l.starts_with('-')).count();
+        old >= self.old_lines && new >= self.new_lines
+    }
+
+    /// Inclusive new-side line span touched by this hunk
+    ///
+    /// Pure deletions have no new lines, so they occupy the single line
+    /// position at which the deletion happened.
+    pub fn new_span(&self) -> (usize, usize) {
+        let start = self.new_start.max(1);
+        (start, start + self.new_lines.max(1) - 1)
+    }
+}
+
+/// Parse all hunks from git-format diff output
+pub(crate) fn parse_git_diff(output: &str) -> Vec<DiffHunk> {
+    let mut hunks = Vec::new();
+    let mut old_path: Option<String> = None;
+    let mut path: Option<String> = None;
+    let mut current: Option<DiffHunk> = None;
+
+    for line in output.lines() {
+        // Body lines are consumed by count so `--- x` content is not taken for a header
+        if let Some(hunk) = current.as_mut().filter(|h| !h.is_complete()) {
+            if line.starts_with([' ', '-', '+']) {
+                hunk.body.push(line.to_string());
+                continue;
+            }
+        }
+
+        if line.starts_with("diff --git ") {
+            hunks.extend(current.take());
+            old_path = None;
+            path = None;
+        } else if let Some(p) = line.strip_prefix("--- ") {
+            old_path = strip_diff_prefix(p);
+        } else if let Some(p) = line.strip_prefix("+++ ") {
+            path = strip_diff_prefix(p).or_else(|| old_path.clone());
+        } else if let Some(header) = line.strip_prefix("@@ ") {
+            hunks.extend(current.take());
+            let Some(file) = path.clone().or_else(|| old_path.clone()) else {
+                continue;
+            };
+            if let Some((old_start, old_lines, new_start, new_lines)) = parse_hunk_header(header) {
+                current = Some(DiffHunk {
+                    path: file,
+                    old_start,
+                    old_lines,
+                    new_start,
+                    new_lines,
+                    body: Vec::new(),
+                });
+            }
+        }
+    }
+
+    hunks.extend(current);
+    hunks
+}
+
+/// Apply a subset of a file's hunks to its old-side content
+///
+/// Hunks must come from a diff against `base` and must not overlap.
+pub(crate) fn apply_hunks(base: &str, hunks: &[&DiffHunk]) -> String {
+    let base_lines: Vec<&str> = base.lines().collect();
+    let mut sorted: Vec<&&DiffHunk> = hunks.iter().collect();
+    sorted.sort_by_key(|h| h.old_start);
+
+    let mut out: Vec<&str> = Vec::with_capacity(base_lines.len());
+    let mut next = 0;
+    for hunk in sorted {
+        // Insertions (`-N,0`) go after line N; replacements start at line N
+        let start = if hunk.old_lines == 0 {
+            hunk.old_start
+        } else {
+            hunk.old_start - 1
+        };
+        out.extend_from_slice(&base_lines[next.min(start)..start.min(base_lines.len())]);
+        out.extend(hunk.new_content());
+        next = start + hunk.old_lines;
+    }
+    if next < base_lines.len() {
+        out.extend_from_slice(&base_lines[next..]);
+    }
+
+    let mut content = out.join("\n");
+    if !content.is_empty() && (base.is_empty() || base.ends_with('\n')) {
+        content.push('\n');
+    }
+    content
+}
+
+/// Strip the `a/` / `b/` prefix from a diff path, mapping `/dev/null` to `None`
+fn strip_diff_prefix(path: &str) -> Option<String> {
+    let path = path.trim();
+    if path == "/dev/null" {
+        return None;
+    }
+    Some(
+        path.strip_prefix("a/")
+            .or_else(|| path.strip_prefix("b/"))
+            .unwrap_or(path)
+            .to_string(),
+    )
+}
+
+/// Parse `-a,b +c,d @@` into `(a, b, c, d)` (counts default to 1)
+fn parse_hunk_header(header: &str) -> Option<(usize, usize, usize, usize)> {
+    let mut parts = header.split_whitespace();
+    let old = parts.next()?.strip_prefix('-')?;
+    let new = parts.next()?.strip_prefix('+')?;
+
+    let parse_range = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+
+    let (old_start, old_lines) = parse_range(old)?;
+    let (new_start, new_lines) = parse_range(new)?;
+    Some((old_start, old_lines, new_start, new_lines))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -2,1 +2,1 @@ fn main() {
+-old two
++new two
+@@ -8,0 +9,2 @@
++added one
++added two
+@@ -20,1 +21,0 @@
+--- not a header
+diff --git a/gone.txt b/gone.txt
+deleted file mode 100644
+--- a/gone.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-bye
+";
+
+    #[test]
+    fn test_parse_git_diff() {
+        let hunks = parse_git_diff(DIFF);
+
+        assert_eq!(hunks.len(), 4);
+        assert_eq!(hunks[0].path, "src/lib.rs");
+        assert_eq!(hunks[0].header(), "@@ -2,1 +2,1 @@");
+        assert_eq!(hunks[0].removed_lines(), vec![2]);
+        assert_eq!(hunks[1].old_lines, 0);
+        assert_eq!(
+            hunks[1].new_content().collect::<Vec<_>>(),
+            vec!["added one", "added two"]
+        );
+        assert_eq!(hunks[1].new_span(), (9, 10));
+        assert_eq!(hunks[2].removed_lines(), vec![20]);
+        assert_eq!(hunks[2].body, vec!["--- not a header"]);
+        assert_eq!(hunks[3].path, "gone.txt");
+        assert_eq!(hunks[3].header(), "@@ -1,1 +0,0 @@");
+    }
+
+    #[test]
+    fn test_apply_hunks_subset() {
+        let base = "one\ntwo\nthree\nfour\n";
+        let hunks = parse_git_diff(
+            "--- a/f
++++ b/f
+@@ -1,1 +1,1 @@
+-one
++ONE
+@@ -2,0 +3,1 @@
++inserted
+@@ -4,1 +4,0 @@
+-four
+",
+        );
+
+        assert_eq!(
+            apply_hunks(base, &[&hunks[0], &hunks[2]]),
+            "ONE\ntwo\nthree\n"
+        );
+        assert_eq!(
+            apply_hunks(base, &[&hunks[1]]),
+            "one\ntwo\ninserted\nthree\nfour\n"
+        );
+        assert_eq!(
+            apply_hunks(base, &hunks.iter().collect::<Vec<_>>()),
+            "ONE\ntwo\ninserted\nthree\n"
+        );
+    }
+}
@@ -0,0 +1,186 @@
+//! Structured errors for failed JJ commands
+
+use hox_core::HoxError;
+use thiserror::Error;
+
+use crate::command::JjOutput;
+
+/// A failed JJ command, classified from its stderr
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum JjError {
+    /// The working directory is not inside a jj repository
+    #[error("Not a jj repository")]
+    NotARepo,
+
+    /// The revset could not be parsed
+    #[error("Invalid revset: {0}")]
+    BadRevset(String),
+
+    /// The revset did not resolve to any revision
+    #[error("No such revision: {0}")]
+    NoSuchRevision(String),
+
+    /// The operation ran into conflicted changes
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    /// Any other failure
+    #[error("JJ command failed: {stderr}")]
+    Other { stderr: String, code: Option<i32> },
+}
+
+impl JjError {
+    /// Classify a failed command from its stderr and exit code
+    pub fn from_stderr(stderr: &str, code: Option<i32>) -> Self {
+        let message = stderr.trim().trim_start_matches("Error: ").to_string();
+        let lower = stderr.to_lowercase();
+
+        if lower.contains("there is no jj repo") || lower.contains("not a jj repo") {
+            JjError::NotARepo
+        } else if lower.contains("failed to parse revset")
+            || lower.contains("syntax error")
+            || (lower.contains("function") && lower.contains("doesn't exist"))
+        {
+            JjError::BadRevset(message)
+        } else if (lower.contains("revision") && lower.contains("doesn't exist"))
+            || lower.contains("no such revision")
+        {
+            JjError::NoSuchRevision(message)
+        } else if lower.contains("conflict") {
+            JjError::Conflict(message)
+        } else {
+            JjError::Other {
+                stderr: stderr.to_string(),
+                code,
+            }
+        }
+    }
+
+    /// Whether retrying after resolving repository state may succeed
+    pub fn is_conflict(&self) -> bool {
+        matches!(self, JjError::Conflict(_))
+    }
+}
+
+impl JjOutput {
+    /// Convert a failed command into a classified [`JjError`]
+    pub fn into_result(self) -> std::result::Result<JjOutput, JjError> {
+        if self.success {
+            Ok(self)
+        } else {
+            Err(JjError::from_stderr(&self.stderr, self.code))
+        }
+    }
+}
+
+impl From<JjError> for HoxError {
+    fn from(err: JjError) -> Self {
+        match err {
+            JjError::NotARepo => HoxError::JjWorkspace(err.to_string()),
+            JjError::BadRevset(msg) => HoxError::JjRevset(msg),
+            JjError::NoSuchRevision(msg) => HoxError::ChangeNotFound(msg),
+            JjError::Conflict(msg) => HoxError::MergeConflict(msg),
+            JjError::Other { stderr, .. } => HoxError::JjCommand(stderr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::{JjExecutor, MockJjExecutor};
+
+    fn failed(stderr: &str) -> JjOutput {
+        JjOutput {
+            stdout: String::new(),
+            stderr: stderr.to_string(),
+            success: false,
+            code: Some(1),
+        }
+    }
+
+    #[test]
+    fn test_into_result_success() {
+        let output = JjOutput {
+            stdout: "abc\n".to_string(),
+            stderr: String::new(),
+            success: true,
+            code: Some(0),
+        };
+        assert_eq!(output.into_result().unwrap().stdout, "abc\n");
+    }
+
+    #[test]
+    fn test_classify_stderr() {
+        assert_eq!(
+            failed("Error: There is no jj repo in \".\"\n")
+                .into_result()
+                .unwrap_err(),
+            JjError::NotARepo
+        );
+        assert!(matches!(
+            failed("Error: Failed to parse revset: Syntax error\n")
+                .into_result()
+                .unwrap_err(),
+            JjError::BadRevset(_)
+        ));
+        assert_eq!(
+            failed("Error: Revision `xyz` doesn't exist\n")
+                .into_result()
+                .unwrap_err(),
+            JjError::NoSuchRevision("Revision `xyz` doesn't exist".to_string())
+        );
+        assert!(
+            failed("Error: Cannot squash: commit abc has unresolved conflicts\n")
+                .into_result()
+                .unwrap_err()
+                .is_conflict()
+        );
+        assert_eq!(
+            failed("Error: something else\n").into_result().unwrap_err(),
+            JjError::Other {
+                stderr: "Error: something else\n".to_string(),
+                code: Some(1),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_other_keeps_exit_code() {
+        let executor = MockJjExecutor::new().with_response(
+            "git push",
+            JjOutput {
+                stdout: String::new(),
+                stderr: "Error: remote rejected\n".to_string(),
+                success: false,
+                code: Some(128),
+            },
+        );
+
+        let err = executor
+            .exec(&["git", "push"])
+            .await
+            .unwrap()
+            .into_result()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            JjError::Other {
+                stderr: "Error: remote rejected\n".to_string(),
+                code: Some(128),
+            }
+        );
+    }
+
+    #[test]
+    fn test_into_hox_error() {
+        assert!(matches!(
+            HoxError::from(JjError::NoSuchRevision("x".to_string())),
+            HoxError::ChangeNotFound(_)
+        ));
+        assert!(matches!(
+            HoxError::from(JjError::Conflict("x".to_string())),
+            HoxError::MergeConflict(_)
+        ));
+    }
+}
@@ -5,16 +5,21 @@
 //! This crate provides:
 //! - JJ command execution abstraction
 //! - Hox metadata read/write operations
-//! - Revset query helpers
+//! - Revset query helpers and a validating revset builder
 //! - Operation log watching
+//! - Change polling for jj and git-only repositories
 
 #![allow(dead_code)]
 
 mod bookmarks;
+mod change_source;
 mod command;
 mod dag;
+mod diff;
+mod error;
 mod metadata;
 pub mod oplog;
+mod revset_builder;
 mod revsets;
 mod validate;
 
@@ -22,11 +27,21 @@ mod validate;
 pub mod lib_backend;
 
 pub use bookmarks::{BookmarkInfo, BookmarkManager};
-pub use command::{JjCommand, JjExecutor, JjOutput, MockJjExecutor};
-pub use dag::{AbsorbResult, DagOperations, EvolutionEntry, ParallelizeResult, SplitResult};
+pub use change_source::{ChangeEvent, ChangeSource, GitChangeSource};
+pub use command::{
+    ChangeIdResolver, JjCommand, JjExecutor, JjOutput, MockExhaustion, MockJjExecutor,
+};
+pub use dag::{
+    AbsorbPlan, AbsorbResult, DagOperations, EvolutionEntry, HunkSelection, ParallelizeFeasibility,
+    ParallelizeResult, RebaseResult, SplitResult, TimelineEntry,
+};
+pub use error::JjError;
 pub use metadata::MetadataManager;
-pub use oplog::{OpLogEvent, OpLogWatcher, OpLogWatcherConfig, OpManager, OperationInfo};
-pub use revsets::RevsetQueries;
+pub use oplog::{
+    OpLogEvent, OpLogMutation, OpLogWatcher, OpLogWatcherConfig, OpManager, OperationInfo,
+};
+pub use revset_builder::RevsetBuilder;
+pub use revsets::{AgentWorkload, ConflictStatus, RevsetQueries, StatusReport};
 pub use validate::{validate_identifier, validate_path, validate_revset};
 
 #[cfg(feature = "jj-lib-integration")]
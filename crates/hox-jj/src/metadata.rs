@@ -4,9 +4,14 @@
 //! on JJ changes using JJ trailers. Trailers are key-value pairs at the
 //! end of commit descriptions in the format `Key: value`.
 
-use hox_core::{ChangeId, HoxMetadata, MessageType, Priority, Result, TaskStatus};
+use hox_core::{ChangeId, HoxError, HoxMetadata, MessageType, Priority, Result, TaskStatus};
+use std::collections::HashMap;
 
 use crate::command::JjExecutor;
+use crate::validate::validate_identifier;
+
+/// Template for batch reads: full change ID, newline, description, NUL terminator
+const BATCH_READ_TEMPLATE: &str = r#"change_id ++ "\n" ++ description ++ "\0""#;
 
 /// Standard Hox trailer keys (without prefix)
 pub mod trailers {
@@ -191,6 +196,71 @@ impl<E: JjExecutor> MetadataManager<E> {
         Ok(Self::parse_description(&output.stdout))
     }
 
+    /// Read metadata from many changes in a single `jj log` call
+    ///
+    /// The returned map is keyed by the IDs as given (prefixes are matched
+    /// against full change IDs). Changes without Hox trailers map to default
+    /// metadata; IDs that match no change are absent from the map.
+    pub async fn read_many(
+        &self,
+        change_ids: &[ChangeId],
+    ) -> Result<HashMap<ChangeId, HoxMetadata>> {
+        if change_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        for change_id in change_ids {
+            validate_identifier(change_id, "change_id")?;
+        }
+
+        // present() keeps one missing change from failing the whole query
+        let revset = change_ids
+            .iter()
+            .map(|id| format!("present({})", id))
+            .collect::<Vec<_>>()
+            .join(" | ");
+
+        let output = self
+            .executor
+            .exec(&[
+                "log",
+                "-r",
+                &revset,
+                "-T",
+                BATCH_READ_TEMPLATE,
+                "--no-graph",
+            ])
+            .await?;
+
+        if !output.success {
+            return Err(HoxError::JjCommand(format!(
+                "Failed to read metadata: {}",
+                output.stderr
+            )));
+        }
+
+        let mut result = HashMap::with_capacity(change_ids.len());
+        for record in output.stdout.split('\0') {
+            let Some((full_id, description)) = record.split_once('\n') else {
+                continue;
+            };
+            let full_id = full_id.trim();
+            if full_id.is_empty() {
+                continue;
+            }
+
+            let metadata = Self::parse_description(description);
+            for change_id in change_ids
+                .iter()
+                .filter(|id| full_id.starts_with(id.as_str()))
+            {
+                result.insert(change_id.clone(), metadata.clone());
+            }
+        }
+
+        Ok(result)
+    }
+
     /// Set metadata on a change using jj describe
     ///
     /// Note: This updates the change description to include metadata trailers.
@@ -320,4 +390,41 @@ Status: open
         assert_eq!(metadata.agent, Some("agent-42".to_string()));
         assert_eq!(metadata.status, Some(TaskStatus::Open));
     }
+
+    #[tokio::test]
+    async fn test_read_many_single_call() {
+        use crate::command::{JjOutput, MockJjExecutor};
+
+        let executor = MockJjExecutor::new().with_response(
+            &format!(
+                "log -r present(aaa) | present(bbb) | present(ccc) -T {} --no-graph",
+                BATCH_READ_TEMPLATE
+            ),
+            JjOutput {
+                stdout: "aaa111\nAdd parser\n\nStatus: done\nAgent: agent-1\n\0\
+                         bbb222\nPlain change with no trailers\n\0"
+                    .to_string(),
+                stderr: String::new(),
+                success: true,
+                code: Some(0),
+            },
+        );
+
+        let manager = MetadataManager::new(executor);
+        let ids = vec!["aaa".to_string(), "bbb".to_string(), "ccc".to_string()];
+        let metadata = manager.read_many(&ids).await.unwrap();
+
+        assert_eq!(metadata.len(), 2);
+        assert_eq!(metadata["aaa"].status, Some(TaskStatus::Done));
+        assert_eq!(metadata["aaa"].agent, Some("agent-1".to_string()));
+        // Present without metadata vs not found at all
+        assert_eq!(metadata["bbb"].status, None);
+        assert!(!metadata.contains_key("ccc"));
+    }
+
+    #[tokio::test]
+    async fn test_read_many_empty() {
+        let manager = MetadataManager::new(crate::command::MockJjExecutor::new());
+        assert!(manager.read_many(&[]).await.unwrap().is_empty());
+    }
 }
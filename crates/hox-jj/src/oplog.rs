@@ -1,14 +1,16 @@
 //! JJ Operation Log watcher for detecting changes
 
+use async_trait::async_trait;
 use hox_core::fail_open::fail_open_with_retries;
 use hox_core::{HoxError, Result};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::time::interval;
 use tracing::{debug, info, warn};
 
-use crate::command::JjExecutor;
+use crate::change_source::{ChangeEvent, ChangeSource};
+use crate::command::{JjExecutor, JjOutput};
 
 /// OpLog event channel buffer - sized for burst jj operations
 const OPLOG_CHANNEL_BUFFER: usize = 100;
@@ -20,6 +22,10 @@ pub enum OpLogEvent {
     NewOperation {
         operation_id: String,
         description: String,
+        /// Files changed by the operation, relative to the repo root
+        ///
+        /// Empty unless `report_affected_files` is on, or if the lookup failed.
+        affected_files: Vec<PathBuf>,
     },
     /// Watcher started
     Started,
@@ -36,6 +42,16 @@ pub struct OpLogWatcherConfig {
     pub poll_interval: Duration,
     /// Number of recent operations to check
     pub check_count: usize,
+    /// File persisting the last processed operation ID across restarts
+    ///
+    /// When set, the watcher resumes from the stored operation and replays
+    /// newer operations (up to `check_count`). A missing file starts from the
+    /// current head.
+    pub cursor_file: Option<PathBuf>,
+    /// Look up the files each new operation changed
+    ///
+    /// Off by default: it costs one extra jj call per operation.
+    pub report_affected_files: bool,
 }
 
 impl Default for OpLogWatcherConfig {
@@ -43,10 +59,65 @@ impl Default for OpLogWatcherConfig {
         Self {
             poll_interval: Duration::from_millis(500),
             check_count: 10,
+            cursor_file: None,
+            report_affected_files: false,
         }
     }
 }
 
+/// Read the last processed operation ID from a cursor file
+async fn read_cursor(path: &Path) -> Option<String> {
+    match tokio::fs::read_to_string(path).await {
+        Ok(content) => Some(content.trim().to_string()).filter(|id| !id.is_empty()),
+        Err(e) => {
+            debug!("No oplog cursor at {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Atomically replace the cursor file with a new operation ID
+async fn write_cursor(path: &Path, operation_id: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    let tmp_path = path.with_extension("tmp");
+    tokio::fs::write(&tmp_path, operation_id).await?;
+    tokio::fs::rename(&tmp_path, path).await?;
+    Ok(())
+}
+
+/// Commit template listing the files a commit changed, one path per line
+const CHANGED_FILES_TEMPLATE: &str = r#"self.diff().files().map(|f| f.path() ++ "\n").join("")"#;
+
+/// Commits an operation created or rewrote
+///
+/// Evaluated at the operation itself: commits reachable from its heads but
+/// not from the heads of its parent operation.
+fn changed_commits_revset(operation_id: &str) -> String {
+    format!(
+        "at_operation({}-, visible_heads())..visible_heads()",
+        operation_id
+    )
+}
+
+/// Parse [`CHANGED_FILES_TEMPLATE`] output into unique paths, in order
+///
+/// Lines are taken verbatim, so paths containing spaces survive.
+fn parse_affected_files(stdout: &str) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = Vec::new();
+
+    for line in stdout.lines().filter(|line| !line.is_empty()) {
+        let path = PathBuf::from(line);
+        if !files.contains(&path) {
+            files.push(path);
+        }
+    }
+
+    files
+}
+
 /// Watches the JJ operation log for changes
 ///
 /// This is more efficient than file system watching for JJ repos
@@ -107,6 +178,110 @@ impl<E: JjExecutor + 'static> OpLogWatcher<E> {
         }
     }
 
+    /// Files changed by an operation
+    ///
+    /// Fails open: a failed lookup is logged and reported as no files.
+    async fn affected_files(&self, operation_id: &str) -> Vec<PathBuf> {
+        if !self.config.report_affected_files {
+            return Vec::new();
+        }
+
+        let revset = changed_commits_revset(operation_id);
+        match self
+            .executor
+            .exec(&[
+                "--at-op",
+                operation_id,
+                "log",
+                "--no-graph",
+                "-r",
+                &revset,
+                "-T",
+                CHANGED_FILES_TEMPLATE,
+            ])
+            .await
+        {
+            Ok(output) if output.success => parse_affected_files(&output.stdout),
+            Ok(output) => {
+                debug!(
+                    "Failed to list files for operation {}: {}",
+                    operation_id, output.stderr
+                );
+                Vec::new()
+            }
+            Err(e) => {
+                debug!("Failed to list files for operation {}: {}", operation_id, e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Build a `NewOperation` event, looking up the operation's files
+    async fn new_operation_event(&self, operation_id: String, description: String) -> OpLogEvent {
+        let affected_files = self.affected_files(&operation_id).await;
+        OpLogEvent::NewOperation {
+            operation_id,
+            description,
+            affected_files,
+        }
+    }
+
+    /// Operations newer than `cursor`, oldest first
+    ///
+    /// Looks back at most `check_count` operations; if the cursor is not among
+    /// them, all of those are returned.
+    async fn operations_since(&self, cursor: &str) -> Result<Vec<(String, String)>> {
+        let output = self
+            .executor
+            .exec(&[
+                "op",
+                "log",
+                "-n",
+                &self.config.check_count.to_string(),
+                "-T",
+                "operation_id ++ \"\\t\" ++ description ++ \"\\n\"",
+                "--no-graph",
+            ])
+            .await?;
+
+        if !output.success {
+            return Err(HoxError::JjCommand(format!(
+                "Failed to read operation log: {}",
+                output.stderr
+            )));
+        }
+
+        let mut operations = Vec::new();
+        let mut found = false;
+        for line in output.stdout.lines().filter(|l| !l.trim().is_empty()) {
+            let (id, description) = line.split_once('\t').unwrap_or((line, ""));
+            if id == cursor {
+                found = true;
+                break;
+            }
+            operations.push((id.to_string(), description.to_string()));
+        }
+
+        if !found {
+            warn!(
+                "OpLog cursor {} not within the last {} operations",
+                cursor, self.config.check_count
+            );
+        }
+
+        operations.reverse();
+        Ok(operations)
+    }
+
+    /// Persist the last processed operation ID, if a cursor file is configured
+    async fn save_cursor(&self) {
+        if let (Some(path), Some(id)) = (&self.config.cursor_file, &self.last_operation_id) {
+            if let Err(e) = write_cursor(path, id).await {
+                warn!("Failed to write oplog cursor {}: {}", path.display(), e);
+            }
+        }
+    }
+
     /// Start watching and return a receiver for events
     pub async fn watch(mut self) -> Result<mpsc::Receiver<OpLogEvent>> {
         // Buffer sized for 100 ops to handle bursts from rapid jj operations
@@ -114,16 +289,38 @@ impl<E: JjExecutor + 'static> OpLogWatcher<E> {
         // immediately so backpressure is unlikely.
         let (tx, rx) = mpsc::channel(OPLOG_CHANNEL_BUFFER);
 
-        // Get initial operation ID
-        if let Some((id, _)) = self.current_operation().await? {
-            self.last_operation_id = Some(id);
-        }
+        let cursor = match &self.config.cursor_file {
+            Some(path) => read_cursor(path).await,
+            None => None,
+        };
 
         if let Err(e) = tx.send(OpLogEvent::Started).await {
             warn!("OpLog channel send failed: {e}");
         }
         info!("OpLog watcher started for {}", self.repo_root().display());
 
+        match cursor {
+            Some(cursor) => {
+                // Replay operations recorded while the watcher was down
+                let missed = self.operations_since(&cursor).await?;
+                self.last_operation_id = Some(cursor);
+                for (id, description) in missed {
+                    self.last_operation_id = Some(id.clone());
+                    let event = self.new_operation_event(id, description).await;
+                    if let Err(e) = tx.send(event).await {
+                        warn!("OpLog channel send failed: {e}");
+                    }
+                }
+            }
+            None => {
+                // Get initial operation ID
+                if let Some((id, _)) = self.current_operation().await? {
+                    self.last_operation_id = Some(id);
+                }
+            }
+        }
+        self.save_cursor().await;
+
         tokio::spawn(async move {
             let mut poll_interval = interval(self.config.poll_interval);
 
@@ -144,19 +341,14 @@ impl<E: JjExecutor + 'static> OpLogWatcher<E> {
                         if self.last_operation_id.as_ref() != Some(&id) {
                             debug!("New operation detected: {}", id);
 
-                            if tx
-                                .send(OpLogEvent::NewOperation {
-                                    operation_id: id.clone(),
-                                    description: desc,
-                                })
-                                .await
-                                .is_err()
-                            {
+                            let event = self.new_operation_event(id.clone(), desc).await;
+                            if tx.send(event).await.is_err() {
                                 // Receiver dropped
                                 break;
                             }
 
                             self.last_operation_id = Some(id);
+                            self.save_cursor().await;
                         }
                     }
                     Some(None) => {
@@ -192,10 +384,8 @@ impl<E: JjExecutor + 'static> OpLogWatcher<E> {
             Some((id, desc)) => {
                 if self.last_operation_id.as_ref() != Some(&id) {
                     self.last_operation_id = Some(id.clone());
-                    Ok(Some(OpLogEvent::NewOperation {
-                        operation_id: id,
-                        description: desc,
-                    }))
+                    self.save_cursor().await;
+                    Ok(Some(self.new_operation_event(id, desc).await))
                 } else {
                     Ok(None)
                 }
@@ -205,6 +395,46 @@ impl<E: JjExecutor + 'static> OpLogWatcher<E> {
     }
 }
 
+#[async_trait]
+impl<E: JjExecutor + 'static> ChangeSource for OpLogWatcher<E> {
+    /// Operations since the previous poll
+    ///
+    /// Resumes from the cursor file when one is configured; otherwise the
+    /// first poll records the current head as the baseline.
+    async fn poll_changes(&mut self) -> Result<Vec<ChangeEvent>> {
+        if self.last_operation_id.is_none() {
+            if let Some(path) = &self.config.cursor_file {
+                self.last_operation_id = read_cursor(path).await;
+            }
+        }
+
+        let operations = match &self.last_operation_id {
+            Some(cursor) => self.operations_since(cursor).await?,
+            None => {
+                self.last_operation_id = self.current_operation().await?.map(|(id, _)| id);
+                self.save_cursor().await;
+                return Ok(Vec::new());
+            }
+        };
+
+        if let Some((id, _)) = operations.last() {
+            self.last_operation_id = Some(id.clone());
+            self.save_cursor().await;
+        }
+
+        let mut changes = Vec::with_capacity(operations.len());
+        for (id, description) in operations {
+            let paths = self.affected_files(&id).await;
+            changes.push(ChangeEvent {
+                id,
+                description,
+                paths,
+            });
+        }
+        Ok(changes)
+    }
+}
+
 /// Operation information from JJ oplog
 #[derive(Debug, Clone)]
 pub struct OperationInfo {
@@ -213,6 +443,48 @@ pub struct OperationInfo {
     pub timestamp: String,
 }
 
+/// How far back [`OpManager::restore`] looks to count discarded operations
+const RESTORE_SCAN_LIMIT: usize = 100;
+
+/// Result of an operation log mutation (undo, restore, abandon)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpLogMutation {
+    /// Operation (or range) the command targeted
+    pub target: String,
+    /// Operations undone, discarded by a restore, or abandoned
+    pub operations_affected: usize,
+    /// JJ's summary of what happened
+    pub message: String,
+}
+
+impl OpLogMutation {
+    fn new(target: &str, operations_affected: usize, output: &JjOutput) -> Self {
+        // jj reports what it did on stderr
+        let message = if output.stderr.trim().is_empty() {
+            output.stdout.trim()
+        } else {
+            output.stderr.trim()
+        };
+        Self {
+            target: target.to_string(),
+            operations_affected,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Parse the count from jj's "Abandoned N operations ..." summary
+fn parse_abandoned_count(output: &JjOutput) -> usize {
+    [&output.stderr, &output.stdout]
+        .iter()
+        .flat_map(|text| text.lines())
+        .find_map(|line| {
+            let rest = line.trim().strip_prefix("Abandoned ")?;
+            rest.split_whitespace().next()?.parse().ok()
+        })
+        .unwrap_or(0)
+}
+
 /// Manager for JJ operation log manipulation
 ///
 /// This provides rollback and recovery capabilities by manipulating
@@ -276,25 +548,36 @@ impl<E: JjExecutor> OpManager<E> {
         Ok(operations)
     }
 
-    /// Undo the most recent operation
-    pub async fn undo(&self) -> Result<()> {
-        let output = self.executor.exec(&["undo"]).await?;
+    /// Undo a specific operation by ID (`@` for the most recent one)
+    ///
+    /// JJ records the undo as a new operation, so later operations are kept.
+    pub async fn undo(&self, operation_id: &str) -> Result<OpLogMutation> {
+        let output = self.executor.exec(&["op", "undo", operation_id]).await?;
 
         if !output.success {
             return Err(HoxError::JjCommand(format!(
-                "Failed to undo operation: {}",
-                output.stderr
+                "Failed to undo operation {}: {}",
+                operation_id, output.stderr
             )));
         }
 
-        Ok(())
+        Ok(OpLogMutation::new(operation_id, 1, &output))
     }
 
     /// Restore to a specific operation by ID
     ///
     /// This makes the given operation the current state, discarding all
-    /// operations that came after it.
-    pub async fn restore(&self, operation_id: &str) -> Result<()> {
+    /// operations that came after it. `operations_affected` counts those
+    /// operations, looking back at most 100 operations.
+    pub async fn restore(&self, operation_id: &str) -> Result<OpLogMutation> {
+        // Count operations that will be discarded (must happen before restoring)
+        let discarded = self
+            .recent_operations(RESTORE_SCAN_LIMIT)
+            .await?
+            .iter()
+            .take_while(|op| op.id != operation_id)
+            .count();
+
         let output = self.executor.exec(&["op", "restore", operation_id]).await?;
 
         if !output.success {
@@ -304,7 +587,27 @@ impl<E: JjExecutor> OpManager<E> {
             )));
         }
 
-        Ok(())
+        Ok(OpLogMutation::new(operation_id, discarded, &output))
+    }
+
+    /// Abandon a range of operations (e.g. `..op1` or `op1..op3`)
+    ///
+    /// This only trims operation history; the repository state is unchanged.
+    pub async fn abandon_operations(&self, range: &str) -> Result<OpLogMutation> {
+        let output = self.executor.exec(&["op", "abandon", range]).await?;
+
+        if !output.success {
+            return Err(HoxError::JjCommand(format!(
+                "Failed to abandon operations {}: {}",
+                range, output.stderr
+            )));
+        }
+
+        Ok(OpLogMutation::new(
+            range,
+            parse_abandoned_count(&output),
+            &output,
+        ))
     }
 
     /// Revert a specific operation by ID (creating inverse operation)
@@ -326,7 +629,7 @@ impl<E: JjExecutor> OpManager<E> {
                     "jj op revert not available, falling back to restore for operation {}",
                     operation_id
                 );
-                return self.restore(operation_id).await;
+                return self.restore(operation_id).await.map(|_| ());
             }
 
             return Err(HoxError::JjCommand(format!(
@@ -364,6 +667,7 @@ mod tests {
                 stdout: "abc123\ttest operation".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -376,6 +680,184 @@ mod tests {
         );
     }
 
+    const CURRENT_OP_COMMAND: &str =
+        "op log -n 1 -T operation_id ++ \"\\t\" ++ description --no-graph";
+    const RECENT_OPS_COMMAND: &str =
+        "op log -n 10 -T operation_id ++ \"\\t\" ++ description ++ \"\\n\" --no-graph";
+
+    fn ok(stdout: &str) -> JjOutput {
+        JjOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            success: true,
+            code: Some(0),
+        }
+    }
+
+    fn cursor_config(path: &Path) -> OpLogWatcherConfig {
+        OpLogWatcherConfig {
+            poll_interval: Duration::from_millis(10),
+            cursor_file: Some(path.to_path_buf()),
+            ..Default::default()
+        }
+    }
+
+    async fn wait_for_cursor(path: &Path, expected: &str) {
+        for _ in 0..100 {
+            if read_cursor(path).await.as_deref() == Some(expected) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("cursor never reached {}", expected);
+    }
+
+    #[tokio::test]
+    async fn test_watch_resumes_from_cursor() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let cursor = dir.path().join("oplog.cursor");
+        std::fs::write(&cursor, "op1\n").unwrap();
+
+        let executor = MockJjExecutor::new()
+            .with_response(
+                RECENT_OPS_COMMAND,
+                ok("op3\trebase\nop2\tdescribe\nop1\tnew\nop0\tinit\n"),
+            )
+            .with_response(CURRENT_OP_COMMAND, ok("op3\trebase"));
+
+        let watcher = OpLogWatcher::new(executor).with_config(cursor_config(&cursor));
+        let mut events = watcher.watch().await.unwrap();
+
+        assert!(matches!(events.recv().await, Some(OpLogEvent::Started)));
+        for expected in ["op2", "op3"] {
+            match events.recv().await {
+                Some(OpLogEvent::NewOperation { operation_id, .. }) => {
+                    assert_eq!(operation_id, expected)
+                }
+                other => panic!("unexpected event: {:?}", other),
+            }
+        }
+        wait_for_cursor(&cursor, "op3").await;
+
+        // Head has not moved since the replay: nothing more is emitted
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_watch_without_cursor_starts_at_head() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let cursor = dir.path().join("state").join("oplog.cursor");
+
+        let executor = MockJjExecutor::new().with_response(CURRENT_OP_COMMAND, ok("op3\trebase"));
+
+        let watcher = OpLogWatcher::new(executor).with_config(cursor_config(&cursor));
+        let mut events = watcher.watch().await.unwrap();
+
+        assert!(matches!(events.recv().await, Some(OpLogEvent::Started)));
+        wait_for_cursor(&cursor, "op3").await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_poll_changes_resumes_from_cursor() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let cursor = dir.path().join("oplog.cursor");
+        std::fs::write(&cursor, "op1\n").unwrap();
+
+        let executor = MockJjExecutor::new().with_response(
+            RECENT_OPS_COMMAND,
+            ok("op3\trebase\nop2\tdescribe\nop1\tnew\n"),
+        );
+        let mut watcher = OpLogWatcher::new(executor).with_config(cursor_config(&cursor));
+
+        let changes = watcher.poll_changes().await.unwrap();
+        let ids: Vec<_> = changes.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["op2", "op3"]);
+        assert_eq!(changes[1].description, "rebase");
+        assert_eq!(read_cursor(&cursor).await.as_deref(), Some("op3"));
+
+        // Head has not moved
+        assert!(watcher.poll_changes().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_check_once_saves_cursor() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let cursor = dir.path().join("oplog.cursor");
+        let executor = MockJjExecutor::new().with_response(CURRENT_OP_COMMAND, ok("op7\tsquash"));
+
+        let mut watcher = OpLogWatcher::new(executor).with_config(cursor_config(&cursor));
+        assert!(watcher.check_once().await.unwrap().is_some());
+
+        assert_eq!(read_cursor(&cursor).await.as_deref(), Some("op7"));
+    }
+
+    /// `CHANGED_FILES_TEMPLATE` output for two commits (the second rewritten
+    /// by the operation, so `src/lib.rs` appears twice)
+    const CHANGED_FILES_OUTPUT: &str =
+        "src/config.rs\nsrc/lib.rs\ndocs/release notes.md\nsrc/lib.rs\n";
+
+    const CHANGED_FILES_COMMAND: &str = r#"--at-op op9 log --no-graph -r at_operation(op9-, visible_heads())..visible_heads() -T self.diff().files().map(|f| f.path() ++ "\n").join("")"#;
+
+    fn changed_files() -> Vec<PathBuf> {
+        vec![
+            PathBuf::from("src/config.rs"),
+            PathBuf::from("src/lib.rs"),
+            PathBuf::from("docs/release notes.md"),
+        ]
+    }
+
+    #[test]
+    fn test_parse_affected_files() {
+        assert_eq!(parse_affected_files(CHANGED_FILES_OUTPUT), changed_files());
+        assert!(parse_affected_files("").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_new_operation_reports_affected_files() {
+        let executor = MockJjExecutor::new()
+            .with_responses(
+                CURRENT_OP_COMMAND,
+                vec![ok("op8\tnew"), ok("op9\tsnapshot working copy")],
+            )
+            .with_response(CHANGED_FILES_COMMAND, ok(CHANGED_FILES_OUTPUT));
+
+        let mut watcher = OpLogWatcher::new(executor).with_config(OpLogWatcherConfig {
+            report_affected_files: true,
+            ..Default::default()
+        });
+        watcher.check_once().await.unwrap();
+        match watcher.check_once().await.unwrap() {
+            Some(OpLogEvent::NewOperation {
+                operation_id,
+                affected_files,
+                ..
+            }) => {
+                assert_eq!(operation_id, "op9");
+                assert_eq!(affected_files, changed_files());
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_affected_files_off_by_default() {
+        let executor = MockJjExecutor::new()
+            .with_response(CURRENT_OP_COMMAND, ok("op9\tsnapshot working copy"))
+            .with_response(CHANGED_FILES_COMMAND, ok(CHANGED_FILES_OUTPUT));
+
+        let mut watcher = OpLogWatcher::new(executor.clone());
+        match watcher.check_once().await.unwrap() {
+            Some(OpLogEvent::NewOperation { affected_files, .. }) => {
+                assert!(affected_files.is_empty())
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+        assert_eq!(executor.call_count(CHANGED_FILES_COMMAND), 0);
+    }
+
     #[tokio::test]
     async fn test_recent_operations() {
         let executor = MockJjExecutor::new().with_response(
@@ -384,6 +866,7 @@ mod tests {
                 stdout: "op3\tdescription 3\t2024-01-01 12:00:00\nop2\tdescription 2\t2024-01-01 11:00:00\nop1\tdescription 1\t2024-01-01 10:00:00\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -396,38 +879,93 @@ mod tests {
         assert_eq!(operations[1].id, "op2");
     }
 
+    const RESTORE_SCAN_COMMAND: &str =
+        "op log -n 100 -T operation_id ++ \"\\t\" ++ description ++ \"\\t\" ++ time ++ \"\\n\" --no-graph";
+
+    fn summary(stderr: &str) -> JjOutput {
+        JjOutput {
+            stdout: String::new(),
+            stderr: stderr.to_string(),
+            success: true,
+            code: Some(0),
+        }
+    }
+
     #[tokio::test]
     async fn test_undo() {
         let executor = MockJjExecutor::new().with_response(
-            "undo",
-            JjOutput {
-                stdout: String::new(),
-                stderr: String::new(),
-                success: true,
-            },
+            "op undo op7",
+            summary("Undid operation: op7 (2024-01-01 12:00:00) describe commit\n"),
         );
 
-        let manager = OpManager::new(executor);
-        let result = manager.undo().await;
+        let manager = OpManager::new(executor.clone());
+        let result = manager.undo("op7").await.unwrap();
 
-        assert!(result.is_ok());
+        assert_eq!(result.target, "op7");
+        assert_eq!(result.operations_affected, 1);
+        assert!(result.message.starts_with("Undid operation: op7"));
+        executor.assert_called_with(&["op", "undo", "op7"]);
     }
 
     #[tokio::test]
-    async fn test_restore() {
+    async fn test_undo_failure() {
         let executor = MockJjExecutor::new().with_response(
-            "op restore test-op-123",
+            "op undo nope",
             JjOutput {
                 stdout: String::new(),
-                stderr: String::new(),
-                success: true,
+                stderr: "Error: No operation ID matching \"nope\"".to_string(),
+                success: false,
+                code: Some(1),
             },
         );
 
+        let err = OpManager::new(executor).undo("nope").await.unwrap_err();
+        assert!(err.to_string().contains("Failed to undo operation nope"));
+    }
+
+    #[tokio::test]
+    async fn test_restore() {
+        let executor = MockJjExecutor::new()
+            .with_response(
+                RESTORE_SCAN_COMMAND,
+                ok("op3\tsquash\t2024-01-01\nop2\tnew\t2024-01-01\ntest-op-123\tinit\t2024-01-01\n"),
+            )
+            .with_response(
+                "op restore test-op-123",
+                summary("Restored to operation: test-op-123\n"),
+            );
+
+        let manager = OpManager::new(executor.clone());
+        let result = manager.restore("test-op-123").await.unwrap();
+
+        assert_eq!(result.operations_affected, 2);
+        assert_eq!(result.message, "Restored to operation: test-op-123");
+        // Operations are counted before restoring
+        let calls = executor.recorded_calls();
+        assert_eq!(calls.last().unwrap()[1], "restore");
+    }
+
+    #[tokio::test]
+    async fn test_abandon_operations() {
+        let executor = MockJjExecutor::new().with_response(
+            "op abandon ..op2",
+            summary("Abandoned 3 operations and reparented 1 descendant operations.\n"),
+        );
+
         let manager = OpManager::new(executor);
-        let result = manager.restore("test-op-123").await;
+        let result = manager.abandon_operations("..op2").await.unwrap();
+
+        assert_eq!(result.target, "..op2");
+        assert_eq!(result.operations_affected, 3);
+    }
 
-        assert!(result.is_ok());
+    #[test]
+    fn test_parse_abandoned_count() {
+        assert_eq!(parse_abandoned_count(&summary("Nothing changed.\n")), 0);
+        assert_eq!(
+            parse_abandoned_count(&summary("Abandoned 12 operations.\n")),
+            12
+        );
     }
 
     #[tokio::test]
@@ -438,6 +976,7 @@ mod tests {
                 stdout: "snapshot-123\tcurrent state\t2024-01-01 12:00:00\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
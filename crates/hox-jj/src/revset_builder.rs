@@ -0,0 +1,317 @@
+//! Typed construction of revset expressions
+//!
+//! [`RevsetBuilder`] validates every user-controlled piece (change IDs,
+//! metadata values, bookmark patterns, paths) as it is added, so callers never
+//! interpolate raw strings into revsets. Combinators insert parentheses where
+//! operator precedence requires them.
+
+use std::fmt;
+
+use hox_core::{HoxError, Result};
+
+use crate::validate::{validate_identifier, validate_path, validate_revset};
+
+/// Top-level operator of an expression, used to decide on parentheses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    /// A symbol or function call; never needs parentheses
+    None,
+    /// `&` or `& ~`
+    Intersection,
+    /// `|`
+    Union,
+}
+
+/// A validated revset expression
+///
+/// ```
+/// use hox_jj::RevsetBuilder;
+///
+/// let revset = RevsetBuilder::bookmarks_glob("agent/agent-1/*")?
+///     .and_not(RevsetBuilder::description_glob("Status", "done")?)
+///     .build();
+/// assert_eq!(
+///     revset,
+///     r#"bookmarks(glob:"agent/agent-1/*") & ~description(glob:"Status: done")"#
+/// );
+/// # Ok::<(), hox_core::HoxError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevsetBuilder {
+    expr: String,
+    operator: Operator,
+}
+
+impl RevsetBuilder {
+    fn atom(expr: String) -> Self {
+        Self {
+            expr,
+            operator: Operator::None,
+        }
+    }
+
+    /// A change by ID or ID prefix
+    pub fn change_id(change_id: &str) -> Result<Self> {
+        validate_identifier(change_id, "change_id")?;
+        Ok(Self::atom(change_id.to_string()))
+    }
+
+    /// Changes whose description matches `{field}: {value}`
+    ///
+    /// Revset: `description(glob:"{field}: {value}")`
+    pub fn description_glob(field: &str, value: &str) -> Result<Self> {
+        validate_identifier(field, "description field")?;
+        validate_identifier(value, field)?;
+        Ok(Self::atom(format!(
+            "description(glob:\"{}: {}\")",
+            field, value
+        )))
+    }
+
+    /// Changes with a bookmark matching `pattern`, where `*` is a wildcard
+    ///
+    /// Revset: `bookmarks(glob:"{pattern}")`
+    pub fn bookmarks_glob(pattern: &str) -> Result<Self> {
+        let mut segments = pattern.split('*').filter(|s| !s.is_empty()).peekable();
+        if segments.peek().is_none() {
+            return Err(HoxError::PathValidation(format!(
+                "bookmark pattern must contain more than wildcards: '{}'",
+                pattern
+            )));
+        }
+        for segment in segments {
+            validate_identifier(segment, "bookmark pattern")?;
+        }
+        Ok(Self::atom(format!("bookmarks(glob:\"{}\")", pattern)))
+    }
+
+    /// Changes touching a file
+    ///
+    /// Revset: `file("{path}")`
+    pub fn file(path: &str) -> Result<Self> {
+        validate_path(path, "file_path")?;
+        Ok(Self::atom(format!("file(\"{}\")", path)))
+    }
+
+    /// An arbitrary revset expression, checked with [`validate_revset`]
+    ///
+    /// The expression is treated as compound and parenthesized when combined.
+    pub fn raw(revset: &str) -> Result<Self> {
+        validate_revset(revset)?;
+        Ok(Self {
+            expr: revset.to_string(),
+            operator: Operator::Union,
+        })
+    }
+
+    /// The working-copy change: `@`
+    pub fn working_copy() -> Self {
+        Self::atom("@".to_string())
+    }
+
+    /// `conflicts()`
+    pub fn conflicts() -> Self {
+        Self::atom("conflicts()".to_string())
+    }
+
+    /// `mutable()`
+    pub fn mutable() -> Self {
+        Self::atom("mutable()".to_string())
+    }
+
+    /// `merges()`
+    pub fn merges() -> Self {
+        Self::atom("merges()".to_string())
+    }
+
+    /// `empty()`
+    pub fn empty() -> Self {
+        Self::atom("empty()".to_string())
+    }
+
+    /// Changes in both: `self & other`
+    pub fn and(self, other: Self) -> Self {
+        let left = self.wrap_if(Operator::Union);
+        let right = other.wrap_if(Operator::Intersection);
+        Self {
+            expr: format!("{} & {}", left, right),
+            operator: Operator::Intersection,
+        }
+    }
+
+    /// Changes in `self` but not `other`: `self & ~other`
+    pub fn and_not(self, other: Self) -> Self {
+        let left = self.wrap_if(Operator::Union);
+        let right = other.wrap_if(Operator::Intersection);
+        Self {
+            expr: format!("{} & ~{}", left, right),
+            operator: Operator::Intersection,
+        }
+    }
+
+    /// Changes in either: `self | other`
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            expr: format!("{} | {}", self.expr, other.expr),
+            operator: Operator::Union,
+        }
+    }
+
+    /// `ancestors(self)`
+    pub fn ancestors(self) -> Self {
+        self.call("ancestors")
+    }
+
+    /// `descendants(self)`
+    pub fn descendants(self) -> Self {
+        self.call("descendants")
+    }
+
+    /// `heads(self)`
+    pub fn heads(self) -> Self {
+        self.call("heads")
+    }
+
+    /// `present(self)`: empty instead of an error if the change is missing
+    pub fn present(self) -> Self {
+        self.call("present")
+    }
+
+    /// `connected(self)`
+    pub fn connected(self) -> Self {
+        self.call("connected")
+    }
+
+    /// `latest(self, count)`
+    pub fn latest(self, count: usize) -> Self {
+        Self::atom(format!("latest({}, {})", self.expr, count))
+    }
+
+    /// The revset string
+    pub fn build(self) -> String {
+        self.expr
+    }
+
+    fn call(self, function: &str) -> Self {
+        Self::atom(format!("{}({})", function, self.expr))
+    }
+
+    /// Parenthesize if the top-level operator binds at most as tightly as `weakest`
+    fn wrap_if(self, weakest: Operator) -> String {
+        let needs_parens = match weakest {
+            Operator::Intersection => self.operator != Operator::None,
+            _ => self.operator == weakest,
+        };
+        if needs_parens {
+            format!("({})", self.expr)
+        } else {
+            self.expr
+        }
+    }
+}
+
+impl fmt::Display for RevsetBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_atoms() {
+        assert_eq!(
+            RevsetBuilder::change_id("abc123").unwrap().build(),
+            "abc123"
+        );
+        assert_eq!(
+            RevsetBuilder::description_glob("Status", "open")
+                .unwrap()
+                .build(),
+            r#"description(glob:"Status: open")"#
+        );
+        assert_eq!(
+            RevsetBuilder::bookmarks_glob("agent/a-1/task/*")
+                .unwrap()
+                .build(),
+            r#"bookmarks(glob:"agent/a-1/task/*")"#
+        );
+        assert_eq!(
+            RevsetBuilder::file("src/main.rs").unwrap().build(),
+            r#"file("src/main.rs")"#
+        );
+    }
+
+    #[test]
+    fn test_functions() {
+        let id = || RevsetBuilder::change_id("abc").unwrap();
+        assert_eq!(id().ancestors().build(), "ancestors(abc)");
+        assert_eq!(id().descendants().build(), "descendants(abc)");
+        assert_eq!(id().present().build(), "present(abc)");
+        assert_eq!(id().connected().build(), "connected(abc)");
+        assert_eq!(RevsetBuilder::mutable().heads().build(), "heads(mutable())");
+        assert_eq!(
+            RevsetBuilder::raw("mutable()").unwrap().latest(5).build(),
+            "latest(mutable(), 5)"
+        );
+    }
+
+    #[test]
+    fn test_combinators_parenthesize_by_precedence() {
+        let id = RevsetBuilder::change_id("abc").unwrap();
+        assert_eq!(
+            id.ancestors()
+                .and(RevsetBuilder::mutable())
+                .and(RevsetBuilder::conflicts())
+                .build(),
+            "ancestors(abc) & mutable() & conflicts()"
+        );
+
+        let union = RevsetBuilder::change_id("a")
+            .unwrap()
+            .or(RevsetBuilder::change_id("b").unwrap());
+        assert_eq!(
+            union
+                .clone()
+                .and_not(RevsetBuilder::conflicts().descendants())
+                .build(),
+            "(a | b) & ~descendants(conflicts())"
+        );
+        assert_eq!(
+            RevsetBuilder::working_copy().and_not(union.clone()).build(),
+            "@ & ~(a | b)"
+        );
+        assert_eq!(
+            RevsetBuilder::empty()
+                .or(RevsetBuilder::merges().and(RevsetBuilder::mutable()))
+                .build(),
+            "empty() | merges() & mutable()"
+        );
+        assert_eq!(
+            RevsetBuilder::mutable()
+                .and_not(RevsetBuilder::merges().and(RevsetBuilder::empty()))
+                .to_string(),
+            "mutable() & ~(merges() & empty())"
+        );
+        assert_eq!(
+            RevsetBuilder::raw("a | b")
+                .unwrap()
+                .and(RevsetBuilder::mutable())
+                .build(),
+            "(a | b) & mutable()"
+        );
+    }
+
+    #[test]
+    fn test_rejects_injection() {
+        assert!(RevsetBuilder::change_id("abc) | all(").is_err());
+        assert!(RevsetBuilder::description_glob("Status", "open\") | all() | (\"").is_err());
+        assert!(RevsetBuilder::description_glob("Status: x", "open").is_err());
+        assert!(RevsetBuilder::bookmarks_glob("task/\"*").is_err());
+        assert!(RevsetBuilder::bookmarks_glob("*").is_err());
+        assert!(RevsetBuilder::file("../etc/passwd").is_err());
+        assert!(RevsetBuilder::raw("foo\"; rm -rf /").is_err());
+    }
+}
@@ -1,9 +1,11 @@
 //! Revset query helpers for Hox orchestration
 
 use hox_core::{ChangeId, Result};
+use serde::{Deserialize, Serialize};
 
 use crate::command::{JjExecutor, JjOutput};
-use crate::validate::{validate_identifier, validate_path, validate_revset};
+use crate::revset_builder::RevsetBuilder;
+use crate::validate::validate_identifier;
 
 /// Helper for building and executing revset queries
 pub struct RevsetQueries<E: JjExecutor> {
@@ -32,6 +34,50 @@ impl<E: JjExecutor> RevsetQueries<E> {
         Ok(parse_change_ids(&output))
     }
 
+    /// Query one page of matching change IDs
+    ///
+    /// jj can limit but not skip, so this fetches `offset + limit` changes
+    /// with `--limit` and drops the first `offset`.
+    pub async fn query_paginated(
+        &self,
+        revset: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<ChangeId>> {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let output = self
+            .executor
+            .exec(&[
+                "log",
+                "-r",
+                revset,
+                "--limit",
+                &offset.saturating_add(limit).to_string(),
+                "-T",
+                "change_id ++ \"\\n\"",
+                "--no-graph",
+            ])
+            .await?;
+
+        Ok(parse_change_ids(&output).into_iter().skip(offset).collect())
+    }
+
+    /// Count the changes matching a revset
+    ///
+    /// Emits one byte per change instead of full change IDs.
+    pub async fn count(&self, revset: &str) -> Result<usize> {
+        let output = self
+            .executor
+            .exec(&["log", "-r", revset, "-T", "\".\"", "--no-graph"])
+            .await?
+            .into_result()?;
+
+        Ok(output.stdout.trim().len())
+    }
+
     /// Find ready tasks (open status, no conflicts, at heads)
     ///
     /// Revset: `heads(description(glob:"Status: open")) - conflicts()`
@@ -48,16 +94,41 @@ impl<E: JjExecutor> RevsetQueries<E> {
     /// Note: When jj-dev is complete, this becomes:
     /// `orchestrator("O-A-1")`
     pub async fn by_orchestrator(&self, orchestrator: &str) -> Result<Vec<ChangeId>> {
-        validate_identifier(orchestrator, "orchestrator")?;
-        let revset = format!("description(glob:\"Orchestrator: {}\")", orchestrator);
-        self.query(&revset).await
+        let revset = RevsetBuilder::description_glob("Orchestrator", orchestrator)?;
+        self.query(&revset.build()).await
     }
 
     /// Find tasks assigned to a specific agent
+    ///
+    /// Combines bookmark ownership with the `Agent:` metadata field.
+    ///
+    /// Revset: `bookmarks(glob:"agent/{name}/task/*") | description(glob:"Agent: {name}")`
     pub async fn by_agent(&self, agent: &str) -> Result<Vec<ChangeId>> {
-        validate_identifier(agent, "agent")?;
-        let revset = format!("description(glob:\"Agent: {}\")", agent);
-        self.query(&revset).await
+        self.query(&agent_revset(agent)?.build()).await
+    }
+
+    /// Find tasks an agent can pick up now: assigned, not done, not blocked
+    ///
+    /// A task is blocked by its `Status: blocked` metadata or by a conflict in
+    /// itself or an ancestor.
+    ///
+    /// Revset: `({assigned}) & ~description(glob:"Status: done")
+    /// & ~description(glob:"Status: blocked") & ~descendants(conflicts())`
+    pub async fn ready_for_agent(&self, agent: &str) -> Result<Vec<ChangeId>> {
+        let revset = agent_revset(agent)?
+            .and_not(status_revset("done")?)
+            .and_not(status_revset("blocked")?)
+            .and_not(RevsetBuilder::conflicts().descendants());
+        self.query(&revset.build()).await
+    }
+
+    /// Collect an agent's assigned and ready tasks
+    pub async fn agent_workload(&self, agent: &str) -> Result<AgentWorkload> {
+        Ok(AgentWorkload {
+            agent: agent.to_string(),
+            assigned: self.by_agent(agent).await?,
+            ready: self.ready_for_agent(agent).await?,
+        })
     }
 
     /// Find messages addressed to a target (supports wildcards)
@@ -65,11 +136,10 @@ impl<E: JjExecutor> RevsetQueries<E> {
     /// Note: When jj-dev is complete with glob support for msg_to:
     /// `msg_to("O-A-*")`
     pub async fn messages_to(&self, target: &str) -> Result<Vec<ChangeId>> {
-        validate_identifier(target, "message target")?;
         // For now, we need to handle wildcards in application code
         // JJ's glob support in description() is limited
-        let revset = format!("description(glob:\"Msg-To: {}\")", target);
-        self.query(&revset).await
+        let revset = RevsetBuilder::description_glob("Msg-To", target)?;
+        self.query(&revset.build()).await
     }
 
     /// Find mutation messages (structural decisions from orchestrators)
@@ -85,30 +155,27 @@ impl<E: JjExecutor> RevsetQueries<E> {
 
     /// Find ancestors of a change (what blocks this task)
     pub async fn ancestors(&self, change_id: &ChangeId) -> Result<Vec<ChangeId>> {
-        validate_identifier(change_id, "change_id")?;
-        let revset = format!("ancestors({}) & mutable()", change_id);
-        self.query(&revset).await
+        let revset = RevsetBuilder::change_id(change_id)?
+            .ancestors()
+            .and(RevsetBuilder::mutable());
+        self.query(&revset.build()).await
     }
 
     /// Find descendants of a change (what this task blocks)
     pub async fn descendants(&self, change_id: &ChangeId) -> Result<Vec<ChangeId>> {
-        validate_identifier(change_id, "change_id")?;
-        let revset = format!("descendants({})", change_id);
-        self.query(&revset).await
+        let revset = RevsetBuilder::change_id(change_id)?.descendants();
+        self.query(&revset.build()).await
     }
 
     /// Find tasks by priority
     pub async fn by_priority(&self, priority: &str) -> Result<Vec<ChangeId>> {
-        validate_identifier(priority, "priority")?;
-        let revset = format!("description(glob:\"Priority: {}\")", priority);
-        self.query(&revset).await
+        let revset = RevsetBuilder::description_glob("Priority", priority)?;
+        self.query(&revset.build()).await
     }
 
     /// Find tasks by status
     pub async fn by_status(&self, status: &str) -> Result<Vec<ChangeId>> {
-        validate_identifier(status, "status")?;
-        let revset = format!("description(glob:\"Status: {}\")", status);
-        self.query(&revset).await
+        self.query(&status_revset(status)?.build()).await
     }
 
     /// Find changes with conflicts
@@ -138,8 +205,8 @@ impl<E: JjExecutor> RevsetQueries<E> {
     /// Revset: `bookmarks(glob:"agent/{name}/task/*")`
     pub async fn agent_tasks_by_bookmark(&self, agent_name: &str) -> Result<Vec<ChangeId>> {
         validate_identifier(agent_name, "agent_name")?;
-        let revset = format!(r#"bookmarks(glob:"agent/{}/task/*")"#, agent_name);
-        self.query(&revset).await
+        let revset = RevsetBuilder::bookmarks_glob(&format!("agent/{}/task/*", agent_name))?;
+        self.query(&revset.build()).await
     }
 
     /// Find orchestrator by bookmark (fast path)
@@ -147,8 +214,8 @@ impl<E: JjExecutor> RevsetQueries<E> {
     /// Revset: `bookmarks(glob:"orchestrator/{id}")`
     pub async fn orchestrator_by_bookmark(&self, orch_id: &str) -> Result<Vec<ChangeId>> {
         validate_identifier(orch_id, "orchestrator_id")?;
-        let revset = format!(r#"bookmarks(glob:"orchestrator/{}")"#, orch_id);
-        self.query(&revset).await
+        let revset = RevsetBuilder::bookmarks_glob(&format!("orchestrator/{}", orch_id))?;
+        self.query(&revset.build()).await
     }
 
     /// Find all orchestrators by bookmark prefix
@@ -163,8 +230,8 @@ impl<E: JjExecutor> RevsetQueries<E> {
     /// Revset: `bookmarks(glob:"session/{id}")`
     pub async fn session_by_bookmark(&self, session_id: &str) -> Result<Vec<ChangeId>> {
         validate_identifier(session_id, "session_id")?;
-        let revset = format!(r#"bookmarks(glob:"session/{}")"#, session_id);
-        self.query(&revset).await
+        let revset = RevsetBuilder::bookmarks_glob(&format!("session/{}", session_id))?;
+        self.query(&revset.build()).await
     }
 
     // ============================================================================
@@ -184,11 +251,9 @@ impl<E: JjExecutor> RevsetQueries<E> {
     /// Revset: `bookmarks(glob:"agent/{name}/*") & ~description(glob:"Status: done")`
     pub async fn agent_active_work(&self, agent_name: &str) -> Result<Vec<ChangeId>> {
         validate_identifier(agent_name, "agent_name")?;
-        let revset = format!(
-            r#"bookmarks(glob:"agent/{}/*") & ~description(glob:"Status: done")"#,
-            agent_name
-        );
-        self.query(&revset).await
+        let revset = RevsetBuilder::bookmarks_glob(&format!("agent/{}/*", agent_name))?
+            .and_not(status_revset("done")?);
+        self.query(&revset.build()).await
     }
 
     /// Find parallelizable tasks (independent heads, no merges, no conflicts)
@@ -203,9 +268,11 @@ impl<E: JjExecutor> RevsetQueries<E> {
     ///
     /// Revset: `ancestors({change_id}) & mutable() & conflicts()`
     pub async fn blocking_conflicts(&self, change_id: &ChangeId) -> Result<Vec<ChangeId>> {
-        validate_identifier(change_id, "change_id")?;
-        let revset = format!("ancestors({}) & mutable() & conflicts()", change_id);
-        self.query(&revset).await
+        let revset = RevsetBuilder::change_id(change_id)?
+            .ancestors()
+            .and(RevsetBuilder::mutable())
+            .and(RevsetBuilder::conflicts());
+        self.query(&revset.build()).await
     }
 
     /// Find empty changes (abandoned tasks)
@@ -219,18 +286,16 @@ impl<E: JjExecutor> RevsetQueries<E> {
     ///
     /// Revset: `file("{path}")`
     pub async fn changes_touching_file(&self, path: &str) -> Result<Vec<ChangeId>> {
-        validate_path(path, "file_path")?;
-        let revset = format!(r#"file("{}")"#, path);
-        self.query(&revset).await
+        let revset = RevsetBuilder::file(path)?;
+        self.query(&revset.build()).await
     }
 
     /// Safe reference that doesn't error if change is missing
     ///
     /// Revset: `present({change_id})`
     pub async fn present(&self, change_id: &ChangeId) -> Result<Option<ChangeId>> {
-        validate_identifier(change_id, "change_id")?;
-        let revset = format!("present({})", change_id);
-        let results = self.query(&revset).await?;
+        let revset = RevsetBuilder::change_id(change_id)?.present();
+        let results = self.query(&revset.build()).await?;
         Ok(results.into_iter().next())
     }
 
@@ -238,23 +303,100 @@ impl<E: JjExecutor> RevsetQueries<E> {
     ///
     /// Revset: `connected({change_id})`
     pub async fn connected_component(&self, change_id: &ChangeId) -> Result<Vec<ChangeId>> {
-        validate_identifier(change_id, "change_id")?;
-        let revset = format!("connected({})", change_id);
-        self.query(&revset).await
+        let revset = RevsetBuilder::change_id(change_id)?.connected();
+        self.query(&revset.build()).await
     }
 
     /// Find most recent N changes matching criteria
     ///
     /// Revset: `latest({revset}, {count})`
     pub async fn latest(&self, revset: &str, count: usize) -> Result<Vec<ChangeId>> {
-        validate_revset(revset)?;
-        let query = format!("latest({}, {})", revset, count);
-        self.query(&query).await
+        let query = RevsetBuilder::raw(revset)?.latest(count);
+        self.query(&query.build()).await
+    }
+
+    /// Collect the repository status summary
+    pub async fn status_report(&self) -> Result<StatusReport> {
+        // Try bookmark query first, fallback to description search
+        let orchestrators = match self.all_orchestrators_by_bookmark().await {
+            Ok(orchestrators) => orchestrators,
+            Err(_) => {
+                self.query("description(glob:\"Orchestrator: O-*\")")
+                    .await?
+            }
+        };
+
+        let mut conflicts = Vec::new();
+        for change_id in self.conflicts().await? {
+            let blocking_conflicts = self
+                .blocking_conflicts(&change_id)
+                .await
+                .unwrap_or_default();
+            conflicts.push(ConflictStatus {
+                change_id,
+                blocking_conflicts,
+            });
+        }
+
+        Ok(StatusReport {
+            orchestrators: orchestrators.len(),
+            in_progress: self.by_status("in_progress").await?.len(),
+            blocked: self.by_status("blocked").await?.len(),
+            parallelizable: self.parallelizable_tasks().await?.len(),
+            empty: self.empty_changes().await?.len(),
+            conflicts,
+            agent: None,
+        })
     }
 }
 
+/// Snapshot of repository task state, as reported by `hox status`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusReport {
+    pub orchestrators: usize,
+    pub in_progress: usize,
+    pub blocked: usize,
+    /// Independent heads that can be worked on in parallel
+    pub parallelizable: usize,
+    /// Empty/abandoned mutable changes
+    pub empty: usize,
+    pub conflicts: Vec<ConflictStatus>,
+    /// Workload of a single agent, when requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<AgentWorkload>,
+}
+
+/// Tasks assigned to an agent, and the subset it can work on now
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentWorkload {
+    pub agent: String,
+    pub assigned: Vec<ChangeId>,
+    pub ready: Vec<ChangeId>,
+}
+
+/// A conflicted change and the conflicting ancestors that block it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConflictStatus {
+    pub change_id: ChangeId,
+    pub blocking_conflicts: Vec<ChangeId>,
+}
+
+/// Revset for changes owned by an agent via bookmark or `Agent:` metadata
+fn agent_revset(agent: &str) -> Result<RevsetBuilder> {
+    validate_identifier(agent, "agent")?;
+    Ok(
+        RevsetBuilder::bookmarks_glob(&format!("agent/{}/task/*", agent))?
+            .or(RevsetBuilder::description_glob("Agent", agent)?),
+    )
+}
+
+/// Revset for changes with a `Status:` metadata value
+fn status_revset(status: &str) -> Result<RevsetBuilder> {
+    RevsetBuilder::description_glob("Status", status)
+}
+
 /// Parse change IDs from JJ output
-fn parse_change_ids(output: &JjOutput) -> Vec<ChangeId> {
+pub(crate) fn parse_change_ids(output: &JjOutput) -> Vec<ChangeId> {
     output
         .stdout
         .lines()
@@ -277,6 +419,7 @@ mod tests {
                 stdout: "abc123\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -286,6 +429,47 @@ mod tests {
         assert_eq!(result, vec!["abc123"]);
     }
 
+    #[tokio::test]
+    async fn test_query_paginated() {
+        let executor = MockJjExecutor::new().with_response(
+            "log -r all() --limit 5 -T change_id ++ \"\\n\" --no-graph",
+            JjOutput {
+                stdout: "a1\na2\na3\na4\na5\n".to_string(),
+                stderr: String::new(),
+                success: true,
+                code: Some(0),
+            },
+        );
+
+        let queries = RevsetQueries::new(executor);
+
+        assert_eq!(
+            queries.query_paginated("all()", 2, 3).await.unwrap(),
+            vec!["a3", "a4", "a5"]
+        );
+        assert!(queries
+            .query_paginated("all()", 2, 0)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_count() {
+        let executor = MockJjExecutor::new().with_response(
+            "log -r all() -T \".\" --no-graph",
+            JjOutput {
+                stdout: "....".to_string(),
+                stderr: String::new(),
+                success: true,
+                code: Some(0),
+            },
+        );
+
+        let queries = RevsetQueries::new(executor);
+        assert_eq!(queries.count("all()").await.unwrap(), 4);
+    }
+
     #[tokio::test]
     async fn test_ready_tasks_v2_revset() {
         let executor = MockJjExecutor::new().with_response(
@@ -294,6 +478,7 @@ mod tests {
                 stdout: "task1\ntask2\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -311,6 +496,7 @@ mod tests {
                 stdout: "work1\nwork2\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -328,6 +514,7 @@ mod tests {
                 stdout: "task1\ntask2\ntask3\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -345,6 +532,7 @@ mod tests {
                 stdout: "conflict1\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -365,6 +553,7 @@ mod tests {
                 stdout: "empty1\nempty2\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -382,6 +571,7 @@ mod tests {
                 stdout: "change1\nchange2\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -399,6 +589,7 @@ mod tests {
                 stdout: "abc123\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -416,6 +607,7 @@ mod tests {
                 stdout: "".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -433,6 +625,7 @@ mod tests {
                 stdout: "abc123\ndef456\nghi789\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -453,6 +646,7 @@ mod tests {
                 stdout: "recent1\nrecent2\nrecent3\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -461,4 +655,102 @@ mod tests {
 
         assert_eq!(result, vec!["recent1", "recent2", "recent3"]);
     }
+
+    #[tokio::test]
+    async fn test_status_report() {
+        let ok = |stdout: &str| JjOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            success: true,
+            code: Some(0),
+        };
+        let query = |revset: &str| format!(r#"log -r {} -T change_id ++ "\n" --no-graph"#, revset);
+        let executor = MockJjExecutor::new()
+            // No bookmark response: falls back to the description search
+            .with_response(
+                &query(r#"description(glob:"Orchestrator: O-*")"#),
+                ok("orch1\n"),
+            )
+            .with_response(
+                &query(r#"description(glob:"Status: in_progress")"#),
+                ok("a\nb\n"),
+            )
+            .with_response(&query(r#"description(glob:"Status: blocked")"#), ok("c\n"))
+            .with_response(
+                &query("heads(mutable()) & ~merges() & ~conflicts()"),
+                ok("a\nb\nd\n"),
+            )
+            .with_response(&query("empty() & mutable()"), ok(""))
+            .with_response(&query("conflicts()"), ok("x1\nx2\n"))
+            .with_response(
+                &query("ancestors(x1) & mutable() & conflicts()"),
+                ok("x1\n"),
+            )
+            .with_response(
+                &query("ancestors(x2) & mutable() & conflicts()"),
+                ok("x2\nx1\n"),
+            );
+
+        let report = RevsetQueries::new(executor).status_report().await.unwrap();
+
+        assert_eq!(report.orchestrators, 1);
+        assert_eq!(report.in_progress, 2);
+        assert_eq!(report.blocked, 1);
+        assert_eq!(report.parallelizable, 3);
+        assert_eq!(report.empty, 0);
+        assert_eq!(
+            report.conflicts,
+            vec![
+                ConflictStatus {
+                    change_id: "x1".to_string(),
+                    blocking_conflicts: vec!["x1".to_string()],
+                },
+                ConflictStatus {
+                    change_id: "x2".to_string(),
+                    blocking_conflicts: vec!["x2".to_string(), "x1".to_string()],
+                },
+            ]
+        );
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["in_progress"], 2);
+        assert_eq!(json["conflicts"][1]["blocking_conflicts"][1], "x1");
+    }
+
+    #[tokio::test]
+    async fn test_agent_queries() {
+        let ok = |stdout: &str| JjOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            success: true,
+            code: Some(0),
+        };
+        let query = |revset: &str| format!(r#"log -r {} -T change_id ++ "\n" --no-graph"#, revset);
+        let assigned =
+            r#"bookmarks(glob:"agent/agent-1/task/*") | description(glob:"Agent: agent-1")"#;
+        let ready = format!(
+            r#"({}) & ~description(glob:"Status: done") & ~description(glob:"Status: blocked") & ~descendants(conflicts())"#,
+            assigned
+        );
+        let executor = MockJjExecutor::new()
+            .with_response(&query(assigned), ok("t1\nt2\nt3\n"))
+            .with_response(&query(&ready), ok("t2\n"));
+        let queries = RevsetQueries::new(executor);
+
+        assert_eq!(
+            queries.by_agent("agent-1").await.unwrap(),
+            vec!["t1", "t2", "t3"]
+        );
+        assert_eq!(
+            queries.ready_for_agent("agent-1").await.unwrap(),
+            vec!["t2"]
+        );
+
+        let workload = queries.agent_workload("agent-1").await.unwrap();
+        assert_eq!(workload.assigned.len(), 3);
+        assert_eq!(workload.ready, vec!["t2"]);
+
+        // Identifiers are validated before reaching jj
+        assert!(queries.ready_for_agent("agent\") | all()").await.is_err());
+    }
 }
@@ -0,0 +1,323 @@
+//! Metrics snapshots and export formats
+//!
+//! Aggregates stored [`AgentMetrics`] into a [`MetricsSnapshot`] that can be
+//! rendered as JSON or in the Prometheus text exposition format.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::collector::{AgentMetrics, TelemetryEvent};
+
+/// Aggregated tool call counters
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolCallStats {
+    pub calls: u64,
+    pub failures: u64,
+    pub duration_ms: u64,
+}
+
+impl ToolCallStats {
+    /// Fraction of successful calls (1.0 when there were none)
+    pub fn success_rate(&self) -> f64 {
+        if self.calls == 0 {
+            return 1.0;
+        }
+        (self.calls - self.failures) as f64 / self.calls as f64
+    }
+}
+
+/// Aggregated metrics for one agent across all of its runs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentSnapshot {
+    /// Number of recorded runs
+    pub runs: u64,
+    pub tool_calls: ToolCallStats,
+    pub align_requests: u64,
+    pub mutation_conflicts: u64,
+    /// Wall-clock time across completed runs
+    pub time_ms: u64,
+}
+
+/// Point-in-time aggregate of stored metrics
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricsSnapshot {
+    pub generated_at: DateTime<Utc>,
+    /// Totals across all agents
+    pub totals: AgentSnapshot,
+    /// Per-agent aggregates, keyed by agent ID
+    pub agents: BTreeMap<String, AgentSnapshot>,
+    /// Per-tool aggregates, keyed by tool name
+    pub tools: BTreeMap<String, ToolCallStats>,
+}
+
+impl MetricsSnapshot {
+    /// Aggregate a set of agent metrics
+    pub fn from_metrics<'a>(metrics: impl IntoIterator<Item = &'a AgentMetrics>) -> Self {
+        let mut totals = AgentSnapshot::default();
+        let mut agents: BTreeMap<String, AgentSnapshot> = BTreeMap::new();
+        let mut tools: BTreeMap<String, ToolCallStats> = BTreeMap::new();
+
+        for run in metrics {
+            let agent = agents.entry(run.agent_id.clone()).or_default();
+            for snapshot in [&mut totals, agent] {
+                snapshot.runs += 1;
+                snapshot.tool_calls.calls += run.telemetry.tool_calls as u64;
+                snapshot.tool_calls.failures += run.telemetry.failed_calls as u64;
+                snapshot.align_requests += run.telemetry.align_requests as u64;
+                snapshot.mutation_conflicts += run.telemetry.mutation_conflicts as u64;
+                snapshot.time_ms += run.telemetry.time_ms;
+            }
+
+            for (_, event) in &run.events {
+                if let TelemetryEvent::ToolCall {
+                    tool_name,
+                    success,
+                    duration_ms,
+                } = event
+                {
+                    let tool = tools.entry(tool_name.clone()).or_default();
+                    tool.calls += 1;
+                    if !success {
+                        tool.failures += 1;
+                    }
+                    tool.duration_ms += duration_ms;
+
+                    totals.tool_calls.duration_ms += duration_ms;
+                    if let Some(agent) = agents.get_mut(&run.agent_id) {
+                        agent.tool_calls.duration_ms += duration_ms;
+                    }
+                }
+            }
+        }
+
+        Self {
+            generated_at: Utc::now(),
+            totals,
+            agents,
+            tools,
+        }
+    }
+
+    /// Render in the Prometheus text exposition format
+    ///
+    /// Metric names are stable; per-agent series carry an `agent` label and
+    /// per-tool series a `tool` label.
+    pub fn to_prometheus(&self) -> String {
+        type AgentField = fn(&AgentSnapshot) -> f64;
+        type ToolField = fn(&ToolCallStats) -> f64;
+
+        const GLOBAL: &[(&str, &str, &str, AgentField)] = &[
+            (
+                "hox_tool_calls_total",
+                "counter",
+                "Tool calls across all agents",
+                |a| a.tool_calls.calls as f64,
+            ),
+            (
+                "hox_tool_failures_total",
+                "counter",
+                "Failed tool calls across all agents",
+                |a| a.tool_calls.failures as f64,
+            ),
+            (
+                "hox_tool_success_ratio",
+                "gauge",
+                "Fraction of successful tool calls",
+                |a| a.tool_calls.success_rate(),
+            ),
+        ];
+        const PER_AGENT: &[(&str, &str, &str, AgentField)] = &[
+            (
+                "hox_agent_runs_total",
+                "counter",
+                "Recorded agent runs",
+                |a| a.runs as f64,
+            ),
+            (
+                "hox_agent_tool_calls_total",
+                "counter",
+                "Tool calls per agent",
+                |a| a.tool_calls.calls as f64,
+            ),
+            (
+                "hox_agent_tool_failures_total",
+                "counter",
+                "Failed tool calls per agent",
+                |a| a.tool_calls.failures as f64,
+            ),
+            (
+                "hox_agent_tool_success_ratio",
+                "gauge",
+                "Fraction of successful tool calls per agent",
+                |a| a.tool_calls.success_rate(),
+            ),
+            (
+                "hox_agent_align_requests_total",
+                "counter",
+                "Alignment requests per agent",
+                |a| a.align_requests as f64,
+            ),
+            (
+                "hox_agent_mutation_conflicts_total",
+                "counter",
+                "Mutation conflicts per agent",
+                |a| a.mutation_conflicts as f64,
+            ),
+            (
+                "hox_agent_run_seconds_total",
+                "counter",
+                "Wall-clock time of completed runs per agent",
+                |a| a.time_ms as f64 / 1000.0,
+            ),
+            (
+                "hox_agent_tool_seconds_total",
+                "counter",
+                "Time spent in tool calls per agent",
+                |a| a.tool_calls.duration_ms as f64 / 1000.0,
+            ),
+        ];
+        const PER_TOOL: &[(&str, &str, &str, ToolField)] = &[
+            (
+                "hox_tool_calls_by_tool_total",
+                "counter",
+                "Calls per tool",
+                |t| t.calls as f64,
+            ),
+            (
+                "hox_tool_failures_by_tool_total",
+                "counter",
+                "Failed calls per tool",
+                |t| t.failures as f64,
+            ),
+            (
+                "hox_tool_seconds_by_tool_total",
+                "counter",
+                "Time spent per tool",
+                |t| t.duration_ms as f64 / 1000.0,
+            ),
+        ];
+
+        let mut out = String::new();
+        for (name, kind, help, value) in GLOBAL {
+            family(&mut out, name, kind, help);
+            let _ = writeln!(out, "{} {}", name, value(&self.totals));
+        }
+        for (name, kind, help, value) in PER_AGENT {
+            family(&mut out, name, kind, help);
+            for (agent, snapshot) in &self.agents {
+                labeled(&mut out, name, "agent", agent, value(snapshot));
+            }
+        }
+        for (name, kind, help, value) in PER_TOOL {
+            family(&mut out, name, kind, help);
+            for (tool, stats) in &self.tools {
+                labeled(&mut out, name, "tool", tool, value(stats));
+            }
+        }
+        out
+    }
+}
+
+fn family(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+fn labeled(out: &mut String, name: &str, key: &str, label: &str, value: f64) {
+    let _ = writeln!(
+        out,
+        "{}{{{}=\"{}\"}} {}",
+        name,
+        key,
+        escape_label(label),
+        value
+    );
+}
+
+/// Escape a label value (backslash, double quote and newline)
+fn escape_label(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(agent: &str, calls: &[(&str, bool, u64)]) -> AgentMetrics {
+        let mut metrics = AgentMetrics::new(agent, format!("{}-change", agent));
+        for (tool, success, duration_ms) in calls {
+            metrics.record_event(TelemetryEvent::ToolCall {
+                tool_name: tool.to_string(),
+                success: *success,
+                duration_ms: *duration_ms,
+            });
+        }
+        metrics
+    }
+
+    #[test]
+    fn test_snapshot_aggregates_runs() {
+        let runs = vec![
+            run("agent-1", &[("read", true, 100), ("write", false, 50)]),
+            run("agent-1", &[("read", true, 30)]),
+            run("agent-2", &[("bash", true, 1000)]),
+        ];
+
+        let snapshot = MetricsSnapshot::from_metrics(&runs);
+
+        assert_eq!(snapshot.totals.runs, 3);
+        assert_eq!(snapshot.totals.tool_calls.calls, 4);
+        assert_eq!(snapshot.totals.tool_calls.failures, 1);
+        assert_eq!(snapshot.totals.tool_calls.duration_ms, 1180);
+
+        let agent = &snapshot.agents["agent-1"];
+        assert_eq!(agent.runs, 2);
+        assert_eq!(agent.tool_calls.calls, 3);
+        assert!((agent.tool_calls.success_rate() - 2.0 / 3.0).abs() < 1e-9);
+
+        assert_eq!(snapshot.tools["read"].calls, 2);
+        assert_eq!(snapshot.tools["write"].failures, 1);
+    }
+
+    #[test]
+    fn test_prometheus_format() {
+        let snapshot = MetricsSnapshot::from_metrics(&[run(
+            "agent-1",
+            &[("read", true, 1500), ("read", false, 500)],
+        )]);
+
+        let text = snapshot.to_prometheus();
+
+        assert!(text.contains("# TYPE hox_tool_calls_total counter\nhox_tool_calls_total 2\n"));
+        assert!(text.contains("hox_tool_success_ratio 0.5\n"));
+        assert!(text.contains("hox_agent_tool_failures_total{agent=\"agent-1\"} 1\n"));
+        assert!(text.contains("hox_tool_seconds_by_tool_total{tool=\"read\"} 2\n"));
+        // Every sample line belongs to a declared family
+        for line in text.lines().filter(|l| !l.starts_with('#')) {
+            let name = line.split(['{', ' ']).next().unwrap();
+            assert!(text.contains(&format!("# TYPE {} ", name)), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_escape_label() {
+        assert_eq!(escape_label(r#"a"b\c"#), r#"a\"b\\c"#);
+        assert_eq!(escape_label("line\nbreak"), "line\\nbreak");
+
+        let snapshot = MetricsSnapshot::from_metrics(&[run("say \"hi\"", &[])]);
+        assert!(snapshot
+            .to_prometheus()
+            .contains("hox_agent_runs_total{agent=\"say \\\"hi\\\"\"} 1\n"));
+    }
+}
@@ -4,13 +4,17 @@
 //!
 //! This crate provides:
 //! - Agent telemetry collection
-//! - Metrics storage (JJ-native or external)
+//! - Metrics storage (JJ-native, external, or batched HTTP delivery)
 //! - Evaluation hooks at status transitions
+//! - Snapshot export (JSON, Prometheus text format)
 
 #![allow(dead_code)]
 
 mod collector;
+mod export;
+mod sink;
 mod storage;
 
-pub use collector::{MetricsCollector, TelemetryEvent};
-pub use storage::{MetricsStorage, StorageMode};
+pub use collector::{AgentMetrics, MetricsCollector, TelemetryEvent};
+pub use export::{AgentSnapshot, MetricsSnapshot, ToolCallStats};
+pub use storage::{MetricsStorage, StorageMode, METRICS_FILE};
@@ -0,0 +1,354 @@
+//! Batched HTTP delivery of metrics to an OTLP/HTTP collector
+//!
+//! Records are buffered in memory and posted as OTLP JSON log records by a
+//! background task, so storing metrics never waits on the network. The buffer
+//! is bounded; when it is full the oldest records are dropped.
+
+use hox_core::{HoxError, Result};
+use serde_json::json;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tracing::{debug, warn};
+
+use crate::collector::AgentMetrics;
+
+/// Buffer capacity, in batches
+const BUFFER_BATCHES: usize = 64;
+
+/// Delivery attempts per batch before it is requeued for the next flush
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Backoff before the first retry, doubled for each further attempt
+const RETRY_BACKOFF: Duration = Duration::from_millis(200);
+
+/// Timeout for a single POST
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+struct SinkState {
+    endpoint: String,
+    batch_size: usize,
+    capacity: usize,
+    buffer: Mutex<VecDeque<AgentMetrics>>,
+    /// Serialises flushes from the background task and explicit `flush()` calls
+    flush_lock: tokio::sync::Mutex<()>,
+    batch_ready: Notify,
+    client: reqwest::Client,
+}
+
+/// Batched, non-blocking HTTP metrics sink
+pub(crate) struct HttpSink {
+    state: Arc<SinkState>,
+    flush_interval: Duration,
+    worker: OnceLock<()>,
+}
+
+impl HttpSink {
+    pub fn new(endpoint: impl Into<String>, batch_size: usize, flush_interval: Duration) -> Self {
+        let batch_size = batch_size.max(1);
+        Self {
+            state: Arc::new(SinkState {
+                endpoint: endpoint.into(),
+                batch_size,
+                capacity: batch_size * BUFFER_BATCHES,
+                buffer: Mutex::new(VecDeque::new()),
+                flush_lock: tokio::sync::Mutex::new(()),
+                batch_ready: Notify::new(),
+                client: reqwest::Client::builder()
+                    .timeout(REQUEST_TIMEOUT)
+                    .build()
+                    .unwrap_or_default(),
+            }),
+            flush_interval,
+            worker: OnceLock::new(),
+        }
+    }
+
+    /// Queue metrics for delivery without waiting on the network
+    pub fn enqueue(&self, metrics: AgentMetrics) {
+        self.ensure_worker();
+
+        let buffered = {
+            let mut buffer = self.state.buffer.lock().unwrap();
+            push_bounded(&mut buffer, metrics, self.state.capacity);
+            buffer.len()
+        };
+        if buffered >= self.state.batch_size {
+            self.state.batch_ready.notify_one();
+        }
+    }
+
+    /// Deliver everything buffered now
+    pub async fn flush(&self) -> Result<()> {
+        self.state.flush().await
+    }
+
+    /// Number of records waiting for delivery
+    pub fn pending(&self) -> usize {
+        self.state.buffer.lock().unwrap().len()
+    }
+
+    /// Start the background delivery task on first use
+    fn ensure_worker(&self) {
+        self.worker.get_or_init(|| {
+            let state = Arc::downgrade(&self.state);
+            tokio::spawn(run_worker(state, self.flush_interval));
+        });
+    }
+}
+
+/// Flush on every full batch or interval until the sink is dropped
+async fn run_worker(state: Weak<SinkState>, flush_interval: Duration) {
+    loop {
+        let Some(sink) = state.upgrade() else {
+            break;
+        };
+        tokio::select! {
+            _ = sink.batch_ready.notified() => {}
+            _ = tokio::time::sleep(flush_interval) => {}
+        }
+        if let Err(e) = sink.flush().await {
+            warn!("Metrics sink delivery failed, will retry: {}", e);
+        }
+    }
+}
+
+impl SinkState {
+    async fn flush(&self) -> Result<()> {
+        let _guard = self.flush_lock.lock().await;
+
+        loop {
+            let batch: Vec<AgentMetrics> = {
+                let mut buffer = self.buffer.lock().unwrap();
+                let n = buffer.len().min(self.batch_size);
+                buffer.drain(..n).collect()
+            };
+            if batch.is_empty() {
+                return Ok(());
+            }
+
+            if let Err(e) = self.deliver(&batch).await {
+                // Put the batch back in front of anything queued meanwhile
+                let mut buffer = self.buffer.lock().unwrap();
+                for metrics in batch.into_iter().rev() {
+                    buffer.push_front(metrics);
+                }
+                trim_oldest(&mut buffer, self.capacity);
+                return Err(e);
+            }
+        }
+    }
+
+    /// POST one batch, retrying with backoff
+    async fn deliver(&self, batch: &[AgentMetrics]) -> Result<()> {
+        let body = otlp_logs(batch);
+        let mut last_error = String::new();
+
+        for attempt in 0..MAX_ATTEMPTS {
+            if attempt > 0 {
+                tokio::time::sleep(RETRY_BACKOFF * 2u32.pow(attempt - 1)).await;
+            }
+
+            match self.client.post(&self.endpoint).json(&body).send().await {
+                Ok(response) if response.status().is_success() => {
+                    debug!(
+                        "Delivered {} metrics records to {}",
+                        batch.len(),
+                        self.endpoint
+                    );
+                    return Ok(());
+                }
+                Ok(response) => last_error = format!("HTTP {}", response.status()),
+                Err(e) => last_error = e.to_string(),
+            }
+            debug!(
+                "Metrics delivery attempt {}/{} failed: {}",
+                attempt + 1,
+                MAX_ATTEMPTS,
+                last_error
+            );
+        }
+
+        Err(HoxError::Other(format!(
+            "Failed to deliver metrics to {}: {}",
+            self.endpoint, last_error
+        )))
+    }
+}
+
+/// Append to the buffer, dropping the oldest record when full
+fn push_bounded(buffer: &mut VecDeque<AgentMetrics>, metrics: AgentMetrics, capacity: usize) {
+    buffer.push_back(metrics);
+    trim_oldest(buffer, capacity);
+}
+
+fn trim_oldest(buffer: &mut VecDeque<AgentMetrics>, capacity: usize) {
+    let excess = buffer.len().saturating_sub(capacity);
+    if excess > 0 {
+        buffer.drain(..excess);
+        warn!(
+            "Metrics sink buffer full, dropped {} oldest record(s)",
+            excess
+        );
+    }
+}
+
+/// Encode metrics as an OTLP/HTTP JSON logs export request
+fn otlp_logs(batch: &[AgentMetrics]) -> serde_json::Value {
+    let records: Vec<_> = batch
+        .iter()
+        .map(|metrics| {
+            let time = metrics.ended_at.unwrap_or(metrics.started_at);
+            json!({
+                "timeUnixNano": time.timestamp_nanos_opt().unwrap_or_default().to_string(),
+                "body": { "stringValue": serde_json::to_string(metrics).unwrap_or_default() },
+                "attributes": [
+                    { "key": "hox.agent_id", "value": { "stringValue": metrics.agent_id } },
+                    { "key": "hox.change_id", "value": { "stringValue": metrics.change_id } },
+                ],
+            })
+        })
+        .collect();
+
+    json!({
+        "resourceLogs": [{
+            "resource": {
+                "attributes": [{ "key": "service.name", "value": { "stringValue": "hox" } }],
+            },
+            "scopeLogs": [{
+                "scope": { "name": "hox-metrics" },
+                "logRecords": records,
+            }],
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct Collector {
+        /// Respond with 500 to this many requests before accepting
+        failures: AtomicUsize,
+        requests: AtomicUsize,
+        bodies: Mutex<Vec<serde_json::Value>>,
+    }
+
+    async fn collect(
+        State(collector): State<Arc<Collector>>,
+        Json(body): Json<serde_json::Value>,
+    ) -> StatusCode {
+        collector.requests.fetch_add(1, Ordering::SeqCst);
+        let failing = collector
+            .failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok();
+        if failing {
+            return StatusCode::INTERNAL_SERVER_ERROR;
+        }
+        collector.bodies.lock().unwrap().push(body);
+        StatusCode::OK
+    }
+
+    async fn serve(collector: Arc<Collector>) -> String {
+        let app = Router::new()
+            .route("/v1/logs", post(collect))
+            .with_state(collector);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        format!("http://{}/v1/logs", addr)
+    }
+
+    fn record_count(body: &serde_json::Value) -> usize {
+        body["resourceLogs"][0]["scopeLogs"][0]["logRecords"]
+            .as_array()
+            .unwrap()
+            .len()
+    }
+
+    async fn wait_for(condition: impl Fn() -> bool) {
+        for _ in 0..100 {
+            if condition() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("condition not met in time");
+    }
+
+    #[tokio::test]
+    async fn test_full_batch_is_delivered() {
+        let collector = Arc::new(Collector::default());
+        let endpoint = serve(collector.clone()).await;
+        let sink = HttpSink::new(endpoint, 2, Duration::from_secs(3600));
+
+        sink.enqueue(AgentMetrics::new("agent-1", "change-1"));
+        sink.enqueue(AgentMetrics::new("agent-2", "change-2"));
+
+        wait_for(|| !collector.bodies.lock().unwrap().is_empty()).await;
+        let bodies = collector.bodies.lock().unwrap();
+        assert_eq!(bodies.len(), 1);
+        assert_eq!(record_count(&bodies[0]), 2);
+        assert_eq!(
+            bodies[0]["resourceLogs"][0]["scopeLogs"][0]["logRecords"][0]["attributes"][0]["value"]
+                ["stringValue"],
+            "agent-1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_delivery_is_retried() {
+        let collector = Arc::new(Collector {
+            failures: AtomicUsize::new(1),
+            ..Default::default()
+        });
+        let endpoint = serve(collector.clone()).await;
+        let sink = HttpSink::new(endpoint, 10, Duration::from_secs(3600));
+
+        sink.enqueue(AgentMetrics::new("agent-1", "change-1"));
+        sink.flush().await.unwrap();
+
+        assert_eq!(collector.requests.load(Ordering::SeqCst), 2);
+        assert_eq!(record_count(&collector.bodies.lock().unwrap()[0]), 1);
+        assert_eq!(sink.pending(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_undeliverable_batch_stays_buffered() {
+        let collector = Arc::new(Collector {
+            failures: AtomicUsize::new(usize::MAX),
+            ..Default::default()
+        });
+        let endpoint = serve(collector.clone()).await;
+        let sink = HttpSink::new(endpoint, 10, Duration::from_secs(3600));
+
+        sink.enqueue(AgentMetrics::new("agent-1", "change-1"));
+
+        assert!(sink.flush().await.is_err());
+        assert_eq!(
+            collector.requests.load(Ordering::SeqCst),
+            MAX_ATTEMPTS as usize
+        );
+        assert_eq!(sink.pending(), 1);
+    }
+
+    #[test]
+    fn test_full_buffer_drops_oldest() {
+        let mut buffer = VecDeque::new();
+        for i in 0..5 {
+            push_bounded(
+                &mut buffer,
+                AgentMetrics::new(format!("agent-{}", i), "c"),
+                3,
+            );
+        }
+
+        let agents: Vec<_> = buffer.iter().map(|m| m.agent_id.as_str()).collect();
+        assert_eq!(agents, vec!["agent-2", "agent-3", "agent-4"]);
+    }
+}
@@ -2,10 +2,16 @@
 
 use hox_core::{ChangeId, Result};
 use std::path::PathBuf;
+use std::time::Duration;
 use tokio::fs;
 use tracing::debug;
 
 use crate::collector::AgentMetrics;
+use crate::export::MetricsSnapshot;
+use crate::sink::HttpSink;
+
+/// Default append-file name within `.hox/metrics/`
+pub const METRICS_FILE: &str = "agents.jsonl";
 
 /// Storage mode for metrics
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -14,6 +20,15 @@ pub enum StorageMode {
     JjNative,
     /// Store in append-only file
     AppendFile(PathBuf),
+    /// POST batches to an OTLP/HTTP logs endpoint (write-only)
+    HttpSink {
+        /// Collector URL, e.g. `http://localhost:4318/v1/logs`
+        endpoint: String,
+        /// Records per request; a full batch is sent immediately
+        batch_size: usize,
+        /// Maximum time a partial batch waits before being sent
+        flush_interval: Duration,
+    },
     /// Store in Turso database (requires feature)
     #[cfg(feature = "turso")]
     Turso(String),
@@ -22,11 +37,24 @@ pub enum StorageMode {
 /// Metrics storage abstraction
 pub struct MetricsStorage {
     mode: StorageMode,
+    sink: Option<HttpSink>,
 }
 
 impl MetricsStorage {
     pub fn new(mode: StorageMode) -> Self {
-        Self { mode }
+        let sink = match &mode {
+            StorageMode::HttpSink {
+                endpoint,
+                batch_size,
+                flush_interval,
+            } => Some(HttpSink::new(
+                endpoint.clone(),
+                *batch_size,
+                *flush_interval,
+            )),
+            _ => None,
+        };
+        Self { mode, sink }
     }
 
     /// Create JJ-native storage
@@ -39,11 +67,33 @@ impl MetricsStorage {
         Self::new(StorageMode::AppendFile(path.into()))
     }
 
+    /// Create HTTP sink storage
+    pub fn http_sink(
+        endpoint: impl Into<String>,
+        batch_size: usize,
+        flush_interval: Duration,
+    ) -> Self {
+        Self::new(StorageMode::HttpSink {
+            endpoint: endpoint.into(),
+            batch_size,
+            flush_interval,
+        })
+    }
+
     /// Store metrics for an agent
+    ///
+    /// With an HTTP sink this only queues the metrics; delivery happens in
+    /// the background.
     pub async fn store(&self, metrics: &AgentMetrics) -> Result<()> {
         match &self.mode {
             StorageMode::JjNative => self.store_jj_native(metrics).await,
             StorageMode::AppendFile(path) => self.store_append_file(path, metrics).await,
+            StorageMode::HttpSink { .. } => {
+                if let Some(sink) = &self.sink {
+                    sink.enqueue(metrics.clone());
+                }
+                Ok(())
+            }
             #[cfg(feature = "turso")]
             StorageMode::Turso(connection) => self.store_turso(connection, metrics).await,
         }
@@ -54,6 +104,7 @@ impl MetricsStorage {
         match &self.mode {
             StorageMode::JjNative => self.load_jj_native(change_id).await,
             StorageMode::AppendFile(path) => self.load_append_file(path, change_id).await,
+            StorageMode::HttpSink { .. } => Ok(None),
             #[cfg(feature = "turso")]
             StorageMode::Turso(connection) => self.load_turso(connection, change_id).await,
         }
@@ -68,11 +119,25 @@ impl MetricsStorage {
                 Ok(Vec::new())
             }
             StorageMode::AppendFile(path) => self.load_all_from_file(path).await,
+            StorageMode::HttpSink { .. } => Ok(Vec::new()),
             #[cfg(feature = "turso")]
             StorageMode::Turso(connection) => self.load_all_turso(connection).await,
         }
     }
 
+    /// Deliver any metrics still buffered for an HTTP sink
+    pub async fn flush(&self) -> Result<()> {
+        match &self.sink {
+            Some(sink) => sink.flush().await,
+            None => Ok(()),
+        }
+    }
+
+    /// Aggregate all stored metrics into a snapshot
+    pub async fn snapshot(&self) -> Result<MetricsSnapshot> {
+        Ok(MetricsSnapshot::from_metrics(&self.load_all().await?))
+    }
+
     // JJ-native storage implementation
     async fn store_jj_native(&self, metrics: &AgentMetrics) -> Result<()> {
         // TODO: Store as metadata on the change using hox-jj
@@ -226,5 +291,9 @@ mod tests {
 
         let all = storage.load_all().await.unwrap();
         assert_eq!(all.len(), 2);
+
+        let snapshot = storage.snapshot().await.unwrap();
+        assert_eq!(snapshot.totals.runs, 2);
+        assert_eq!(snapshot.agents.len(), 2);
     }
 }
@@ -6,20 +6,113 @@
 //! - Backpressure check results
 //! - Agent output summaries
 //! - Final loop summaries with token usage
+//!
+//! Each entry is also appended to `.hox/activity.jsonl` as an
+//! [`ActivityEntry`], which [`ActivityLog`] reads back for tools that display
+//! recent activity.
+//!
+//! Both files are rotated to timestamped archives (e.g.
+//! `activity.20240101T120000.000000Z.jsonl`) once they exceed the limits in
+//! [`ActivityLoggerConfig`].
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use futures::Stream;
 use hox_core::fail_open::fail_open;
+use serde::{Deserialize, Serialize};
 
 /// Maximum character length for agent output in activity log preview
 const ACTIVITY_LOG_PREVIEW_CHARS: usize = 500;
 use hox_agent::{BackpressureResult, Usage};
-use std::path::PathBuf;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tokio::fs::OpenOptions;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tracing::debug;
+
+/// Structured activity log, relative to the `.hox` directory
+pub const ACTIVITY_JSONL_FILE: &str = "activity.jsonl";
+
+/// How often [`ActivityLog::tail`] checks the log for appends
+const DEFAULT_TAIL_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Timestamp format of rotated archive names; sorts chronologically
+const ARCHIVE_TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%.6fZ";
+
+/// One structured activity log entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActivityEntry {
+    pub timestamp: DateTime<Utc>,
+    #[serde(flatten)]
+    pub event: ActivityEvent,
+}
+
+/// What happened, mirroring the sections of `activity.md`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ActivityEvent {
+    LoopStart {
+        /// First line of the task description
+        task: String,
+        max_iterations: usize,
+    },
+    IterationStart {
+        iteration: usize,
+        max_iterations: usize,
+    },
+    IterationComplete {
+        iteration: usize,
+        checks: Vec<ActivityCheck>,
+        files_created: Vec<String>,
+        files_modified: Vec<String>,
+        /// Agent output, truncated like the markdown preview
+        output_preview: String,
+        errors: Vec<String>,
+    },
+    LoopComplete {
+        total_iterations: usize,
+        success: bool,
+        stop_reason: String,
+        input_tokens: usize,
+        output_tokens: usize,
+    },
+}
+
+/// A backpressure check result as recorded in the activity log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivityCheck {
+    pub name: String,
+    pub passed: bool,
+}
+
+/// Rotation limits for the activity logs
+#[derive(Debug, Clone)]
+pub struct ActivityLoggerConfig {
+    /// Rotate once the active file reaches this many bytes
+    pub max_bytes: u64,
+    /// Rotate once the active file is this old
+    ///
+    /// Only applies on filesystems that record file creation time.
+    pub max_age: Duration,
+    /// Archives kept per log file; the oldest are deleted
+    pub max_files: usize,
+}
+
+impl Default for ActivityLoggerConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: 5 * 1024 * 1024,
+            max_age: Duration::from_secs(7 * 24 * 60 * 60),
+            max_files: 5,
+        }
+    }
+}
 
 /// Activity logger for loop iterations
 pub struct ActivityLogger {
     output_path: PathBuf,
+    entries_path: PathBuf,
+    config: ActivityLoggerConfig,
 }
 
 impl ActivityLogger {
@@ -27,9 +120,17 @@ impl ActivityLogger {
     pub fn new(hox_dir: PathBuf) -> Self {
         Self {
             output_path: hox_dir.join("activity.md"),
+            entries_path: hox_dir.join(ACTIVITY_JSONL_FILE),
+            config: ActivityLoggerConfig::default(),
         }
     }
 
+    /// Set the rotation limits
+    pub fn with_config(mut self, config: ActivityLoggerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
     /// Log the start of a loop
     ///
     /// This operation is fail-open - logging failures won't crash the tool
@@ -60,7 +161,11 @@ impl ActivityLogger {
                 .await
                 .map_err(|e| hox_core::HoxError::Io(e.to_string()))?;
 
-            Ok(())
+            self.append_entry(ActivityEvent::LoopStart {
+                task: task_desc.lines().next().unwrap_or(task_desc).to_string(),
+                max_iterations,
+            })
+            .await
         })
         .await;
     }
@@ -77,7 +182,12 @@ impl ActivityLogger {
                 iteration, max, timestamp
             );
 
-            self.append_internal(&content).await
+            self.append_internal(&content).await?;
+            self.append_entry(ActivityEvent::IterationStart {
+                iteration,
+                max_iterations: max,
+            })
+            .await
         })
         .await;
     }
@@ -155,7 +265,23 @@ impl ActivityLogger {
 
             content.push_str("---\n\n");
 
-            self.append_internal(&content).await
+            self.append_internal(&content).await?;
+            self.append_entry(ActivityEvent::IterationComplete {
+                iteration,
+                checks: backpressure
+                    .checks
+                    .iter()
+                    .map(|c| ActivityCheck {
+                        name: c.name.clone(),
+                        passed: c.passed,
+                    })
+                    .collect(),
+                files_created: files_created.to_vec(),
+                files_modified: files_modified.to_vec(),
+                output_preview,
+                errors: backpressure.errors.clone(),
+            })
+            .await
         })
         .await;
     }
@@ -196,31 +322,321 @@ impl ActivityLogger {
                 total_usage.output_tokens
             );
 
-            self.append_internal(&content).await
+            self.append_internal(&content).await?;
+            self.append_entry(ActivityEvent::LoopComplete {
+                total_iterations,
+                success,
+                stop_reason: stop_reason.to_string(),
+                input_tokens: total_usage.input_tokens,
+                output_tokens: total_usage.output_tokens,
+            })
+            .await
         })
         .await;
     }
 
     /// Append content to the activity log (internal, returns Result for fail_open)
     async fn append_internal(&self, content: &str) -> hox_core::Result<()> {
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&self.output_path)
-            .await
-            .map_err(|e| hox_core::HoxError::Io(e.to_string()))?;
+        self.rotate_if_needed(&self.output_path).await?;
+        append_to(&self.output_path, content).await
+    }
 
-        file.write_all(content.as_bytes())
-            .await
-            .map_err(|e| hox_core::HoxError::Io(e.to_string()))?;
-        file.flush()
-            .await
-            .map_err(|e| hox_core::HoxError::Io(e.to_string()))?;
+    /// Append a structured entry to `activity.jsonl`
+    async fn append_entry(&self, event: ActivityEvent) -> hox_core::Result<()> {
+        let entry = ActivityEntry {
+            timestamp: Utc::now(),
+            event,
+        };
+        let mut line = serde_json::to_string(&entry)?;
+        line.push('\n');
+        self.rotate_if_needed(&self.entries_path).await?;
+        append_to(&self.entries_path, &line).await
+    }
+
+    /// Move `path` to a timestamped archive if it exceeds the rotation limits
+    async fn rotate_if_needed(&self, path: &Path) -> hox_core::Result<()> {
+        let metadata = match tokio::fs::metadata(path).await {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        if metadata.len() == 0 {
+            return Ok(());
+        }
+
+        let too_big = metadata.len() >= self.config.max_bytes;
+        let too_old = metadata
+            .created()
+            .ok()
+            .and_then(|created| created.elapsed().ok())
+            .is_some_and(|age| age >= self.config.max_age);
+        if !too_big && !too_old {
+            return Ok(());
+        }
+
+        let mut timestamp = Utc::now();
+        let mut archive = archive_path(path, timestamp);
+        while tokio::fs::try_exists(&archive).await? {
+            timestamp += chrono::Duration::microseconds(1);
+            archive = archive_path(path, timestamp);
+        }
+        tokio::fs::rename(path, &archive).await?;
+        debug!("Rotated {} to {}", path.display(), archive.display());
+
+        let archives = list_archives(path).await?;
+        let excess = archives.len().saturating_sub(self.config.max_files);
+        for old in &archives[..excess] {
+            if let Err(e) = tokio::fs::remove_file(old).await {
+                debug!("Failed to remove old activity log {}: {}", old.display(), e);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Split `activity.jsonl` into (`activity`, `jsonl`)
+fn stem_and_extension(path: &Path) -> (String, String) {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    match name.rsplit_once('.') {
+        Some((stem, ext)) => (stem.to_string(), ext.to_string()),
+        None => (name, String::new()),
+    }
+}
+
+/// Archive path for `path` rotated at `timestamp`
+fn archive_path(path: &Path, timestamp: DateTime<Utc>) -> PathBuf {
+    let (stem, ext) = stem_and_extension(path);
+    path.with_file_name(format!(
+        "{}.{}.{}",
+        stem,
+        timestamp.format(ARCHIVE_TIMESTAMP_FORMAT),
+        ext
+    ))
+}
+
+/// Whether `name` is a rotated archive of the log file named `log_name`
+pub(crate) fn is_archive_name(name: &str, log_name: &str) -> bool {
+    let (stem, ext) = stem_and_extension(Path::new(log_name));
+    name.strip_prefix(&format!("{}.", stem))
+        .and_then(|rest| rest.strip_suffix(&format!(".{}", ext)))
+        .is_some_and(|timestamp| timestamp.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+/// Rotated archives of `path`, oldest first
+async fn list_archives(path: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let Some(dir) = path.parent() else {
+        return Ok(Vec::new());
+    };
+    let log_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut archives = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if is_archive_name(&name, &log_name) {
+            archives.push(entry.path());
+        }
+    }
+    archives.sort();
+    Ok(archives)
+}
+
+/// Append content to a file, creating it if needed
+async fn append_to(path: &Path, content: &str) -> hox_core::Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await
+        .map_err(|e| hox_core::HoxError::Io(e.to_string()))?;
+
+    file.write_all(content.as_bytes())
+        .await
+        .map_err(|e| hox_core::HoxError::Io(e.to_string()))?;
+    file.flush()
+        .await
+        .map_err(|e| hox_core::HoxError::Io(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Typed reader for `.hox/activity.jsonl`
+///
+/// Malformed lines (e.g. a partially written final line) are skipped.
+#[derive(Debug, Clone)]
+pub struct ActivityLog {
+    path: PathBuf,
+    poll_interval: Duration,
+}
+
+impl ActivityLog {
+    /// Reader for the activity log in a `.hox` directory
+    pub fn new(hox_dir: impl AsRef<Path>) -> Self {
+        Self {
+            path: hox_dir.as_ref().join(ACTIVITY_JSONL_FILE),
+            poll_interval: DEFAULT_TAIL_POLL_INTERVAL,
+        }
+    }
+
+    /// Set how often [`Self::tail`] checks for appended entries
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// The `n` most recent entries, oldest first
+    ///
+    /// Reads back through rotated archives when the active file has fewer.
+    pub async fn read_recent(&self, n: usize) -> hox_core::Result<Vec<ActivityEntry>> {
+        let mut files = list_archives(&self.path).await?;
+        files.push(self.path.clone());
+
+        let mut entries: VecDeque<ActivityEntry> = VecDeque::with_capacity(n);
+        for file in files.iter().rev() {
+            if entries.len() >= n {
+                break;
+            }
+            let content = match tokio::fs::read_to_string(file).await {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            let file_entries: Vec<ActivityEntry> =
+                content.lines().filter_map(parse_entry).collect();
+            for entry in file_entries.into_iter().rev() {
+                if entries.len() >= n {
+                    break;
+                }
+                entries.push_front(entry);
+            }
+        }
+        Ok(entries.into())
+    }
+
+    /// Stream entries appended from now on
+    ///
+    /// Polls the log file. If it is truncated or replaced (e.g. rotated), the
+    /// stream starts over from the beginning of the new file.
+    pub fn tail(&self) -> impl Stream<Item = ActivityEntry> + Send + 'static {
+        let start = std::fs::metadata(&self.path).ok();
+        let state = TailState {
+            path: self.path.clone(),
+            poll_interval: self.poll_interval,
+            offset: start.as_ref().map_or(0, |m| m.len()),
+            file_id: start.as_ref().and_then(file_id),
+            partial: Vec::new(),
+            pending: VecDeque::new(),
+            first_poll: true,
+        };
+
+        futures::stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(entry) = state.pending.pop_front() {
+                    return Some((entry, state));
+                }
+                if !state.first_poll {
+                    tokio::time::sleep(state.poll_interval).await;
+                }
+                state.first_poll = false;
+                if let Err(e) = state.poll().await {
+                    debug!(
+                        "Failed to read activity log {}: {}",
+                        state.path.display(),
+                        e
+                    );
+                }
+            }
+        })
+    }
+}
+
+/// Position of an [`ActivityLog::tail`] stream in the log file
+struct TailState {
+    path: PathBuf,
+    poll_interval: Duration,
+    offset: u64,
+    file_id: Option<u64>,
+    /// Bytes of an incomplete final line
+    partial: Vec<u8>,
+    pending: VecDeque<ActivityEntry>,
+    first_poll: bool,
+}
+
+impl TailState {
+    /// Read newly appended complete lines into `pending`
+    async fn poll(&mut self) -> std::io::Result<()> {
+        let metadata = match tokio::fs::metadata(&self.path).await {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        let id = file_id(&metadata);
+        if metadata.len() < self.offset || id != self.file_id {
+            debug!(
+                "Activity log {} was truncated or replaced",
+                self.path.display()
+            );
+            self.offset = 0;
+            self.partial.clear();
+            self.file_id = id;
+        }
+        if metadata.len() == self.offset {
+            return Ok(());
+        }
 
+        let mut file = tokio::fs::File::open(&self.path).await?;
+        file.seek(std::io::SeekFrom::Start(self.offset)).await?;
+        let mut appended = Vec::new();
+        file.read_to_end(&mut appended).await?;
+        self.offset += appended.len() as u64;
+        self.partial.extend_from_slice(&appended);
+
+        if let Some(end) = self.partial.iter().rposition(|&b| b == b'\n') {
+            let complete: Vec<u8> = self.partial.drain(..=end).collect();
+            let text = String::from_utf8_lossy(&complete);
+            self.pending.extend(text.lines().filter_map(parse_entry));
+        }
         Ok(())
     }
 }
 
+/// Parse one JSONL line, skipping blank or malformed lines
+fn parse_entry(line: &str) -> Option<ActivityEntry> {
+    if line.trim().is_empty() {
+        return None;
+    }
+    match serde_json::from_str(line) {
+        Ok(entry) => Some(entry),
+        Err(e) => {
+            debug!("Skipping malformed activity entry: {}", e);
+            None
+        }
+    }
+}
+
+/// Identity of a file, to notice when the log is replaced rather than appended to
+#[cfg(unix)]
+fn file_id(metadata: &std::fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.ino())
+}
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &std::fs::Metadata) -> Option<u64> {
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -349,4 +765,210 @@ mod tests {
         assert!(content.contains("..."));
         assert!(!content.contains(&"x".repeat(600)));
     }
+
+    #[tokio::test]
+    async fn test_activity_log_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let hox_dir = temp_dir.path().to_path_buf();
+        let logger = ActivityLogger::new(hox_dir.clone());
+        let log = ActivityLog::new(&hox_dir);
+
+        assert!(log.read_recent(10).await.unwrap().is_empty());
+
+        logger
+            .log_loop_start("Implement feature X\nDetails", 3)
+            .await;
+        logger.log_iteration_start(1, 3).await;
+        let backpressure = BackpressureResult {
+            checks: vec![hox_agent::CheckOutcome {
+                name: "build".into(),
+                passed: false,
+                severity: hox_agent::Severity::Breaking,
+                output: "error".into(),
+            }],
+            errors: vec!["error".to_string()],
+        };
+        logger
+            .log_iteration_complete(1, "done", &["src/a.rs".to_string()], &[], &backpressure)
+            .await;
+        let usage = Usage {
+            input_tokens: 10,
+            output_tokens: 20,
+        };
+        logger
+            .log_loop_complete(1, false, &usage, "max iterations")
+            .await;
+
+        let entries = log.read_recent(10).await.unwrap();
+        assert_eq!(entries.len(), 4);
+        assert_eq!(
+            entries[0].event,
+            ActivityEvent::LoopStart {
+                task: "Implement feature X".to_string(),
+                max_iterations: 3,
+            }
+        );
+        match &entries[2].event {
+            ActivityEvent::IterationComplete {
+                checks,
+                files_created,
+                output_preview,
+                errors,
+                ..
+            } => {
+                assert_eq!(
+                    checks,
+                    &vec![ActivityCheck {
+                        name: "build".to_string(),
+                        passed: false,
+                    }]
+                );
+                assert_eq!(files_created, &vec!["src/a.rs".to_string()]);
+                assert_eq!(output_preview, "done");
+                assert_eq!(errors, &vec!["error".to_string()]);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        let recent = log.read_recent(2).await.unwrap();
+        assert_eq!(recent, entries[2..].to_vec());
+        assert!(matches!(
+            recent[1].event,
+            ActivityEvent::LoopComplete {
+                success: false,
+                input_tokens: 10,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_activity_log_skips_malformed_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let logger = ActivityLogger::new(temp_dir.path().to_path_buf());
+        logger.log_iteration_start(1, 2).await;
+        append_to(&temp_dir.path().join(ACTIVITY_JSONL_FILE), "{not json\n")
+            .await
+            .unwrap();
+        logger.log_iteration_start(2, 2).await;
+
+        let entries = ActivityLog::new(temp_dir.path())
+            .read_recent(5)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_activity_log_tail_follows_appends_and_truncation() {
+        use futures::StreamExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let hox_dir = temp_dir.path().to_path_buf();
+        let logger = ActivityLogger::new(hox_dir.clone());
+        logger.log_iteration_start(1, 9).await;
+
+        let log = ActivityLog::new(&hox_dir).with_poll_interval(Duration::from_millis(10));
+        let mut tail = Box::pin(log.tail());
+
+        // Existing entries are not replayed
+        logger.log_iteration_start(2, 9).await;
+        let next = tokio::time::timeout(Duration::from_secs(5), tail.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            next.event,
+            ActivityEvent::IterationStart {
+                iteration: 2,
+                max_iterations: 9,
+            }
+        );
+
+        // A truncated log is read again from the start
+        tokio::fs::write(hox_dir.join(ACTIVITY_JSONL_FILE), "")
+            .await
+            .unwrap();
+        logger.log_iteration_start(3, 9).await;
+        let next = tokio::time::timeout(Duration::from_secs(5), tail.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            next.event,
+            ActivityEvent::IterationStart { iteration: 3, .. }
+        ));
+    }
+
+    fn rotating_config(max_files: usize) -> ActivityLoggerConfig {
+        ActivityLoggerConfig {
+            max_bytes: 300,
+            max_files,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rotation_past_size_limit() {
+        let temp_dir = TempDir::new().unwrap();
+        let hox_dir = temp_dir.path().to_path_buf();
+        let logger = ActivityLogger::new(hox_dir.clone()).with_config(rotating_config(10));
+
+        for iteration in 1..=12 {
+            logger.log_iteration_start(iteration, 12).await;
+        }
+
+        let active = hox_dir.join(ACTIVITY_JSONL_FILE);
+        let archives = list_archives(&active).await.unwrap();
+        assert!(!archives.is_empty());
+        assert!(std::fs::metadata(&active).unwrap().len() < 300 + 100);
+        for archive in &archives {
+            let name = archive.file_name().unwrap().to_string_lossy().into_owned();
+            assert!(name.starts_with("activity.2") && name.ends_with(".jsonl"));
+        }
+        // The markdown log rotates alongside
+        assert!(!list_archives(&hox_dir.join("activity.md"))
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Reads span the archives, in order
+        let entries = ActivityLog::new(&hox_dir).read_recent(100).await.unwrap();
+        let iterations: Vec<usize> = entries
+            .iter()
+            .map(|e| match e.event {
+                ActivityEvent::IterationStart { iteration, .. } => iteration,
+                _ => 0,
+            })
+            .collect();
+        assert_eq!(iterations, (1..=12).collect::<Vec<_>>());
+
+        let recent = ActivityLog::new(&hox_dir).read_recent(5).await.unwrap();
+        assert!(matches!(
+            recent[0].event,
+            ActivityEvent::IterationStart { iteration: 8, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rotation_keeps_max_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let hox_dir = temp_dir.path().to_path_buf();
+        let logger = ActivityLogger::new(hox_dir.clone()).with_config(rotating_config(1));
+
+        for iteration in 1..=20 {
+            logger.log_iteration_start(iteration, 20).await;
+        }
+
+        let active = hox_dir.join(ACTIVITY_JSONL_FILE);
+        assert_eq!(list_archives(&active).await.unwrap().len(), 1);
+
+        // Entries in deleted archives are gone; the newest survive
+        let entries = ActivityLog::new(&hox_dir).read_recent(100).await.unwrap();
+        assert!(entries.len() < 20);
+        assert!(matches!(
+            entries.last().unwrap().event,
+            ActivityEvent::IterationStart { iteration: 20, .. }
+        ));
+    }
 }
@@ -0,0 +1,216 @@
+//! Agent logs - read-only diagnostics for a single agent's recent work
+//!
+//! Combines the agent's task bookmarks, each change's metadata and evolution
+//! log, and the matching sections of `.hox/activity.md` into one report.
+
+use hox_core::{ChangeId, HoxMetadata, Result};
+use hox_jj::{BookmarkManager, DagOperations, EvolutionEntry, JjExecutor, MetadataManager};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::workspace::agent_workspace_path;
+
+/// One change assigned to an agent
+#[derive(Debug, Clone)]
+pub struct AgentChangeLog {
+    /// Task ID from the `agent/{name}/task/{task_id}` bookmark
+    pub task_id: String,
+    pub change_id: ChangeId,
+    /// First line of the change description
+    pub title: String,
+    pub metadata: HoxMetadata,
+    /// Most recent evolution log entries (newest first)
+    pub evolog: Vec<EvolutionEntry>,
+}
+
+/// Everything known about an agent's recent work
+#[derive(Debug, Clone)]
+pub struct AgentLogs {
+    pub agent: String,
+    /// Assigned changes, sorted by task ID
+    pub changes: Vec<AgentChangeLog>,
+    /// Activity log sections for loops run on the agent's tasks
+    pub activity: Vec<String>,
+}
+
+/// Collect an agent's assigned changes and activity
+///
+/// `evolog_limit` caps the evolution entries kept per change. Activity is read
+/// from `.hox/activity.md` in both the repository and the agent's workspace.
+pub async fn collect_agent_logs<E: JjExecutor + Clone>(
+    executor: E,
+    agent: &str,
+    evolog_limit: usize,
+) -> Result<AgentLogs> {
+    let repo_root = executor.repo_root().clone();
+    let bookmarks = BookmarkManager::new(executor.clone());
+    let dag_ops = DagOperations::new(executor.clone());
+
+    let mut tasks: Vec<(String, ChangeId)> =
+        bookmarks.agent_tasks(agent).await?.into_iter().collect();
+    tasks.sort();
+
+    let mut changes = Vec::with_capacity(tasks.len());
+    for (task_id, change_id) in tasks {
+        let output = executor
+            .exec(&["log", "-r", &change_id, "-T", "description", "--no-graph"])
+            .await?
+            .into_result()?;
+        let description = output.stdout.trim();
+
+        let mut evolog = dag_ops.evolution_log(&change_id).await?;
+        evolog.truncate(evolog_limit);
+
+        changes.push(AgentChangeLog {
+            task_id,
+            change_id,
+            title: description.lines().next().unwrap_or_default().to_string(),
+            metadata: MetadataManager::<E>::parse_description(description),
+            evolog,
+        });
+    }
+
+    let titles: Vec<&str> = changes.iter().map(|c| c.title.as_str()).collect();
+    let mut activity = Vec::new();
+    for path in activity_log_paths(&repo_root, agent) {
+        match tokio::fs::read_to_string(&path).await {
+            Ok(content) => activity.extend(matching_activity_sections(&content, &titles)),
+            Err(e) => debug!("No activity log at {}: {}", path.display(), e),
+        }
+    }
+
+    Ok(AgentLogs {
+        agent: agent.to_string(),
+        changes,
+        activity,
+    })
+}
+
+/// Activity log locations relevant to an agent
+fn activity_log_paths(repo_root: &Path, agent: &str) -> Vec<PathBuf> {
+    vec![
+        repo_root.join(".hox").join("activity.md"),
+        agent_workspace_path(repo_root, agent)
+            .join(".hox")
+            .join("activity.md"),
+    ]
+}
+
+/// Split an activity log into per-loop sections and keep those for the given task titles
+fn matching_activity_sections(content: &str, titles: &[&str]) -> Vec<String> {
+    content
+        .split("## Task: ")
+        .skip(1)
+        .filter(|section| {
+            let task = section.lines().next().unwrap_or_default().trim();
+            titles.iter().any(|t| !t.is_empty() && *t == task)
+        })
+        .map(|section| {
+            // Each loop restarts the log with a top-level header; drop it from the previous section
+            let section = section.trim_end();
+            let section = section
+                .strip_suffix("# Hox Activity Log")
+                .unwrap_or(section)
+                .trim_end();
+            format!("## Task: {}", section)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hox_core::{HoxError, TaskStatus};
+    use hox_jj::{JjOutput, MockJjExecutor};
+
+    fn ok(stdout: &str) -> JjOutput {
+        JjOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            success: true,
+            code: Some(0),
+        }
+    }
+
+    const EVOLOG_TEMPLATE: &str =
+        r#"commit_id ++ "\t" ++ description.first_line() ++ "\t" ++ committer.timestamp() ++ "\n""#;
+
+    #[tokio::test]
+    async fn test_collect_agent_logs() {
+        let executor = MockJjExecutor::new()
+            .with_response(
+                r#"bookmark list --all -T name ++ "|" ++ change_id ++ "|" ++ if(tracked, remote_name, "") ++ "\n""#,
+                ok("agent/agent-7/task/t2|bbb222|\nagent/agent-7/task/t1|aaa111|\nagent/other/task/t3|ccc333|\n"),
+            )
+            .with_response(
+                "log -r aaa111 -T description --no-graph",
+                ok("Add parser\n\nStatus: in_progress\nAgent: agent-7\n"),
+            )
+            .with_response(
+                "log -r bbb222 -T description --no-graph",
+                ok("Write docs\n\nStatus: done\n"),
+            )
+            .with_response(
+                &format!("evolog -r aaa111 -T {} --no-graph", EVOLOG_TEMPLATE),
+                ok("c3\tAdd parser\t2025-01-30 12:30:00\nc2\tAdd parser\t2025-01-30 12:20:00\nc1\tWIP\t2025-01-30 12:00:00\n"),
+            )
+            .with_response(
+                &format!("evolog -r bbb222 -T {} --no-graph", EVOLOG_TEMPLATE),
+                ok("d1\tWrite docs\t2025-01-30 13:00:00\n"),
+            );
+
+        let logs = collect_agent_logs(executor, "agent-7", 2).await.unwrap();
+
+        assert_eq!(logs.agent, "agent-7");
+        assert_eq!(logs.changes.len(), 2);
+        assert_eq!(logs.changes[0].task_id, "t1");
+        assert_eq!(logs.changes[0].change_id, "aaa111");
+        assert_eq!(logs.changes[0].title, "Add parser");
+        assert_eq!(
+            logs.changes[0].metadata.status,
+            Some(TaskStatus::InProgress)
+        );
+        assert_eq!(logs.changes[0].evolog.len(), 2);
+        assert_eq!(logs.changes[0].evolog[0].commit_id, "c3");
+        assert_eq!(logs.changes[1].title, "Write docs");
+        assert_eq!(logs.changes[1].metadata.status, Some(TaskStatus::Done));
+        // Mock repo root has no activity log
+        assert!(logs.activity.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_collect_agent_logs_reports_failed_log() {
+        let executor = MockJjExecutor::new()
+            .with_response(
+                r#"bookmark list --all -T name ++ "|" ++ change_id ++ "|" ++ if(tracked, remote_name, "") ++ "\n""#,
+                ok("agent/agent-7/task/t1|aaa111|\n"),
+            )
+            .with_response(
+                "log -r aaa111 -T description --no-graph",
+                JjOutput {
+                    stdout: String::new(),
+                    stderr: "Error: Revision `aaa111` doesn't exist\n".to_string(),
+                    success: false,
+                    code: Some(1),
+                },
+            );
+
+        let err = collect_agent_logs(executor, "agent-7", 2)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, HoxError::ChangeNotFound(_)), "{}", err);
+    }
+
+    #[test]
+    fn test_matching_activity_sections() {
+        let content = "# Hox Activity Log\n\n## Task: Add parser\n**Started**: now\n\n### Iteration 1/5\n---\n\n\
+                       # Hox Activity Log\n\n## Task: Unrelated\n**Started**: later\n";
+
+        let sections = matching_activity_sections(content, &["Add parser"]);
+
+        assert_eq!(sections.len(), 1);
+        assert!(sections[0].starts_with("## Task: Add parser"));
+        assert!(sections[0].contains("### Iteration 1/5"));
+        assert!(!sections[0].contains("Unrelated"));
+    }
+}
@@ -10,20 +10,30 @@
 //! - Breaking-only errors: only compilation/syntax errors go into the prompt
 //! - jj fix: auto-format before checks to eliminate formatting conflicts
 //! - Selective checks: fast checks every iteration, slow checks periodically
+//! - Scoped tests (opt-in): test only the packages touched this iteration,
+//!   trading completeness for speed; re-runs of failed checks stay unscoped
 
 use hox_agent::{BackpressureResult, CheckOutcome, Severity};
-use hox_core::config::{BackpressureConfig, SlowCheck};
-use hox_core::Result;
+use hox_core::config::{
+    BackpressureConfig, CheckDefinition, CheckSeverity, ChecksConfig, ContainerConfig, SlowCheck,
+};
+use hox_core::{HoxError, Result};
 use hox_jj::JjExecutor;
-use std::collections::HashMap;
+use std::collections::hash_map::{DefaultHasher, Entry};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::io::Read as _;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 
 /// Default timeout for each check command
 const DEFAULT_TIMEOUT_SECS: u64 = 120;
 
+/// Cancellation flag for callers that never cancel
+static NEVER_CANCELLED: AtomicBool = AtomicBool::new(false);
+
 /// Maximum total characters of error output to include in the agent prompt.
 const MAX_ERROR_PROMPT_CHARS: usize = 6000;
 
@@ -38,23 +48,97 @@ pub struct CheckCommand {
     pub args: Vec<String>,
     pub timeout_secs: u64,
     pub severity: Severity,
+    /// Directory to run in, relative to the workspace (workspace root if unset)
+    pub working_dir: Option<PathBuf>,
+}
+
+impl CheckCommand {
+    /// Wrap this check to run inside a container with the workspace mounted
+    ///
+    /// Produces `<runtime> run --rm -v <workspace>:<mount> -w <mount>[/<dir>]
+    /// <run_args...> <image> <program> <args...>`. If the runtime is not
+    /// installed the check is skipped like any other missing program.
+    ///
+    /// A program under the workspace is rewritten to its path under the
+    /// mount, except tools from the workspace `.venv`: a host venv's scripts
+    /// point at the host interpreter, so the image's own tool is used instead.
+    pub fn in_container(self, container: &ContainerConfig, workspace_path: &Path) -> Self {
+        let host = std::path::absolute(workspace_path).unwrap_or_else(|_| workspace_path.into());
+        let mount = Path::new(&container.mount_path);
+        let program = container_program(&self.program, workspace_path, &host, mount);
+        let workdir = match &self.working_dir {
+            Some(dir) => mount.join(dir),
+            None => mount.to_path_buf(),
+        };
+
+        let mut args = vec![
+            "run".to_string(),
+            "--rm".to_string(),
+            "-v".to_string(),
+            format!("{}:{}", host.display(), mount.display()),
+            "-w".to_string(),
+            workdir.display().to_string(),
+        ];
+        args.extend(container.run_args.iter().cloned());
+        args.push(container.image.clone());
+        args.push(program);
+        args.extend(self.args);
+
+        Self {
+            program: container.runtime.clone(),
+            args,
+            working_dir: None,
+            ..self
+        }
+    }
+}
+
+/// The program to run in a container for a check's host `program`
+fn container_program(program: &str, workspace_path: &Path, host: &Path, mount: &Path) -> String {
+    let path = Path::new(program);
+    let Ok(relative) = path
+        .strip_prefix(host)
+        .or_else(|_| path.strip_prefix(workspace_path))
+    else {
+        return program.to_string();
+    };
+
+    if relative.starts_with(Path::new(".venv").join("bin")) {
+        if let Some(tool) = relative.file_name() {
+            return tool.to_string_lossy().into_owned();
+        }
+    }
+    mount.join(relative).display().to_string()
 }
 
 /// Run all checks in parallel with timeouts
 ///
 /// Auto-detects project type if no explicit commands provided.
 pub fn run_all_checks(workspace_path: &Path) -> Result<BackpressureResult> {
-    let commands = detect_checks(workspace_path);
+    let commands = detect_checks(workspace_path)?;
     run_checks(workspace_path, &commands)
 }
 
+/// Run all detected checks, stopping early once `cancel` is set
+pub fn run_all_checks_cancellable(
+    workspace_path: &Path,
+    cancel: &AtomicBool,
+) -> Result<BackpressureResult> {
+    let commands = detect_checks(workspace_path)?;
+    run_checks_cancellable(workspace_path, &commands, cancel)
+}
+
 /// Run only checks that previously failed (selective re-run)
 ///
 /// Takes the previous result and only re-runs checks whose names match
 /// previously failed checks. Checks that passed last time are skipped.
+/// Re-runs are never scoped: a failing test may live in a package the
+/// current iteration did not touch, so only the full suite can show that it
+/// was fixed. Running checks are killed as soon as `cancel` is set.
 pub fn run_failed_checks(
     workspace_path: &Path,
     previous: &BackpressureResult,
+    cancel: &AtomicBool,
 ) -> Result<BackpressureResult> {
     let failed_names: Vec<&str> = previous.failed_check_names();
 
@@ -63,14 +147,10 @@ pub fn run_failed_checks(
         return Ok(BackpressureResult::all_pass());
     }
 
-    let all_commands = detect_checks(workspace_path);
-    let commands: Vec<CheckCommand> = all_commands
-        .into_iter()
-        .filter(|cmd| failed_names.contains(&cmd.name.as_str()))
-        .collect();
+    let commands = rerun_commands(workspace_path, &failed_names)?;
 
     // Start with previous passing results, then overlay re-run results
-    let rerun_result = run_checks(workspace_path, &commands)?;
+    let rerun_result = run_checks_cancellable(workspace_path, &commands, cancel)?;
 
     // Merge: keep previous passing checks + new results for re-run checks
     let mut checks: Vec<CheckOutcome> = previous
@@ -90,8 +170,29 @@ pub fn run_failed_checks(
     Ok(BackpressureResult { checks, errors })
 }
 
+/// Unscoped commands for the checks named in `failed_names`
+fn rerun_commands(workspace_path: &Path, failed_names: &[&str]) -> Result<Vec<CheckCommand>> {
+    Ok(detect_checks(workspace_path)?
+        .into_iter()
+        .filter(|cmd| failed_names.contains(&cmd.name.as_str()))
+        .collect())
+}
+
 /// Run a set of check commands in parallel with timeouts
 pub fn run_checks(workspace_path: &Path, commands: &[CheckCommand]) -> Result<BackpressureResult> {
+    run_checks_cancellable(workspace_path, commands, &NEVER_CANCELLED)
+}
+
+/// Run a set of check commands in parallel, killing them once `cancel` is set
+///
+/// Checks interrupted by cancellation, or not started because of it, are
+/// reported as failed warnings marked `[CANCELLED]`: they did not complete,
+/// so they never count as passing, but they add no breaking errors.
+pub fn run_checks_cancellable(
+    workspace_path: &Path,
+    commands: &[CheckCommand],
+    cancel: &AtomicBool,
+) -> Result<BackpressureResult> {
     if commands.is_empty() {
         return Ok(BackpressureResult::all_pass());
     }
@@ -105,7 +206,7 @@ pub fn run_checks(workspace_path: &Path, commands: &[CheckCommand]) -> Result<Ba
     let outcomes: Vec<CheckOutcome> = std::thread::scope(|s| {
         let handles: Vec<_> = commands
             .iter()
-            .map(|cmd| s.spawn(|| run_check_with_timeout(workspace_path, cmd)))
+            .map(|cmd| s.spawn(|| run_check_with_timeout(workspace_path, cmd, cancel)))
             .collect();
 
         handles
@@ -150,9 +251,44 @@ pub fn run_checks(workspace_path: &Path, commands: &[CheckCommand]) -> Result<Ba
 
 /// Detect check commands for a workspace based on project files
 ///
-/// Auto-detects project type and returns appropriate commands.
-/// Falls through gracefully - missing tools are handled at runtime.
-pub fn detect_checks(workspace_path: &Path) -> Vec<CheckCommand> {
+/// Auto-detects project type and returns appropriate commands, then merges
+/// the checks in `.hox/checks.toml` over them by name. A `[container]` table
+/// in `.hox/checks.toml` wraps the result to run inside a container.
+/// Missing tools are handled at runtime, but a malformed `.hox/checks.toml`
+/// is an error.
+pub fn detect_checks(workspace_path: &Path) -> Result<Vec<CheckCommand>> {
+    detect_checks_for_changes(workspace_path, &[])
+}
+
+/// Detect check commands, scoping tests to the files touched this iteration
+///
+/// With `scoped_tests = true` in `.hox/checks.toml`, test checks only cover
+/// the packages containing `touched_files` (paths relative to the workspace).
+/// Otherwise, or when no files were touched, this is [`detect_checks`].
+pub fn detect_checks_for_changes(
+    workspace_path: &Path,
+    touched_files: &[String],
+) -> Result<Vec<CheckCommand>> {
+    let config = ChecksConfig::load(workspace_path)?.unwrap_or_default();
+    let mut checks = merge_configured_checks(auto_detect_checks(workspace_path), config.checks)?;
+    if config.scoped_tests {
+        checks = checks
+            .into_iter()
+            .map(|check| scope_test_check(workspace_path, check, touched_files))
+            .collect();
+    }
+
+    Ok(match &config.container {
+        Some(container) => checks
+            .into_iter()
+            .map(|check| check.in_container(container, workspace_path))
+            .collect(),
+        None => checks,
+    })
+}
+
+/// Detect check commands from project files
+fn auto_detect_checks(workspace_path: &Path) -> Vec<CheckCommand> {
     let mut checks = Vec::new();
 
     // Rust
@@ -163,6 +299,7 @@ pub fn detect_checks(workspace_path: &Path) -> Vec<CheckCommand> {
             args: vec!["build".into()],
             timeout_secs: DEFAULT_TIMEOUT_SECS,
             severity: Severity::Breaking,
+            working_dir: None,
         });
         checks.push(CheckCommand {
             name: "lint".into(),
@@ -170,6 +307,7 @@ pub fn detect_checks(workspace_path: &Path) -> Vec<CheckCommand> {
             args: vec!["clippy".into(), "--".into(), "-D".into(), "warnings".into()],
             timeout_secs: DEFAULT_TIMEOUT_SECS,
             severity: Severity::Warning,
+            working_dir: None,
         });
         checks.push(CheckCommand {
             name: "test".into(),
@@ -177,6 +315,7 @@ pub fn detect_checks(workspace_path: &Path) -> Vec<CheckCommand> {
             args: vec!["test".into(), "--".into(), "--nocapture".into()],
             timeout_secs: DEFAULT_TIMEOUT_SECS,
             severity: Severity::Warning,
+            working_dir: None,
         });
     }
 
@@ -190,6 +329,7 @@ pub fn detect_checks(workspace_path: &Path) -> Vec<CheckCommand> {
             args: vec!["check".into(), ".".into()],
             timeout_secs: DEFAULT_TIMEOUT_SECS,
             severity: Severity::Warning,
+            working_dir: None,
         });
 
         let pytest = python_tool(workspace_path, "pytest");
@@ -199,6 +339,7 @@ pub fn detect_checks(workspace_path: &Path) -> Vec<CheckCommand> {
             args: vec!["-v".into(), "--continue-on-collection-errors".into()],
             timeout_secs: DEFAULT_TIMEOUT_SECS,
             severity: Severity::Warning,
+            working_dir: None,
         });
 
         // Python build/import check
@@ -211,6 +352,7 @@ pub fn detect_checks(workspace_path: &Path) -> Vec<CheckCommand> {
                     args: vec!["-c".into(), format!("import {}", name)],
                     timeout_secs: DEFAULT_TIMEOUT_SECS,
                     severity: Severity::Breaking,
+                    working_dir: None,
                 });
             }
         }
@@ -226,6 +368,7 @@ pub fn detect_checks(workspace_path: &Path) -> Vec<CheckCommand> {
                     args: vec!["run".into(), "build".into()],
                     timeout_secs: DEFAULT_TIMEOUT_SECS,
                     severity: Severity::Breaking,
+                    working_dir: None,
                 });
             }
             if content.contains("\"test\"") {
@@ -235,6 +378,7 @@ pub fn detect_checks(workspace_path: &Path) -> Vec<CheckCommand> {
                     args: vec!["test".into()],
                     timeout_secs: DEFAULT_TIMEOUT_SECS,
                     severity: Severity::Warning,
+                    working_dir: None,
                 });
             }
             if content.contains("\"lint\"") {
@@ -244,6 +388,7 @@ pub fn detect_checks(workspace_path: &Path) -> Vec<CheckCommand> {
                     args: vec!["run".into(), "lint".into()],
                     timeout_secs: DEFAULT_TIMEOUT_SECS,
                     severity: Severity::Warning,
+                    working_dir: None,
                 });
             }
         }
@@ -257,6 +402,7 @@ pub fn detect_checks(workspace_path: &Path) -> Vec<CheckCommand> {
             args: vec!["build".into(), "./...".into()],
             timeout_secs: DEFAULT_TIMEOUT_SECS,
             severity: Severity::Breaking,
+            working_dir: None,
         });
         checks.push(CheckCommand {
             name: "test".into(),
@@ -264,6 +410,7 @@ pub fn detect_checks(workspace_path: &Path) -> Vec<CheckCommand> {
             args: vec!["test".into(), "./...".into()],
             timeout_secs: DEFAULT_TIMEOUT_SECS,
             severity: Severity::Warning,
+            working_dir: None,
         });
     }
 
@@ -282,6 +429,7 @@ pub fn detect_checks(workspace_path: &Path) -> Vec<CheckCommand> {
                         } else {
                             Severity::Warning
                         },
+                        working_dir: None,
                     });
                 }
             }
@@ -303,6 +451,7 @@ pub fn detect_checks(workspace_path: &Path) -> Vec<CheckCommand> {
                         } else {
                             Severity::Warning
                         },
+                        working_dir: None,
                     });
                 }
             }
@@ -312,8 +461,261 @@ pub fn detect_checks(workspace_path: &Path) -> Vec<CheckCommand> {
     checks
 }
 
+/// Merge `.hox/checks.toml` entries over detected checks
+///
+/// An entry whose name matches detected checks overrides the fields it sets
+/// on each of them, or removes them with `enabled = false`. Any other entry
+/// adds a check and must name a program.
+fn merge_configured_checks(
+    mut checks: Vec<CheckCommand>,
+    definitions: Vec<CheckDefinition>,
+) -> Result<Vec<CheckCommand>> {
+    let invalid = |message: String| HoxError::Other(format!(".hox/checks.toml: {}", message));
+
+    let mut seen = HashSet::new();
+    for definition in definitions {
+        if !seen.insert(definition.name.clone()) {
+            return Err(invalid(format!(
+                "check '{}' is defined more than once",
+                definition.name
+            )));
+        }
+        if definition.program.as_deref().is_some_and(str::is_empty) {
+            return Err(invalid(format!(
+                "check '{}' has an empty program",
+                definition.name
+            )));
+        }
+        if let Some(dir) = &definition.working_dir {
+            let inside_workspace = dir
+                .components()
+                .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+            if !inside_workspace {
+                return Err(invalid(format!(
+                    "check '{}' has working_dir '{}'; it must be a relative path inside the workspace",
+                    definition.name,
+                    dir.display()
+                )));
+            }
+        }
+
+        if !definition.enabled {
+            checks.retain(|check| check.name != definition.name);
+            continue;
+        }
+
+        let severity = definition
+            .severity
+            .or(definition.breaking.map(|breaking| {
+                if breaking {
+                    CheckSeverity::Breaking
+                } else {
+                    CheckSeverity::Warning
+                }
+            }))
+            .map(|severity| match severity {
+                CheckSeverity::Breaking => Severity::Breaking,
+                CheckSeverity::Warning => Severity::Warning,
+            });
+
+        let mut matched = false;
+        for check in checks.iter_mut().filter(|c| c.name == definition.name) {
+            matched = true;
+            if let Some(program) = &definition.program {
+                check.program = program.clone();
+            }
+            if let Some(args) = &definition.args {
+                check.args = args.clone();
+            }
+            if let Some(timeout_secs) = definition.timeout_secs {
+                check.timeout_secs = timeout_secs;
+            }
+            if let Some(severity) = severity {
+                check.severity = severity;
+            }
+            if definition.working_dir.is_some() {
+                check.working_dir = definition.working_dir.clone();
+            }
+        }
+        if matched {
+            continue;
+        }
+
+        let program = definition.program.ok_or_else(|| {
+            invalid(format!(
+                "check '{}' is not auto-detected, so it needs a `program`",
+                definition.name
+            ))
+        })?;
+        checks.push(CheckCommand {
+            name: definition.name,
+            program,
+            args: definition.args.unwrap_or_default(),
+            timeout_secs: definition.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS),
+            severity: severity.unwrap_or(Severity::Warning),
+            working_dir: definition.working_dir,
+        });
+    }
+
+    Ok(checks)
+}
+
+/// Scope a test check to the packages containing `touched_files`
+///
+/// Supports `cargo test` and `cargo nextest run` (one `-p` per crate) and
+/// `go test ./...` (one pattern per package directory). Any other check, or a
+/// touched file outside a recognizable package, is returned unchanged so the
+/// full suite runs.
+fn scope_test_check(
+    workspace_path: &Path,
+    check: CheckCommand,
+    touched_files: &[String],
+) -> CheckCommand {
+    if touched_files.is_empty()
+        || touched_files.iter().any(|file| {
+            Path::new(file)
+                .components()
+                .any(|c| c == Component::ParentDir)
+        })
+    {
+        return check;
+    }
+
+    let files: Vec<PathBuf> = touched_files
+        .iter()
+        .map(|file| workspace_path.join(file))
+        .collect();
+    let cwd = match &check.working_dir {
+        Some(dir) => workspace_path.join(dir),
+        None => workspace_path.to_path_buf(),
+    };
+
+    let scoped_args = match check.program.as_str() {
+        "cargo" => scoped_cargo_test_args(workspace_path, &check.args, &files),
+        "go" => scoped_go_test_args(&cwd, &check.args, &files),
+        _ => None,
+    };
+    match scoped_args {
+        Some(args) => {
+            tracing::debug!("Scoped {} check to: {}", check.name, args.join(" "));
+            CheckCommand { args, ..check }
+        }
+        None => check,
+    }
+}
+
+/// `cargo test` arguments limited to the crates containing `files`
+fn scoped_cargo_test_args(
+    workspace_path: &Path,
+    args: &[String],
+    files: &[PathBuf],
+) -> Option<Vec<String>> {
+    let subcommand_len = match args {
+        [test, ..] if test == "test" => 1,
+        [nextest, run, ..] if nextest == "nextest" && run == "run" => 2,
+        _ => return None,
+    };
+    let rest = &args[subcommand_len..];
+    let (options, test_args) =
+        rest.split_at(rest.iter().position(|a| a == "--").unwrap_or(rest.len()));
+    if options
+        .iter()
+        .any(|a| a == "-p" || a.starts_with("--package") || a.starts_with("--manifest-path"))
+    {
+        // Already scoped explicitly
+        return None;
+    }
+
+    let mut packages = BTreeSet::new();
+    for file in files {
+        packages.insert(cargo_package_for(workspace_path, file)?);
+    }
+
+    let mut scoped = args[..subcommand_len].to_vec();
+    for package in packages {
+        scoped.push("-p".into());
+        scoped.push(package);
+    }
+    scoped.extend(
+        options
+            .iter()
+            .filter(|a| !matches!(a.as_str(), "--workspace" | "--all"))
+            .cloned(),
+    );
+    scoped.extend(test_args.iter().cloned());
+    Some(scoped)
+}
+
+/// Name of the crate whose manifest is nearest above `file`
+///
+/// `None` for files outside any crate, including files only covered by a
+/// virtual workspace manifest.
+fn cargo_package_for(workspace_path: &Path, file: &Path) -> Option<String> {
+    let mut dir = file.parent()?;
+    while dir.starts_with(workspace_path) {
+        let manifest = dir.join("Cargo.toml");
+        if manifest.exists() {
+            let content = std::fs::read_to_string(manifest).ok()?;
+            return extract_toml_table_name(&content, "[package]");
+        }
+        dir = dir.parent()?;
+    }
+    None
+}
+
+/// `go test` arguments limited to the package directories containing `files`
+fn scoped_go_test_args(cwd: &Path, args: &[String], files: &[PathBuf]) -> Option<Vec<String>> {
+    if args.first().map(String::as_str) != Some("test") {
+        return None;
+    }
+    let pattern = args.iter().position(|a| a == "./...")?;
+
+    let mut packages = BTreeSet::new();
+    for file in files {
+        if file.extension()? != "go" {
+            return None;
+        }
+        let dir = file.parent()?.strip_prefix(cwd).ok()?;
+        packages.insert(if dir.as_os_str().is_empty() {
+            ".".to_string()
+        } else {
+            format!("./{}", dir.display())
+        });
+    }
+
+    let mut scoped = args.to_vec();
+    scoped.splice(pattern..=pattern, packages);
+    Some(scoped)
+}
+
+/// Outcome reported for a check stopped by cancellation
+fn cancelled_outcome(cmd: &CheckCommand) -> CheckOutcome {
+    CheckOutcome {
+        name: cmd.name.clone(),
+        passed: false,
+        severity: Severity::Warning,
+        output: format!("[CANCELLED] {} check interrupted by shutdown", cmd.name),
+    }
+}
+
+/// Why a check process was stopped before exiting on its own
+enum Interrupted {
+    TimedOut,
+    Cancelled,
+}
+
 /// Run a single check command with a timeout
-fn run_check_with_timeout(workspace_path: &Path, cmd: &CheckCommand) -> CheckOutcome {
+///
+/// The child process is killed when the timeout elapses or `cancel` is set.
+fn run_check_with_timeout(
+    workspace_path: &Path,
+    cmd: &CheckCommand,
+    cancel: &AtomicBool,
+) -> CheckOutcome {
+    if cancel.load(Ordering::SeqCst) {
+        return cancelled_outcome(cmd);
+    }
+
     tracing::debug!(
         "Running check: {} ({} {})",
         cmd.name,
@@ -321,9 +723,14 @@ fn run_check_with_timeout(workspace_path: &Path, cmd: &CheckCommand) -> CheckOut
         cmd.args.join(" ")
     );
 
+    let cwd = match &cmd.working_dir {
+        Some(dir) => workspace_path.join(dir),
+        None => workspace_path.to_path_buf(),
+    };
+
     let mut child = match Command::new(&cmd.program)
         .args(&cmd.args)
-        .current_dir(workspace_path)
+        .current_dir(&cwd)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
@@ -378,32 +785,30 @@ fn run_check_with_timeout(workspace_path: &Path, cmd: &CheckCommand) -> CheckOut
     let timeout = Duration::from_secs(cmd.timeout_secs);
     let start = Instant::now();
     let status = loop {
+        let interrupted = if cancel.load(Ordering::SeqCst) {
+            Some(Interrupted::Cancelled)
+        } else if start.elapsed() >= timeout {
+            Some(Interrupted::TimedOut)
+        } else {
+            None
+        };
+
         match child.try_wait() {
-            Ok(Some(status)) => break Some(status),
-            Ok(None) => {
-                if start.elapsed() >= timeout {
-                    if let Err(e) = child.kill() {
-                        tracing::warn!("Failed to kill child process: {}", e);
-                    }
-                    if let Err(e) = child.wait() {
-                        tracing::warn!("Failed to wait for child process: {}", e);
-                    }
-                    break None;
+            Ok(Some(status)) => break Ok(status),
+            polled => {
+                if let Err(e) = polled {
+                    tracing::warn!("Error polling {} process: {}", cmd.name, e);
                 }
-                std::thread::sleep(Duration::from_millis(100));
-            }
-            Err(e) => {
-                tracing::warn!("Error polling {} process: {}", cmd.name, e);
-                if start.elapsed() >= timeout {
+                if let Some(reason) = interrupted {
                     if let Err(e) = child.kill() {
                         tracing::warn!("Failed to kill child process: {}", e);
                     }
                     if let Err(e) = child.wait() {
                         tracing::warn!("Failed to wait for child process: {}", e);
                     }
-                    break None;
+                    break Err(reason);
                 }
-                // Transient error, retry
+                // Still running, or a transient polling error: retry
                 std::thread::sleep(Duration::from_millis(100));
             }
         }
@@ -413,7 +818,7 @@ fn run_check_with_timeout(workspace_path: &Path, cmd: &CheckCommand) -> CheckOut
     let stderr = stderr_thread.join().unwrap_or_default();
 
     match status {
-        Some(exit_status) => {
+        Ok(exit_status) => {
             let passed = exit_status.success();
             let output = if !passed {
                 format_check_output(&cmd.name, &cmd.program, &cmd.args, &stdout, &stderr)
@@ -427,7 +832,11 @@ fn run_check_with_timeout(workspace_path: &Path, cmd: &CheckCommand) -> CheckOut
                 output,
             }
         }
-        None => {
+        Err(Interrupted::Cancelled) => {
+            tracing::info!("{} cancelled", cmd.name);
+            cancelled_outcome(cmd)
+        }
+        Err(Interrupted::TimedOut) => {
             tracing::warn!("{} timed out after {}s", cmd.name, cmd.timeout_secs);
             CheckOutcome {
                 name: cmd.name.clone(),
@@ -486,25 +895,30 @@ fn python_tool(workspace_path: &Path, tool: &str) -> String {
 
 /// Extract the main package name from a pyproject.toml
 fn extract_python_package_name(content: &str) -> Option<String> {
-    let mut in_project = false;
+    extract_toml_table_name(content, "[project]").map(|name| name.replace('-', "_"))
+}
+
+/// Extract the `name` key of a TOML table header such as `[package]`
+fn extract_toml_table_name(content: &str, table: &str) -> Option<String> {
+    let mut in_table = false;
     for line in content.lines() {
         let trimmed = line.trim();
-        if trimmed == "[project]" {
-            in_project = true;
+        if trimmed == table {
+            in_table = true;
             continue;
         }
-        if trimmed.starts_with('[') && trimmed != "[project]" {
-            in_project = false;
+        if trimmed.starts_with('[') {
+            in_table = false;
             continue;
         }
-        if in_project {
+        if in_table {
             if let Some(rest) = trimmed.strip_prefix("name") {
                 let rest = rest.trim_start();
                 if let Some(rest) = rest.strip_prefix('=') {
                     let rest = rest.trim();
                     let name = rest.trim_matches('"').trim_matches('\'');
                     if !name.is_empty() {
-                        return Some(name.replace('-', "_"));
+                        return Some(name.to_string());
                     }
                 }
             }
@@ -554,11 +968,15 @@ pub async fn run_jj_fix<E: JjExecutor>(executor: &E, change_id: Option<&str>) ->
 /// conflicts from causing check failures.
 ///
 /// Note: jj fix failures are NON-FATAL. If fix fails, we log a warning
-/// and continue with standard checks.
+/// and continue with standard checks. Tests are scoped to `touched_files` if
+/// enabled (see [`detect_checks_for_changes`]). Running checks are killed as
+/// soon as `cancel` is set.
 pub async fn run_all_checks_with_fix<E: JjExecutor>(
     workspace_path: &Path,
     executor: &E,
     change_id: Option<&str>,
+    touched_files: &[String],
+    cancel: &AtomicBool,
 ) -> Result<BackpressureResult> {
     // Run jj fix FIRST to clean formatting
     let fix_result = run_jj_fix(executor, change_id).await;
@@ -577,13 +995,15 @@ pub async fn run_all_checks_with_fix<E: JjExecutor>(
     }
 
     // Then run standard checks
-    run_all_checks(workspace_path)
+    let commands = detect_checks_for_changes(workspace_path, touched_files)?;
+    run_checks_cancellable(workspace_path, &commands, cancel)
 }
 
 /// Format backpressure errors for inclusion in agent prompt.
 ///
 /// Applies smart truncation: keeps the first N lines of each error block and
 /// appends a summary of how many total error lines were omitted.
+/// Identical errors reported by several checks are included once.
 /// Only includes Breaking errors.
 pub fn format_errors_for_prompt(result: &BackpressureResult) -> String {
     if result.errors.is_empty() {
@@ -595,7 +1015,7 @@ pub fn format_errors_for_prompt(result: &BackpressureResult) -> String {
 
     let mut budget = MAX_ERROR_PROMPT_CHARS;
 
-    for error in &result.errors {
+    for (error, duplicates) in dedup_errors(&result.errors) {
         if budget == 0 {
             output.push_str("*(additional errors omitted - fix the above first)*\n\n");
             break;
@@ -604,7 +1024,15 @@ pub fn format_errors_for_prompt(result: &BackpressureResult) -> String {
         let truncated = truncate_error_for_prompt(error, budget);
         output.push_str("```\n");
         output.push_str(&truncated);
-        output.push_str("\n```\n\n");
+        output.push_str("\n```\n");
+        if duplicates > 0 {
+            output.push_str(&format!(
+                "*(also reported by {} other check{})*\n",
+                duplicates,
+                if duplicates == 1 { "" } else { "s" }
+            ));
+        }
+        output.push('\n');
 
         budget = budget.saturating_sub(truncated.len());
     }
@@ -612,6 +1040,40 @@ pub fn format_errors_for_prompt(result: &BackpressureResult) -> String {
     output
 }
 
+/// Collapse identical error blocks, keeping first-seen order
+///
+/// Returns each distinct error with the number of additional checks that
+/// reported it. Errors are compared by a hash of their content without the
+/// `<check> (<command>) failed:` header, so the same compiler error from
+/// `build` and `test` counts once.
+fn dedup_errors(errors: &[String]) -> Vec<(&str, usize)> {
+    let mut seen: HashMap<u64, usize> = HashMap::new();
+    let mut unique: Vec<(&str, usize)> = Vec::new();
+
+    for error in errors {
+        let mut hasher = DefaultHasher::new();
+        error_body(error).hash(&mut hasher);
+
+        match seen.entry(hasher.finish()) {
+            Entry::Occupied(entry) => unique[*entry.get()].1 += 1,
+            Entry::Vacant(entry) => {
+                entry.insert(unique.len());
+                unique.push((error, 0));
+            }
+        }
+    }
+
+    unique
+}
+
+/// Error content without the check-specific header line
+fn error_body(error: &str) -> &str {
+    match error.split_once('\n') {
+        Some((header, body)) if header.ends_with(" failed:") => body,
+        _ => error,
+    }
+}
+
 /// Truncate a single error block for the prompt.
 fn truncate_error_for_prompt(error: &str, max_chars: usize) -> String {
     if error.len() <= max_chars {
@@ -703,9 +1165,10 @@ impl BackpressureEngine {
                 args,
                 timeout_secs: DEFAULT_TIMEOUT_SECS,
                 severity: Severity::Breaking,
+                working_dir: None,
             };
 
-            let outcome = run_check_with_timeout(workspace_path, &cmd);
+            let outcome = run_check_with_timeout(workspace_path, &cmd, &NEVER_CANCELLED);
             let elapsed = check_start.elapsed();
 
             checks.insert(
@@ -751,9 +1214,10 @@ impl BackpressureEngine {
                     args,
                     timeout_secs: DEFAULT_TIMEOUT_SECS,
                     severity: Severity::Warning,
+                    working_dir: None,
                 };
 
-                let outcome = run_check_with_timeout(workspace_path, &cmd);
+                let outcome = run_check_with_timeout(workspace_path, &cmd, &NEVER_CANCELLED);
                 let elapsed = check_start.elapsed();
 
                 checks.insert(
@@ -793,7 +1257,7 @@ impl BackpressureEngine {
     /// Determine if a slow check should run on this iteration
     fn should_run_slow_check(&self, check: &SlowCheck, iteration: usize) -> bool {
         // Regular schedule: run every N iterations
-        if iteration > 0 && iteration % check.every_n_iterations == 0 {
+        if iteration > 0 && iteration.is_multiple_of(check.every_n_iterations) {
             tracing::debug!(
                 "Running slow check '{}' on regular schedule (every {} iterations)",
                 check.command,
@@ -941,6 +1405,27 @@ mod tests {
         assert!(formatted.contains("test_foo"));
     }
 
+    #[test]
+    fn test_format_errors_dedups_identical_errors() {
+        let stderr = "error[E0425]: cannot find value `x` in this scope\n --> src/lib.rs:3:5";
+        let build = format_check_output("build", "cargo", &["build".into()], "", stderr);
+        let test = format_check_output("test", "cargo", &["test".into()], "", stderr);
+        let other = format_check_output("lint", "ruff", &["check".into()], "", "E501");
+
+        let result = BackpressureResult {
+            checks: Vec::new(),
+            errors: vec![build, test, other],
+        };
+        let formatted = format_errors_for_prompt(&result);
+
+        assert_eq!(formatted.matches("cannot find value").count(), 1);
+        assert!(formatted.contains("build (cargo build) failed"));
+        assert!(!formatted.contains("test (cargo test) failed"));
+        let annotation = "(also reported by 1 other check)";
+        assert_eq!(formatted.matches(annotation).count(), 1);
+        assert!(formatted.contains("E501"));
+    }
+
     #[test]
     fn test_format_errors_truncates_large_output() {
         let mut big_error = String::from("ruff check . failed:\n\nSTDOUT:\n");
@@ -1003,7 +1488,7 @@ mod tests {
         )
         .unwrap();
 
-        let checks = detect_checks(temp_dir.path());
+        let checks = detect_checks(temp_dir.path()).unwrap();
         assert!(!checks.is_empty());
 
         let names: Vec<&str> = checks.iter().map(|c| c.name.as_str()).collect();
@@ -1028,7 +1513,7 @@ mod tests {
         )
         .unwrap();
 
-        let checks = detect_checks(temp_dir.path());
+        let checks = detect_checks(temp_dir.path()).unwrap();
         assert!(!checks.is_empty());
 
         let names: Vec<&str> = checks.iter().map(|c| c.name.as_str()).collect();
@@ -1046,7 +1531,7 @@ mod tests {
         )
         .unwrap();
 
-        let checks = detect_checks(temp_dir.path());
+        let checks = detect_checks(temp_dir.path()).unwrap();
         assert!(!checks.is_empty());
 
         let names: Vec<&str> = checks.iter().map(|c| c.name.as_str()).collect();
@@ -1054,6 +1539,443 @@ mod tests {
         assert!(names.contains(&"test"));
     }
 
+    #[test]
+    fn test_configured_check_working_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".hox")).unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("sub")).unwrap();
+        // Auto-detected and kept alongside the configured check
+        std::fs::write(temp_dir.path().join("Makefile"), "build:\n\ttrue\n").unwrap();
+        std::fs::write(
+            temp_dir.path().join(".hox/checks.toml"),
+            r#"
+[[check]]
+name = "where"
+program = "sh"
+args = ["-c", "pwd -P; exit 1"]
+breaking = true
+working_dir = "sub"
+"#,
+        )
+        .unwrap();
+
+        let checks = detect_checks(temp_dir.path()).unwrap();
+        let names: Vec<&str> = checks.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["build", "where"]);
+        assert_eq!(checks[1].working_dir, Some(PathBuf::from("sub")));
+
+        let result = run_checks(temp_dir.path(), &checks).unwrap();
+        let expected = temp_dir.path().join("sub").canonicalize().unwrap();
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].contains(&expected.display().to_string()));
+    }
+
+    fn write_checks_toml(dir: &Path, content: &str) {
+        std::fs::create_dir_all(dir.join(".hox")).unwrap();
+        std::fs::write(dir.join(".hox/checks.toml"), content).unwrap();
+    }
+
+    #[test]
+    fn test_checks_toml_adds_check() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("go.mod"), "module example\n").unwrap();
+        write_checks_toml(
+            temp_dir.path(),
+            r#"
+[[check]]
+name = "vet"
+program = "go"
+args = ["vet", "./..."]
+timeout = 30
+severity = "breaking"
+"#,
+        );
+
+        let checks = detect_checks(temp_dir.path()).unwrap();
+        let names: Vec<&str> = checks.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["build", "test", "vet"]);
+        assert_eq!(checks[2].program, "go");
+        assert_eq!(checks[2].args, ["vet", "./..."]);
+        assert_eq!(checks[2].timeout_secs, 30);
+        assert_eq!(checks[2].severity, Severity::Breaking);
+    }
+
+    #[test]
+    fn test_checks_toml_replaces_detected_fields() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("Cargo.toml"), "[package]\n").unwrap();
+        write_checks_toml(
+            temp_dir.path(),
+            r#"
+[[check]]
+name = "test"
+args = ["nextest", "run"]
+severity = "warning"
+"#,
+        );
+
+        let detected = auto_detect_checks(temp_dir.path());
+        let checks = detect_checks(temp_dir.path()).unwrap();
+        assert_eq!(checks.len(), detected.len());
+
+        let test = checks.iter().find(|c| c.name == "test").unwrap();
+        let detected_test = detected.iter().find(|c| c.name == "test").unwrap();
+        // Only the fields set in the file change
+        assert_eq!(test.program, "cargo");
+        assert_eq!(test.args, ["nextest", "run"]);
+        assert_eq!(test.timeout_secs, detected_test.timeout_secs);
+        assert_eq!(test.severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_checks_toml_disables_detected_check() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("go.mod"), "module example\n").unwrap();
+        write_checks_toml(
+            temp_dir.path(),
+            r#"
+[[check]]
+name = "test"
+enabled = false
+"#,
+        );
+
+        let checks = detect_checks(temp_dir.path()).unwrap();
+        let names: Vec<&str> = checks.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["build"]);
+    }
+
+    #[test]
+    fn test_checks_toml_errors_are_reported() {
+        let cases = [
+            ("[[check]\nname = \"x\"\n", "Failed to parse"),
+            (
+                "[[check]]\nname = \"build\"\ntimout = 5\n",
+                "unknown field `timout`",
+            ),
+            (
+                "[[check]]\nname = \"build\"\nseverity = \"fatal\"\n",
+                "unknown variant `fatal`",
+            ),
+            (
+                "[[check]]\nname = \"typecheck\"\n",
+                "check 'typecheck' is not auto-detected, so it needs a `program`",
+            ),
+            (
+                "[[check]]\nname = \"vet\"\nprogram = \"go\"\n\n[[check]]\nname = \"vet\"\nprogram = \"go\"\n",
+                "check 'vet' is defined more than once",
+            ),
+            (
+                "[[check]]\nname = \"vet\"\nprogram = \"\"\n",
+                "check 'vet' has an empty program",
+            ),
+            (
+                "[[check]]\nname = \"lint\"\nprogram = \"true\"\nworking_dir = \"/etc\"\n",
+                "check 'lint' has working_dir '/etc'",
+            ),
+            (
+                "[[check]]\nname = \"lint\"\nprogram = \"true\"\nworking_dir = \"sub/../../out\"\n",
+                "must be a relative path inside the workspace",
+            ),
+        ];
+
+        for (content, expected) in cases {
+            let temp_dir = TempDir::new().unwrap();
+            write_checks_toml(temp_dir.path(), content);
+
+            let err = detect_checks(temp_dir.path()).unwrap_err().to_string();
+            assert!(err.contains(expected), "{:?} gave {}", content, err);
+            assert!(run_all_checks(temp_dir.path()).is_err());
+        }
+    }
+
+    fn cargo_workspace(dir: &Path) {
+        std::fs::write(
+            dir.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+        for (path, name) in [("crates/a", "alpha"), ("crates/b", "beta")] {
+            std::fs::create_dir_all(dir.join(path)).unwrap();
+            std::fs::write(
+                dir.join(path).join("Cargo.toml"),
+                format!("[package]\nname = \"{}\"\n\n[dependencies]\n", name),
+            )
+            .unwrap();
+        }
+    }
+
+    fn test_args(checks: &[CheckCommand]) -> Vec<String> {
+        checks
+            .iter()
+            .find(|c| c.name == "test")
+            .unwrap()
+            .args
+            .clone()
+    }
+
+    #[test]
+    fn test_scoped_tests_cargo_packages() {
+        let temp_dir = TempDir::new().unwrap();
+        cargo_workspace(temp_dir.path());
+        let touched = vec![
+            "crates/b/src/lib.rs".to_string(),
+            "crates/a/src/deep/mod.rs".to_string(),
+        ];
+
+        // Opt-in only
+        let checks = detect_checks_for_changes(temp_dir.path(), &touched).unwrap();
+        assert_eq!(test_args(&checks), ["test", "--", "--nocapture"]);
+
+        write_checks_toml(temp_dir.path(), "scoped_tests = true\n");
+        let checks = detect_checks_for_changes(temp_dir.path(), &touched).unwrap();
+        assert_eq!(
+            test_args(&checks),
+            ["test", "-p", "alpha", "-p", "beta", "--", "--nocapture"]
+        );
+        // Only test checks are scoped
+        let lint = checks.iter().find(|c| c.name == "lint").unwrap();
+        assert_eq!(lint.args[0], "clippy");
+        assert_eq!(lint.args.len(), 4);
+
+        // A file outside any crate falls back to the full suite
+        let touched = vec!["crates/a/src/lib.rs".to_string(), "README.md".to_string()];
+        let checks = detect_checks_for_changes(temp_dir.path(), &touched).unwrap();
+        assert_eq!(test_args(&checks), ["test", "--", "--nocapture"]);
+
+        // Nothing touched: full suite
+        let checks = detect_checks_for_changes(temp_dir.path(), &[]).unwrap();
+        assert_eq!(test_args(&checks), ["test", "--", "--nocapture"]);
+    }
+
+    #[test]
+    fn test_rerun_of_failed_tests_is_unscoped() {
+        let temp_dir = TempDir::new().unwrap();
+        cargo_workspace(temp_dir.path());
+        write_checks_toml(temp_dir.path(), "scoped_tests = true\n");
+
+        // A test failure in one crate must not be "fixed" by an iteration
+        // that only touched another crate
+        let commands = rerun_commands(temp_dir.path(), &["test"]).unwrap();
+        assert_eq!(commands.len(), 1);
+        assert_eq!(test_args(&commands), ["test", "--", "--nocapture"]);
+    }
+
+    #[test]
+    fn test_scoped_tests_cargo_nextest_override() {
+        let temp_dir = TempDir::new().unwrap();
+        cargo_workspace(temp_dir.path());
+        write_checks_toml(
+            temp_dir.path(),
+            r#"
+scoped_tests = true
+
+[[check]]
+name = "test"
+args = ["nextest", "run", "--workspace"]
+"#,
+        );
+
+        let touched = vec!["crates/a/src/lib.rs".to_string()];
+        let checks = detect_checks_for_changes(temp_dir.path(), &touched).unwrap();
+        assert_eq!(test_args(&checks), ["nextest", "run", "-p", "alpha"]);
+    }
+
+    #[test]
+    fn test_scoped_tests_go_packages() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("go.mod"), "module example\n").unwrap();
+        write_checks_toml(temp_dir.path(), "scoped_tests = true\n");
+
+        let touched = vec!["pkg/api/handler.go".to_string(), "main.go".to_string()];
+        let checks = detect_checks_for_changes(temp_dir.path(), &touched).unwrap();
+        assert_eq!(test_args(&checks), ["test", ".", "./pkg/api"]);
+        assert_eq!(checks[0].args, ["build", "./..."]);
+
+        let touched = vec!["pkg/api/handler.go".to_string(), "go.mod".to_string()];
+        let checks = detect_checks_for_changes(temp_dir.path(), &touched).unwrap();
+        assert_eq!(test_args(&checks), ["test", "./..."]);
+    }
+
+    #[test]
+    fn test_container_wraps_checks() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".hox")).unwrap();
+        std::fs::write(
+            temp_dir.path().join(".hox/checks.toml"),
+            r#"
+[container]
+runtime = "podman"
+image = "rust:1.80"
+run_args = ["--network", "none"]
+
+[[check]]
+name = "build"
+program = "cargo"
+args = ["build"]
+breaking = true
+working_dir = "crates/core"
+"#,
+        )
+        .unwrap();
+
+        let checks = detect_checks(temp_dir.path()).unwrap();
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].name, "build");
+        assert_eq!(checks[0].program, "podman");
+        assert_eq!(checks[0].working_dir, None);
+        assert_eq!(
+            checks[0].args,
+            vec![
+                "run".to_string(),
+                "--rm".to_string(),
+                "-v".to_string(),
+                format!("{}:/workspace", temp_dir.path().display()),
+                "-w".to_string(),
+                "/workspace/crates/core".to_string(),
+                "--network".to_string(),
+                "none".to_string(),
+                "rust:1.80".to_string(),
+                "cargo".to_string(),
+                "build".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_container_wraps_auto_detected_checks() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".hox")).unwrap();
+        std::fs::write(temp_dir.path().join("go.mod"), "module example\n").unwrap();
+        std::fs::write(
+            temp_dir.path().join(".hox/checks.toml"),
+            "[container]\nimage = \"golang:1.22\"\n",
+        )
+        .unwrap();
+
+        let checks = detect_checks(temp_dir.path()).unwrap();
+        assert_eq!(checks.len(), 2);
+        for check in &checks {
+            assert_eq!(check.program, "docker");
+            assert_eq!(&check.args[4..6], ["-w", "/workspace"]);
+        }
+        assert_eq!(
+            &checks[0].args[6..],
+            ["golang:1.22", "go", "build", "./..."]
+        );
+    }
+
+    #[test]
+    fn test_container_maps_workspace_programs() {
+        let temp_dir = TempDir::new().unwrap();
+        let venv_bin = temp_dir.path().join(".venv").join("bin");
+        std::fs::create_dir_all(&venv_bin).unwrap();
+        std::fs::write(venv_bin.join("pytest"), "").unwrap();
+        let container = ContainerConfig {
+            runtime: "docker".into(),
+            image: "python:3.12".into(),
+            mount_path: "/workspace".into(),
+            run_args: vec![],
+        };
+        let wrap = |program: String| {
+            CheckCommand {
+                name: "test".into(),
+                program,
+                args: vec![],
+                timeout_secs: DEFAULT_TIMEOUT_SECS,
+                severity: Severity::Breaking,
+                working_dir: None,
+            }
+            .in_container(&container, temp_dir.path())
+            .args
+            .last()
+            .cloned()
+            .unwrap()
+        };
+
+        // The host venv is skipped in favor of the image's pytest
+        assert_eq!(wrap(python_tool(temp_dir.path(), "pytest")), "pytest");
+        assert_eq!(
+            wrap(temp_dir.path().join("scripts/check.sh").display().to_string()),
+            "/workspace/scripts/check.sh"
+        );
+        assert_eq!(wrap("/usr/bin/make".into()), "/usr/bin/make");
+    }
+
+    #[test]
+    fn test_container_runtime_missing_skips_check() {
+        let temp_dir = TempDir::new().unwrap();
+        let container = ContainerConfig {
+            runtime: "hox-missing-container-runtime".into(),
+            image: "rust:1.80".into(),
+            mount_path: "/workspace".into(),
+            run_args: vec![],
+        };
+        let check = CheckCommand {
+            name: "build".into(),
+            program: "cargo".into(),
+            args: vec!["build".into()],
+            timeout_secs: DEFAULT_TIMEOUT_SECS,
+            severity: Severity::Breaking,
+            working_dir: None,
+        }
+        .in_container(&container, temp_dir.path());
+
+        let result = run_checks(temp_dir.path(), &[check]).unwrap();
+        assert!(result.all_passed());
+        assert!(result.checks[0].output.contains("[SKIPPED]"));
+        assert!(result.checks[0]
+            .output
+            .contains("hox-missing-container-runtime"));
+    }
+
+    #[test]
+    fn test_cancel_kills_running_check() {
+        let temp_dir = TempDir::new().unwrap();
+        let check = CheckCommand {
+            name: "slow".into(),
+            program: "sleep".into(),
+            args: vec!["30".into()],
+            timeout_secs: 60,
+            severity: Severity::Breaking,
+            working_dir: None,
+        };
+        let cancel = std::sync::Arc::new(AtomicBool::new(false));
+
+        let trip = std::sync::Arc::clone(&cancel);
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(200));
+            trip.store(true, Ordering::SeqCst);
+        });
+
+        let start = Instant::now();
+        let result = run_checks_cancellable(temp_dir.path(), &[check], &cancel).unwrap();
+        canceller.join().unwrap();
+
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(!result.all_passed());
+        assert!(result.errors.is_empty());
+        assert_eq!(result.failed_check_names(), vec!["slow"]);
+        assert_eq!(result.checks[0].severity, Severity::Warning);
+        assert!(result.checks[0].output.starts_with("[CANCELLED]"));
+
+        // Once cancelled, further checks are not started at all
+        let result = run_checks_cancellable(
+            temp_dir.path(),
+            &[CheckCommand {
+                name: "never".into(),
+                program: "hox-missing-check-program".into(),
+                args: vec![],
+                timeout_secs: 60,
+                severity: Severity::Breaking,
+                working_dir: None,
+            }],
+            &cancel,
+        )
+        .unwrap();
+        assert!(result.checks[0].output.starts_with("[CANCELLED]"));
+    }
+
     #[test]
     fn test_extract_python_package_name() {
         let toml = r#"
@@ -1157,6 +2079,7 @@ line-length = 88
                 stdout: "Fixed 3 files".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -1173,6 +2096,7 @@ line-length = 88
                 stdout: "Fixed change abc123".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -1189,6 +2113,7 @@ line-length = 88
                 stdout: String::new(),
                 stderr: "jj fix not configured".to_string(),
                 success: false,
+                code: Some(1),
             },
         );
 
@@ -1205,15 +2130,17 @@ line-length = 88
                 stdout: "Fixed files".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
         let temp_dir = TempDir::new().unwrap();
 
         // Should run fix first, then standard checks
-        let result = run_all_checks_with_fix(temp_dir.path(), &executor, None)
-            .await
-            .unwrap();
+        let result =
+            run_all_checks_with_fix(temp_dir.path(), &executor, None, &[], &NEVER_CANCELLED)
+                .await
+                .unwrap();
 
         // Standard checks should pass (no project detected)
         assert!(result.all_passed());
@@ -1227,15 +2154,17 @@ line-length = 88
                 stdout: String::new(),
                 stderr: "fix failed".to_string(),
                 success: false,
+                code: Some(1),
             },
         );
 
         let temp_dir = TempDir::new().unwrap();
 
         // Should continue with standard checks even if fix fails
-        let result = run_all_checks_with_fix(temp_dir.path(), &executor, None)
-            .await
-            .unwrap();
+        let result =
+            run_all_checks_with_fix(temp_dir.path(), &executor, None, &[], &NEVER_CANCELLED)
+                .await
+                .unwrap();
 
         // Standard checks should still run and pass
         assert!(result.all_passed());
@@ -0,0 +1,205 @@
+//! Reuse of backpressure results across iterations with an unchanged tree
+//!
+//! An iteration in which the agent edits nothing leaves the change's content
+//! as it was, so re-running the checks would only reproduce the last result.
+//! The cache key fingerprints the change ID, its parents and its diff; any
+//! failure to compute it disables caching for that run.
+
+use hox_agent::BackpressureResult;
+use hox_core::Result;
+use hox_jj::JjExecutor;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use tracing::debug;
+
+/// Template identifying a change and the commits it sits on
+const IDENTITY_TEMPLATE: &str =
+    r#"change_id ++ "\n" ++ parents.map(|p| p.commit_id()).join(" ") ++ "\n""#;
+
+/// Last backpressure result, keyed by the tree fingerprint it was run on
+#[derive(Debug, Default)]
+pub(crate) struct BackpressureCache {
+    entry: Option<(u64, BackpressureResult)>,
+}
+
+impl BackpressureCache {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Result cached for `key`, if any
+    pub(crate) fn get(&self, key: Option<u64>) -> Option<&BackpressureResult> {
+        match (&self.entry, key) {
+            (Some((cached, result)), Some(key)) if *cached == key => Some(result),
+            _ => None,
+        }
+    }
+
+    /// Remember `result` for `key`; without a key nothing is cached
+    pub(crate) fn insert(&mut self, key: Option<u64>, result: &BackpressureResult) {
+        self.entry = key.map(|key| (key, result.clone()));
+    }
+
+    /// Forget the cached result
+    pub(crate) fn invalidate(&mut self) {
+        self.entry = None;
+    }
+
+    /// Return the cached result if the tree is unchanged, otherwise `run` the checks
+    ///
+    /// `files_changed` reports whether this iteration wrote any files; if so
+    /// the cache is invalidated regardless of the fingerprint.
+    pub(crate) async fn get_or_run<E: JjExecutor>(
+        &mut self,
+        executor: &E,
+        change_id: &str,
+        files_changed: bool,
+        run: impl FnOnce() -> Result<BackpressureResult>,
+    ) -> Result<BackpressureResult> {
+        if files_changed {
+            self.invalidate();
+        }
+
+        let key = tree_fingerprint(executor, change_id).await;
+        if let Some(result) = self.get(key) {
+            debug!(
+                "Tree of {} unchanged, reusing backpressure result",
+                change_id
+            );
+            return Ok(result.clone());
+        }
+
+        let result = run()?;
+        self.insert(key, &result);
+        Ok(result)
+    }
+}
+
+/// Fingerprint of a change's content, or `None` if jj cannot provide it
+pub(crate) async fn tree_fingerprint<E: JjExecutor>(executor: &E, change_id: &str) -> Option<u64> {
+    let identity = executor
+        .exec(&[
+            "log",
+            "-r",
+            change_id,
+            "--no-graph",
+            "-T",
+            IDENTITY_TEMPLATE,
+        ])
+        .await
+        .ok()
+        .filter(|output| output.success)?;
+    let diff = executor
+        .exec(&["diff", "-r", change_id, "--git"])
+        .await
+        .ok()
+        .filter(|output| output.success)?;
+
+    let mut hasher = DefaultHasher::new();
+    identity.stdout.hash(&mut hasher);
+    diff.stdout.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hox_jj::{JjOutput, MockJjExecutor};
+    use std::cell::Cell;
+
+    fn output(stdout: &str) -> JjOutput {
+        JjOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            success: true,
+            code: Some(0),
+        }
+    }
+
+    fn log_key() -> String {
+        format!("log -r abc --no-graph -T {}", IDENTITY_TEMPLATE)
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_tree_skips_checks_until_edit() {
+        let executor = MockJjExecutor::new()
+            .with_response(&log_key(), output("abc\nparent1\n"))
+            .with_responses(
+                "diff -r abc --git",
+                vec![
+                    output("+fn a() {}\n"),
+                    output("+fn a() {}\n"),
+                    output("+fn a() {}\n+fn b() {}\n"),
+                ],
+            );
+        let mut cache = BackpressureCache::new();
+        let runs = Cell::new(0);
+        let run = || {
+            runs.set(runs.get() + 1);
+            Ok(BackpressureResult::all_pass())
+        };
+
+        cache
+            .get_or_run(&executor, "abc", false, run)
+            .await
+            .unwrap();
+        assert_eq!(runs.get(), 1);
+
+        // No edits: the tree is unchanged and the checks are skipped
+        cache
+            .get_or_run(&executor, "abc", false, run)
+            .await
+            .unwrap();
+        assert_eq!(runs.get(), 1);
+
+        // After an edit the diff differs and the fingerprint alone re-runs the checks
+        cache
+            .get_or_run(&executor, "abc", false, run)
+            .await
+            .unwrap();
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_file_changes_invalidate_even_with_same_fingerprint() {
+        let executor = MockJjExecutor::new()
+            .with_response(&log_key(), output("abc\nparent1\n"))
+            .with_response("diff -r abc --git", output("+fn a() {}\n"));
+        let mut cache = BackpressureCache::new();
+        let runs = Cell::new(0);
+        let run = || {
+            runs.set(runs.get() + 1);
+            Ok(BackpressureResult::all_pass())
+        };
+
+        cache
+            .get_or_run(&executor, "abc", false, run)
+            .await
+            .unwrap();
+        cache.get_or_run(&executor, "abc", true, run).await.unwrap();
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_no_caching_without_fingerprint() {
+        // No mock responses: jj fails, so every call runs the checks
+        let executor = MockJjExecutor::new();
+        let mut cache = BackpressureCache::new();
+        let runs = Cell::new(0);
+        let run = || {
+            runs.set(runs.get() + 1);
+            Ok(BackpressureResult::all_pass())
+        };
+
+        cache
+            .get_or_run(&executor, "abc", false, run)
+            .await
+            .unwrap();
+        cache
+            .get_or_run(&executor, "abc", false, run)
+            .await
+            .unwrap();
+        assert_eq!(runs.get(), 2);
+        assert!(cache.get(None).is_none());
+    }
+}
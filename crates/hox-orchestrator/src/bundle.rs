@@ -0,0 +1,381 @@
+//! Run bundles - portable archives of an orchestration run
+//!
+//! A bundle is a tar archive of the `.hox` state needed to audit or reproduce
+//! a run, plus a snapshot of the Hox metadata on every task change.
+
+use crate::activity_logger::{is_archive_name, ACTIVITY_JSONL_FILE};
+use hox_core::{ChangeId, HoxError, HoxMetadata, Result};
+use hox_jj::{JjExecutor, MetadataManager, RevsetQueries};
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+use tokio::process::Command;
+use tracing::{debug, info};
+
+/// `.hox` entries packaged into a bundle when present
+const BUNDLE_ENTRIES: &[&str] = &[
+    "config.toml",
+    "config.json",
+    "patterns",
+    "metrics",
+    "activity.md",
+    ACTIVITY_JSONL_FILE,
+    "decomposition.json",
+    "prd.json",
+];
+
+/// Bundle entries whose rotated archives are packaged alongside them
+const ROTATED_ENTRIES: &[&str] = &["activity.md", ACTIVITY_JSONL_FILE];
+
+/// Whether a top-level `.hox` name belongs in a bundle
+fn is_bundle_entry(name: &str) -> bool {
+    BUNDLE_ENTRIES.contains(&name)
+        || ROTATED_ENTRIES
+            .iter()
+            .any(|log_name| is_archive_name(name, log_name))
+}
+
+/// File name of the metadata snapshot inside a bundle
+pub const METADATA_SNAPSHOT_FILE: &str = "metadata-snapshot.json";
+
+/// Hox metadata of one task change at export time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataSnapshotEntry {
+    pub change_id: ChangeId,
+    pub metadata: HoxMetadata,
+}
+
+/// Snapshot the Hox metadata of all bookmarked task changes
+pub async fn snapshot_metadata<E: JjExecutor + Clone>(
+    executor: E,
+) -> Result<Vec<MetadataSnapshotEntry>> {
+    let change_ids = RevsetQueries::new(executor.clone())
+        .all_tasks_by_bookmark()
+        .await?;
+    let mut metadata = MetadataManager::new(executor)
+        .read_many(&change_ids)
+        .await?;
+
+    Ok(change_ids
+        .into_iter()
+        .filter_map(|change_id| {
+            metadata
+                .remove(&change_id)
+                .map(|metadata| MetadataSnapshotEntry {
+                    change_id,
+                    metadata,
+                })
+        })
+        .collect())
+}
+
+/// Package a `.hox` directory and a metadata snapshot into a tar archive
+///
+/// Returns the top-level bundle entries that were written.
+pub async fn export_bundle(
+    hox_dir: &Path,
+    snapshot: &[MetadataSnapshotEntry],
+    bundle: &Path,
+) -> Result<Vec<String>> {
+    let staging = std::env::temp_dir().join(format!("hox-export-{}", uuid::Uuid::new_v4()));
+    tokio::fs::create_dir_all(&staging).await?;
+    tokio::fs::write(
+        staging.join(METADATA_SNAPSHOT_FILE),
+        serde_json::to_string_pretty(snapshot)?,
+    )
+    .await?;
+
+    let mut entries: Vec<String> = BUNDLE_ENTRIES
+        .iter()
+        .filter(|entry| hox_dir.join(entry).exists())
+        .map(|entry| entry.to_string())
+        .collect();
+    let mut archives: Vec<String> = std::fs::read_dir(hox_dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| !BUNDLE_ENTRIES.contains(&name.as_str()) && is_bundle_entry(name))
+        .collect();
+    archives.sort();
+    entries.extend(archives);
+    debug!("Bundling {:?} from {}", entries, hox_dir.display());
+
+    let mut command = Command::new("tar");
+    command.arg("-cf").arg(bundle).arg("-C").arg(hox_dir);
+    command.args(&entries);
+    command.arg("-C").arg(&staging).arg(METADATA_SNAPSHOT_FILE);
+
+    let output = command.output().await;
+    let _ = tokio::fs::remove_dir_all(&staging).await;
+    let output = output.map_err(|e| HoxError::Io(format!("Failed to run tar: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(HoxError::Io(format!(
+            "Failed to create bundle: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+
+    entries.push(METADATA_SNAPSHOT_FILE.to_string());
+    info!("Exported {} entries to {}", entries.len(), bundle.display());
+    Ok(entries)
+}
+
+/// Restore a bundle into a `.hox` directory
+///
+/// Existing files with the same names are overwritten. The metadata snapshot
+/// is restored as a file for auditing; it is not applied to any changes.
+/// Returns the paths of all restored archive members.
+///
+/// Bundles are untrusted input: every member must be a relative path under
+/// one of the bundle entries, and only regular files and directories are
+/// restored. The archive is extracted into a private staging directory and
+/// checked there before anything is copied into `hox_dir`.
+pub async fn import_bundle(bundle: &Path, hox_dir: &Path) -> Result<Vec<String>> {
+    let listing = run_tar(&["-tf".as_ref(), bundle.as_os_str()], "read bundle").await?;
+    let members: Vec<String> = listing
+        .lines()
+        .map(|line| line.trim_start_matches("./").to_string())
+        .filter(|line| !line.is_empty())
+        .collect();
+    for member in &members {
+        validate_member(member)?;
+    }
+
+    let staging = std::env::temp_dir().join(format!("hox-import-{}", uuid::Uuid::new_v4()));
+    tokio::fs::create_dir(&staging).await?;
+    let restored = extract_checked(bundle, &staging, hox_dir).await;
+    let _ = tokio::fs::remove_dir_all(&staging).await;
+    restored?;
+
+    info!(
+        "Imported {} files into {}",
+        members.len(),
+        hox_dir.display()
+    );
+    Ok(members)
+}
+
+/// Reject bundle members that could land outside the `.hox` directory
+fn validate_member(member: &str) -> Result<()> {
+    let path = Path::new(member);
+    let mut components = path.components();
+    let top = match components.next() {
+        Some(Component::Normal(top)) => top.to_string_lossy(),
+        _ => {
+            return Err(HoxError::PathValidation(format!(
+                "Bundle member '{}' is not a relative path",
+                member
+            )))
+        }
+    };
+    if !components.all(|c| matches!(c, Component::Normal(_))) {
+        return Err(HoxError::PathValidation(format!(
+            "Bundle member '{}' escapes the bundle root",
+            member
+        )));
+    }
+    if top != METADATA_SNAPSHOT_FILE && !is_bundle_entry(&top) {
+        return Err(HoxError::PathValidation(format!(
+            "Bundle member '{}' is not a bundle entry",
+            member
+        )));
+    }
+    Ok(())
+}
+
+/// Extract a bundle into `staging`, then copy it into `hox_dir`
+async fn extract_checked(bundle: &Path, staging: &Path, hox_dir: &Path) -> Result<()> {
+    run_tar(
+        &[
+            "-xf".as_ref(),
+            bundle.as_os_str(),
+            "-C".as_ref(),
+            staging.as_os_str(),
+        ],
+        "extract bundle",
+    )
+    .await?;
+
+    let mut files = Vec::new();
+    collect_regular_files(staging, staging, &mut files)?;
+
+    tokio::fs::create_dir_all(hox_dir).await?;
+    for relative in files {
+        let target = hox_dir.join(&relative);
+        if let Some(parent) = target.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::copy(staging.join(&relative), &target).await?;
+    }
+    Ok(())
+}
+
+/// Collect the relative paths of all regular files under `dir`
+///
+/// Fails on symlinks, hard links and special files.
+fn collect_regular_files(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let metadata = std::fs::symlink_metadata(&path)?;
+        let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+        if metadata.is_dir() {
+            collect_regular_files(root, &path, files)?;
+        } else if metadata.is_file() && !is_hard_link(&metadata) {
+            files.push(relative);
+        } else {
+            return Err(HoxError::PathValidation(format!(
+                "Bundle member '{}' is not a regular file",
+                relative.display()
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn is_hard_link(metadata: &std::fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    metadata.nlink() > 1
+}
+
+#[cfg(not(unix))]
+fn is_hard_link(_metadata: &std::fs::Metadata) -> bool {
+    false
+}
+
+/// Run tar and return its stdout
+async fn run_tar(args: &[&std::ffi::OsStr], action: &str) -> Result<String> {
+    let output = Command::new("tar")
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| HoxError::Io(format!("Failed to run tar: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(HoxError::Io(format!(
+            "Failed to {}: {}",
+            action,
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hox_core::TaskStatus;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_export_import_round_trip() {
+        let source = TempDir::new().unwrap();
+        let hox_dir = source.path().join(".hox");
+        std::fs::create_dir_all(hox_dir.join("patterns")).unwrap();
+        std::fs::create_dir_all(hox_dir.join("metrics")).unwrap();
+        std::fs::write(hox_dir.join("config.json"), r#"{"version":"0.1.0"}"#).unwrap();
+        std::fs::write(hox_dir.join("patterns/retry.json"), r#"{"name":"retry"}"#).unwrap();
+        std::fs::write(hox_dir.join("metrics/run.jsonl"), "{\"tokens\":10}\n").unwrap();
+        std::fs::write(hox_dir.join("activity.md"), "# Hox Activity Log\n").unwrap();
+        std::fs::write(hox_dir.join("activity.jsonl"), "{\"event\":\"new\"}\n").unwrap();
+        std::fs::write(
+            hox_dir.join("activity.20260101T000000.000000Z.jsonl"),
+            "{\"event\":\"old\"}\n",
+        )
+        .unwrap();
+        std::fs::write(hox_dir.join("decomposition.json"), r#"{"phases":[]}"#).unwrap();
+        // Not part of a bundle
+        std::fs::write(hox_dir.join("scratch.txt"), "ignored").unwrap();
+
+        let snapshot = vec![MetadataSnapshotEntry {
+            change_id: "abc123".to_string(),
+            metadata: HoxMetadata::new().with_status(TaskStatus::Done),
+        }];
+        let bundle = source.path().join("run.tar");
+
+        let exported = export_bundle(&hox_dir, &snapshot, &bundle).await.unwrap();
+        assert_eq!(
+            exported,
+            vec![
+                "config.json",
+                "patterns",
+                "metrics",
+                "activity.md",
+                "activity.jsonl",
+                "decomposition.json",
+                "activity.20260101T000000.000000Z.jsonl",
+                METADATA_SNAPSHOT_FILE,
+            ]
+        );
+
+        let target = TempDir::new().unwrap();
+        let restored_dir = target.path().join(".hox");
+        let members = import_bundle(&bundle, &restored_dir).await.unwrap();
+        assert!(members.contains(&"patterns/retry.json".to_string()));
+
+        for file in [
+            "config.json",
+            "patterns/retry.json",
+            "metrics/run.jsonl",
+            "activity.md",
+            "activity.jsonl",
+            "activity.20260101T000000.000000Z.jsonl",
+            "decomposition.json",
+        ] {
+            assert_eq!(
+                std::fs::read_to_string(restored_dir.join(file)).unwrap(),
+                std::fs::read_to_string(hox_dir.join(file)).unwrap(),
+                "{} did not round-trip",
+                file
+            );
+        }
+        assert!(!restored_dir.join("scratch.txt").exists());
+
+        let restored: Vec<MetadataSnapshotEntry> = serde_json::from_str(
+            &std::fs::read_to_string(restored_dir.join(METADATA_SNAPSHOT_FILE)).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].change_id, "abc123");
+        assert_eq!(restored[0].metadata.status, Some(TaskStatus::Done));
+    }
+
+    #[test]
+    fn test_validate_member() {
+        assert!(validate_member("patterns/retry.json").is_ok());
+        assert!(validate_member("patterns/").is_ok());
+        assert!(validate_member(METADATA_SNAPSHOT_FILE).is_ok());
+        assert!(validate_member("activity.jsonl").is_ok());
+        assert!(validate_member("activity.20260101T000000.000000Z.jsonl").is_ok());
+        assert!(validate_member("activity.20260101T000000.000000Z.md").is_ok());
+        assert!(validate_member("activity.backup.jsonl").is_err());
+        assert!(validate_member("/etc/passwd").is_err());
+        assert!(validate_member("../outside.json").is_err());
+        assert!(validate_member("patterns/../../outside.json").is_err());
+        assert!(validate_member("scratch.txt").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_symlink_member() {
+        let source = TempDir::new().unwrap();
+        let content = source.path().join("content");
+        std::fs::create_dir_all(content.join("patterns")).unwrap();
+        std::os::unix::fs::symlink("/etc", content.join("patterns/escape")).unwrap();
+        let bundle = source.path().join("evil.tar");
+        let status = std::process::Command::new("tar")
+            .arg("-cf")
+            .arg(&bundle)
+            .arg("-C")
+            .arg(&content)
+            .arg("patterns")
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let target = TempDir::new().unwrap();
+        let restored_dir = target.path().join(".hox");
+        let err = import_bundle(&bundle, &restored_dir).await.unwrap_err();
+        assert!(matches!(err, HoxError::PathValidation(_)), "{}", err);
+        assert!(!restored_dir.join("patterns/escape").exists());
+    }
+}
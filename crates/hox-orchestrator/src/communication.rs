@@ -1,8 +1,25 @@
 //! Communication protocol for orchestrators and agents
-
-use hox_core::{ChangeId, MessageType, OrchestratorId};
-use std::collections::HashMap;
-use tracing::debug;
+//!
+//! Messages sent with [`MessageRouter::send_with_ack`] carry an ack ID. The
+//! receiving agent confirms it consumed the message by adding a
+//! `Msg-Ack: <ack-id>` trailer to its change's description
+//! ([`Message::ack_trailer`]); the returned [`AckHandle`] polls jj for that
+//! trailer until it appears or the timeout expires.
+
+use chrono::{DateTime, Utc};
+use hox_core::{ChangeId, HoxError, MessageType, OrchestratorId, Result};
+use hox_jj::{JjExecutor, RevsetBuilder};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{debug, warn};
+
+/// Trailer key an agent adds to its change to acknowledge a message
+pub const ACK_TRAILER: &str = "Msg-Ack";
+
+/// Default interval between checks for an acknowledgement
+const DEFAULT_ACK_POLL_INTERVAL: Duration = Duration::from_millis(500);
 
 /// A message in the Hox communication protocol
 #[derive(Debug, Clone)]
@@ -17,6 +34,10 @@ pub struct Message {
     pub content: String,
     /// Change ID where this message is stored
     pub change_id: Option<ChangeId>,
+    /// ID the target acknowledges, set when sent with [`MessageRouter::send_with_ack`]
+    pub ack_id: Option<String>,
+    /// Position in the router's history, assigned when routed
+    pub sequence: Option<u64>,
 }
 
 impl Message {
@@ -27,6 +48,8 @@ impl Message {
             msg_type,
             content: String::new(),
             change_id: None,
+            ack_id: None,
+            sequence: None,
         }
     }
 
@@ -63,6 +86,15 @@ impl Message {
         Self::new(from, to, MessageType::Info).with_content(content)
     }
 
+    /// Trailer the target adds to its change description to acknowledge this message
+    ///
+    /// `None` if the message was not sent with an acknowledgement request.
+    pub fn ack_trailer(&self) -> Option<String> {
+        self.ack_id
+            .as_ref()
+            .map(|id| format!("{}: {}", ACK_TRAILER, id))
+    }
+
     /// Check if this message matches a target pattern
     pub fn matches_target(&self, target: &str) -> bool {
         if self.to == "*" {
@@ -83,12 +115,180 @@ impl Message {
     }
 }
 
+/// Selects messages in [`MessageRouter::messages_for`]
+///
+/// Every criterion left unset matches all messages.
+///
+/// ```
+/// use hox_core::MessageType;
+/// use hox_orchestrator::MessageFilter;
+///
+/// let filter = MessageFilter::new()
+///     .of_type(MessageType::Mutation)
+///     .from("O-A-1")
+///     .unread();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageFilter {
+    /// Only messages of this type
+    pub msg_type: Option<MessageType>,
+    /// Only messages from this sender
+    pub from: Option<String>,
+    /// Only messages the reader has not marked read
+    pub unread_only: bool,
+}
+
+impl MessageFilter {
+    /// A filter matching every message
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match messages of `msg_type`
+    pub fn of_type(mut self, msg_type: MessageType) -> Self {
+        self.msg_type = Some(msg_type);
+        self
+    }
+
+    /// Only match messages sent by `sender`
+    pub fn from(mut self, sender: impl Into<String>) -> Self {
+        self.from = Some(sender.into());
+        self
+    }
+
+    /// Only match messages not yet marked read
+    pub fn unread(mut self) -> Self {
+        self.unread_only = true;
+        self
+    }
+
+    fn matches(&self, message: &Message, read: bool) -> bool {
+        self.msg_type.is_none_or(|t| t == message.msg_type)
+            && self.from.as_ref().is_none_or(|from| *from == message.from)
+            && !(self.unread_only && read)
+    }
+}
+
+/// A message awaiting acknowledgement from its target
+#[derive(Debug, Clone)]
+pub struct PendingAck {
+    /// ID the target must acknowledge
+    pub ack_id: String,
+    /// Target of the message
+    pub to: String,
+    /// Message type
+    pub msg_type: MessageType,
+    /// When the message was sent
+    pub sent_at: DateTime<Utc>,
+}
+
+type AckRegistry = Arc<Mutex<HashMap<String, PendingAck>>>;
+
+/// Resolves once the target of a message acknowledges it
+///
+/// The message stays in [`MessageRouter::pending_acks`] until the handle is
+/// waited on to completion or dropped.
+pub struct AckHandle {
+    ack_id: String,
+    to: String,
+    timeout: Duration,
+    poll_interval: Duration,
+    acks: AckRegistry,
+}
+
+impl AckHandle {
+    /// ID the target must acknowledge
+    pub fn ack_id(&self) -> &str {
+        &self.ack_id
+    }
+
+    /// Set how often jj is queried for the acknowledgement
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Wait for the acknowledgement, returning the change that carries it
+    ///
+    /// `executor` is queried for the acknowledgement trailer every poll
+    /// interval. Fails with [`HoxError::MessageRouting`] if none appears
+    /// within the timeout.
+    pub async fn wait<E: JjExecutor>(self, executor: &E) -> Result<ChangeId> {
+        let revset = RevsetBuilder::description_glob(ACK_TRAILER, &self.ack_id)?.build();
+        let deadline = Instant::now() + self.timeout;
+
+        loop {
+            if let Some(change_id) = self.find_ack(executor, &revset).await {
+                debug!("Message {} acknowledged by {}", self.ack_id, change_id);
+                return Ok(change_id);
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                warn!(
+                    "Message {} to {} not acknowledged within {:?}",
+                    self.ack_id, self.to, self.timeout
+                );
+                return Err(HoxError::MessageRouting(format!(
+                    "Message {} to {} was not acknowledged within {:?}",
+                    self.ack_id, self.to, self.timeout
+                )));
+            }
+            tokio::time::sleep(self.poll_interval.min(deadline - now)).await;
+        }
+    }
+
+    /// Change carrying the acknowledgement trailer, if any
+    async fn find_ack<E: JjExecutor>(&self, executor: &E, revset: &str) -> Option<ChangeId> {
+        let output = match executor
+            .exec(&[
+                "log",
+                "-r",
+                revset,
+                "--no-graph",
+                "-T",
+                "change_id ++ \"\\n\"",
+            ])
+            .await
+        {
+            Ok(output) if output.success => output,
+            Ok(output) => {
+                debug!("Ack query for {} failed: {}", self.ack_id, output.stderr);
+                return None;
+            }
+            Err(e) => {
+                debug!("Ack query for {} failed: {}", self.ack_id, e);
+                return None;
+            }
+        };
+
+        output
+            .stdout
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(str::to_string)
+    }
+}
+
+impl Drop for AckHandle {
+    fn drop(&mut self) {
+        if let Ok(mut acks) = self.acks.lock() {
+            acks.remove(&self.ack_id);
+        }
+    }
+}
+
 /// Routes messages between orchestrators and agents
 pub struct MessageRouter {
     /// Pending messages indexed by target
     pending: HashMap<String, Vec<Message>>,
     /// Message history
     history: Vec<Message>,
+    /// Messages awaiting acknowledgement, by ack ID
+    acks: AckRegistry,
+    /// Sequence numbers of the messages each reader has marked read
+    read: HashMap<String, HashSet<u64>>,
 }
 
 impl MessageRouter {
@@ -96,11 +296,59 @@ impl MessageRouter {
         Self {
             pending: HashMap::new(),
             history: Vec::new(),
+            acks: Arc::new(Mutex::new(HashMap::new())),
+            read: HashMap::new(),
+        }
+    }
+
+    /// Route a message and return a handle that resolves when its target acknowledges it
+    ///
+    /// The message is assigned a fresh ack ID; the target acknowledges it by
+    /// adding [`Message::ack_trailer`] to its change description. The handle
+    /// fails if that does not happen within `timeout`.
+    pub fn send_with_ack(&mut self, mut message: Message, timeout: Duration) -> AckHandle {
+        let ack_id = uuid::Uuid::new_v4().to_string();
+        message.ack_id = Some(ack_id.clone());
+
+        let pending = PendingAck {
+            ack_id: ack_id.clone(),
+            to: message.to.clone(),
+            msg_type: message.msg_type,
+            sent_at: Utc::now(),
+        };
+        self.acks
+            .lock()
+            .expect("ack registry lock poisoned")
+            .insert(ack_id.clone(), pending);
+
+        let to = message.to.clone();
+        self.route(message);
+
+        AckHandle {
+            ack_id,
+            to,
+            timeout,
+            poll_interval: DEFAULT_ACK_POLL_INTERVAL,
+            acks: Arc::clone(&self.acks),
         }
     }
 
+    /// Messages still awaiting acknowledgement, oldest first
+    pub fn pending_acks(&self) -> Vec<PendingAck> {
+        let mut acks: Vec<PendingAck> = self
+            .acks
+            .lock()
+            .expect("ack registry lock poisoned")
+            .values()
+            .cloned()
+            .collect();
+        acks.sort_by_key(|ack| ack.sent_at);
+        acks
+    }
+
     /// Route a message to its target
-    pub fn route(&mut self, message: Message) {
+    pub fn route(&mut self, mut message: Message) {
+        message.sequence = Some(self.history.len() as u64);
         debug!(
             "Routing message from {} to {}: {}",
             message.from, message.to, message.msg_type
@@ -169,6 +417,39 @@ impl MessageRouter {
         false
     }
 
+    /// Messages targeted at `agent` that match `filter`, oldest first
+    ///
+    /// Unlike [`get_pending`](Self::get_pending) this does not consume
+    /// anything: messages stay unread until [`mark_read`](Self::mark_read).
+    pub fn messages_for(&self, agent: &str, filter: &MessageFilter) -> Vec<Message> {
+        let read = self.read.get(agent);
+        self.history
+            .iter()
+            .filter(|m| m.matches_target(agent))
+            .filter(|m| {
+                let is_read = m
+                    .sequence
+                    .is_some_and(|seq| read.is_some_and(|read| read.contains(&seq)));
+                filter.matches(m, is_read)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Mark a routed message as read by `agent`
+    ///
+    /// Returns `false` if the message was never routed through this router.
+    pub fn mark_read(&mut self, agent: &str, message: &Message) -> bool {
+        let Some(seq) = message.sequence else {
+            return false;
+        };
+        if seq as usize >= self.history.len() {
+            return false;
+        }
+        self.read.entry(agent.to_string()).or_default().insert(seq);
+        true
+    }
+
     /// Get message history
     pub fn history(&self) -> &[Message] {
         &self.history
@@ -200,6 +481,23 @@ impl Default for MessageRouter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use hox_jj::{JjOutput, MockJjExecutor};
+
+    fn output(stdout: &str) -> JjOutput {
+        JjOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            success: true,
+            code: Some(0),
+        }
+    }
+
+    fn ack_query(ack_id: &str) -> String {
+        format!(
+            "log -r description(glob:\"Msg-Ack: {}\") --no-graph -T change_id ++ \"\\n\"",
+            ack_id
+        )
+    }
 
     #[test]
     fn test_message_wildcard_matching() {
@@ -237,4 +535,135 @@ mod tests {
         let messages = router.get_pending("O-B-1");
         assert_eq!(messages.len(), 1);
     }
+
+    fn agent_mutation() -> Message {
+        let orchestrator = OrchestratorId::new('A', 1);
+        let mut message = Message::mutation(&orchestrator, "Rename id to user_id");
+        message.to = "agent-1".to_string();
+        message
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_send_with_ack_resolves_on_ack() {
+        let mut router = MessageRouter::new();
+        let handle = router.send_with_ack(agent_mutation(), Duration::from_secs(5));
+        let ack_id = handle.ack_id().to_string();
+
+        let pending = router.pending_acks();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].ack_id, ack_id);
+        assert_eq!(pending[0].to, "agent-1");
+        assert_eq!(
+            router.history()[0].ack_trailer(),
+            Some(format!("Msg-Ack: {}", ack_id))
+        );
+
+        // The agent acknowledges on the third poll
+        let executor = MockJjExecutor::new().with_responses(
+            &ack_query(&ack_id),
+            vec![output(""), output(""), output("zzzacked\n")],
+        );
+        let change_id = handle.wait(&executor).await.unwrap();
+
+        assert_eq!(change_id, "zzzacked");
+        assert_eq!(executor.call_count(&ack_query(&ack_id)), 3);
+        assert!(router.pending_acks().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_send_with_ack_times_out() {
+        let mut router = MessageRouter::new();
+        let handle = router
+            .send_with_ack(agent_mutation(), Duration::from_secs(2))
+            .with_poll_interval(Duration::from_millis(500));
+        let query = ack_query(handle.ack_id());
+        let executor = MockJjExecutor::new().with_response(&query, output(""));
+
+        let err = handle.wait(&executor).await.unwrap_err();
+
+        assert!(err.to_string().contains("not acknowledged"), "{}", err);
+        // Polls at 0, 0.5, 1, 1.5 and 2 seconds
+        assert_eq!(executor.call_count(&query), 5);
+        assert!(router.pending_acks().is_empty());
+    }
+
+    #[test]
+    fn test_route_without_ack_has_no_trailer() {
+        let mut router = MessageRouter::new();
+        router.route(agent_mutation());
+
+        assert!(router.history()[0].ack_trailer().is_none());
+        assert!(router.pending_acks().is_empty());
+    }
+
+    /// Router holding a mutation and an info message from O-A-1 and an
+    /// alignment request from agent-2, all addressed to agent-1
+    fn router_with_mixed_messages() -> MessageRouter {
+        let mut router = MessageRouter::new();
+        router.route(agent_mutation());
+        router.route(Message::info("O-A-1", "agent-1", "FYI"));
+        router.route(Message::new(
+            "agent-2",
+            "agent-1",
+            MessageType::AlignRequest,
+        ));
+        router.route(Message::info("O-A-1", "agent-3", "Not for agent-1"));
+        router
+    }
+
+    #[test]
+    fn test_messages_for_filters_by_type() {
+        let router = router_with_mixed_messages();
+
+        let all = router.messages_for("agent-1", &MessageFilter::new());
+        assert_eq!(all.len(), 3);
+
+        let mutations = router.messages_for(
+            "agent-1",
+            &MessageFilter::new().of_type(MessageType::Mutation),
+        );
+        assert_eq!(mutations.len(), 1);
+        assert_eq!(mutations[0].content, "Rename id to user_id");
+    }
+
+    #[test]
+    fn test_messages_for_filters_by_sender() {
+        let router = router_with_mixed_messages();
+
+        let from_orchestrator = router.messages_for("agent-1", &MessageFilter::new().from("O-A-1"));
+        assert_eq!(from_orchestrator.len(), 2);
+
+        let from_peer = router.messages_for(
+            "agent-1",
+            &MessageFilter::new()
+                .from("agent-2")
+                .of_type(MessageType::AlignRequest),
+        );
+        assert_eq!(from_peer.len(), 1);
+        assert_eq!(from_peer[0].from, "agent-2");
+    }
+
+    #[test]
+    fn test_messages_for_unread_until_marked() {
+        let mut router = router_with_mixed_messages();
+        let unread = MessageFilter::new().unread();
+
+        // Reading does not consume
+        let first = router.messages_for("agent-1", &unread);
+        assert_eq!(first.len(), 3);
+        assert_eq!(router.messages_for("agent-1", &unread).len(), 3);
+        assert!(router.has_pending("agent-1"));
+
+        assert!(router.mark_read("agent-1", &first[0]));
+        let remaining = router.messages_for("agent-1", &unread);
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.iter().all(|m| m.sequence != first[0].sequence));
+
+        // Read status is per reader and doesn't hide read messages without the filter
+        assert_eq!(
+            router.messages_for("agent-1", &MessageFilter::new()).len(),
+            3
+        );
+        assert!(!router.mark_read("agent-1", &agent_mutation()));
+    }
 }
@@ -5,6 +5,9 @@
 //! - Pick-side resolution (ours/theirs)
 //! - Agent-based semantic resolution (future)
 //! - Human review escalation
+//!
+//! Conflicted file content is parsed into per-side hunks for both of jj's
+//! marker styles (`ui.conflict-marker-style = "diff"` and `"snapshot"`).
 
 use hox_core::{HoxError, Result};
 use hox_jj::{JjExecutor, RevsetQueries};
@@ -40,6 +43,197 @@ pub struct ConflictInfo {
     pub is_formatting_only: bool,
 }
 
+/// How jj materializes conflicts in working-copy files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictMarkerStyle {
+    /// `%%%%%%%` diff from a base to one side plus `+++++++` snapshots (jj default)
+    #[default]
+    Diff,
+    /// `+++++++` side and `-------` base snapshots only
+    Snapshot,
+}
+
+impl ConflictMarkerStyle {
+    /// Value for jj's `ui.conflict-marker-style` setting
+    pub fn as_config_value(&self) -> &'static str {
+        match self {
+            Self::Diff => "diff",
+            Self::Snapshot => "snapshot",
+        }
+    }
+
+    /// Detect the marker style used in conflicted file content
+    ///
+    /// Returns `None` when the content has no markers in a supported style
+    /// (for example git-style `=======` markers).
+    pub fn detect(content: &str) -> Option<Self> {
+        let mut style = None;
+        for hunk in marker_sections(content) {
+            for (marker, _, _) in &hunk {
+                match marker {
+                    '%' => return Some(Self::Diff),
+                    '-' => style = Some(Self::Snapshot),
+                    _ => {}
+                }
+            }
+        }
+        style
+    }
+}
+
+/// One conflicted region of a file, with the full content of every term
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConflictHunk {
+    /// File the hunk belongs to (empty when parsed from bare content)
+    pub path: String,
+    /// Side contents in jj's order (side #1 first)
+    pub sides: Vec<String>,
+    /// Base contents in jj's order (one fewer than sides)
+    pub bases: Vec<String>,
+}
+
+impl ConflictHunk {
+    /// Content of side #1 ("ours")
+    pub fn left(&self) -> Option<&str> {
+        self.sides.first().map(String::as_str)
+    }
+
+    /// Content of side #2 ("theirs")
+    pub fn right(&self) -> Option<&str> {
+        self.sides.get(1).map(String::as_str)
+    }
+
+    /// Content of the first base
+    pub fn base(&self) -> Option<&str> {
+        self.bases.first().map(String::as_str)
+    }
+}
+
+/// Parse conflict hunks from content in either supported marker style
+///
+/// Diff sections are expanded into the base and side they describe, so the
+/// same conflict yields identical hunks in both styles.
+pub fn parse_conflict_hunks(content: &str) -> Vec<ConflictHunk> {
+    marker_sections(content)
+        .into_iter()
+        .map(|sections| {
+            let mut sides: Vec<(usize, String)> = Vec::new();
+            let mut bases: Vec<(usize, String)> = Vec::new();
+
+            for (marker, header, lines) in sections {
+                let side_number = term_number(header, "side").unwrap_or(sides.len() + 1);
+                let base_number = term_number(header, "base").unwrap_or(bases.len() + 1);
+
+                match marker {
+                    '%' => {
+                        let mut base = String::new();
+                        let mut side = String::new();
+                        for line in lines {
+                            let (prefix, rest) = line.split_at(line.len().min(1));
+                            match prefix {
+                                "-" => push_line(&mut base, rest),
+                                "+" => push_line(&mut side, rest),
+                                _ => {
+                                    push_line(&mut base, rest);
+                                    push_line(&mut side, rest);
+                                }
+                            }
+                        }
+                        bases.push((base_number, base));
+                        sides.push((side_number, side));
+                    }
+                    '+' => sides.push((side_number, join_lines(&lines))),
+                    '-' => bases.push((base_number, join_lines(&lines))),
+                    _ => {}
+                }
+            }
+
+            sides.sort_by_key(|(n, _)| *n);
+            bases.sort_by_key(|(n, _)| *n);
+            ConflictHunk {
+                path: String::new(),
+                sides: sides.into_iter().map(|(_, s)| s).collect(),
+                bases: bases.into_iter().map(|(_, b)| b).collect(),
+            }
+        })
+        .collect()
+}
+
+/// A marker section: marker char, header text, and body lines
+type MarkerSection<'a> = (char, &'a str, Vec<&'a str>);
+
+/// Split content into conflict hunks, each a list of marker sections
+///
+/// Marker length comes from the hunk's opening `<<<<<<<` line, since jj
+/// lengthens markers when the file contains marker-like lines.
+fn marker_sections(content: &str) -> Vec<Vec<MarkerSection<'_>>> {
+    let mut hunks = Vec::new();
+    let mut lines = content.lines();
+
+    while let Some(line) = lines.next() {
+        let Some(len) = marker_len(line, '<') else {
+            continue;
+        };
+
+        let mut sections: Vec<MarkerSection<'_>> = Vec::new();
+        for line in lines.by_ref() {
+            if marker_len(line, '>') == Some(len) {
+                break;
+            }
+            let marker = ['%', '+', '-']
+                .into_iter()
+                .find(|&c| marker_len(line, c) == Some(len));
+            match (marker, sections.last_mut()) {
+                (Some(marker), _) => sections.push((marker, line[len..].trim(), Vec::new())),
+                // A backslash marker line continues a diff header (e.g. "to: <change>")
+                (None, Some(_)) if marker_len(line, '\\') == Some(len) => {}
+                (None, Some((_, _, body))) => body.push(line),
+                (None, None) => {}
+            }
+        }
+        hunks.push(sections);
+    }
+
+    hunks
+}
+
+/// Length of a conflict marker (7+ repetitions of `c`) at the start of a line
+fn marker_len(line: &str, c: char) -> Option<usize> {
+    let len = line.chars().take_while(|&ch| ch == c).count();
+    let rest = &line[len..];
+    (len >= 7 && (rest.is_empty() || rest.starts_with(' '))).then_some(len)
+}
+
+/// Extract the number of a `side #N` / `base #N` term from a section header
+///
+/// A bare `base` (two-sided conflicts) is base #1.
+fn term_number(header: &str, term: &str) -> Option<usize> {
+    let start = header.find(term)? + term.len();
+    let rest = header[start..].trim_start();
+    match rest.strip_prefix('#') {
+        Some(digits) => digits
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect::<String>()
+            .parse()
+            .ok(),
+        None => Some(1),
+    }
+}
+
+fn push_line(content: &mut String, line: &str) {
+    content.push_str(line);
+    content.push('\n');
+}
+
+fn join_lines(lines: &[&str]) -> String {
+    let mut content = String::new();
+    for line in lines {
+        push_line(&mut content, line);
+    }
+    content
+}
+
 /// Report of resolution attempt results
 #[derive(Debug, Clone, Default)]
 pub struct ResolutionReport {
@@ -59,12 +253,118 @@ impl ResolutionReport {
 /// Conflict resolution pipeline
 pub struct ConflictResolver<E: JjExecutor> {
     executor: E,
+    /// Expected marker style (detected from file content if unset)
+    marker_style: Option<ConflictMarkerStyle>,
 }
 
 impl<E: JjExecutor + Clone> ConflictResolver<E> {
     /// Create a new conflict resolver
     pub fn new(executor: E) -> Self {
-        Self { executor }
+        Self {
+            executor,
+            marker_style: None,
+        }
+    }
+
+    /// Expect conflicts materialized in a specific marker style
+    pub fn with_marker_style(mut self, style: ConflictMarkerStyle) -> Self {
+        self.marker_style = Some(style);
+        self
+    }
+
+    /// Extract the conflicted hunks of a file, with every side's content
+    ///
+    /// Parses the file as jj materializes it. If the markers are not in a
+    /// supported style (or not the configured one), asks jj to materialize the
+    /// file again in the configured style, or `diff` if none is configured.
+    pub async fn extract_sides(&self, change_id: &str, path: &str) -> Result<Vec<ConflictHunk>> {
+        let content = self.materialize(change_id, path, None).await?;
+        let detected = ConflictMarkerStyle::detect(&content);
+
+        let content = if detected.is_some()
+            && (self.marker_style.is_none() || detected == self.marker_style)
+        {
+            content
+        } else {
+            let style = self.marker_style.unwrap_or_default();
+            debug!(
+                "Conflict markers in {} are not {:?}, re-materializing",
+                path, style
+            );
+            self.materialize(change_id, path, Some(style)).await?
+        };
+
+        Ok(parse_conflict_hunks(&content)
+            .into_iter()
+            .map(|hunk| ConflictHunk {
+                path: path.to_string(),
+                ..hunk
+            })
+            .collect())
+    }
+
+    /// Preview every conflicted hunk on a change, across all files
+    ///
+    /// Lets a caller inspect each side's content before picking a
+    /// [`ResolutionStrategy`].
+    pub async fn preview(&self, change_id: &str) -> Result<Vec<ConflictHunk>> {
+        let mut hunks = Vec::new();
+        for path in self.conflicted_files(change_id).await? {
+            hunks.extend(self.extract_sides(change_id, &path).await?);
+        }
+        Ok(hunks)
+    }
+
+    /// List files with unresolved conflicts on a change
+    pub async fn conflicted_files(&self, change_id: &str) -> Result<Vec<String>> {
+        let output = self
+            .executor
+            .exec(&["resolve", "--list", "-r", change_id])
+            .await?;
+
+        if !output.success {
+            // jj treats an empty conflict list as an error
+            if output.stderr.contains("No conflicts") {
+                return Ok(Vec::new());
+            }
+            return Err(HoxError::JjCommand(format!(
+                "Failed to list conflicts for {}: {}",
+                change_id, output.stderr
+            )));
+        }
+
+        Ok(output
+            .stdout
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .map(|line| strip_conflict_description(line).to_string())
+            .collect())
+    }
+
+    /// Read a file's content at a change, optionally forcing a marker style
+    async fn materialize(
+        &self,
+        change_id: &str,
+        path: &str,
+        style: Option<ConflictMarkerStyle>,
+    ) -> Result<String> {
+        let config;
+        let mut args = vec!["file", "show", "-r", change_id, path];
+        if let Some(style) = style {
+            config = format!("ui.conflict-marker-style={}", style.as_config_value());
+            args.extend(["--config", config.as_str()]);
+        }
+
+        let output = self.executor.exec(&args).await?;
+        if !output.success {
+            return Err(HoxError::JjCommand(format!(
+                "Failed to read {} at {}: {}",
+                path, change_id, output.stderr
+            )));
+        }
+
+        Ok(output.stdout)
     }
 
     /// Analyze conflicts on a change - find conflicted files
@@ -292,6 +592,19 @@ impl<E: JjExecutor + Clone> ConflictResolver<E> {
     }
 }
 
+/// Path part of a `jj resolve --list` line
+///
+/// Lines are `<path><padding> <N>-sided conflict[ including ...]`. Paths
+/// wider than the padding column get a single space, and paths may contain
+/// spaces themselves, so the description is stripped from the right.
+fn strip_conflict_description(line: &str) -> &str {
+    let Some(marker) = line.rfind("-sided conflict") else {
+        return line.trim();
+    };
+    let path = line[..marker].trim_end_matches(|c: char| c.is_ascii_digit());
+    path.trim()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -305,6 +618,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -322,6 +636,7 @@ mod tests {
                 stdout: " src/main.rs | 5 ++---\n src/lib.rs  | 3 ++-\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -392,6 +707,7 @@ mod tests {
                     stdout: String::new(),
                     stderr: String::new(),
                     success: true,
+                    code: Some(0),
                 },
             )
             .with_response(
@@ -400,6 +716,7 @@ mod tests {
                     stdout: String::new(), // No conflicts remaining
                     stderr: String::new(),
                     success: true,
+                    code: Some(0),
                 },
             );
 
@@ -417,6 +734,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -437,6 +755,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -448,4 +767,163 @@ mod tests {
         assert_eq!(report.needs_human, 0);
         assert_eq!(report.failed, 0);
     }
+
+    const DIFF_STYLE: &str = "fn main() {
+<<<<<<< Conflict 1 of 1
+%%%%%%% Changes from base to side #1
+-    apple();
++    grape();
+ shared();
++++++++ Contents of side #2
+    APPLE();
+shared();
+>>>>>>> Conflict 1 of 1 ends
+}
+";
+
+    const SNAPSHOT_STYLE: &str = "fn main() {
+<<<<<<< Conflict 1 of 1
++++++++ Contents of side #1
+    grape();
+shared();
+------- Contents of base
+    apple();
+shared();
++++++++ Contents of side #2
+    APPLE();
+shared();
+>>>>>>> Conflict 1 of 1 ends
+}
+";
+
+    #[test]
+    fn test_parse_conflict_hunks_styles_match() {
+        let expected = vec![ConflictHunk {
+            path: String::new(),
+            sides: vec![
+                "    grape();\nshared();\n".to_string(),
+                "    APPLE();\nshared();\n".to_string(),
+            ],
+            bases: vec!["    apple();\nshared();\n".to_string()],
+        }];
+
+        assert_eq!(parse_conflict_hunks(DIFF_STYLE), expected);
+        assert_eq!(parse_conflict_hunks(SNAPSHOT_STYLE), expected);
+    }
+
+    #[test]
+    fn test_detect_marker_style() {
+        assert_eq!(
+            ConflictMarkerStyle::detect(DIFF_STYLE),
+            Some(ConflictMarkerStyle::Diff)
+        );
+        assert_eq!(
+            ConflictMarkerStyle::detect(SNAPSHOT_STYLE),
+            Some(ConflictMarkerStyle::Snapshot)
+        );
+        assert_eq!(ConflictMarkerStyle::detect("no conflicts\n"), None);
+    }
+
+    #[test]
+    fn test_parse_conflict_hunks_long_markers() {
+        // jj lengthens markers when the file has marker-like content
+        let content = "<<<<<<<<<<< Conflict 1 of 1
++++++++++++ Contents of side #1
+------- not a marker
+----------- Contents of base
+old
++++++++++++ Contents of side #2
+new
+>>>>>>>>>>> Conflict 1 of 1 ends
+";
+
+        let hunks = parse_conflict_hunks(content);
+
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].sides, vec!["------- not a marker\n", "new\n"]);
+        assert_eq!(hunks[0].bases, vec!["old\n"]);
+    }
+
+    #[tokio::test]
+    async fn test_extract_sides_rematerializes_unknown_style() {
+        let git_style =
+            "<<<<<<< Side #1 (Conflict 1 of 1)\ngrape\n=======\nAPPLE\n>>>>>>> Side #2\n";
+        let ok = |stdout: &str| JjOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            success: true,
+            code: Some(0),
+        };
+        let executor = MockJjExecutor::new()
+            .with_response("file show -r test-change src/main.rs", ok(git_style))
+            .with_response(
+                "file show -r test-change src/main.rs --config ui.conflict-marker-style=snapshot",
+                ok(SNAPSHOT_STYLE),
+            );
+
+        let resolver =
+            ConflictResolver::new(executor).with_marker_style(ConflictMarkerStyle::Snapshot);
+        let hunks = resolver
+            .extract_sides("test-change", "src/main.rs")
+            .await
+            .unwrap();
+
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].path, "src/main.rs");
+        assert_eq!(hunks[0].sides, parse_conflict_hunks(DIFF_STYLE)[0].sides);
+    }
+
+    #[tokio::test]
+    async fn test_preview_conflicted_files() {
+        let ok = |stdout: &str| JjOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            success: true,
+            code: Some(0),
+        };
+        let executor = MockJjExecutor::new()
+            .with_response(
+                "resolve --list -r test-change",
+                ok(concat!(
+                    "src/main.rs                      2-sided conflict\n",
+                    "crates/hox-orchestrator/src/conflict_resolver.rs 3-sided conflict including 1 deletion\n",
+                )),
+            )
+            .with_response("file show -r test-change src/main.rs", ok(SNAPSHOT_STYLE))
+            .with_response(
+                "file show -r test-change crates/hox-orchestrator/src/conflict_resolver.rs",
+                ok(DIFF_STYLE),
+            );
+
+        let resolver = ConflictResolver::new(executor);
+        let hunks = resolver.preview("test-change").await.unwrap();
+
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks[0].path, "src/main.rs");
+        assert_eq!(
+            hunks[1].path,
+            "crates/hox-orchestrator/src/conflict_resolver.rs"
+        );
+        for hunk in &hunks {
+            assert_eq!(hunk.left(), Some("    grape();\nshared();\n"));
+            assert_eq!(hunk.right(), Some("    APPLE();\nshared();\n"));
+            assert_eq!(hunk.base(), Some("    apple();\nshared();\n"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_preview_no_conflicts() {
+        let executor = MockJjExecutor::new().with_response(
+            "resolve --list -r test-change",
+            JjOutput {
+                stdout: String::new(),
+                stderr: "Error: No conflicts found at this revision\n".to_string(),
+                success: false,
+                code: Some(1),
+            },
+        );
+
+        let resolver = ConflictResolver::new(executor);
+        assert!(resolver.preview("test-change").await.unwrap().is_empty());
+    }
 }
@@ -1,21 +1,42 @@
-//! Hook pipeline for post-iteration callbacks
+//! Hook pipeline for lifecycle callbacks
 //!
 //! This module provides a fail-open hook system that allows registering
-//! callbacks to run after agent tool execution. Hooks are fail-open:
-//! failures are logged but never propagate errors.
+//! callbacks to run after agent tool execution and around integration.
+//! Hooks are fail-open: failures are logged but never propagate errors,
+//! unless a hook is marked required.
+//!
+//! Besides the built-in hooks, teams can configure [`CommandHook`]s in
+//! `.hox/hooks.toml` to run notifications or deploy steps.
+//!
+//! Hooks run in ascending priority, ties in registration order, and always
+//! after the hooks named in their `after` list (see [`HookRegistration`]).
+//! Built-in priorities:
+//!
+//! | Hook               | Priority |
+//! |--------------------|----------|
+//! | [`AutoCommitHook`] | -20      |
+//! | [`SnapshotHook`]   | -10      |
+//! | anything else      | 0        |
 
 use async_trait::async_trait;
-use std::path::PathBuf;
+use hox_core::{HookDefinition, HookEvent, HooksConfig, HoxError, Result};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::process::Command;
 use tracing::{info, warn};
 
+/// Default timeout for command hooks
+const DEFAULT_COMMAND_HOOK_TIMEOUT_SECS: u64 = 300;
+
 /// Context passed to hooks
 #[derive(Debug, Clone)]
 pub struct HookContext {
-    /// Change ID being worked on
+    /// Change ID being worked on (empty if there is none, e.g. integration)
     pub change_id: String,
     /// Path to the workspace
     pub workspace_path: PathBuf,
-    /// Current iteration number
+    /// Current loop iteration, starting at 1 (0 outside a loop)
     pub iteration: usize,
 }
 
@@ -46,7 +67,7 @@ impl HookResult {
     }
 }
 
-/// Trait for post-tools hooks
+/// Trait for pipeline hooks
 #[async_trait]
 pub trait PostToolsHook: Send + Sync {
     /// Execute the hook
@@ -55,11 +76,65 @@ pub trait PostToolsHook: Send + Sync {
     /// Implementations should handle their own errors and return
     /// failure results rather than propagating errors.
     async fn execute(&self, context: &HookContext) -> HookResult;
+
+    /// Lifecycle event the hook runs on
+    fn event(&self) -> HookEvent {
+        HookEvent::PostTools
+    }
+
+    /// Whether a failure stops the pipeline
+    fn required(&self) -> bool {
+        false
+    }
+
+    /// Name that other hooks refer to in `after` (the type name by default)
+    fn name(&self) -> &str {
+        let type_name = std::any::type_name::<Self>();
+        type_name.rsplit("::").next().unwrap_or(type_name)
+    }
+
+    /// Ordering used when registered with [`HookPipeline::add_hook`]
+    fn registration(&self) -> HookRegistration {
+        HookRegistration::default()
+    }
+}
+
+/// Ordering of a hook within a [`HookPipeline`]
+///
+/// Names in `after` that match no hook in the pipeline are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HookRegistration {
+    /// Lower priorities run first; ties keep registration order
+    pub priority: i32,
+    /// Names of hooks that must run before this one
+    pub after: Vec<String>,
+}
+
+impl HookRegistration {
+    /// Registration with the given priority and no `after` constraints
+    pub fn new(priority: i32) -> Self {
+        Self {
+            priority,
+            after: Vec::new(),
+        }
+    }
+
+    /// Also run after the hook named `name`
+    pub fn run_after(mut self, name: impl Into<String>) -> Self {
+        self.after.push(name.into());
+        self
+    }
+}
+
+/// A hook and its ordering constraints
+struct RegisteredHook {
+    hook: Box<dyn PostToolsHook + Send + Sync>,
+    registration: HookRegistration,
 }
 
 /// Pipeline for executing multiple hooks in sequence
 pub struct HookPipeline {
-    hooks: Vec<Box<dyn PostToolsHook + Send + Sync>>,
+    hooks: Vec<RegisteredHook>,
 }
 
 impl HookPipeline {
@@ -68,23 +143,113 @@ impl HookPipeline {
         Self { hooks: Vec::new() }
     }
 
-    /// Add a hook to the pipeline
+    /// Add a hook to the pipeline with its own [`PostToolsHook::registration`]
     pub fn add_hook(&mut self, hook: Box<dyn PostToolsHook + Send + Sync>) {
-        self.hooks.push(hook);
+        let registration = hook.registration();
+        self.add_hook_with(hook, registration);
+    }
+
+    /// Add a hook to the pipeline with explicit ordering
+    pub fn add_hook_with(
+        &mut self,
+        hook: Box<dyn PostToolsHook + Send + Sync>,
+        registration: HookRegistration,
+    ) {
+        self.hooks.push(RegisteredHook { hook, registration });
+    }
+
+    /// Names of all hooks in execution order
+    ///
+    /// Fails if the `after` constraints form a cycle.
+    pub fn execution_order(&self) -> Result<Vec<&str>> {
+        Ok(self
+            .ordered()?
+            .into_iter()
+            .map(|entry| entry.hook.name())
+            .collect())
+    }
+
+    /// Hooks sorted by priority and `after` constraints
+    fn ordered(&self) -> Result<Vec<&RegisteredHook>> {
+        // Number of unfinished predecessors per hook, and who waits on whom
+        let mut blocked_by = vec![0usize; self.hooks.len()];
+        let mut dependents = vec![Vec::new(); self.hooks.len()];
+        for (idx, entry) in self.hooks.iter().enumerate() {
+            for name in &entry.registration.after {
+                for (other, candidate) in self.hooks.iter().enumerate() {
+                    if candidate.hook.name() == name {
+                        blocked_by[idx] += 1;
+                        dependents[other].push(idx);
+                    }
+                }
+            }
+        }
+
+        let priority = |idx: usize| self.hooks[idx].registration.priority;
+        let mut ready: BTreeSet<(i32, usize)> = (0..self.hooks.len())
+            .filter(|&idx| blocked_by[idx] == 0)
+            .map(|idx| (priority(idx), idx))
+            .collect();
+        let mut order = Vec::with_capacity(self.hooks.len());
+        while let Some((_, idx)) = ready.pop_first() {
+            order.push(&self.hooks[idx]);
+            for &dependent in &dependents[idx] {
+                blocked_by[dependent] -= 1;
+                if blocked_by[dependent] == 0 {
+                    ready.insert((priority(dependent), dependent));
+                }
+            }
+        }
+
+        if order.len() < self.hooks.len() {
+            let stuck: Vec<&str> = (0..self.hooks.len())
+                .filter(|&idx| blocked_by[idx] > 0)
+                .map(|idx| self.hooks[idx].hook.name())
+                .collect();
+            return Err(HoxError::Orchestrator(format!(
+                "Hook ordering has a cycle; cannot order: {}",
+                stuck.join(", ")
+            )));
+        }
+        Ok(order)
+    }
+
+    /// Add the command hooks configured in `.hox/hooks.toml`
+    ///
+    /// Returns how many were added. A malformed file, or `after`
+    /// constraints that form a cycle, is an error.
+    pub fn load_command_hooks(&mut self, repo_root: &Path) -> Result<usize> {
+        let hooks = CommandHook::load(repo_root)?;
+        let count = hooks.len();
+        for hook in hooks {
+            self.add_hook(Box::new(hook));
+        }
+        self.ordered()?;
+        Ok(count)
     }
 
-    /// Execute all hooks in order (fail-open)
+    /// Execute all post-tools hooks in order (fail-open)
     ///
     /// Runs each hook sequentially. If a hook fails, logs a warning
-    /// but continues executing remaining hooks. Never propagates errors.
+    /// but continues executing remaining hooks. Never propagates errors;
+    /// if the ordering has a cycle, hooks run in registration order.
     pub async fn execute_all(&self, context: &HookContext) -> Vec<HookResult> {
+        let ordered = self.ordered().unwrap_or_else(|e| {
+            warn!("{}, running hooks in registration order", e);
+            self.hooks.iter().collect()
+        });
+        let hooks: Vec<_> = ordered
+            .into_iter()
+            .map(|entry| &entry.hook)
+            .filter(|hook| hook.event() == HookEvent::PostTools)
+            .collect();
         let mut results = Vec::new();
 
-        for (idx, hook) in self.hooks.iter().enumerate() {
+        for (idx, hook) in hooks.iter().enumerate() {
             info!(
                 "Executing hook {} of {} for change {}",
                 idx + 1,
-                self.hooks.len(),
+                hooks.len(),
                 context.change_id
             );
 
@@ -102,6 +267,52 @@ impl HookPipeline {
         results
     }
 
+    /// Execute the hooks for `event` in order
+    ///
+    /// Like [`Self::execute_all`], failing hooks are logged and the rest
+    /// still run, except for required hooks: their failure stops the
+    /// pipeline and is returned as an error. So is an ordering cycle.
+    pub async fn execute_event(
+        &self,
+        event: HookEvent,
+        context: &HookContext,
+    ) -> Result<Vec<HookResult>> {
+        let hooks: Vec<_> = self
+            .ordered()?
+            .into_iter()
+            .map(|entry| &entry.hook)
+            .filter(|hook| hook.event() == event)
+            .collect();
+        let mut results = Vec::new();
+
+        for (idx, hook) in hooks.iter().enumerate() {
+            info!(
+                "Executing {} hook {} of {} for change {}",
+                event,
+                idx + 1,
+                hooks.len(),
+                context.change_id
+            );
+
+            let result = hook.execute(context).await;
+
+            if result.success {
+                info!("Hook {} succeeded: {}", idx + 1, result.message);
+            } else if hook.required() {
+                return Err(HoxError::Orchestrator(format!(
+                    "Required {} hook failed: {}",
+                    event, result.message
+                )));
+            } else {
+                warn!("Hook {} failed (continuing): {}", idx + 1, result.message);
+            }
+
+            results.push(result);
+        }
+
+        Ok(results)
+    }
+
     /// Get number of hooks in pipeline
     pub fn len(&self) -> usize {
         self.hooks.len()
@@ -122,11 +333,20 @@ impl Default for HookPipeline {
 /// Auto-commit hook (placeholder implementation)
 ///
 /// In production, this would create a JJ snapshot after each iteration.
-/// Currently logs that it would commit.
+/// Currently logs that it would commit. Runs first, at priority -20.
 pub struct AutoCommitHook;
 
+impl AutoCommitHook {
+    /// Default priority
+    pub const PRIORITY: i32 = -20;
+}
+
 #[async_trait]
 impl PostToolsHook for AutoCommitHook {
+    fn registration(&self) -> HookRegistration {
+        HookRegistration::new(Self::PRIORITY)
+    }
+
     async fn execute(&self, context: &HookContext) -> HookResult {
         info!(
             "AutoCommitHook: Would create snapshot for change {} at iteration {}",
@@ -146,11 +366,20 @@ impl PostToolsHook for AutoCommitHook {
 /// Snapshot hook (placeholder implementation)
 ///
 /// In production, this would create a JJ operation snapshot.
-/// Currently logs that a snapshot was taken.
+/// Currently logs that a snapshot was taken. Runs at priority -10.
 pub struct SnapshotHook;
 
+impl SnapshotHook {
+    /// Default priority
+    pub const PRIORITY: i32 = -10;
+}
+
 #[async_trait]
 impl PostToolsHook for SnapshotHook {
+    fn registration(&self) -> HookRegistration {
+        HookRegistration::new(Self::PRIORITY)
+    }
+
     async fn execute(&self, context: &HookContext) -> HookResult {
         info!(
             "SnapshotHook: Would create operation snapshot for change {} at workspace {:?}",
@@ -167,6 +396,118 @@ impl PostToolsHook for SnapshotHook {
     }
 }
 
+/// Hook that runs an external command
+///
+/// Configured in `.hox/hooks.toml`. The command runs in the workspace with
+/// `HOX_HOOK_EVENT` set, plus `HOX_CHANGE_ID` and `HOX_ITERATION` when the
+/// context has them (integration hooks have neither), and fails if it exits
+/// non-zero, cannot be started, or outlives its timeout.
+#[derive(Debug, Clone)]
+pub struct CommandHook {
+    /// Hook name used in results
+    pub name: String,
+    /// Program to execute
+    pub program: String,
+    /// Program arguments
+    pub args: Vec<String>,
+    /// Lifecycle event the hook runs on
+    pub run_on: HookEvent,
+    /// Whether a failure stops the pipeline
+    pub required: bool,
+    /// Time allowed before the command is killed
+    pub timeout: Duration,
+    /// Ordering within the pipeline
+    pub registration: HookRegistration,
+}
+
+impl CommandHook {
+    /// Load the hooks configured in `.hox/hooks.toml`, if any
+    pub fn load(repo_root: &Path) -> Result<Vec<Self>> {
+        Ok(HooksConfig::load(repo_root)?
+            .map(|config| config.hooks.into_iter().map(Self::from).collect())
+            .unwrap_or_default())
+    }
+}
+
+impl From<HookDefinition> for CommandHook {
+    fn from(definition: HookDefinition) -> Self {
+        Self {
+            name: definition.name,
+            program: definition.program,
+            args: definition.args,
+            run_on: definition.run_on,
+            required: definition.required,
+            timeout: Duration::from_secs(
+                definition
+                    .timeout_secs
+                    .unwrap_or(DEFAULT_COMMAND_HOOK_TIMEOUT_SECS),
+            ),
+            registration: HookRegistration {
+                priority: definition.priority,
+                after: definition.after,
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl PostToolsHook for CommandHook {
+    async fn execute(&self, context: &HookContext) -> HookResult {
+        let mut command = Command::new(&self.program);
+        command
+            .args(&self.args)
+            .current_dir(&context.workspace_path)
+            .env("HOX_HOOK_EVENT", self.run_on.to_string())
+            .env_remove("HOX_CHANGE_ID")
+            .env_remove("HOX_ITERATION")
+            .kill_on_drop(true);
+        if !context.change_id.is_empty() {
+            command.env("HOX_CHANGE_ID", &context.change_id);
+        }
+        if context.iteration > 0 {
+            command.env("HOX_ITERATION", context.iteration.to_string());
+        }
+        let output = command.output();
+
+        match tokio::time::timeout(self.timeout, output).await {
+            Ok(Ok(output)) if output.status.success() => {
+                HookResult::success(format!("{} succeeded", self.name))
+            }
+            Ok(Ok(output)) => HookResult::failure(format!(
+                "{} failed ({}): {}",
+                self.name,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )),
+            Ok(Err(e)) => HookResult::failure(format!(
+                "{} could not run {}: {}",
+                self.name, self.program, e
+            )),
+            Err(_) => HookResult::failure(format!(
+                "{} timed out after {}s",
+                self.name,
+                self.timeout.as_secs()
+            )),
+        }
+    }
+
+    fn event(&self) -> HookEvent {
+        self.run_on
+    }
+
+    fn required(&self) -> bool {
+        self.required
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn registration(&self) -> HookRegistration {
+        self.registration.clone()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -337,6 +678,312 @@ mod tests {
         assert!(results[1].success);
     }
 
+    fn command_hook(name: &str, script: &str, run_on: HookEvent, required: bool) -> CommandHook {
+        CommandHook {
+            name: name.to_string(),
+            program: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            run_on,
+            required,
+            timeout: Duration::from_secs(10),
+            registration: HookRegistration::default(),
+        }
+    }
+
+    fn workspace_context(dir: &Path) -> HookContext {
+        HookContext {
+            change_id: "abc123".to_string(),
+            workspace_path: dir.to_path_buf(),
+            iteration: 4,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_command_hook_runs_in_workspace() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let hook = command_hook(
+            "notify",
+            "echo \"$HOX_HOOK_EVENT $HOX_CHANGE_ID $HOX_ITERATION\" > hook.out",
+            HookEvent::PostIntegrate,
+            false,
+        );
+
+        let result = hook.execute(&workspace_context(temp_dir.path())).await;
+        assert!(result.success, "{}", result.message);
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("hook.out")).unwrap(),
+            "post-integrate abc123 4\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_command_hook_omits_missing_context() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let hook = command_hook(
+            "notify",
+            "echo \"${HOX_CHANGE_ID-none} ${HOX_ITERATION-none}\" > hook.out",
+            HookEvent::PreIntegrate,
+            false,
+        );
+        let context = HookContext {
+            change_id: String::new(),
+            workspace_path: temp_dir.path().to_path_buf(),
+            iteration: 0,
+        };
+
+        let result = hook.execute(&context).await;
+        assert!(result.success, "{}", result.message);
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("hook.out")).unwrap(),
+            "none none\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_command_hook_failures() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let context = workspace_context(temp_dir.path());
+
+        let failing = command_hook(
+            "deploy",
+            "echo boom >&2; exit 3",
+            HookEvent::PostTools,
+            false,
+        );
+        let result = failing.execute(&context).await;
+        assert!(!result.success);
+        assert!(result.message.starts_with("deploy failed"));
+        assert!(result.message.ends_with("boom"));
+
+        let missing = CommandHook {
+            program: "hox-missing-hook-program".to_string(),
+            ..failing.clone()
+        };
+        let result = missing.execute(&context).await;
+        assert!(!result.success);
+        assert!(result
+            .message
+            .contains("could not run hox-missing-hook-program"));
+
+        let slow = CommandHook {
+            timeout: Duration::from_millis(100),
+            ..command_hook("slow", "sleep 5", HookEvent::PostTools, false)
+        };
+        let result = slow.execute(&context).await;
+        assert!(!result.success);
+        assert!(result.message.contains("timed out"));
+    }
+
+    #[tokio::test]
+    async fn test_required_hook_failure_stops_pipeline() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut pipeline = HookPipeline::new();
+        pipeline.add_hook(Box::new(command_hook(
+            "optional",
+            "exit 1",
+            HookEvent::PreIntegrate,
+            false,
+        )));
+        pipeline.add_hook(Box::new(command_hook(
+            "gate",
+            "exit 1",
+            HookEvent::PreIntegrate,
+            true,
+        )));
+        pipeline.add_hook(Box::new(command_hook(
+            "after",
+            "touch after.out",
+            HookEvent::PreIntegrate,
+            false,
+        )));
+
+        let err = pipeline
+            .execute_event(HookEvent::PreIntegrate, &workspace_context(temp_dir.path()))
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Required pre-integrate hook failed: gate failed"));
+        assert!(!temp_dir.path().join("after.out").exists());
+
+        // Without the required hook, failures are logged and the pipeline continues
+        let mut pipeline = HookPipeline::new();
+        pipeline.add_hook(Box::new(command_hook(
+            "optional",
+            "exit 1",
+            HookEvent::PreIntegrate,
+            false,
+        )));
+        pipeline.add_hook(Box::new(command_hook(
+            "after",
+            "touch after.out",
+            HookEvent::PreIntegrate,
+            false,
+        )));
+        let results = pipeline
+            .execute_event(HookEvent::PreIntegrate, &workspace_context(temp_dir.path()))
+            .await
+            .unwrap();
+        assert!(!results[0].success);
+        assert!(results[1].success);
+        assert!(temp_dir.path().join("after.out").exists());
+    }
+
+    #[tokio::test]
+    async fn test_load_command_hooks_from_config() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".hox")).unwrap();
+        std::fs::write(
+            temp_dir.path().join(".hox/hooks.toml"),
+            r#"
+[[hook]]
+name = "notify"
+program = "sh"
+args = ["-c", "touch notified"]
+run_on = "post-integrate"
+
+[[hook]]
+name = "lint-docs"
+program = "sh"
+args = ["-c", "touch linted"]
+run_on = "post-tools"
+required = true
+timeout_secs = 30
+"#,
+        )
+        .unwrap();
+
+        let mut pipeline = HookPipeline::new();
+        pipeline.add_hook(Box::new(AutoCommitHook));
+        assert_eq!(pipeline.load_command_hooks(temp_dir.path()).unwrap(), 2);
+        assert_eq!(pipeline.len(), 3);
+
+        // Only hooks for the requested event run
+        let context = workspace_context(temp_dir.path());
+        let results = pipeline
+            .execute_event(HookEvent::PostIntegrate, &context)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(temp_dir.path().join("notified").exists());
+        assert!(!temp_dir.path().join("linted").exists());
+
+        let results = pipeline.execute_all(&context).await;
+        assert_eq!(results.len(), 2);
+        assert!(temp_dir.path().join("linted").exists());
+    }
+
+    #[tokio::test]
+    async fn test_load_command_hooks_rejects_malformed_config() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".hox")).unwrap();
+        std::fs::write(
+            temp_dir.path().join(".hox/hooks.toml"),
+            "[[hook]]\nname = \"x\"\nprogram = \"true\"\nrun_on = \"post-merge\"\n",
+        )
+        .unwrap();
+
+        let err = HookPipeline::new()
+            .load_command_hooks(temp_dir.path())
+            .unwrap_err();
+        assert!(err.to_string().contains("hooks.toml"));
+        assert!(err.to_string().contains("unknown variant `post-merge`"));
+
+        // No file: nothing to load
+        assert_eq!(
+            HookPipeline::new()
+                .load_command_hooks(&temp_dir.path().join("missing"))
+                .unwrap(),
+            0
+        );
+    }
+
+    fn ordered_hook(name: &str, registration: HookRegistration) -> Box<CommandHook> {
+        Box::new(CommandHook {
+            registration,
+            ..command_hook(name, "true", HookEvent::PostTools, false)
+        })
+    }
+
+    #[tokio::test]
+    async fn test_hook_ordering_priority_and_after() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut pipeline = HookPipeline::new();
+        pipeline.add_hook(Box::new(SnapshotHook));
+        pipeline.add_hook(Box::new(AutoCommitHook));
+        // Would run first on priority alone, but must follow AutoCommitHook
+        pipeline.add_hook(ordered_hook(
+            "notify",
+            HookRegistration::new(-50).run_after("AutoCommitHook"),
+        ));
+        pipeline.add_hook(ordered_hook("first", HookRegistration::new(-100)));
+        // Ties keep registration order; unknown names are ignored
+        pipeline.add_hook(ordered_hook("tie-a", HookRegistration::default()));
+        pipeline.add_hook(ordered_hook(
+            "tie-b",
+            HookRegistration::default().run_after("not-registered"),
+        ));
+
+        assert_eq!(
+            pipeline.execution_order().unwrap(),
+            [
+                "first",
+                "AutoCommitHook",
+                "notify",
+                "SnapshotHook",
+                "tie-a",
+                "tie-b"
+            ]
+        );
+
+        let results = pipeline
+            .execute_all(&workspace_context(temp_dir.path()))
+            .await;
+        let messages: Vec<&str> = results.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(messages[0], "first succeeded");
+        assert!(messages[1].starts_with("Auto-commit"));
+        assert_eq!(messages[2], "notify succeeded");
+        assert!(messages[3].starts_with("Snapshot"));
+    }
+
+    #[tokio::test]
+    async fn test_hook_ordering_cycle_is_an_error() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut pipeline = HookPipeline::new();
+        pipeline.add_hook(ordered_hook("free", HookRegistration::default()));
+        pipeline.add_hook(ordered_hook("a", HookRegistration::new(0).run_after("b")));
+        pipeline.add_hook(ordered_hook("b", HookRegistration::new(0).run_after("a")));
+
+        let err = pipeline.execution_order().unwrap_err();
+        assert!(err.to_string().contains("cycle; cannot order: a, b"));
+
+        let context = workspace_context(temp_dir.path());
+        assert!(pipeline
+            .execute_event(HookEvent::PostTools, &context)
+            .await
+            .is_err());
+        // Fail-open execution still runs every hook
+        assert_eq!(pipeline.execute_all(&context).await.len(), 3);
+
+        // A cycle in .hox/hooks.toml is reported when loading
+        std::fs::create_dir_all(temp_dir.path().join(".hox")).unwrap();
+        std::fs::write(
+            temp_dir.path().join(".hox/hooks.toml"),
+            r#"
+[[hook]]
+name = "self"
+program = "true"
+run_on = "post-tools"
+after = ["self"]
+"#,
+        )
+        .unwrap();
+        let err = HookPipeline::new()
+            .load_command_hooks(temp_dir.path())
+            .unwrap_err();
+        assert!(err.to_string().contains("cannot order: self"));
+    }
+
     #[tokio::test]
     async fn test_hook_result_creation() {
         let success = HookResult::success("Everything worked");
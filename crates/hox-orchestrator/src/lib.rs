@@ -12,7 +12,10 @@
 #![allow(dead_code)]
 
 mod activity_logger;
+mod agent_logs;
 mod backpressure;
+mod backpressure_cache;
+mod bundle;
 mod communication;
 mod conflict_resolver;
 mod hooks;
@@ -26,24 +29,47 @@ mod speculative;
 mod state_machine;
 mod workspace;
 
-pub use activity_logger::ActivityLogger;
+pub use activity_logger::{
+    ActivityCheck, ActivityEntry, ActivityEvent, ActivityLog, ActivityLogger, ActivityLoggerConfig,
+    ACTIVITY_JSONL_FILE,
+};
+pub use agent_logs::{collect_agent_logs, AgentChangeLog, AgentLogs};
 pub use backpressure::{
-    detect_checks, format_errors_for_prompt, run_all_checks, run_checks, run_failed_checks,
+    detect_checks, detect_checks_for_changes, format_errors_for_prompt, run_all_checks,
+    run_all_checks_cancellable, run_checks, run_checks_cancellable, run_failed_checks,
     CheckCommand,
 };
-pub use communication::{Message, MessageRouter};
+pub use bundle::{
+    export_bundle, import_bundle, snapshot_metadata, MetadataSnapshotEntry, METADATA_SNAPSHOT_FILE,
+};
+pub use communication::{
+    AckHandle, Message, MessageFilter, MessageRouter, PendingAck, ACK_TRAILER,
+};
 pub use conflict_resolver::{
-    ConflictInfo, ConflictResolver, ConflictSide, ResolutionReport, ResolutionStrategy,
+    parse_conflict_hunks, ConflictHunk, ConflictInfo, ConflictMarkerStyle, ConflictResolver,
+    ConflictSide, ResolutionReport, ResolutionStrategy,
+};
+pub use hooks::{
+    AutoCommitHook, CommandHook, HookContext, HookPipeline, HookResult, PostToolsHook, SnapshotHook,
+};
+pub use loop_engine::{
+    request_stop, stop_file_path, watch_stop_file, ApiAgent, IterationAgent, LoopEngine,
 };
-pub use hooks::{AutoCommitHook, HookContext, HookPipeline, HookResult, PostToolsHook, SnapshotHook};
-pub use loop_engine::LoopEngine;
 pub use loop_external::{
-    create_initial_state, load_state, run_external_iteration, save_state, ExternalIterationConfig,
+    advance_state, create_initial_state, load_state, run_external_iteration, save_state,
+    ExternalIterationConfig,
+};
+pub use orchestrator::{
+    ConflictResolutionAgent, LoopConflictAgent, Orchestrator, OrchestratorConfig,
+    OrchestratorEvent, OrchestratorSnapshot, OrchestratorState,
 };
-pub use orchestrator::{Orchestrator, OrchestratorConfig, OrchestratorState};
-pub use phases::{PhaseManager, PhaseStatus};
+pub use phases::{PhaseEvent, PhaseManager, PhaseStatus};
 pub use prompt::{build_iteration_prompt, build_simple_prompt, parse_context_update};
-pub use recovery::{RecoveryManager, RecoveryPoint, RollbackResult};
-pub use speculative::SpeculativeExecutor;
-pub use state_machine::{transition, Action, Event, State};
-pub use workspace::WorkspaceManager;
+pub use recovery::{
+    RecoveryManager, RecoveryPoint, RollbackResult, CHECKPOINT_FILE, DEFAULT_MAX_CHECKPOINTS,
+};
+pub use speculative::{
+    CandidateEvaluation, CandidateEvaluator, SelectionPolicy, SelectionReport, SpeculativeExecutor,
+};
+pub use state_machine::{transition, Action, Event, EventKind, InvalidTransition, State};
+pub use workspace::{WorkspaceInfo, WorkspaceManager};
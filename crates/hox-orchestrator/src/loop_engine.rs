@@ -10,19 +10,184 @@
 
 use crate::activity_logger::ActivityLogger;
 use crate::backpressure::{run_all_checks_with_fix, run_failed_checks};
+use crate::backpressure_cache::{tree_fingerprint, BackpressureCache};
 use crate::hooks::{AutoCommitHook, HookContext, HookPipeline, SnapshotHook};
 use crate::prompt::{build_iteration_prompt, parse_context_update};
 use crate::recovery::RecoveryManager;
 use crate::workspace::WorkspaceManager;
+use async_trait::async_trait;
 use hox_agent::{
-    execute_file_operations, spawn_agent, BackpressureResult, CompletionPromise, LoopConfig,
-    LoopResult, StopReason, Usage,
+    execute_file_operations, spawn_agent, AgentResult, BackpressureResult, CompletionPromise,
+    LoopConfig, LoopResult, Model, StopReason, Usage,
 };
-use hox_core::{BackpressureStatus, CheckStatusEntry, HandoffContext, HoxError, Result, Task};
+use hox_core::{
+    BackpressureStatus, CheckStatusEntry, HandoffContext, HookEvent, HoxError, Result, Task,
+};
+use hox_evolution::{OrchestrationTrace, PatternExtractor, PatternStore, ReviewGate};
 use hox_jj::{JjExecutor, MetadataManager};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use tracing::{debug, info, warn};
 
+/// Base delay between transient retries (multiplied by the attempt number)
+const TRANSIENT_RETRY_BACKOFF_SECS: u64 = 5;
+
+/// How often a running loop looks for its stop file
+const STOP_FILE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// File that asks the loop running on `change_id` to stop
+///
+/// `change_id` should be the full change ID, so that the loop and
+/// `hox loop stop` agree on the path whatever prefix the user typed.
+pub fn stop_file_path(hox_dir: &Path, change_id: &str) -> PathBuf {
+    hox_dir.join("loops").join(format!("{}.stop", change_id))
+}
+
+/// Ask the loop running on `change_id` to stop (see [`watch_stop_file`])
+pub async fn request_stop(hox_dir: &Path, change_id: &str) -> Result<PathBuf> {
+    let path = stop_file_path(hox_dir, change_id);
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(&path, b"").await?;
+    Ok(path)
+}
+
+/// Set `cancel` once `stop_file` appears, consuming the file
+///
+/// A stop file left over from an earlier request is removed first, so only
+/// requests made while the watcher runs take effect. Abort the returned task
+/// when the loop ends.
+pub fn watch_stop_file(stop_file: PathBuf, cancel: Arc<AtomicBool>) -> tokio::task::JoinHandle<()> {
+    let _ = std::fs::remove_file(&stop_file);
+    tokio::spawn(async move {
+        loop {
+            if tokio::fs::try_exists(&stop_file).await.unwrap_or(false) {
+                info!("Stop requested via {}", stop_file.display());
+                let _ = tokio::fs::remove_file(&stop_file).await;
+                cancel.store(true, Ordering::SeqCst);
+                return;
+            }
+            tokio::time::sleep(STOP_FILE_POLL_INTERVAL).await;
+        }
+    })
+}
+
+/// Runs the fresh agent for one loop iteration
+#[async_trait]
+pub trait IterationAgent: Send + Sync {
+    async fn run(
+        &self,
+        prompt: &str,
+        iteration: usize,
+        model: Model,
+        max_tokens: usize,
+    ) -> Result<AgentResult>;
+}
+
+/// Default [`IterationAgent`]: a fresh Anthropic API call via [`spawn_agent`]
+pub struct ApiAgent;
+
+#[async_trait]
+impl IterationAgent for ApiAgent {
+    async fn run(
+        &self,
+        prompt: &str,
+        iteration: usize,
+        model: Model,
+        max_tokens: usize,
+    ) -> Result<AgentResult> {
+        spawn_agent(prompt, iteration, model, max_tokens).await
+    }
+}
+
+/// Aborts a background task when dropped
+struct AbortOnDrop(tokio::task::JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Whether the loop must stop before another iteration to stay within budget
+///
+/// The next iteration is assumed to cost as much as the last one, so the loop
+/// stops once the running total plus that estimate would exceed the budget.
+fn exceeds_budget(
+    max_budget_usd: Option<f64>,
+    total_cost_usd: f64,
+    iteration_cost_usd: f64,
+) -> bool {
+    match max_budget_usd {
+        Some(max) => total_cost_usd + iteration_cost_usd > max,
+        None => false,
+    }
+}
+
+/// Record a finished loop's outcome against the approved patterns it applied
+///
+/// Cancelled loops say nothing about the patterns and are not recorded.
+/// Returns the IDs of patterns that now need re-review, worst first.
+async fn record_pattern_outcome(
+    extractor: &mut PatternExtractor,
+    result: &LoopResult,
+) -> Result<Vec<String>> {
+    let applied: Vec<String> = extractor
+        .store()
+        .approved()
+        .iter()
+        .map(|p| p.id.clone())
+        .collect();
+    if applied.is_empty() || matches!(result.stop_reason, StopReason::Cancelled) {
+        return Ok(Vec::new());
+    }
+
+    let trace = OrchestrationTrace {
+        iterations: result.iterations,
+        task_type: "loop".to_string(),
+        success: result.success,
+        backpressure_config: None,
+        agent_performance: None,
+        applied_patterns: applied,
+    };
+    extractor.record_trace(&trace).await?;
+
+    Ok(ReviewGate::new()
+        .rereview_candidates(extractor.store())
+        .into_iter()
+        .map(|p| p.id.clone())
+        .collect())
+}
+
+/// What to do when spawning the agent for an iteration fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpawnFailureAction {
+    /// Infrastructure hiccup: retry the same iteration
+    Retry,
+    /// Infrastructure keeps failing: stop with `TransientRetriesExhausted`
+    Exhausted,
+    /// Not transient: propagate the error
+    Abort,
+}
+
+/// Classify a spawn failure under the configured transient retry policy
+fn classify_spawn_failure(
+    error: &HoxError,
+    retries_used: usize,
+    max_retries: usize,
+) -> SpawnFailureAction {
+    if !error.is_transient() {
+        SpawnFailureAction::Abort
+    } else if retries_used < max_retries {
+        SpawnFailureAction::Retry
+    } else {
+        SpawnFailureAction::Exhausted
+    }
+}
+
 /// Loop engine for running Ralph-style autonomous iterations
 pub struct LoopEngine<E: JjExecutor> {
     executor: E,
@@ -31,6 +196,10 @@ pub struct LoopEngine<E: JjExecutor> {
     workspace_path: PathBuf,
     activity_logger: Option<ActivityLogger>,
     hook_pipeline: HookPipeline,
+    cancel: Arc<AtomicBool>,
+    stop_file: Option<PathBuf>,
+    patterns: Option<PatternExtractor>,
+    agent: Arc<dyn IterationAgent>,
 }
 
 impl<E: JjExecutor + Clone + 'static> LoopEngine<E> {
@@ -53,6 +222,10 @@ impl<E: JjExecutor + Clone + 'static> LoopEngine<E> {
             workspace_path,
             activity_logger: None,
             hook_pipeline,
+            cancel: Arc::new(AtomicBool::new(false)),
+            stop_file: None,
+            patterns: None,
+            agent: Arc::new(ApiAgent),
         }
     }
 
@@ -62,6 +235,88 @@ impl<E: JjExecutor + Clone + 'static> LoopEngine<E> {
         self
     }
 
+    /// Add the command hooks configured in `.hox/hooks.toml` under `repo_root`
+    pub fn with_command_hooks(mut self, repo_root: &Path) -> Result<Self> {
+        self.hook_pipeline.load_command_hooks(repo_root)?;
+        Ok(self)
+    }
+
+    /// Run iterations with `agent` instead of the Anthropic API
+    pub fn with_agent(mut self, agent: impl IterationAgent + 'static) -> Self {
+        self.agent = Arc::new(agent);
+        self
+    }
+
+    /// Apply the approved patterns in `store` to every iteration prompt
+    ///
+    /// When the loop finishes, its outcome is recorded against those patterns
+    /// and patterns whose success rate has dropped are flagged for re-review.
+    pub fn with_patterns(mut self, store: PatternStore) -> Self {
+        self.patterns = Some(PatternExtractor::new(store));
+        self
+    }
+
+    /// Stop the loop when `stop_file` appears (see [`stop_file_path`])
+    ///
+    /// The file is polled while [`Self::run`] is in progress, so another
+    /// process such as `hox loop stop` can cancel the loop.
+    pub fn with_stop_file(mut self, stop_file: PathBuf) -> Self {
+        self.stop_file = Some(stop_file);
+        self
+    }
+
+    /// Stop the loop when `token` is set, instead of a flag of its own
+    pub fn with_cancellation_token(mut self, token: Arc<AtomicBool>) -> Self {
+        self.cancel = token;
+        self
+    }
+
+    /// Shared flag that stops the loop when set
+    ///
+    /// Setting it kills any running backpressure checks and ends the loop with
+    /// [`StopReason::Cancelled`] before the next iteration starts.
+    pub fn cancellation_token(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.cancel)
+    }
+
+    /// Request the loop to stop
+    pub fn stop(&self) {
+        self.cancel.store(true, Ordering::SeqCst);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::SeqCst)
+    }
+
+    /// Log and build the result for a loop stopped via [`Self::stop`]
+    async fn cancelled_result(
+        &self,
+        iterations: usize,
+        final_status: BackpressureResult,
+        files_created: Vec<String>,
+        files_modified: Vec<String>,
+        total_usage: Usage,
+    ) -> LoopResult {
+        info!("Loop cancelled after {} iterations", iterations);
+
+        if let Some(logger) = &self.activity_logger {
+            logger
+                .log_loop_complete(iterations, false, &total_usage, "Cancelled")
+                .await;
+        }
+
+        LoopResult {
+            iterations,
+            success: false,
+            final_status,
+            files_created,
+            files_modified,
+            total_cost_usd: self.config.estimate_cost_usd(&total_usage),
+            total_usage,
+            stop_reason: StopReason::Cancelled,
+        }
+    }
+
     /// Run the loop on a task
     ///
     /// This is the main entry point for Ralph-style autonomous iteration.
@@ -75,6 +330,35 @@ impl<E: JjExecutor + Clone + 'static> LoopEngine<E> {
     /// 7. Updates JJ change with new metadata
     /// 8. Repeats until all checks pass or max iterations
     pub async fn run(&mut self, task: &Task) -> Result<LoopResult> {
+        // Also aborted if this future is dropped, e.g. by a timeout
+        let stop_watcher = self
+            .stop_file
+            .clone()
+            .map(|path| AbortOnDrop(watch_stop_file(path, self.cancellation_token())));
+        let result = self.run_iterations(task).await;
+        drop(stop_watcher);
+
+        if let (Ok(result), Some(extractor)) = (&result, self.patterns.as_mut()) {
+            match record_pattern_outcome(extractor, result).await {
+                Ok(rereview) => {
+                    for id in rereview {
+                        if let Some(pattern) = extractor.store().get(&id) {
+                            warn!(
+                                "Pattern {} ({}) is down to {:.0}% success, needs re-review",
+                                pattern.name,
+                                id,
+                                pattern.success_rate * 100.0
+                            );
+                        }
+                    }
+                }
+                Err(e) => warn!("Failed to record pattern outcomes: {}", e),
+            }
+        }
+        result
+    }
+
+    async fn run_iterations(&mut self, task: &Task) -> Result<LoopResult> {
         info!("Starting loop for task: {}", task.change_id);
 
         // Log loop start if activity logging is enabled
@@ -99,12 +383,35 @@ impl<E: JjExecutor + Clone + 'static> LoopEngine<E> {
         let recovery_manager =
             RecoveryManager::new(self.executor.clone(), self.workspace_path.clone());
 
+        // Skips re-running checks on iterations that leave the tree unchanged
+        let mut backpressure_cache = BackpressureCache::new();
+
+        // Approved patterns are applied to every iteration
+        let patterns_section = self
+            .patterns
+            .as_ref()
+            .map(|p| p.store())
+            .filter(|store| !store.approved().is_empty())
+            .map(|store| store.format_for_prompt());
+
         let mut iteration: usize = 0;
+        let mut transient_retries: usize = 0;
         loop {
             iteration += 1;
             if self.config.max_iterations > 0 && iteration > self.config.max_iterations {
                 break;
             }
+            if self.is_cancelled() {
+                return Ok(self
+                    .cancelled_result(
+                        iteration - 1,
+                        backpressure,
+                        files_created,
+                        files_modified,
+                        total_usage,
+                    )
+                    .await);
+            }
             let max_display = if self.config.max_iterations == 0 {
                 "unlimited".to_string()
             } else {
@@ -125,8 +432,7 @@ impl<E: JjExecutor + Clone + 'static> LoopEngine<E> {
 
                 // Log usage summary
                 let total_tokens = total_usage.input_tokens + total_usage.output_tokens;
-                let cost_usd = (total_usage.input_tokens as f64 * 3.0 / 1_000_000.0)
-                    + (total_usage.output_tokens as f64 * 15.0 / 1_000_000.0);
+                let cost_usd = self.config.estimate_cost_usd(&total_usage);
                 info!(
                     "Loop usage summary: {} total tokens ({} input, {} output), estimated cost: ${:.4}",
                     total_tokens, total_usage.input_tokens, total_usage.output_tokens, cost_usd
@@ -145,6 +451,7 @@ impl<E: JjExecutor + Clone + 'static> LoopEngine<E> {
                     final_status: backpressure,
                     files_created,
                     files_modified,
+                    total_cost_usd: self.config.estimate_cost_usd(&total_usage),
                     total_usage,
                     stop_reason: StopReason::AllChecksPassed,
                 });
@@ -160,24 +467,85 @@ impl<E: JjExecutor + Clone + 'static> LoopEngine<E> {
             );
 
             // Build prompt
-            let prompt = build_iteration_prompt(
+            let mut prompt = build_iteration_prompt(
                 task,
                 &context,
                 &backpressure,
                 iteration,
                 self.config.max_iterations,
             );
+            if let Some(section) = &patterns_section {
+                prompt.push('\n');
+                prompt.push_str(section);
+            }
 
             debug!("Prompt length: {} chars", prompt.len());
 
             // Spawn fresh agent
-            let result = spawn_agent(
-                &prompt,
-                iteration,
-                self.config.model,
-                self.config.max_tokens,
-            )
-            .await?;
+            let result = match self
+                .agent
+                .run(
+                    &prompt,
+                    iteration,
+                    self.config.model,
+                    self.config.max_tokens,
+                )
+                .await
+            {
+                Ok(result) => {
+                    transient_retries = 0;
+                    result
+                }
+                Err(e) => {
+                    match classify_spawn_failure(&e, transient_retries, self.config.transient_retry)
+                    {
+                        SpawnFailureAction::Retry => {
+                            transient_retries += 1;
+                            warn!(
+                                "Iteration {} hit transient error ({}), retry {}/{}",
+                                iteration, e, transient_retries, self.config.transient_retry
+                            );
+                            tokio::time::sleep(Duration::from_secs(
+                                TRANSIENT_RETRY_BACKOFF_SECS * transient_retries as u64,
+                            ))
+                            .await;
+
+                            // Retry without consuming the iteration budget
+                            iteration -= 1;
+                            continue;
+                        }
+                        SpawnFailureAction::Exhausted => {
+                            warn!(
+                                "Iteration {} failed after {} transient retries: {}",
+                                iteration, transient_retries, e
+                            );
+
+                            if let Some(logger) = &self.activity_logger {
+                                logger
+                                    .log_loop_complete(
+                                        iteration - 1,
+                                        false,
+                                        &total_usage,
+                                        &format!("Transient retries exhausted: {}", e),
+                                    )
+                                    .await;
+                            }
+
+                            return Ok(LoopResult {
+                                iterations: iteration - 1,
+                                success: false,
+                                final_status: backpressure,
+                                files_created,
+                                files_modified,
+                                total_cost_usd: self.config.estimate_cost_usd(&total_usage),
+                                total_usage,
+                                stop_reason: StopReason::TransientRetriesExhausted,
+                            });
+                        }
+                        SpawnFailureAction::Abort => return Err(e),
+                    }
+                }
+            };
 
             // Check if agent output is empty or broken
             if result.output.trim().is_empty() {
@@ -237,34 +605,6 @@ impl<E: JjExecutor + Clone + 'static> LoopEngine<E> {
                 return Err(HoxError::BudgetExceeded(msg));
             }
 
-            // Check cost limit if configured (Sonnet pricing: $3/MTok input, $15/MTok output)
-            if let Some(max_budget_usd) = self.config.max_budget_usd {
-                let cost_usd = (total_usage.input_tokens as f64 * 3.0 / 1_000_000.0)
-                    + (total_usage.output_tokens as f64 * 15.0 / 1_000_000.0);
-
-                if cost_usd > max_budget_usd {
-                    let msg = format!(
-                        "Cost budget exceeded: ${:.4} spent (limit: ${:.2})",
-                        cost_usd, max_budget_usd
-                    );
-                    warn!("{}", msg);
-
-                    // Log completion
-                    if let Some(logger) = &self.activity_logger {
-                        logger
-                            .log_loop_complete(
-                                iteration,
-                                false,
-                                &total_usage,
-                                &format!("Budget exceeded: {}", msg),
-                            )
-                            .await;
-                    }
-
-                    return Err(HoxError::BudgetExceeded(msg));
-                }
-            }
-
             // Context freshness warning at 60% of 200K context window
             const CONTEXT_WINDOW: usize = 200_000;
             const FRESHNESS_THRESHOLD: usize = (CONTEXT_WINDOW as f64 * 0.6) as usize;
@@ -302,19 +642,52 @@ impl<E: JjExecutor + Clone + 'static> LoopEngine<E> {
 
             // Run backpressure checks (selective: only re-run previously failed)
             if self.config.backpressure_enabled {
+                let touched_files: Vec<String> = iteration_files_created
+                    .iter()
+                    .chain(&iteration_files_modified)
+                    .cloned()
+                    .collect();
                 backpressure = if iteration == 1 {
                     // First iteration: run all checks with jj fix to establish baseline
-                    run_all_checks_with_fix(
+                    let result = run_all_checks_with_fix(
                         &self.workspace_path,
                         &self.executor,
                         Some(&task.change_id),
+                        &touched_files,
+                        &self.cancel,
                     )
-                    .await?
+                    .await?;
+                    // Fingerprint after jj fix, which may have reformatted files
+                    let key = tree_fingerprint(&self.executor, &task.change_id).await;
+                    backpressure_cache.insert(key, &result);
+                    result
                 } else {
-                    // Subsequent: only re-run checks that failed last time
-                    run_failed_checks(&self.workspace_path, &backpressure)?
+                    // Subsequent: only re-run checks that failed last time,
+                    // and only if the tree changed since they last ran
+                    let previous = &backpressure;
+                    backpressure_cache
+                        .get_or_run(
+                            &self.executor,
+                            &task.change_id,
+                            !touched_files.is_empty(),
+                            || run_failed_checks(&self.workspace_path, previous, &self.cancel),
+                        )
+                        .await?
                 };
 
+                // Results of interrupted checks are meaningless; stop without recording them
+                if self.is_cancelled() {
+                    return Ok(self
+                        .cancelled_result(
+                            iteration,
+                            backpressure,
+                            files_created,
+                            files_modified,
+                            total_usage,
+                        )
+                        .await);
+                }
+
                 for check in &backpressure.checks {
                     info!(
                         "  {} {}",
@@ -346,8 +719,10 @@ impl<E: JjExecutor + Clone + 'static> LoopEngine<E> {
                 workspace_path: self.workspace_path.clone(),
                 iteration,
             };
-            let _hook_results = self.hook_pipeline.execute_all(&hook_context).await;
-            // Hooks are fail-open, so we don't check results
+            // Hooks are fail-open unless marked required
+            self.hook_pipeline
+                .execute_event(HookEvent::PostTools, &hook_context)
+                .await?;
 
             // Log iteration completion
             if let Some(logger) = &self.activity_logger {
@@ -368,8 +743,7 @@ impl<E: JjExecutor + Clone + 'static> LoopEngine<E> {
 
                 // Log usage summary
                 let total_tokens = total_usage.input_tokens + total_usage.output_tokens;
-                let cost_usd = (total_usage.input_tokens as f64 * 3.0 / 1_000_000.0)
-                    + (total_usage.output_tokens as f64 * 15.0 / 1_000_000.0);
+                let cost_usd = self.config.estimate_cost_usd(&total_usage);
                 info!(
                     "Loop usage summary: {} total tokens ({} input, {} output), estimated cost: ${:.4}",
                     total_tokens, total_usage.input_tokens, total_usage.output_tokens, cost_usd
@@ -393,6 +767,7 @@ impl<E: JjExecutor + Clone + 'static> LoopEngine<E> {
                     final_status: backpressure,
                     files_created,
                     files_modified,
+                    total_cost_usd: self.config.estimate_cost_usd(&total_usage),
                     total_usage,
                     stop_reason: StopReason::AgentStop,
                 });
@@ -420,8 +795,7 @@ impl<E: JjExecutor + Clone + 'static> LoopEngine<E> {
 
                 // Log usage summary
                 let total_tokens = total_usage.input_tokens + total_usage.output_tokens;
-                let cost_usd = (total_usage.input_tokens as f64 * 3.0 / 1_000_000.0)
-                    + (total_usage.output_tokens as f64 * 15.0 / 1_000_000.0);
+                let cost_usd = self.config.estimate_cost_usd(&total_usage);
                 info!(
                     "Loop usage summary: {} total tokens ({} input, {} output), estimated cost: ${:.4}",
                     total_tokens, total_usage.input_tokens, total_usage.output_tokens, cost_usd
@@ -453,10 +827,55 @@ impl<E: JjExecutor + Clone + 'static> LoopEngine<E> {
                     final_status: backpressure,
                     files_created,
                     files_modified,
+                    total_cost_usd: self.config.estimate_cost_usd(&total_usage),
                     total_usage,
                     stop_reason,
                 });
             }
+
+            // Budget enforcement: stop before an iteration that would overrun the cost limit.
+            // The work of the current iteration is kept, even if it alone exceeded the budget.
+            let total_cost_usd = self.config.estimate_cost_usd(&total_usage);
+            let iteration_cost_usd = result
+                .usage
+                .as_ref()
+                .map(|usage| self.config.estimate_cost_usd(usage))
+                .unwrap_or(0.0);
+            if exceeds_budget(
+                self.config.max_budget_usd,
+                total_cost_usd,
+                iteration_cost_usd,
+            ) {
+                let msg = format!(
+                    "Cost budget exceeded: ${:.4} spent, next iteration estimated at ${:.4} (limit: ${:.2})",
+                    total_cost_usd,
+                    iteration_cost_usd,
+                    self.config.max_budget_usd.unwrap_or_default()
+                );
+                warn!("{}", msg);
+
+                if let Some(logger) = &self.activity_logger {
+                    logger
+                        .log_loop_complete(
+                            iteration,
+                            backpressure.all_passed(),
+                            &total_usage,
+                            &format!("Budget exceeded: {}", msg),
+                        )
+                        .await;
+                }
+
+                return Ok(LoopResult {
+                    iterations: iteration,
+                    success: backpressure.all_passed(),
+                    final_status: backpressure,
+                    files_created,
+                    files_modified,
+                    total_cost_usd,
+                    total_usage,
+                    stop_reason: StopReason::BudgetExceeded,
+                });
+            }
         }
 
         // Max iterations reached (only when max_iterations > 0)
@@ -464,8 +883,7 @@ impl<E: JjExecutor + Clone + 'static> LoopEngine<E> {
 
         // Log usage summary
         let total_tokens = total_usage.input_tokens + total_usage.output_tokens;
-        let cost_usd = (total_usage.input_tokens as f64 * 3.0 / 1_000_000.0)
-            + (total_usage.output_tokens as f64 * 15.0 / 1_000_000.0);
+        let cost_usd = self.config.estimate_cost_usd(&total_usage);
         info!(
             "Loop usage summary: {} total tokens ({} input, {} output), estimated cost: ${:.4}",
             total_tokens, total_usage.input_tokens, total_usage.output_tokens, cost_usd
@@ -489,6 +907,7 @@ impl<E: JjExecutor + Clone + 'static> LoopEngine<E> {
             final_status: backpressure,
             files_created,
             files_modified,
+            total_cost_usd: self.config.estimate_cost_usd(&total_usage),
             total_usage,
             stop_reason: StopReason::MaxIterations,
         })
@@ -499,6 +918,12 @@ impl<E: JjExecutor + Clone + 'static> LoopEngine<E> {
         let manager = MetadataManager::new(self.executor.clone());
         let metadata = manager.read(&task.change_id).await?;
 
+        // Prefer the lossless block written by a previous iteration
+        if let Some(mut context) = HandoffContext::from_description_block(&task.description) {
+            context.loop_iteration = context.loop_iteration.or(metadata.loop_iteration);
+            return Ok(context);
+        }
+
         // Build context from task description and metadata
         let mut context = HandoffContext {
             current_focus: task.description.clone(),
@@ -639,6 +1064,10 @@ fn format_description(task: &Task, context: &HandoffContext, iteration: usize) -
         }
     }
 
+    // Machine-readable copy of the full context for the next read
+    desc.push('\n');
+    desc.push_str(&context.to_description_block());
+
     desc
 }
 
@@ -646,6 +1075,223 @@ fn format_description(task: &Task, context: &HandoffContext, iteration: usize) -
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_stop_file_trips_cancellation() {
+        let hox_dir = tempfile::TempDir::new().unwrap();
+        let stop_file = stop_file_path(hox_dir.path(), "abc123");
+
+        // A request left over from before the loop started is ignored
+        request_stop(hox_dir.path(), "abc123").await.unwrap();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let watcher = watch_stop_file(stop_file.clone(), Arc::clone(&cancel));
+        tokio::time::sleep(STOP_FILE_POLL_INTERVAL * 2).await;
+        assert!(!cancel.load(Ordering::SeqCst));
+
+        request_stop(hox_dir.path(), "abc123").await.unwrap();
+        tokio::time::timeout(Duration::from_secs(5), watcher)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(cancel.load(Ordering::SeqCst));
+        assert!(!stop_file.exists());
+    }
+
+    fn loop_result(success: bool, stop_reason: StopReason) -> LoopResult {
+        LoopResult {
+            iterations: 3,
+            success,
+            final_status: BackpressureResult::all_pass(),
+            files_created: Vec::new(),
+            files_modified: Vec::new(),
+            total_usage: Usage::default(),
+            total_cost_usd: 0.0,
+            stop_reason,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_pattern_outcome() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut store = PatternStore::new(dir.path());
+        let mut approved = hox_evolution::Pattern::new(
+            "Small steps",
+            hox_evolution::PatternCategory::Decomposition,
+            "One change per iteration",
+        );
+        approved.approve();
+        let pending = hox_evolution::Pattern::new(
+            "Pending",
+            hox_evolution::PatternCategory::Validation,
+            "Not applied",
+        );
+        let (approved_id, pending_id) = (approved.id.clone(), pending.id.clone());
+        store.save(approved).await.unwrap();
+        store.save(pending).await.unwrap();
+        let mut extractor = PatternExtractor::new(store);
+
+        let cancelled = loop_result(false, StopReason::Cancelled);
+        record_pattern_outcome(&mut extractor, &cancelled)
+            .await
+            .unwrap();
+        assert_eq!(extractor.store().get(&approved_id).unwrap().usage_count, 0);
+
+        let mut rereview = Vec::new();
+        for _ in 0..3 {
+            let failed = loop_result(false, StopReason::MaxIterations);
+            rereview = record_pattern_outcome(&mut extractor, &failed)
+                .await
+                .unwrap();
+        }
+        let pattern = extractor.store().get(&approved_id).unwrap();
+        assert_eq!(pattern.usage_count, 3);
+        assert_eq!(pattern.success_rate, 0.0);
+        assert_eq!(rereview, vec![approved_id]);
+        assert_eq!(extractor.store().get(&pending_id).unwrap().usage_count, 0);
+    }
+
+    /// Agent that answers every iteration with the same usage and no edits
+    struct FixedUsageAgent {
+        usage: Usage,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl IterationAgent for Arc<FixedUsageAgent> {
+        async fn run(
+            &self,
+            _prompt: &str,
+            iteration: usize,
+            _model: Model,
+            _max_tokens: usize,
+        ) -> Result<AgentResult> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(AgentResult {
+                iteration,
+                output: "Working on it.".to_string(),
+                timestamp: chrono::Utc::now(),
+                usage: Some(self.usage.clone()),
+            })
+        }
+    }
+
+    fn ok(stdout: &str) -> hox_jj::JjOutput {
+        hox_jj::JjOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            success: true,
+            code: Some(0),
+        }
+    }
+
+    /// Accepts every `describe`, whose message changes each iteration
+    #[derive(Clone)]
+    struct AcceptDescribe(hox_jj::MockJjExecutor);
+
+    #[async_trait]
+    impl JjExecutor for AcceptDescribe {
+        async fn exec(&self, args: &[&str]) -> Result<hox_jj::JjOutput> {
+            if args.first() == Some(&"describe") {
+                return Ok(ok(""));
+            }
+            self.0.exec(args).await
+        }
+
+        fn repo_root(&self) -> &PathBuf {
+            self.0.repo_root()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_loop_stops_when_budget_exceeded() {
+        let mock = hox_jj::MockJjExecutor::new()
+            .with_response("log -r abc123 -T description --no-graph", ok("Add a feature\n"))
+            .with_response(
+                "op log -n 1 -T operation_id ++ \"\\t\" ++ description ++ \"\\t\" ++ time ++ \"\\n\" --no-graph",
+                ok("op1\tsnapshot\t2024-01-01 12:00:00\n"),
+            );
+        let executor = AcceptDescribe(mock);
+        let workspace = tempfile::TempDir::new().unwrap();
+        // A check that never passes, so only the budget can stop the loop
+        std::fs::create_dir_all(workspace.path().join(".hox")).unwrap();
+        std::fs::write(
+            workspace.path().join(".hox/checks.toml"),
+            "[[check]]\nname = \"gate\"\nprogram = \"false\"\nseverity = \"breaking\"\n",
+        )
+        .unwrap();
+        // Sonnet list prices: $3/M input, $15/M output, so $0.045 per iteration
+        let agent = Arc::new(FixedUsageAgent {
+            usage: Usage {
+                input_tokens: 10_000,
+                output_tokens: 1_000,
+            },
+            calls: Default::default(),
+        });
+        let config = LoopConfig {
+            max_iterations: 10,
+            model: Model::Sonnet,
+            max_budget_usd: Some(0.10),
+            ..LoopConfig::default()
+        };
+        let mut engine = LoopEngine::new(
+            executor.clone(),
+            WorkspaceManager::new(executor),
+            config,
+            workspace.path().to_path_buf(),
+        )
+        .with_agent(Arc::clone(&agent));
+
+        let result = engine
+            .run(&Task::new("abc123", "Add a feature"))
+            .await
+            .unwrap();
+
+        // $0.09 after two iterations; a third would reach $0.135
+        assert!(
+            matches!(result.stop_reason, StopReason::BudgetExceeded),
+            "{:?}",
+            result
+        );
+        assert_eq!(agent.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(result.iterations, 2);
+        assert!((result.total_cost_usd - 0.09).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_external_cancellation_token_stops_loop() {
+        let executor = hox_jj::MockJjExecutor::new().with_response(
+            "log -r abc123 -T description --no-graph",
+            ok("Add a feature\n"),
+        );
+        let workspace = tempfile::TempDir::new().unwrap();
+        let agent = Arc::new(FixedUsageAgent {
+            usage: Usage::default(),
+            calls: Default::default(),
+        });
+        // Set by the caller, e.g. a Ctrl-C handler, before the first iteration
+        let token = Arc::new(AtomicBool::new(true));
+        let mut engine = LoopEngine::new(
+            executor.clone(),
+            WorkspaceManager::new(executor),
+            LoopConfig::default(),
+            workspace.path().to_path_buf(),
+        )
+        .with_agent(Arc::clone(&agent))
+        .with_cancellation_token(Arc::clone(&token));
+        assert!(Arc::ptr_eq(&engine.cancellation_token(), &token));
+
+        let result = engine
+            .run(&Task::new("abc123", "Add a feature"))
+            .await
+            .unwrap();
+
+        assert!(
+            matches!(result.stop_reason, StopReason::Cancelled),
+            "{:?}",
+            result
+        );
+        assert_eq!(agent.calls.load(Ordering::SeqCst), 0);
+    }
+
     #[test]
     fn test_extract_section() {
         let text = r#"
@@ -670,6 +1316,85 @@ Some description
         assert!(next.contains("Do thing"));
     }
 
+    #[test]
+    fn test_exceeds_budget() {
+        assert!(!exceeds_budget(None, 100.0, 100.0));
+        assert!(!exceeds_budget(Some(1.0), 0.4, 0.4));
+        // The next iteration is expected to cost as much as the last one
+        assert!(exceeds_budget(Some(1.0), 0.6, 0.5));
+        // A first iteration that alone overran the budget still stops the loop
+        assert!(exceeds_budget(Some(1.0), 1.5, 1.5));
+    }
+
+    #[test]
+    fn test_transient_failure_is_retried() {
+        let err = HoxError::ApiLimit("Circuit breaker is OPEN".to_string());
+
+        assert_eq!(
+            classify_spawn_failure(&err, 0, 2),
+            SpawnFailureAction::Retry
+        );
+        assert_eq!(
+            classify_spawn_failure(&err, 1, 2),
+            SpawnFailureAction::Retry
+        );
+        assert_eq!(
+            classify_spawn_failure(&err, 2, 2),
+            SpawnFailureAction::Exhausted
+        );
+
+        let timeout = HoxError::Api("Failed to send request: operation timed out".to_string());
+        assert_eq!(
+            classify_spawn_failure(&timeout, 0, 1),
+            SpawnFailureAction::Retry
+        );
+    }
+
+    #[test]
+    fn test_substantive_failure_is_not_retried() {
+        let err = HoxError::Auth("No API key".to_string());
+        assert_eq!(
+            classify_spawn_failure(&err, 0, 5),
+            SpawnFailureAction::Abort
+        );
+
+        let err = HoxError::JjCommand("describe failed".to_string());
+        assert_eq!(
+            classify_spawn_failure(&err, 0, 5),
+            SpawnFailureAction::Abort
+        );
+
+        for msg in [
+            "Anthropic API error 400 Bad Request: invalid model",
+            "Anthropic API error 401 Unauthorized: bad key",
+            "Failed to parse response: missing field `content`",
+            "No content in response",
+        ] {
+            let err = HoxError::Api(msg.to_string());
+            assert_eq!(
+                classify_spawn_failure(&err, 0, 5),
+                SpawnFailureAction::Abort,
+                "{}",
+                msg
+            );
+        }
+    }
+
+    #[test]
+    fn test_transient_retry_disabled() {
+        let err = HoxError::Api("Anthropic API error 503".to_string());
+        assert_eq!(
+            classify_spawn_failure(&err, 0, 0),
+            SpawnFailureAction::Exhausted
+        );
+
+        let err = HoxError::Api("Anthropic API error 429 Too Many Requests: slow down".to_string());
+        assert_eq!(
+            classify_spawn_failure(&err, 0, 0),
+            SpawnFailureAction::Exhausted
+        );
+    }
+
     #[test]
     fn test_parse_checklist() {
         let text = r#"
@@ -706,6 +1431,7 @@ Some description
         assert!(desc.contains("Adding tests"));
         assert!(desc.contains("Created module"));
         assert!(desc.contains("src/lib.rs"));
+        assert_eq!(HandoffContext::from_description_block(&desc), Some(context));
     }
 
     #[test]
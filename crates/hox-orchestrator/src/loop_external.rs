@@ -17,6 +17,7 @@ use hox_agent::{
 use hox_core::{BackpressureStatus, CheckStatusEntry, HandoffContext, HoxError, Result, Task};
 use hox_jj::{JjExecutor, MetadataManager};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
 use tracing::{debug, info};
 
 /// Configuration for a single external iteration
@@ -39,6 +40,8 @@ pub struct ExternalIterationConfig<'a> {
     pub workspace_path: PathBuf,
     /// Whether to run backpressure checks
     pub run_backpressure: bool,
+    /// Set to stop the iteration; kills in-flight backpressure checks
+    pub cancel: &'a AtomicBool,
 }
 
 /// Run a single external iteration
@@ -107,19 +110,28 @@ pub async fn run_external_iteration<E: JjExecutor>(
 
     // Update iteration tracking
     updated_context.loop_iteration = Some(config.iteration);
-    updated_context
-        .files_touched
-        .extend(exec_result.files_created.clone());
-    updated_context
-        .files_touched
-        .extend(exec_result.files_modified.clone());
+    extend_unique(
+        &mut updated_context.files_touched,
+        exec_result
+            .files_created
+            .iter()
+            .chain(&exec_result.files_modified),
+    );
 
     // Run backpressure checks if enabled (with jj fix)
     let new_backpressure = if config.run_backpressure {
+        let touched_files: Vec<String> = exec_result
+            .files_created
+            .iter()
+            .chain(&exec_result.files_modified)
+            .cloned()
+            .collect();
         let bp = run_all_checks_with_fix(
             &config.workspace_path,
             executor,
             Some(&config.task.change_id),
+            &touched_files,
+            config.cancel,
         )
         .await?;
         for check in &bp.checks {
@@ -143,9 +155,9 @@ pub async fn run_external_iteration<E: JjExecutor>(
             last_errors: bp.errors.clone(),
         });
 
-        bp
+        Some(bp)
     } else {
-        BackpressureResult::all_pass()
+        None
     };
 
     // Detect stop signals
@@ -160,13 +172,16 @@ pub async fn run_external_iteration<E: JjExecutor>(
 
     Ok(ExternalLoopResult {
         iteration: config.iteration,
-        success: new_backpressure.all_passed(),
+        success: new_backpressure
+            .as_ref()
+            .is_none_or(BackpressureResult::all_passed),
         output: result.output,
         context: context_json,
         files_created: exec_result.files_created,
         files_modified: exec_result.files_modified,
         usage: result.usage,
         stop_signal,
+        backpressure: new_backpressure,
     })
 }
 
@@ -220,6 +235,35 @@ pub async fn create_initial_state<E: JjExecutor>(
     })
 }
 
+/// State for the next iteration after `result` was produced from `state`
+///
+/// The next iteration is steered by this iteration's backpressure results;
+/// if checks were skipped there are none to carry forward.
+pub fn advance_state(state: &ExternalLoopState, result: &ExternalLoopResult) -> ExternalLoopState {
+    let mut files_touched = state.files_touched.clone();
+    extend_unique(
+        &mut files_touched,
+        result.files_created.iter().chain(&result.files_modified),
+    );
+
+    ExternalLoopState {
+        change_id: state.change_id.clone(),
+        iteration: result.iteration,
+        context: result.context.clone(),
+        backpressure: result.backpressure.clone(),
+        files_touched,
+    }
+}
+
+/// Append the files not already in `files`, keeping first-seen order
+fn extend_unique<'a>(files: &mut Vec<String>, new: impl IntoIterator<Item = &'a String>) {
+    for file in new {
+        if !files.contains(file) {
+            files.push(file.clone());
+        }
+    }
+}
+
 /// Detect stop signals in agent output
 ///
 /// Returns Some(signal_type) if a stop signal is detected:
@@ -244,6 +288,7 @@ fn detect_stop_signal(output: &str) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use hox_agent::{CheckOutcome, Severity};
 
     #[test]
     fn test_detect_stop_signal_legacy() {
@@ -265,4 +310,59 @@ mod tests {
         let output = "Still working...";
         assert_eq!(detect_stop_signal(output), None);
     }
+
+    fn state(iteration: usize) -> ExternalLoopState {
+        ExternalLoopState {
+            change_id: "abc123".to_string(),
+            iteration,
+            context: serde_json::json!({ "current_focus": "x".repeat(iteration * 100) }),
+            backpressure: None,
+            files_touched: (0..iteration)
+                .map(|i| format!("src/file_{}.rs", i))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_advance_state() {
+        let result = ExternalLoopResult {
+            iteration: 3,
+            success: true,
+            output: String::new(),
+            context: serde_json::json!({ "current_focus": "next" }),
+            files_created: vec!["src/new.rs".to_string()],
+            files_modified: vec!["src/lib.rs".to_string(), "src/file_1.rs".to_string()],
+            usage: None,
+            stop_signal: None,
+            backpressure: Some(BackpressureResult {
+                checks: vec![CheckOutcome {
+                    name: "test".to_string(),
+                    passed: false,
+                    severity: Severity::Breaking,
+                    output: "1 failed".to_string(),
+                }],
+                errors: vec!["1 failed".to_string()],
+            }),
+        };
+
+        let next = advance_state(&state(2), &result);
+
+        assert_eq!(next.change_id, "abc123");
+        assert_eq!(next.iteration, 3);
+        assert_eq!(next.context, result.context);
+        assert_eq!(
+            next.files_touched,
+            vec!["src/file_0.rs", "src/file_1.rs", "src/new.rs", "src/lib.rs"]
+        );
+        let backpressure = next.backpressure.unwrap();
+        assert!(!backpressure.all_passed());
+        assert_eq!(backpressure.failed_check_names(), vec!["test"]);
+
+        // Skipped checks leave nothing to carry forward
+        let skipped = ExternalLoopResult {
+            backpressure: None,
+            ..result
+        };
+        assert!(advance_state(&state(2), &skipped).backpressure.is_none());
+    }
 }
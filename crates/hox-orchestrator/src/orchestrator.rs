@@ -8,22 +8,38 @@ const CHILD_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 /// Sleep interval in the main orchestrator poll loop
 const POLL_LOOP_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long a conflict-resolution agent gets before the next attempt
+const CONFLICT_RESOLUTION_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Loop iterations a conflict-resolution agent gets per attempt
+const CONFLICT_RESOLUTION_MAX_ITERATIONS: usize = 5;
+
+/// Events buffered per subscriber before slow subscribers start lagging
+const EVENT_CHANNEL_CAPACITY: usize = 256;
 use hox_core::{
-    AgentId, ChangeId, ChildHandle, ChildStatus, DelegationPlan, DelegationStrategy, HoxError,
-    HoxMetadata, MessageType, OrchestratorId, Phase, Result, Task, TaskStatus,
+    AgentId, ChangeId, ChildHandle, ChildStatus, DelegationPlan, DelegationStrategy, HookEvent,
+    HoxError, HoxMetadata, MessageType, OrchestratorId, Phase, Result, Task, TaskStatus,
 };
+use hox_evolution::PatternStore;
 use hox_jj::{
-    AbsorbResult, BookmarkManager, DagOperations, JjCommand, JjExecutor, MetadataManager,
-    OpLogEvent, OpLogWatcher, ParallelizeResult, RevsetQueries, SplitResult,
+    AbsorbResult, BookmarkManager, ChangeIdResolver, DagOperations, JjCommand, JjExecutor,
+    MetadataManager, OpLogEvent, OpLogWatcher, ParallelizeResult, RevsetQueries, SplitResult,
 };
 
-use crate::loop_engine::LoopEngine;
+use crate::loop_engine::{stop_file_path, LoopEngine};
 use crate::workspace::WorkspaceManager as WM;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use tokio::sync::broadcast;
 use tracing::{debug, info, warn};
 
 use crate::communication::MessageRouter;
+use crate::hooks::{HookContext, HookPipeline};
 use crate::phases::{PhaseManager, PhaseStatus};
 use crate::state_machine;
 use crate::workspace::WorkspaceManager;
@@ -41,6 +57,10 @@ pub struct OrchestratorConfig {
     pub max_agents: usize,
     /// Strategy for delegating work to child orchestrators
     pub delegation_strategy: DelegationStrategy,
+    /// Conflict-resolution agents to spawn after integration before failing
+    pub max_conflict_retries: usize,
+    /// Maximum agent workspaces on disk (unlimited if unset)
+    pub max_workspaces: Option<usize>,
 }
 
 impl OrchestratorConfig {
@@ -51,6 +71,8 @@ impl OrchestratorConfig {
             parent: None,
             max_agents: 4,
             delegation_strategy: DelegationStrategy::None,
+            max_conflict_retries: 3,
+            max_workspaces: None,
         }
     }
 
@@ -68,6 +90,16 @@ impl OrchestratorConfig {
         self.delegation_strategy = strategy;
         self
     }
+
+    pub fn with_max_conflict_retries(mut self, retries: usize) -> Self {
+        self.max_conflict_retries = retries;
+        self
+    }
+
+    pub fn with_max_workspaces(mut self, max: usize) -> Self {
+        self.max_workspaces = Some(max);
+        self
+    }
 }
 
 /// State of an orchestrator
@@ -83,6 +115,8 @@ pub enum OrchestratorState {
     Waiting,
     /// Integrating results
     Integrating,
+    /// Waiting for agents to resolve integration conflicts
+    ResolvingConflicts,
     /// Validating results
     Validating,
     /// Completed successfully
@@ -91,6 +125,42 @@ pub enum OrchestratorState {
     Failed(String),
 }
 
+/// Observable orchestrator activity, delivered via [`Orchestrator::subscribe`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrchestratorEvent {
+    /// The orchestrator moved to a new state
+    StateChanged {
+        from: OrchestratorState,
+        to: OrchestratorState,
+    },
+    /// An agent was spawned with its own workspace and change
+    AgentSpawned {
+        agent: String,
+        change_id: Option<ChangeId>,
+    },
+    /// A Ralph-style loop on a task finished
+    LoopCompleted {
+        change_id: ChangeId,
+        success: bool,
+        iterations: usize,
+    },
+    /// A child orchestrator was spawned for a phase
+    ChildSpawned { child: OrchestratorId, phase: u32 },
+    /// A child orchestrator reported a new status
+    ChildStatusChanged {
+        child: OrchestratorId,
+        status: ChildStatus,
+    },
+    /// The current phase completed and the orchestrator moved on
+    PhaseAdvanced { completed: u32, next: Option<u32> },
+    /// Integration merge produced conflicts
+    IntegrationConflicts {
+        conflicts: usize,
+        auto_resolved: usize,
+        needs_human: usize,
+    },
+}
+
 /// The main orchestrator struct
 pub struct Orchestrator<E: JjExecutor> {
     config: OrchestratorConfig,
@@ -105,6 +175,114 @@ pub struct Orchestrator<E: JjExecutor> {
     children: HashMap<OrchestratorId, ChildHandle>,
     /// State machine for observability and pattern tracking
     sm_state: state_machine::State,
+    /// State machine events rejected as invalid in the state they arrived in
+    rejected_transitions: usize,
+    /// Event stream for subscribers (dashboard, progress reporting)
+    events: broadcast::Sender<OrchestratorEvent>,
+    /// Pre- and post-integration hooks from `.hox/hooks.toml`
+    hook_pipeline: HookPipeline,
+    /// Resolves integration conflicts (a [`LoopConflictAgent`] if unset)
+    conflict_agent: Option<Arc<dyn ConflictResolutionAgent>>,
+    /// Stops the loop run by [`Self::run_loop`] when set
+    loop_cancel: Arc<AtomicBool>,
+}
+
+/// Agent that resolves the conflicts left by an integration merge
+#[async_trait]
+pub trait ConflictResolutionAgent: Send + Sync {
+    /// Work on the conflicted merge `change_id` until it is done
+    ///
+    /// The orchestrator checks `conflicts()` afterwards, so returning `Ok`
+    /// does not have to mean every conflict was resolved.
+    async fn resolve(&self, change_id: &str, attempt: usize) -> Result<()>;
+}
+
+/// Default [`ConflictResolutionAgent`]: a [`LoopEngine`] run on the merge change
+pub struct LoopConflictAgent<E: JjExecutor> {
+    executor: E,
+    repo_root: PathBuf,
+    config: LoopConfig,
+}
+
+impl<E: JjExecutor + Clone + 'static> LoopConflictAgent<E> {
+    pub fn new(executor: E, repo_root: PathBuf) -> Self {
+        Self {
+            executor,
+            repo_root,
+            config: LoopConfig {
+                max_iterations: CONFLICT_RESOLUTION_MAX_ITERATIONS,
+                ..LoopConfig::default()
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl<E: JjExecutor + Clone + Send + Sync + 'static> ConflictResolutionAgent
+    for LoopConflictAgent<E>
+{
+    async fn resolve(&self, change_id: &str, attempt: usize) -> Result<()> {
+        let task = Task::new(
+            change_id,
+            format!(
+                "Resolve the merge conflicts in this integration merge (attempt {}). \
+                 Edit every conflicted file so no conflict markers remain, keeping the \
+                 intent of both sides.",
+                attempt
+            ),
+        );
+        let mut engine = LoopEngine::new(
+            self.executor.clone(),
+            WM::new(self.executor.clone()),
+            self.config.clone(),
+            self.repo_root.clone(),
+        );
+        engine.run(&task).await?;
+        Ok(())
+    }
+}
+
+/// Persisted orchestrator state, used to resume an interrupted run
+///
+/// Saved to `.hox/orchestrators/{id}.json` after initialization, agent spawns
+/// and phase advances.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrchestratorSnapshot {
+    pub id: OrchestratorId,
+    /// Orchestrator base change
+    pub change_id: Option<ChangeId>,
+    pub phases: Vec<Phase>,
+    /// Numbers of phases that had completed when the snapshot was taken
+    #[serde(default)]
+    pub completed_phases: Vec<u32>,
+    /// Spawned agents by name
+    #[serde(default)]
+    pub agents: HashMap<String, AgentId>,
+}
+
+impl OrchestratorSnapshot {
+    /// Path of the snapshot file for an orchestrator
+    pub fn path(repo_root: &Path, id: &OrchestratorId) -> PathBuf {
+        repo_root
+            .join(".hox")
+            .join("orchestrators")
+            .join(format!("{}.json", id))
+    }
+
+    /// Load the snapshot for an orchestrator, or `None` if none was saved
+    pub async fn load(repo_root: &Path, id: &OrchestratorId) -> Result<Option<Self>> {
+        let path = Self::path(repo_root, id);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| HoxError::Io(format!("Failed to read orchestrator state: {}", e)))?;
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| HoxError::Io(format!("Failed to parse orchestrator state: {}", e)))
+    }
 }
 
 impl Orchestrator<JjCommand> {
@@ -118,7 +296,12 @@ impl Orchestrator<JjCommand> {
 impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
     /// Create a new orchestrator with a custom executor
     pub async fn with_executor(config: OrchestratorConfig, executor: E) -> Result<Self> {
-        let workspace_manager = WorkspaceManager::new(executor.clone());
+        let mut workspace_manager = WorkspaceManager::new(executor.clone());
+        if let Some(max) = config.max_workspaces {
+            workspace_manager = workspace_manager.with_max_workspaces(max);
+        }
+        let mut hook_pipeline = HookPipeline::new();
+        hook_pipeline.load_command_hooks(&config.repo_root)?;
 
         Ok(Self {
             config,
@@ -131,9 +314,122 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
             change_id: None,
             children: HashMap::new(),
             sm_state: state_machine::State::Idle,
+            rejected_transitions: 0,
+            events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+            hook_pipeline,
+            conflict_agent: None,
+            loop_cancel: Arc::new(AtomicBool::new(false)),
         })
     }
 
+    /// Shared flag that stops [`Self::run_loop`] gracefully when set
+    ///
+    /// Running backpressure checks are killed and the loop ends with
+    /// `StopReason::Cancelled`. Callers wire this to their own signal handling
+    /// (the CLI sets it on Ctrl-C).
+    pub fn loop_cancellation_token(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.loop_cancel)
+    }
+
+    /// Use `agent` to resolve conflicts left by integration
+    pub fn with_conflict_agent(mut self, agent: impl ConflictResolutionAgent + 'static) -> Self {
+        self.conflict_agent = Some(Arc::new(agent));
+        self
+    }
+
+    /// Resume an orchestrator from its saved snapshot
+    ///
+    /// Reconnects to the existing base change instead of creating a new one,
+    /// restores phases and agents, and skips phases that are completed -
+    /// either recorded as such in the snapshot or with every task marked
+    /// done in its metadata.
+    pub async fn resume(config: OrchestratorConfig, executor: E) -> Result<Self> {
+        let snapshot = OrchestratorSnapshot::load(&config.repo_root, &config.id)
+            .await?
+            .ok_or_else(|| {
+                HoxError::Orchestrator(format!("No saved state for orchestrator {}", config.id))
+            })?;
+
+        info!("Resuming orchestrator {}", config.id);
+
+        if let Some(change_id) = &snapshot.change_id {
+            let queries = RevsetQueries::new(executor.clone());
+            if queries.present(change_id).await?.is_none() {
+                return Err(HoxError::Orchestrator(format!(
+                    "Base change {} of orchestrator {} no longer exists",
+                    change_id, config.id
+                )));
+            }
+        }
+
+        let mut orchestrator = Self::with_executor(config, executor).await?;
+        orchestrator.change_id = snapshot.change_id;
+        orchestrator.agents = snapshot.agents;
+
+        let manager = MetadataManager::new(orchestrator.executor.clone());
+        for phase in snapshot.phases {
+            let number = phase.number;
+            let mut completed = snapshot.completed_phases.contains(&number);
+            if !completed && !phase.tasks.is_empty() {
+                completed = true;
+                for task in &phase.tasks {
+                    if manager.read(task).await?.status != Some(TaskStatus::Done) {
+                        completed = false;
+                        break;
+                    }
+                }
+            }
+
+            orchestrator.phases.add_phase(phase);
+            if completed {
+                debug!("Phase {} already completed", number);
+                orchestrator
+                    .phases
+                    .set_phase_status(number, PhaseStatus::Completed);
+            }
+        }
+        orchestrator.phases.skip_completed();
+
+        orchestrator.persist().await;
+        Ok(orchestrator)
+    }
+
+    /// Capture the state needed to resume this orchestrator
+    pub fn snapshot(&self) -> OrchestratorSnapshot {
+        let phases = self.phases.phases().to_vec();
+        let completed_phases = phases
+            .iter()
+            .map(|p| p.number)
+            .filter(|n| self.phases.phase_status(*n) == Some(&PhaseStatus::Completed))
+            .collect();
+
+        OrchestratorSnapshot {
+            id: self.config.id.clone(),
+            change_id: self.change_id.clone(),
+            phases,
+            completed_phases,
+            agents: self.agents.clone(),
+        }
+    }
+
+    /// Write the current snapshot to `.hox/orchestrators/{id}.json`
+    pub async fn save_snapshot(&self) -> Result<()> {
+        let path = OrchestratorSnapshot::path(&self.config.repo_root, &self.config.id);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let json = serde_json::to_string_pretty(&self.snapshot())?;
+        write_atomic(&path, &json).await
+    }
+
+    /// Save a snapshot, logging rather than failing on errors
+    async fn persist(&self) {
+        if let Err(e) = self.save_snapshot().await {
+            warn!("Failed to save orchestrator state: {}", e);
+        }
+    }
+
     /// Get the orchestrator ID
     pub fn id(&self) -> &OrchestratorId {
         &self.config.id
@@ -144,6 +440,37 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
         &self.state
     }
 
+    /// Number of state machine events rejected as invalid transitions
+    ///
+    /// Non-zero means the orchestrator's control flow and the state machine
+    /// disagree about where the run is.
+    pub fn rejected_transitions(&self) -> usize {
+        self.rejected_transitions
+    }
+
+    /// Subscribe to orchestrator events
+    ///
+    /// Each subscriber receives every event emitted after it subscribed.
+    /// Subscribers that fall more than `EVENT_CHANNEL_CAPACITY` events behind
+    /// miss the oldest ones (`RecvError::Lagged`).
+    pub fn subscribe(&self) -> broadcast::Receiver<OrchestratorEvent> {
+        self.events.subscribe()
+    }
+
+    /// Publish an event to all current subscribers
+    fn emit(&self, event: OrchestratorEvent) {
+        // No subscribers is fine - events are purely observational
+        let _ = self.events.send(event);
+    }
+
+    /// Transition to a new state, emitting `StateChanged` if it differs
+    fn set_state(&mut self, state: OrchestratorState) {
+        if self.state != state {
+            let from = std::mem::replace(&mut self.state, state.clone());
+            self.emit(OrchestratorEvent::StateChanged { from, to: state });
+        }
+    }
+
     /// Initialize the orchestrator's workspace and base change
     pub async fn initialize(&mut self) -> Result<()> {
         info!("Initializing orchestrator {}", self.config.id);
@@ -185,7 +512,8 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
                 .await?;
         }
 
-        self.state = OrchestratorState::Initialized;
+        self.set_state(OrchestratorState::Initialized);
+        self.persist().await;
         Ok(())
     }
 
@@ -223,23 +551,27 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
 
         // Set agent metadata and create bookmark assignment
         let queries = RevsetQueries::new(self.executor.clone());
-        if let Some(change_id) = queries.current().await? {
+        let change_id = queries.current().await?;
+        if let Some(change_id) = &change_id {
             let metadata = HoxMetadata::new()
                 .with_status(TaskStatus::InProgress)
                 .with_agent(&agent_name)
                 .with_orchestrator(self.config.id.to_string());
 
             let manager = MetadataManager::new(self.executor.clone());
-            manager.set(&change_id, &metadata).await?;
+            manager.set(change_id, &metadata).await?;
 
             // Create bookmark assignment for the agent
             let bookmark_manager = BookmarkManager::new(self.executor.clone());
-            bookmark_manager
-                .assign_task(&agent_name, &change_id)
-                .await?;
+            bookmark_manager.assign_task(&agent_name, change_id).await?;
         }
 
         self.agents.insert(agent_name.clone(), agent_id.clone());
+        self.emit(OrchestratorEvent::AgentSpawned {
+            agent: agent_name,
+            change_id,
+        });
+        self.persist().await;
         Ok(agent_id)
     }
 
@@ -303,7 +635,7 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
 
     /// Start the orchestration loop
     pub async fn run(&mut self) -> Result<()> {
-        self.state = OrchestratorState::Running;
+        self.set_state(OrchestratorState::Running);
         info!("Orchestrator {} starting run", self.config.id);
 
         // Start oplog watcher
@@ -322,11 +654,17 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
             if let Some(current_phase) = self.phases.current_phase() {
                 match self.phases.phase_status(current_phase.number) {
                     Some(PhaseStatus::Completed) => {
-                        info!("Phase {} completed, advancing", current_phase.number);
+                        let completed = current_phase.number;
+                        info!("Phase {} completed, advancing", completed);
                         self.phases.advance()?;
+                        self.emit(OrchestratorEvent::PhaseAdvanced {
+                            completed,
+                            next: self.phases.current_phase().map(|p| p.number),
+                        });
+                        self.persist().await;
                     }
                     Some(PhaseStatus::Failed(reason)) => {
-                        self.state = OrchestratorState::Failed(reason.clone());
+                        self.set_state(OrchestratorState::Failed(reason.clone()));
                         break;
                     }
                     // Pending and Active statuses continue waiting
@@ -334,7 +672,7 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
                 }
             } else {
                 // No more phases
-                self.state = OrchestratorState::Integrating;
+                self.set_state(OrchestratorState::Integrating);
                 break;
             }
 
@@ -359,8 +697,14 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
             OpLogEvent::NewOperation {
                 operation_id,
                 description,
+                affected_files,
             } => {
-                debug!("New operation: {} - {}", operation_id, description);
+                debug!(
+                    "New operation: {} - {} ({} files)",
+                    operation_id,
+                    description,
+                    affected_files.len()
+                );
                 // Check if this affects our agents
                 // TODO: Parse operation and update state accordingly
             }
@@ -377,15 +721,12 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
     /// Integrate completed agent work
     async fn integrate(&mut self) -> Result<()> {
         info!("Integrating agent work");
-        self.state = OrchestratorState::Integrating;
+        self.set_state(OrchestratorState::Integrating);
 
         // State machine transition: Moving to integration
-        let (new_sm_state, actions) = state_machine::transition(
-            self.sm_state.clone(),
-            state_machine::Event::AllTasksComplete,
-        );
-        self.sm_state = new_sm_state;
-        self.execute_actions(actions);
+        self.apply_sm_event(state_machine::Event::AllTasksComplete);
+
+        self.run_integration_hooks(HookEvent::PreIntegrate).await?;
 
         // Get all agent changes - try bookmark-based query first, fallback to description
         let queries = RevsetQueries::new(self.executor.clone());
@@ -418,6 +759,11 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
 
                 let resolver = crate::ConflictResolver::new(self.executor.clone());
                 let report = resolver.resolve_all().await?;
+                self.emit(OrchestratorEvent::IntegrationConflicts {
+                    conflicts: conflicts.len(),
+                    auto_resolved: report.auto_resolved,
+                    needs_human: report.needs_human,
+                });
 
                 if report.needs_human > 0 {
                     warn!("{} conflicts need human review", report.needs_human);
@@ -428,18 +774,140 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
                 if report.auto_resolved > 0 {
                     info!("Auto-resolved {} conflicts", report.auto_resolved);
                 }
+
+                let remaining = queries.conflicts().await?;
+                if !remaining.is_empty() && !self.resolve_conflicts_with_agents(remaining).await? {
+                    return Ok(());
+                }
             }
         }
 
-        self.state = OrchestratorState::Validating;
+        self.run_integration_hooks(HookEvent::PostIntegrate).await?;
+
+        self.set_state(OrchestratorState::Validating);
+        Ok(())
+    }
+
+    /// Run the hooks for an integration event, failing the run if one fails
+    ///
+    /// Otherwise the orchestrator would be left mid-integration with no way
+    /// forward.
+    async fn run_integration_hooks(&mut self, event: HookEvent) -> Result<()> {
+        if let Err(e) = self.run_hooks(event).await {
+            self.set_state(OrchestratorState::Failed(e.to_string()));
+            return Err(e);
+        }
         Ok(())
     }
 
+    /// Run the configured hooks for an integration event
+    ///
+    /// Integration is not part of a loop, so hooks see iteration 0 and no
+    /// `HOX_ITERATION`; `HOX_CHANGE_ID` is the orchestrator's base change.
+    async fn run_hooks(&self, event: HookEvent) -> Result<()> {
+        let context = HookContext {
+            change_id: self
+                .change_id
+                .as_ref()
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            workspace_path: self.config.repo_root.clone(),
+            iteration: 0,
+        };
+        self.hook_pipeline.execute_event(event, &context).await?;
+        Ok(())
+    }
+
+    /// Run conflict-resolution agents on the merge until `conflicts()` is empty
+    ///
+    /// Each attempt runs the conflict agent for up to
+    /// `CONFLICT_RESOLUTION_TIMEOUT`. Returns false, leaving the orchestrator
+    /// `Failed`, if the agent cannot run or `max_conflict_retries` attempts
+    /// are exhausted.
+    async fn resolve_conflicts_with_agents(&mut self, conflicts: Vec<ChangeId>) -> Result<bool> {
+        self.set_state(OrchestratorState::ResolvingConflicts);
+        self.apply_sm_event(state_machine::Event::IntegrationConflict {
+            description: format!("{} conflicted changes", conflicts.len()),
+        });
+
+        let queries = RevsetQueries::new(self.executor.clone());
+        let merge = match queries.current().await? {
+            Some(merge) => merge,
+            None => {
+                self.set_state(OrchestratorState::Failed(
+                    "Integration merge change not found".to_string(),
+                ));
+                return Ok(false);
+            }
+        };
+        let agent = self.conflict_agent.clone().unwrap_or_else(|| {
+            Arc::new(LoopConflictAgent::new(
+                self.executor.clone(),
+                self.config.repo_root.clone(),
+            ))
+        });
+        let max_retries = self.config.max_conflict_retries;
+        let mut remaining = conflicts.len();
+
+        for attempt in 1..=max_retries {
+            warn!(
+                "{} conflicts remain, running resolution agent (attempt {}/{})",
+                remaining, attempt, max_retries
+            );
+            match tokio::time::timeout(CONFLICT_RESOLUTION_TIMEOUT, agent.resolve(&merge, attempt))
+                .await
+            {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    self.set_state(OrchestratorState::Failed(format!(
+                        "Conflict resolution agent failed: {}",
+                        e
+                    )));
+                    return Ok(false);
+                }
+                Err(_) => warn!(
+                    "Conflict resolution agent timed out after {:?}",
+                    CONFLICT_RESOLUTION_TIMEOUT
+                ),
+            }
+
+            remaining = queries.conflicts().await?.len();
+            if remaining == 0 {
+                info!("Integration conflicts resolved after {} attempts", attempt);
+                return Ok(true);
+            }
+        }
+
+        self.set_state(OrchestratorState::Failed(format!(
+            "{} integration conflicts unresolved after {} attempts",
+            remaining, max_retries
+        )));
+        Ok(false)
+    }
+
     /// Get the orchestrator's change ID
     pub fn change_id(&self) -> Option<&ChangeId> {
         self.change_id.as_ref()
     }
 
+    /// Advance the state machine and execute the resulting actions
+    ///
+    /// The state machine is advisory: an event that is invalid in the current
+    /// state leaves the state unchanged, but is logged as a warning and
+    /// counted in [`rejected_transitions`](Self::rejected_transitions).
+    fn apply_sm_event(&mut self, event: state_machine::Event) {
+        match state_machine::transition(self.sm_state.clone(), event) {
+            Ok((new_sm_state, actions)) => {
+                self.sm_state = new_sm_state;
+                self.execute_actions(actions);
+            }
+            Err(e) => {
+                self.rejected_transitions += 1;
+                warn!("[State Machine] Rejected event: {}", e);
+            }
+        }
+    }
+
     /// Execute actions from state machine transition
     ///
     /// This method handles the side effects produced by state machine transitions.
@@ -483,6 +951,11 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
         &self.agents
     }
 
+    /// Get the orchestrator's phases
+    pub fn phases(&self) -> &PhaseManager {
+        &self.phases
+    }
+
     /// Plan how to distribute phases across orchestrators
     pub fn plan_delegation(&self, phases: &[Phase]) -> Vec<DelegationPlan> {
         match &self.config.delegation_strategy {
@@ -579,6 +1052,10 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
         };
 
         self.children.insert(child_id.clone(), handle);
+        self.emit(OrchestratorEvent::ChildSpawned {
+            child: child_id.clone(),
+            phase: phase_number,
+        });
         Ok(child_id)
     }
 
@@ -592,7 +1069,13 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
     /// Update a child's status
     pub fn update_child_status(&mut self, child_id: &OrchestratorId, status: ChildStatus) {
         if let Some(handle) = self.children.get_mut(child_id) {
-            handle.status = status;
+            if handle.status != status {
+                handle.status = status.clone();
+                self.emit(OrchestratorEvent::ChildStatusChanged {
+                    child: child_id.clone(),
+                    status,
+                });
+            }
         }
     }
 
@@ -621,15 +1104,37 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
             .await
             .map_err(|e| HoxError::Io(format!("Failed to create .hox directory: {}", e)))?;
 
+        // `hox loop stop` addresses the loop by full change ID
+        let full_change_id = ChangeIdResolver::new(self.executor.clone())
+            .normalize_change_id(&task.change_id)
+            .await
+            .unwrap_or_else(|_| task.change_id.clone());
+        let stop_file = stop_file_path(&hox_dir, &full_change_id);
+
+        let mut patterns = PatternStore::new(hox_dir.join("patterns"));
+        if let Err(e) = patterns.load().await {
+            warn!("Failed to load patterns, running without them: {}", e);
+        }
+
         let mut loop_engine = LoopEngine::new(
             self.executor.clone(),
             workspace_manager,
             config,
             self.config.repo_root.clone(),
         )
-        .with_activity_logging(hox_dir);
-
-        loop_engine.run(&task).await
+        .with_activity_logging(hox_dir)
+        .with_command_hooks(&self.config.repo_root)?
+        .with_stop_file(stop_file)
+        .with_patterns(patterns)
+        .with_cancellation_token(self.loop_cancellation_token());
+
+        let result = loop_engine.run(&task).await?;
+        self.emit(OrchestratorEvent::LoopCompleted {
+            change_id: task.change_id.clone(),
+            success: result.success,
+            iterations: result.iterations,
+        });
+        Ok(result)
     }
 
     /// Send assignment to a child orchestrator
@@ -704,9 +1209,7 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
 
         // Apply updates
         for (child_id, status) in &updates {
-            if let Some(handle) = self.children.get_mut(child_id) {
-                handle.status = status.clone();
-            }
+            self.update_child_status(child_id, status.clone());
         }
 
         Ok(updates)
@@ -719,21 +1222,22 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
 
     /// Run orchestration with hierarchical delegation
     pub async fn run_with_delegation(&mut self) -> Result<()> {
-        self.state = OrchestratorState::Planning;
+        self.set_state(OrchestratorState::Planning);
         info!("Orchestrator {} starting with delegation", self.config.id);
 
         // State machine transition: Start orchestration
-        let (new_sm_state, actions) = state_machine::transition(
-            self.sm_state.clone(),
-            state_machine::Event::StartOrchestration {
-                goal: "Run hierarchical delegation".to_string(),
-            },
-        );
-        self.sm_state = new_sm_state;
-        self.execute_actions(actions);
-
-        // Get phases from the phase manager
-        let phases: Vec<Phase> = self.phases.phases().to_vec();
+        self.apply_sm_event(state_machine::Event::StartOrchestration {
+            goal: "Run hierarchical delegation".to_string(),
+        });
+
+        // Get phases from the phase manager, skipping any completed before a resume
+        let phases: Vec<Phase> = self
+            .phases
+            .phases()
+            .iter()
+            .filter(|p| self.phases.phase_status(p.number) != Some(&PhaseStatus::Completed))
+            .cloned()
+            .collect();
 
         // Plan delegation
         let delegation_plans = self.plan_delegation(&phases);
@@ -746,12 +1250,7 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
 
         // State machine transition: Planning complete
         let task_count = delegation_plans.iter().filter(|p| matches!(p, DelegationPlan::ToChild { .. })).count();
-        let (new_sm_state, actions) = state_machine::transition(
-            self.sm_state.clone(),
-            state_machine::Event::PlanningComplete { task_count },
-        );
-        self.sm_state = new_sm_state;
-        self.execute_actions(actions);
+        self.apply_sm_event(state_machine::Event::PlanningComplete { task_count });
 
         // Spawn children for delegated phases
         for plan in &delegation_plans {
@@ -764,7 +1263,7 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
             }
         }
 
-        self.state = OrchestratorState::Running;
+        self.set_state(OrchestratorState::Running);
 
         // Monitor children until all complete (with timeout)
         let delegation_start = std::time::Instant::now();
@@ -829,39 +1328,24 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
         }
 
         // All children done -> Integration phase
-        self.state = OrchestratorState::Integrating;
+        self.set_state(OrchestratorState::Integrating);
 
         // State machine transition: All tasks complete
-        let (new_sm_state, actions) = state_machine::transition(
-            self.sm_state.clone(),
-            state_machine::Event::AllTasksComplete,
-        );
-        self.sm_state = new_sm_state;
-        self.execute_actions(actions);
+        self.apply_sm_event(state_machine::Event::AllTasksComplete);
 
         self.integrate_child_work().await?;
 
         // State machine transition: Integration clean (simplified - actual conflict detection in integrate_child_work)
-        let (new_sm_state, actions) = state_machine::transition(
-            self.sm_state.clone(),
-            state_machine::Event::IntegrationClean,
-        );
-        self.sm_state = new_sm_state;
-        self.execute_actions(actions);
+        self.apply_sm_event(state_machine::Event::IntegrationClean);
 
         // Validation phase
-        self.state = OrchestratorState::Validating;
+        self.set_state(OrchestratorState::Validating);
         // TODO: Run validation phase
 
         // State machine transition: Validation passed (simplified)
-        let (new_sm_state, actions) = state_machine::transition(
-            self.sm_state.clone(),
-            state_machine::Event::ValidationPassed,
-        );
-        self.sm_state = new_sm_state;
-        self.execute_actions(actions);
+        self.apply_sm_event(state_machine::Event::ValidationPassed);
 
-        self.state = OrchestratorState::Completed;
+        self.set_state(OrchestratorState::Completed);
         info!("Orchestrator {} completed with delegation", self.config.id);
 
         Ok(())
@@ -911,6 +1395,11 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
 
                 let resolver = crate::ConflictResolver::new(self.executor.clone());
                 let report = resolver.resolve_all().await?;
+                self.emit(OrchestratorEvent::IntegrationConflicts {
+                    conflicts: conflicts.len(),
+                    auto_resolved: report.auto_resolved,
+                    needs_human: report.needs_human,
+                });
 
                 if report.needs_human > 0 {
                     warn!(
@@ -995,6 +1484,29 @@ impl<E: JjExecutor + Clone + 'static> Orchestrator<E> {
     }
 }
 
+/// Write a file via a temporary sibling and rename, so readers never see partial content
+async fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| HoxError::Io(format!("Invalid state file path: {}", path.display())))?;
+    let tmp_path = path.with_file_name(format!(
+        ".{}.{}.tmp",
+        file_name.to_string_lossy(),
+        uuid::Uuid::new_v4()
+    ));
+
+    tokio::fs::write(&tmp_path, contents)
+        .await
+        .map_err(|e| HoxError::Io(format!("Failed to write state file: {}", e)))?;
+
+    if let Err(e) = tokio::fs::rename(&tmp_path, path).await {
+        let _ = tokio::fs::remove_file(&tmp_path).await;
+        return Err(HoxError::Io(format!("Failed to replace state file: {}", e)));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1033,7 +1545,7 @@ mod tests {
         let config = OrchestratorConfig::new(OrchestratorId::root(), "/tmp/repo");
 
         // Create a mock orchestrator-like struct to test plan_delegation logic
-        let phases = vec![
+        let phases = [
             Phase::contracts("Define interfaces"),
             Phase {
                 number: 1,
@@ -1069,7 +1581,7 @@ mod tests {
         let config = OrchestratorConfig::new(OrchestratorId::root(), "/tmp/repo")
             .with_delegation_strategy(DelegationStrategy::PhasePerChild);
 
-        let phases = vec![
+        let phases = [
             Phase::contracts("Define interfaces"), // blocking -> Local
             Phase {
                 number: 1,
@@ -1107,4 +1619,387 @@ mod tests {
             DelegationStrategy::PhasePerChild
         ));
     }
+
+    #[tokio::test]
+    async fn test_subscribe_receives_run_events() {
+        let config = OrchestratorConfig::new(OrchestratorId::root(), "/tmp/repo");
+        let mut orchestrator = Orchestrator::with_executor(config, hox_jj::MockJjExecutor::new())
+            .await
+            .unwrap();
+        let mut first = orchestrator.subscribe();
+        let mut second = orchestrator.subscribe();
+
+        // No phases: plan, run, integrate and validate without touching jj
+        orchestrator.run_with_delegation().await.unwrap();
+
+        let transition = |from, to| OrchestratorEvent::StateChanged { from, to };
+        let expected = vec![
+            transition(OrchestratorState::Initialized, OrchestratorState::Planning),
+            transition(OrchestratorState::Planning, OrchestratorState::Running),
+            transition(OrchestratorState::Running, OrchestratorState::Integrating),
+            transition(
+                OrchestratorState::Integrating,
+                OrchestratorState::Validating,
+            ),
+            transition(OrchestratorState::Validating, OrchestratorState::Completed),
+        ];
+
+        for receiver in [&mut first, &mut second] {
+            let mut events = Vec::new();
+            while let Ok(event) = receiver.try_recv() {
+                events.push(event);
+            }
+            assert_eq!(events, expected);
+        }
+    }
+
+    fn ok(stdout: &str) -> hox_jj::JjOutput {
+        hox_jj::JjOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            success: true,
+            code: Some(0),
+        }
+    }
+
+    fn integration_executor(conflicts: &str) -> hox_jj::MockJjExecutor {
+        conflict_sequence_executor(&[conflicts])
+    }
+
+    /// `conflicts()` answers in order (the last repeats): the merge check,
+    /// the auto-resolver, the re-check, then one per resolution attempt
+    fn conflict_sequence_executor(conflicts: &[&str]) -> hox_jj::MockJjExecutor {
+        hox_jj::MockJjExecutor::new()
+            .with_response(
+                r#"log -r bookmarks(glob:"orchestrator/*") -T change_id ++ "\n" --no-graph"#,
+                ok("aaa111\nbbb222\n"),
+            )
+            .with_response("new aaa111 bbb222 -m Integration merge", ok(""))
+            .with_responses(
+                r#"log -r conflicts() -T change_id ++ "\n" --no-graph"#,
+                conflicts.iter().map(|c| ok(c)).collect(),
+            )
+            .with_response(
+                r#"log -r @ -T change_id ++ "\n" --no-graph"#,
+                ok("ccc333\n"),
+            )
+            .with_response("diff -r ccc333 --stat", ok(" docs/design.md | 4 ++--\n"))
+    }
+
+    /// Conflict agent that hangs or fails on chosen attempts
+    #[derive(Default)]
+    struct ScriptedConflictAgent {
+        attempts: std::sync::Mutex<Vec<(String, usize)>>,
+        hang_on: Option<usize>,
+        fail_on: Option<usize>,
+    }
+
+    #[async_trait]
+    impl ConflictResolutionAgent for Arc<ScriptedConflictAgent> {
+        async fn resolve(&self, change_id: &str, attempt: usize) -> Result<()> {
+            self.attempts
+                .lock()
+                .unwrap()
+                .push((change_id.to_string(), attempt));
+            if self.hang_on == Some(attempt) {
+                std::future::pending::<()>().await;
+            }
+            if self.fail_on == Some(attempt) {
+                return Err(HoxError::Orchestrator("Maximum agents (4) reached".into()));
+            }
+            Ok(())
+        }
+    }
+
+    fn state_changes(
+        events: &mut broadcast::Receiver<OrchestratorEvent>,
+    ) -> Vec<OrchestratorState> {
+        let mut states = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let OrchestratorEvent::StateChanged { to, .. } = event {
+                states.push(to);
+            }
+        }
+        states
+    }
+
+    #[tokio::test]
+    async fn test_integrate_clean_merge_validates() {
+        let config = OrchestratorConfig::new(OrchestratorId::root(), "/tmp/repo");
+        let executor = integration_executor("");
+        let mut orchestrator = Orchestrator::with_executor(config, executor.clone())
+            .await
+            .unwrap();
+
+        orchestrator.integrate().await.unwrap();
+
+        assert_eq!(orchestrator.state(), &OrchestratorState::Validating);
+        executor.assert_called_with(&["new", "aaa111", "bbb222", "-m", "Integration merge"]);
+
+        // The merge happens before checking it for conflicts
+        let calls = executor.recorded_calls();
+        let merge = calls.iter().position(|c| c[0] == "new").unwrap();
+        let conflicts = calls
+            .iter()
+            .position(|c| c.iter().any(|a| a == "conflicts()"))
+            .unwrap();
+        assert!(merge < conflicts);
+    }
+
+    #[tokio::test]
+    async fn test_required_pre_integrate_hook_blocks_merge() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".hox")).unwrap();
+        std::fs::write(
+            temp_dir.path().join(".hox/hooks.toml"),
+            "[[hook]]\nname = \"gate\"\nprogram = \"false\"\nrun_on = \"pre-integrate\"\nrequired = true\n",
+        )
+        .unwrap();
+        let config = OrchestratorConfig::new(OrchestratorId::root(), temp_dir.path());
+        let executor = integration_executor("");
+        let mut orchestrator = Orchestrator::with_executor(config, executor.clone())
+            .await
+            .unwrap();
+
+        let err = orchestrator.integrate().await.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Required pre-integrate hook failed"));
+        assert!(executor.recorded_calls().iter().all(|c| c[0] != "new"));
+        assert!(matches!(orchestrator.state(), OrchestratorState::Failed(_)));
+    }
+
+    #[tokio::test]
+    async fn test_integrate_fails_when_conflict_retries_exhausted() {
+        let config = OrchestratorConfig::new(OrchestratorId::root(), "/tmp/repo")
+            .with_max_conflict_retries(0);
+        let mut orchestrator =
+            Orchestrator::with_executor(config, integration_executor("ccc333\n"))
+                .await
+                .unwrap();
+        let mut events = orchestrator.subscribe();
+
+        orchestrator.integrate().await.unwrap();
+
+        let states = state_changes(&mut events);
+        assert_eq!(states[0], OrchestratorState::Integrating);
+        assert_eq!(states[1], OrchestratorState::ResolvingConflicts);
+        assert!(matches!(states[2], OrchestratorState::Failed(_)));
+        assert_eq!(states.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_integrate_from_idle_counts_rejected_transition() {
+        let config = OrchestratorConfig::new(OrchestratorId::root(), "/tmp/repo");
+        let mut orchestrator = Orchestrator::with_executor(config, integration_executor(""))
+            .await
+            .unwrap();
+        assert_eq!(orchestrator.sm_state, state_machine::State::Idle);
+
+        orchestrator.integrate().await.unwrap();
+
+        // AllTasksComplete is only valid while executing
+        assert_eq!(orchestrator.rejected_transitions(), 1);
+        assert_eq!(orchestrator.sm_state, state_machine::State::Idle);
+        assert_eq!(orchestrator.state(), &OrchestratorState::Validating);
+    }
+
+    #[tokio::test]
+    async fn test_integrate_conflicts_resolved_on_retry() {
+        let config = OrchestratorConfig::new(OrchestratorId::root(), "/tmp/repo");
+        let executor =
+            conflict_sequence_executor(&["ccc333\n", "ccc333\n", "ccc333\n", "ccc333\n", ""]);
+        let agent = Arc::new(ScriptedConflictAgent::default());
+        let mut orchestrator = Orchestrator::with_executor(config, executor)
+            .await
+            .unwrap()
+            .with_conflict_agent(Arc::clone(&agent));
+        let mut events = orchestrator.subscribe();
+
+        orchestrator.integrate().await.unwrap();
+
+        assert_eq!(
+            *agent.attempts.lock().unwrap(),
+            vec![("ccc333".to_string(), 1), ("ccc333".to_string(), 2)]
+        );
+        assert_eq!(
+            state_changes(&mut events),
+            vec![
+                OrchestratorState::Integrating,
+                OrchestratorState::ResolvingConflicts,
+                OrchestratorState::Validating,
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_integrate_conflict_agent_timeout_moves_on() {
+        let config = OrchestratorConfig::new(OrchestratorId::root(), "/tmp/repo")
+            .with_max_conflict_retries(2);
+        let executor = conflict_sequence_executor(&["ccc333\n"]);
+        let agent = Arc::new(ScriptedConflictAgent {
+            hang_on: Some(1),
+            ..Default::default()
+        });
+        let mut orchestrator = Orchestrator::with_executor(config, executor)
+            .await
+            .unwrap()
+            .with_conflict_agent(Arc::clone(&agent));
+
+        let started = tokio::time::Instant::now();
+        orchestrator.integrate().await.unwrap();
+
+        // The hung first attempt is abandoned at the timeout, then retried
+        assert_eq!(agent.attempts.lock().unwrap().len(), 2);
+        assert!(started.elapsed() >= CONFLICT_RESOLUTION_TIMEOUT);
+        assert!(matches!(
+            orchestrator.state(),
+            OrchestratorState::Failed(reason) if reason.contains("after 2 attempts")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_integrate_conflict_agent_error_fails_integration() {
+        let config = OrchestratorConfig::new(OrchestratorId::root(), "/tmp/repo");
+        let agent = Arc::new(ScriptedConflictAgent {
+            fail_on: Some(1),
+            ..Default::default()
+        });
+        let mut orchestrator =
+            Orchestrator::with_executor(config, integration_executor("ccc333\n"))
+                .await
+                .unwrap()
+                .with_conflict_agent(Arc::clone(&agent));
+
+        orchestrator.integrate().await.unwrap();
+
+        assert!(matches!(
+            orchestrator.state(),
+            OrchestratorState::Failed(reason) if reason.contains("Maximum agents")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_child_status_events_only_on_change() {
+        let config = OrchestratorConfig::new(OrchestratorId::root(), "/tmp/repo");
+        let mut orchestrator = Orchestrator::with_executor(config, hox_jj::MockJjExecutor::new())
+            .await
+            .unwrap();
+        let child_id = orchestrator.config.id.child(1);
+        orchestrator.children.insert(
+            child_id.clone(),
+            ChildHandle {
+                id: child_id.clone(),
+                phase_assignment: 1,
+                workspace_path: PathBuf::from("/tmp/repo/.hox-orchestrators/O-B-1"),
+                status: ChildStatus::Spawning,
+            },
+        );
+        let mut events = orchestrator.subscribe();
+
+        orchestrator.update_child_status(&child_id, ChildStatus::Running);
+        orchestrator.update_child_status(&child_id, ChildStatus::Running);
+        orchestrator.update_child_status(&child_id, ChildStatus::Completed);
+
+        assert_eq!(
+            events.try_recv().unwrap(),
+            OrchestratorEvent::ChildStatusChanged {
+                child: child_id.clone(),
+                status: ChildStatus::Running,
+            }
+        );
+        assert_eq!(
+            events.try_recv().unwrap(),
+            OrchestratorEvent::ChildStatusChanged {
+                child: child_id,
+                status: ChildStatus::Completed,
+            }
+        );
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resume_skips_completed_phase() {
+        let repo = tempfile::TempDir::new().unwrap();
+        let id = OrchestratorId::root();
+        let phase = |number: u32, tasks: &[&str]| Phase {
+            number,
+            name: format!("phase-{}", number),
+            description: String::new(),
+            blocking: true,
+            tasks: tasks.iter().map(|t| t.to_string()).collect(),
+        };
+        let agent_id = AgentId::new(id.clone());
+        let snapshot = OrchestratorSnapshot {
+            id: id.clone(),
+            change_id: Some("base111".to_string()),
+            phases: vec![
+                phase(1, &["aaa111", "aaa222"]),
+                phase(2, &["bbb111"]),
+                phase(3, &[]),
+            ],
+            completed_phases: vec![],
+            agents: HashMap::from([("agent-1".to_string(), agent_id.clone())]),
+        };
+        let path = OrchestratorSnapshot::path(repo.path(), &id);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, serde_json::to_string(&snapshot).unwrap()).unwrap();
+
+        let ok = |stdout: &str| hox_jj::JjOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            success: true,
+            code: Some(0),
+        };
+        // No `new` responses: any attempt to re-create a change fails the test
+        let executor = hox_jj::MockJjExecutor::new()
+            .with_response(
+                r#"log -r present(base111) -T change_id ++ "\n" --no-graph"#,
+                ok("base111\n"),
+            )
+            .with_response(
+                "log -r aaa111 -T description --no-graph",
+                ok("Define API\n\nStatus: done\n"),
+            )
+            .with_response(
+                "log -r aaa222 -T description --no-graph",
+                ok("Define types\n\nStatus: done\n"),
+            )
+            .with_response(
+                "log -r bbb111 -T description --no-graph",
+                ok("Implement API\n\nStatus: in_progress\n"),
+            );
+
+        let config = OrchestratorConfig::new(id.clone(), repo.path());
+        let orchestrator = Orchestrator::resume(config, executor).await.unwrap();
+
+        assert_eq!(orchestrator.change_id(), Some(&"base111".to_string()));
+        assert_eq!(orchestrator.agents().get("agent-1"), Some(&agent_id));
+        assert_eq!(orchestrator.phases.current_phase().unwrap().number, 2);
+        assert_eq!(
+            orchestrator.phases.phase_status(1),
+            Some(&PhaseStatus::Completed)
+        );
+        assert_eq!(
+            orchestrator.phases.phase_status(2),
+            Some(&PhaseStatus::Pending)
+        );
+
+        // The refreshed snapshot records phase 1 as completed
+        let saved = OrchestratorSnapshot::load(repo.path(), &id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.completed_phases, vec![1]);
+    }
+
+    #[tokio::test]
+    async fn test_resume_without_snapshot_fails() {
+        let repo = tempfile::TempDir::new().unwrap();
+        let config = OrchestratorConfig::new(OrchestratorId::root(), repo.path());
+
+        let result = Orchestrator::resume(config, hox_jj::MockJjExecutor::new()).await;
+
+        assert!(matches!(result, Err(HoxError::Orchestrator(_))));
+    }
 }
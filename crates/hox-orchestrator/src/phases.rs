@@ -1,7 +1,12 @@
 //! Phase management for orchestrated execution
 
+use chrono::{DateTime, Utc};
 use hox_core::{ChangeId, HoxError, Phase, Result, TaskStatus};
 use std::collections::HashMap;
+use tokio::sync::broadcast;
+
+/// Buffered phase events per subscriber before the oldest are dropped
+const PHASE_EVENT_CAPACITY: usize = 64;
 
 /// Status of a phase
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -12,22 +17,56 @@ pub enum PhaseStatus {
     Failed(String),
 }
 
+/// A phase status transition, or the current phase changing on advance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseEvent {
+    pub phase_number: u32,
+    pub status: PhaseStatus,
+    pub timestamp: DateTime<Utc>,
+}
+
 /// Manages phases in an orchestration run
 pub struct PhaseManager {
     phases: Vec<Phase>,
     current_phase_idx: usize,
     phase_status: HashMap<u32, PhaseStatus>,
+    events: broadcast::Sender<PhaseEvent>,
 }
 
 impl PhaseManager {
     pub fn new() -> Self {
+        let (events, _) = broadcast::channel(PHASE_EVENT_CAPACITY);
         Self {
             phases: Vec::new(),
             current_phase_idx: 0,
             phase_status: HashMap::new(),
+            events,
         }
     }
 
+    /// Subscribe to phase events for live progress updates
+    ///
+    /// Slow receivers that fall more than a few dozen events behind get
+    /// `RecvError::Lagged` and skip ahead.
+    pub fn subscribe(&self) -> broadcast::Receiver<PhaseEvent> {
+        self.events.subscribe()
+    }
+
+    /// Record a status and notify subscribers
+    fn transition(&mut self, number: u32, status: PhaseStatus) {
+        self.phase_status.insert(number, status.clone());
+        self.emit(number, status);
+    }
+
+    /// Notify subscribers; having none is fine
+    fn emit(&self, phase_number: u32, status: PhaseStatus) {
+        let _ = self.events.send(PhaseEvent {
+            phase_number,
+            status,
+            timestamp: Utc::now(),
+        });
+    }
+
     /// Add a phase
     pub fn add_phase(&mut self, phase: Phase) {
         self.phase_status.insert(phase.number, PhaseStatus::Pending);
@@ -52,14 +91,13 @@ impl PhaseManager {
 
     /// Set phase status
     pub fn set_phase_status(&mut self, number: u32, status: PhaseStatus) {
-        self.phase_status.insert(number, status);
+        self.transition(number, status);
     }
 
     /// Mark current phase as in progress
     pub fn start_current_phase(&mut self) -> Result<()> {
-        if let Some(phase) = self.current_phase() {
-            self.phase_status
-                .insert(phase.number, PhaseStatus::InProgress);
+        if let Some(number) = self.current_phase().map(|p| p.number) {
+            self.transition(number, PhaseStatus::InProgress);
             Ok(())
         } else {
             Err(HoxError::Phase("No current phase".to_string()))
@@ -68,9 +106,8 @@ impl PhaseManager {
 
     /// Mark current phase as completed and advance
     pub fn complete_current_phase(&mut self) -> Result<()> {
-        if let Some(phase) = self.current_phase() {
-            self.phase_status
-                .insert(phase.number, PhaseStatus::Completed);
+        if let Some(number) = self.current_phase().map(|p| p.number) {
+            self.transition(number, PhaseStatus::Completed);
             self.current_phase_idx += 1;
             Ok(())
         } else {
@@ -83,6 +120,14 @@ impl PhaseManager {
         if let Some(phase) = self.current_phase() {
             if self.phase_status.get(&phase.number) == Some(&PhaseStatus::Completed) {
                 self.current_phase_idx += 1;
+                if let Some(next) = self.current_phase() {
+                    let status = self
+                        .phase_status
+                        .get(&next.number)
+                        .cloned()
+                        .unwrap_or(PhaseStatus::Pending);
+                    self.emit(next.number, status);
+                }
                 Ok(())
             } else {
                 Err(HoxError::Phase(format!(
@@ -95,6 +140,18 @@ impl PhaseManager {
         }
     }
 
+    /// Move past any completed phases at the current position
+    ///
+    /// Used when resuming, where earlier phases may already be done.
+    pub fn skip_completed(&mut self) {
+        while let Some(phase) = self.current_phase() {
+            if self.phase_status.get(&phase.number) != Some(&PhaseStatus::Completed) {
+                break;
+            }
+            self.current_phase_idx += 1;
+        }
+    }
+
     /// Check if all phases are completed
     pub fn all_completed(&self) -> bool {
         self.phases
@@ -423,4 +480,41 @@ mod tests {
         assert_eq!(result.unwrap(), PhaseStatus::Completed);
         assert!(manager.current_phase().is_none());
     }
+
+    #[test]
+    fn test_phase_events() {
+        let mut manager = PhaseManager::new();
+        manager.add_phase(Phase::contracts("Phase 0"));
+        manager.add_phase(Phase::integration(1, "Integration"));
+        let mut events = manager.subscribe();
+
+        manager.start_current_phase().unwrap();
+        manager.set_phase_status(0, PhaseStatus::Completed);
+        manager.advance().unwrap();
+        manager.set_phase_status(1, PhaseStatus::Failed("conflict".to_string()));
+
+        let received: Vec<_> = std::iter::from_fn(|| events.try_recv().ok())
+            .map(|e| (e.phase_number, e.status))
+            .collect();
+        assert_eq!(
+            received,
+            vec![
+                (0, PhaseStatus::InProgress),
+                (0, PhaseStatus::Completed),
+                (1, PhaseStatus::Pending),
+                (1, PhaseStatus::Failed("conflict".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_phase_events_without_subscribers() {
+        let mut manager = PhaseManager::new();
+        manager.add_phase(Phase::contracts("Phase 0"));
+
+        // Sending with no receivers must not affect the synchronous API
+        manager.start_current_phase().unwrap();
+        manager.complete_current_phase().unwrap();
+        assert!(manager.all_completed());
+    }
 }
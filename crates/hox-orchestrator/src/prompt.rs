@@ -8,7 +8,7 @@
 
 use crate::backpressure::format_errors_for_prompt;
 use hox_agent::{file_operation_instructions, BackpressureResult};
-use hox_core::{HandoffContext, Task};
+use hox_core::{HandoffContext, Task, HANDOFF_BLOCK_START};
 
 /// Build a prompt for a loop iteration
 ///
@@ -152,36 +152,63 @@ pub fn build_iteration_prompt(
 }
 
 /// Parse context updates from agent output
+///
+/// Accepts either the `FOCUS:`/`PROGRESS:`/`NEXT:`/`BLOCKERS:` lines requested
+/// in the prompt, or a serialized [`HandoffContext`] block. In the line format,
+/// lines without a key continue the previous entry, so multi-line entries
+/// survive. The closing fence must be on its own line.
 pub fn parse_context_update(output: &str) -> Option<HandoffContext> {
     let start = output.find("```context")?;
-    let content_start = start + "```context".len();
-
-    // Find the closing ``` after the opening tag
-    let end = output[content_start..]
-        .find("```")
-        .map(|e| content_start + e)?;
-
-    if end <= content_start {
+    let mut lines = output[start + "```context".len()..].lines();
+    // Rest of the opening fence line
+    lines.next();
+
+    let mut block = Vec::new();
+    let mut closed = false;
+    for line in lines {
+        if line.trim() == "```" {
+            closed = true;
+            break;
+        }
+        block.push(line);
+    }
+    if !closed {
         return None;
     }
 
-    let block = &output[content_start..end];
+    if block
+        .iter()
+        .any(|line| line.trim_end() == HANDOFF_BLOCK_START)
+    {
+        return HandoffContext::from_description_block(&block.join("\n"));
+    }
 
     let mut context = HandoffContext::default();
+    // The entry that unkeyed continuation lines are appended to
+    let mut current: Option<&mut String> = None;
 
-    for line in block.lines() {
-        let line = line.trim();
-        if let Some(focus) = line.strip_prefix("FOCUS:") {
+    for line in block {
+        let trimmed = line.trim();
+        if let Some(focus) = trimmed.strip_prefix("FOCUS:") {
             context.current_focus = focus.trim().to_string();
-        } else if let Some(progress) = line.strip_prefix("PROGRESS:") {
+            current = Some(&mut context.current_focus);
+        } else if let Some(progress) = trimmed.strip_prefix("PROGRESS:") {
             context.progress.push(progress.trim().to_string());
-        } else if let Some(next) = line.strip_prefix("NEXT:") {
+            current = context.progress.last_mut();
+        } else if let Some(next) = trimmed.strip_prefix("NEXT:") {
             context.next_steps.push(next.trim().to_string());
-        } else if let Some(blockers) = line.strip_prefix("BLOCKERS:") {
+            current = context.next_steps.last_mut();
+        } else if let Some(blockers) = trimmed.strip_prefix("BLOCKERS:") {
             let blockers = blockers.trim();
             if blockers.to_lowercase() != "none" && !blockers.is_empty() {
                 context.blockers.push(blockers.to_string());
+                current = context.blockers.last_mut();
+            } else {
+                current = None;
             }
+        } else if let Some(entry) = current.as_mut().filter(|_| !trimmed.is_empty()) {
+            entry.push('\n');
+            entry.push_str(trimmed);
         }
     }
 
@@ -310,4 +337,30 @@ BLOCKERS: Need API documentation
         // No closing ```, should return None
         assert!(parse_context_update(output).is_none());
     }
+
+    #[test]
+    fn test_parse_context_multiline_entries() {
+        let output = "```context\nFOCUS: Parser\nPROGRESS: Added tokenizer\n  and the ``` fence test\nNEXT: Error recovery\nBLOCKERS: none\n```\n";
+
+        let context = parse_context_update(output).unwrap();
+        assert_eq!(
+            context.progress,
+            vec!["Added tokenizer\nand the ``` fence test"]
+        );
+        assert_eq!(context.next_steps, vec!["Error recovery"]);
+        assert!(context.blockers.is_empty());
+    }
+
+    #[test]
+    fn test_parse_context_handoff_block() {
+        let handoff = HandoffContext {
+            current_focus: "Parser".to_string(),
+            progress: vec!["Line one\nFOCUS: not a key".to_string()],
+            loop_iteration: Some(2),
+            ..Default::default()
+        };
+        let output = format!("Done.\n```context\n{}```\n", handoff.to_description_block());
+
+        assert_eq!(parse_context_update(&output), Some(handoff));
+    }
 }
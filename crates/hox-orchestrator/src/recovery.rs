@@ -4,20 +4,33 @@
 //! - Rolling back operations after bad agent iterations
 //! - Creating recovery points before risky operations
 //! - Restoring from saved recovery points
+//! - Named checkpoints persisted to `.jj/hox-recovery.jsonl`
 //! - Cleaning up agent workspaces after rollback
 
 use chrono::{DateTime, Utc};
-use hox_core::Result;
+pub use hox_core::DEFAULT_MAX_CHECKPOINTS;
+use hox_core::{HoxError, Result};
 use hox_jj::{JjExecutor, OpManager};
-use std::path::PathBuf;
-use tracing::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, info, warn};
+
+/// Checkpoint log file, relative to the repo root
+///
+/// Kept under `.jj/` so it is not part of the working copy: restoring an
+/// earlier operation must not roll back the checkpoints recorded after it.
+pub const CHECKPOINT_FILE: &str = ".jj/hox-recovery.jsonl";
 
 /// A recovery point representing a known-good state
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecoveryPoint {
     pub operation_id: String,
     pub created_at: DateTime<Utc>,
     pub description: String,
+    /// Checkpoint label (only set for named checkpoints)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
 }
 
 impl RecoveryPoint {
@@ -27,6 +40,7 @@ impl RecoveryPoint {
             operation_id,
             created_at: Utc::now(),
             description,
+            label: None,
         }
     }
 }
@@ -59,6 +73,8 @@ impl RollbackResult {
 pub struct RecoveryManager<E: JjExecutor> {
     op_manager: OpManager<E>,
     workspaces_dir: PathBuf,
+    checkpoint_file: PathBuf,
+    max_checkpoints: usize,
 }
 
 impl<E: JjExecutor> RecoveryManager<E> {
@@ -69,13 +85,22 @@ impl<E: JjExecutor> RecoveryManager<E> {
             .parent()
             .unwrap_or(&repo_root)
             .join(".hox-workspaces");
+        let checkpoint_file = repo_root.join(CHECKPOINT_FILE);
 
         Self {
             op_manager,
             workspaces_dir,
+            checkpoint_file,
+            max_checkpoints: DEFAULT_MAX_CHECKPOINTS,
         }
     }
 
+    /// Set how many checkpoints are kept before the oldest are pruned
+    pub fn with_max_checkpoints(mut self, max_checkpoints: usize) -> Self {
+        self.max_checkpoints = max_checkpoints.max(1);
+        self
+    }
+
     /// Create a recovery point at the current operation
     ///
     /// Returns a recovery point that can be used to restore state later.
@@ -87,6 +112,71 @@ impl<E: JjExecutor> RecoveryManager<E> {
         Ok(RecoveryPoint::new(operation_id, description))
     }
 
+    /// Record a named checkpoint at the current operation
+    ///
+    /// The checkpoint is appended to `.jj/hox-recovery.jsonl`, and the oldest
+    /// entries are pruned once the log exceeds the configured maximum.
+    pub async fn checkpoint(&self, label: &str) -> Result<RecoveryPoint> {
+        let operation_id = self.op_manager.snapshot().await?;
+        let point = RecoveryPoint {
+            label: Some(label.to_string()),
+            ..RecoveryPoint::new(operation_id, format!("Checkpoint: {}", label))
+        };
+
+        if let Some(parent) = self.checkpoint_file.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let mut line = serde_json::to_string(&point)?;
+        line.push('\n');
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.checkpoint_file)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+        drop(file);
+
+        self.prune_checkpoints().await?;
+
+        info!("Created checkpoint '{}' at {}", label, point.operation_id);
+        Ok(point)
+    }
+
+    /// List recorded checkpoints, oldest first
+    pub async fn list_checkpoints(&self) -> Result<Vec<RecoveryPoint>> {
+        read_checkpoints(&self.checkpoint_file).await
+    }
+
+    /// Find the most recent checkpoint with a label
+    pub async fn find_checkpoint(&self, label: &str) -> Result<Option<RecoveryPoint>> {
+        Ok(self
+            .list_checkpoints()
+            .await?
+            .into_iter()
+            .rev()
+            .find(|point| point.label.as_deref() == Some(label)))
+    }
+
+    /// Drop the oldest checkpoints beyond the configured maximum
+    async fn prune_checkpoints(&self) -> Result<()> {
+        let points = self.list_checkpoints().await?;
+        if points.len() <= self.max_checkpoints {
+            return Ok(());
+        }
+
+        let excess = points.len() - self.max_checkpoints;
+        debug!("Pruning {} old checkpoints", excess);
+
+        let mut content = String::new();
+        for point in &points[excess..] {
+            content.push_str(&serde_json::to_string(point)?);
+            content.push('\n');
+        }
+        tokio::fs::write(&self.checkpoint_file, content).await?;
+        Ok(())
+    }
+
     /// Restore from a recovery point
     ///
     /// This restores the repository to the state captured in the recovery point,
@@ -97,26 +187,15 @@ impl<E: JjExecutor> RecoveryManager<E> {
             recovery_point.operation_id, recovery_point.description
         );
 
-        // Count operations that will be undone
-        let current_ops = self.op_manager.recent_operations(100).await?;
-        let mut operations_undone = 0;
-
-        for op in &current_ops {
-            if op.id == recovery_point.operation_id {
-                break;
-            }
-            operations_undone += 1;
-        }
-
-        // Restore to the recovery point
-        self.op_manager
+        let restored = self
+            .op_manager
             .restore(&recovery_point.operation_id)
             .await?;
 
         info!("Restored to operation {}", recovery_point.operation_id);
 
         Ok(RollbackResult {
-            operations_undone,
+            operations_undone: restored.operations_affected,
             agent_cleaned: false,
             workspace_removed: false,
         })
@@ -134,26 +213,21 @@ impl<E: JjExecutor> RecoveryManager<E> {
             return Ok(RollbackResult::none());
         }
 
-        // Undo operations one by one
-        // Note: We could optimize this by getting the Nth operation and restoring to it
-        for i in 0..count {
-            match self.op_manager.undo().await {
-                Ok(_) => {
-                    info!("Undone operation {}/{}", i + 1, count);
-                }
-                Err(e) => {
-                    warn!("Failed to undo operation {}/{}: {}", i + 1, count, e);
-                    return Ok(RollbackResult {
-                        operations_undone: i,
-                        agent_cleaned: false,
-                        workspace_removed: false,
-                    });
-                }
-            }
-        }
+        // Restore to the operation just before the last N
+        let operations = self.op_manager.recent_operations(count + 1).await?;
+        let Some(target) = operations.get(count) else {
+            return Err(HoxError::JjCommand(format!(
+                "Cannot roll back {} operations: only {} in the operation log",
+                count,
+                operations.len()
+            )));
+        };
+
+        let restored = self.op_manager.restore(&target.id).await?;
+        info!("Rolled back to operation {}", target.id);
 
         Ok(RollbackResult {
-            operations_undone: count,
+            operations_undone: restored.operations_affected,
             agent_cleaned: false,
             workspace_removed: false,
         })
@@ -176,19 +250,8 @@ impl<E: JjExecutor> RecoveryManager<E> {
             agent_name, snapshot_op_id
         );
 
-        // Count operations that will be undone (must happen BEFORE restore)
-        let current_ops = self.op_manager.recent_operations(100).await?;
-        let mut operations_undone = 0;
-
-        for op in &current_ops {
-            if op.id == snapshot_op_id {
-                break;
-            }
-            operations_undone += 1;
-        }
-
         // Restore to snapshot
-        self.op_manager.restore(snapshot_op_id).await?;
+        let restored = self.op_manager.restore(snapshot_op_id).await?;
 
         // Clean up workspace if requested
         let workspace_removed = if remove_workspace {
@@ -212,7 +275,7 @@ impl<E: JjExecutor> RecoveryManager<E> {
         };
 
         Ok(RollbackResult {
-            operations_undone,
+            operations_undone: restored.operations_affected,
             agent_cleaned: true,
             workspace_removed,
         })
@@ -231,11 +294,91 @@ impl<E: JjExecutor> RecoveryManager<E> {
     }
 }
 
+/// Read checkpoints from a JSONL log, skipping malformed lines
+async fn read_checkpoints(path: &Path) -> Result<Vec<RecoveryPoint>> {
+    let content = match tokio::fs::read_to_string(path).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(point) => Some(point),
+            Err(e) => {
+                warn!("Skipping malformed checkpoint entry: {}", e);
+                None
+            }
+        })
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use hox_jj::{JjOutput, MockJjExecutor};
 
+    const SNAPSHOT_COMMAND: &str =
+        "op log -n 1 -T operation_id ++ \"\\t\" ++ description ++ \"\\t\" ++ time ++ \"\\n\" --no-graph";
+
+    fn snapshot_executor(op_id: &str) -> MockJjExecutor {
+        MockJjExecutor::new().with_response(
+            SNAPSHOT_COMMAND,
+            JjOutput {
+                stdout: format!("{}\tcurrent state\t2024-01-01 12:00:00\n", op_id),
+                stderr: String::new(),
+                success: true,
+                code: Some(0),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = RecoveryManager::new(snapshot_executor("op-1"), dir.path().into());
+
+        let point = manager.checkpoint("before-squash").await.unwrap();
+        assert_eq!(point.operation_id, "op-1");
+        assert_eq!(point.label.as_deref(), Some("before-squash"));
+        manager.checkpoint("before-absorb").await.unwrap();
+
+        let points = manager.list_checkpoints().await.unwrap();
+        assert_eq!(points.len(), 2);
+        assert!(dir.path().join(CHECKPOINT_FILE).exists());
+
+        let found = manager
+            .find_checkpoint("before-absorb")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.label.as_deref(), Some("before-absorb"));
+        assert_eq!(found.operation_id, "op-1");
+        assert!(manager.find_checkpoint("missing").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_checkpoints_pruned_to_max() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = RecoveryManager::new(snapshot_executor("op-1"), dir.path().into())
+            .with_max_checkpoints(2);
+
+        for label in ["a", "b", "c"] {
+            manager.checkpoint(label).await.unwrap();
+        }
+
+        let labels: Vec<_> = manager
+            .list_checkpoints()
+            .await
+            .unwrap()
+            .into_iter()
+            .filter_map(|p| p.label)
+            .collect();
+        assert_eq!(labels, vec!["b", "c"]);
+    }
+
     #[tokio::test]
     async fn test_create_recovery_point() {
         let executor = MockJjExecutor::new().with_response(
@@ -244,6 +387,7 @@ mod tests {
                 stdout: "snapshot-123\tcurrent state\t2024-01-01 12:00:00\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -257,32 +401,55 @@ mod tests {
         assert_eq!(point.description, "Before risky operation");
     }
 
+    fn op_log(count: usize, stdout: &str) -> (String, JjOutput) {
+        (
+            format!(
+                "op log -n {} -T operation_id ++ \"\\t\" ++ description ++ \"\\t\" ++ time ++ \"\\n\" --no-graph",
+                count
+            ),
+            JjOutput {
+                stdout: stdout.to_string(),
+                stderr: String::new(),
+                success: true,
+                code: Some(0),
+            },
+        )
+    }
+
     #[tokio::test]
     async fn test_rollback_operations() {
+        let log = "op-3\tsquash\tt3\nop-2\tnew\tt2\nop-1\tinit\tt1\n";
+        let (recent, recent_output) = op_log(3, log);
+        let (scan, scan_output) = op_log(100, log);
         let executor = MockJjExecutor::new()
+            .with_response(&recent, recent_output)
+            .with_response(&scan, scan_output)
             .with_response(
-                "undo",
-                JjOutput {
-                    stdout: String::new(),
-                    stderr: String::new(),
-                    success: true,
-                },
-            )
-            .with_response(
-                "undo",
+                "op restore op-1",
                 JjOutput {
                     stdout: String::new(),
                     stderr: String::new(),
                     success: true,
+                    code: Some(0),
                 },
             );
 
-        let manager = RecoveryManager::new(executor, PathBuf::from("/tmp/repo"));
+        let manager = RecoveryManager::new(executor.clone(), PathBuf::from("/tmp/repo"));
         let result = manager.rollback_operations(2).await.unwrap();
 
         assert_eq!(result.operations_undone, 2);
         assert!(!result.agent_cleaned);
         assert!(!result.workspace_removed);
+        executor.assert_called_with(&["op", "restore", "op-1"]);
+    }
+
+    #[tokio::test]
+    async fn test_rollback_operations_beyond_log() {
+        let (recent, recent_output) = op_log(3, "op-1\tinit\tt1\n");
+        let executor = MockJjExecutor::new().with_response(&recent, recent_output);
+
+        let manager = RecoveryManager::new(executor, PathBuf::from("/tmp/repo"));
+        assert!(manager.rollback_operations(2).await.is_err());
     }
 
     #[tokio::test]
@@ -4,16 +4,67 @@
 //! - Trying multiple approaches to a task in parallel
 //! - Maintaining complete audit trails via evolution logs
 //! - Safe reversion without destructive history editing
+//! - Selecting a winning approach and abandoning the rest
 //! - DAG cleanup after complex multi-agent operations
 
+use async_trait::async_trait;
+use futures::stream::{FuturesUnordered, StreamExt};
 use hox_core::Result;
 use hox_jj::{BookmarkManager, DagOperations, EvolutionEntry, JjExecutor};
-use tracing::{debug, instrument};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{debug, info, instrument};
+
+/// How the winning approach is chosen among speculative candidates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SelectionPolicy {
+    /// First passing candidate in the given order; later ones are not evaluated
+    FirstPassing,
+    /// Passing candidate with the highest score
+    #[default]
+    BestScore,
+    /// Passing candidate whose evaluation finished first
+    FastestWallClock,
+}
+
+/// Evaluation of one speculative candidate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CandidateEvaluation {
+    pub change_id: String,
+    /// Whether backpressure/validation passed
+    pub passed: bool,
+    /// Quality score (higher is better)
+    pub score: f32,
+    /// Wall-clock time of the evaluation
+    pub elapsed: Duration,
+}
+
+/// Evaluates a candidate change, typically by running backpressure checks or validators
+#[async_trait]
+pub trait CandidateEvaluator: Send + Sync {
+    /// Return whether the change passes and its score
+    async fn evaluate(&self, change_id: &str) -> Result<(bool, f32)>;
+}
+
+/// Outcome of [`SpeculativeExecutor::select_winner`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectionReport {
+    pub policy: SelectionPolicy,
+    /// Winning change, or `None` if no candidate passed
+    pub winner: Option<String>,
+    pub winner_score: Option<f32>,
+    /// Every candidate that was evaluated
+    pub evaluations: Vec<CandidateEvaluation>,
+    /// Losing candidates that were abandoned
+    pub abandoned: Vec<String>,
+}
 
 /// Manager for speculative execution patterns
 pub struct SpeculativeExecutor<E: JjExecutor> {
     dag_ops: DagOperations<E>,
     bookmark_manager: BookmarkManager<E>,
+    policy: SelectionPolicy,
 }
 
 impl<E: JjExecutor + Clone> SpeculativeExecutor<E> {
@@ -25,9 +76,21 @@ impl<E: JjExecutor + Clone> SpeculativeExecutor<E> {
         Self {
             dag_ops,
             bookmark_manager,
+            policy: SelectionPolicy::default(),
         }
     }
 
+    /// Set the policy used by [`select_winner`](Self::select_winner)
+    pub fn with_policy(mut self, policy: SelectionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Get the selection policy
+    pub fn policy(&self) -> SelectionPolicy {
+        self.policy
+    }
+
     /// Try multiple approaches to a task in parallel
     ///
     /// Creates N duplicates of the task change, each with a different strategy bookmark.
@@ -77,6 +140,83 @@ impl<E: JjExecutor + Clone> SpeculativeExecutor<E> {
         Ok(duplicate_ids)
     }
 
+    /// Evaluate candidate approaches, pick a winner, and abandon the losers
+    ///
+    /// Candidates are usually the change IDs returned by
+    /// [`try_approaches`](Self::try_approaches). They are evaluated in parallel,
+    /// except under [`SelectionPolicy::FirstPassing`], which evaluates them in
+    /// order and stops at the first pass. Unevaluated candidates count as losers.
+    /// If nothing passes, nothing is abandoned so the attempts can be inspected.
+    #[instrument(skip(self, evaluator))]
+    pub async fn select_winner<V: CandidateEvaluator>(
+        &self,
+        candidates: &[String],
+        evaluator: &V,
+    ) -> Result<SelectionReport> {
+        let evaluations = match self.policy {
+            SelectionPolicy::FirstPassing => {
+                let mut evaluations = Vec::new();
+                for change_id in candidates {
+                    let evaluation = evaluate_candidate(evaluator, change_id).await?;
+                    let passed = evaluation.passed;
+                    evaluations.push(evaluation);
+                    if passed {
+                        break;
+                    }
+                }
+                evaluations
+            }
+            SelectionPolicy::BestScore | SelectionPolicy::FastestWallClock => {
+                let mut pending: FuturesUnordered<_> = candidates
+                    .iter()
+                    .map(|change_id| evaluate_candidate(evaluator, change_id))
+                    .collect();
+                let mut evaluations = Vec::with_capacity(candidates.len());
+                while let Some(evaluation) = pending.next().await {
+                    evaluations.push(evaluation?);
+                }
+                evaluations
+            }
+        };
+
+        let mut passing = evaluations.iter().filter(|e| e.passed);
+        let winner = match self.policy {
+            SelectionPolicy::FirstPassing => passing.next(),
+            SelectionPolicy::BestScore => passing.max_by(|a, b| a.score.total_cmp(&b.score)),
+            SelectionPolicy::FastestWallClock => passing.min_by_key(|e| e.elapsed),
+        }
+        .cloned();
+
+        let abandoned: Vec<String> = match &winner {
+            Some(winner) => candidates
+                .iter()
+                .filter(|id| **id != winner.change_id)
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+        self.dag_ops.abandon(&abandoned).await?;
+
+        match &winner {
+            Some(winner) => info!(
+                "Selected {} (score {:.2}) by {:?}, abandoned {}",
+                winner.change_id,
+                winner.score,
+                self.policy,
+                abandoned.len()
+            ),
+            None => info!("No passing candidate among {}", candidates.len()),
+        }
+
+        Ok(SelectionReport {
+            policy: self.policy,
+            winner_score: winner.as_ref().map(|w| w.score),
+            winner: winner.map(|w| w.change_id),
+            evaluations,
+            abandoned,
+        })
+    }
+
     /// Get evolution history for a change (audit trail)
     ///
     /// Returns the complete evolution log showing all rewrites, amends, and
@@ -111,6 +251,27 @@ impl<E: JjExecutor + Clone> SpeculativeExecutor<E> {
     }
 }
 
+/// Evaluate one candidate, timing it
+async fn evaluate_candidate<V: CandidateEvaluator>(
+    evaluator: &V,
+    change_id: &str,
+) -> Result<CandidateEvaluation> {
+    let start = Instant::now();
+    let (passed, score) = evaluator.evaluate(change_id).await?;
+    let elapsed = start.elapsed();
+    debug!(
+        "Candidate {}: passed={} score={:.2} in {:?}",
+        change_id, passed, score, elapsed
+    );
+
+    Ok(CandidateEvaluation {
+        change_id: change_id.to_string(),
+        passed,
+        score,
+        elapsed,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,6 +288,7 @@ mod tests {
                     stdout: "Created new change def456789abc\n".to_string(),
                     stderr: String::new(),
                     success: true,
+                    code: Some(0),
                 },
             )
             .with_response(
@@ -135,6 +297,7 @@ mod tests {
                     stdout: String::new(),
                     stderr: String::new(),
                     success: true,
+                    code: Some(0),
                 },
             );
 
@@ -157,6 +320,7 @@ mod tests {
                 stdout: "abc123def456\tInitial commit\t2025-01-30 12:00:00\ndef456ghi789\tAmended message\t2025-01-30 12:30:00\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -176,6 +340,7 @@ mod tests {
                 stdout: "Created backout change xyz987654abc\n".to_string(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -193,6 +358,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 success: true,
+                code: Some(0),
             },
         );
 
@@ -201,4 +367,98 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    /// Candidates keyed by change ID: (passed, score, evaluation time)
+    struct MockEvaluator(std::collections::HashMap<&'static str, (bool, f32, u64)>);
+
+    #[async_trait]
+    impl CandidateEvaluator for MockEvaluator {
+        async fn evaluate(&self, change_id: &str) -> Result<(bool, f32)> {
+            let (passed, score, millis) = self.0[change_id];
+            tokio::time::sleep(Duration::from_millis(millis)).await;
+            Ok((passed, score))
+        }
+    }
+
+    fn candidates() -> (Vec<String>, MockEvaluator) {
+        let evaluator = MockEvaluator(
+            [
+                ("aaa", (false, 0.9, 10)),
+                ("bbb", (true, 0.6, 30)),
+                ("ccc", (true, 0.8, 50)),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let ids = ["aaa", "bbb", "ccc"].map(String::from).to_vec();
+        (ids, evaluator)
+    }
+
+    fn abandon_ok(command: &str) -> MockJjExecutor {
+        MockJjExecutor::new().with_response(
+            command,
+            JjOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+                success: true,
+                code: Some(0),
+            },
+        )
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_select_best_score() {
+        let (ids, evaluator) = candidates();
+        let spec_exec = SpeculativeExecutor::new(abandon_ok("abandon aaa bbb"))
+            .with_policy(SelectionPolicy::BestScore);
+
+        let report = spec_exec.select_winner(&ids, &evaluator).await.unwrap();
+
+        // aaa scores higher but fails
+        assert_eq!(report.winner.as_deref(), Some("ccc"));
+        assert_eq!(report.winner_score, Some(0.8));
+        assert_eq!(report.abandoned, vec!["aaa", "bbb"]);
+        assert_eq!(report.evaluations.len(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_select_fastest_wall_clock() {
+        let (ids, evaluator) = candidates();
+        let spec_exec = SpeculativeExecutor::new(abandon_ok("abandon aaa ccc"))
+            .with_policy(SelectionPolicy::FastestWallClock);
+
+        let report = spec_exec.select_winner(&ids, &evaluator).await.unwrap();
+
+        assert_eq!(report.winner.as_deref(), Some("bbb"));
+        assert_eq!(report.abandoned, vec!["aaa", "ccc"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_select_first_passing_stops_early() {
+        let (ids, evaluator) = candidates();
+        let spec_exec = SpeculativeExecutor::new(abandon_ok("abandon aaa ccc"))
+            .with_policy(SelectionPolicy::FirstPassing);
+
+        let report = spec_exec.select_winner(&ids, &evaluator).await.unwrap();
+
+        assert_eq!(report.winner.as_deref(), Some("bbb"));
+        // ccc was never evaluated
+        assert_eq!(report.evaluations.len(), 2);
+        assert_eq!(report.abandoned, vec!["aaa", "ccc"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_select_without_passing_candidate_keeps_all() {
+        let evaluator = MockEvaluator([("aaa", (false, 0.5, 10))].into_iter().collect());
+        // No abandon response mocked: abandoning anything would fail
+        let spec_exec = SpeculativeExecutor::new(MockJjExecutor::new());
+
+        let report = spec_exec
+            .select_winner(&["aaa".to_string()], &evaluator)
+            .await
+            .unwrap();
+
+        assert!(report.winner.is_none());
+        assert!(report.abandoned.is_empty());
+    }
 }
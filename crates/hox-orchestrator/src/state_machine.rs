@@ -4,11 +4,13 @@
 //! All state transitions are deterministic and testable.
 //!
 //! Key design principles:
-//! - Pure function: transition(state, event) -> (state, actions)
+//! - Pure function: transition(state, event) -> Result<(state, actions), InvalidTransition>
 //! - No async, no I/O, no dependencies on other hox crates
-//! - Invalid transitions go to Failed state (never panic)
+//! - Invalid transitions are rejected with an error (never panic)
 //! - Simple types (String for IDs, usize for counts)
 
+use thiserror::Error;
+
 /// Orchestration state
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum State {
@@ -31,6 +33,40 @@ pub enum State {
     Failed { error: String },
 }
 
+impl State {
+    /// Kinds of events this state accepts
+    ///
+    /// Terminal states (`Complete`, `Failed`) accept none. Lets UIs offer
+    /// only the applicable actions.
+    pub fn valid_events(&self) -> &'static [EventKind] {
+        match self {
+            State::Idle => &[EventKind::StartOrchestration, EventKind::Error],
+            State::Planning { .. } => &[EventKind::PlanningComplete, EventKind::Error],
+            State::Executing { .. } => &[
+                EventKind::PhaseComplete,
+                EventKind::AllTasksComplete,
+                EventKind::Error,
+            ],
+            State::Integrating { .. } => &[
+                EventKind::IntegrationConflict,
+                EventKind::IntegrationClean,
+                EventKind::Error,
+            ],
+            State::Validating { .. } => &[
+                EventKind::ValidationPassed,
+                EventKind::ValidationFailed,
+                EventKind::Error,
+            ],
+            State::Complete { .. } | State::Failed { .. } => &[],
+        }
+    }
+
+    /// Whether `event` is valid in this state
+    pub fn accepts(&self, event: &Event) -> bool {
+        self.valid_events().contains(&event.kind())
+    }
+}
+
 /// Events that trigger state transitions
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Event {
@@ -54,6 +90,47 @@ pub enum Event {
     Error { message: String },
 }
 
+/// Event variants without their data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    StartOrchestration,
+    PlanningComplete,
+    PhaseComplete,
+    AllTasksComplete,
+    IntegrationConflict,
+    IntegrationClean,
+    ValidationPassed,
+    ValidationFailed,
+    Error,
+}
+
+impl Event {
+    /// The variant of this event
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Event::StartOrchestration { .. } => EventKind::StartOrchestration,
+            Event::PlanningComplete { .. } => EventKind::PlanningComplete,
+            Event::PhaseComplete => EventKind::PhaseComplete,
+            Event::AllTasksComplete => EventKind::AllTasksComplete,
+            Event::IntegrationConflict { .. } => EventKind::IntegrationConflict,
+            Event::IntegrationClean => EventKind::IntegrationClean,
+            Event::ValidationPassed => EventKind::ValidationPassed,
+            Event::ValidationFailed { .. } => EventKind::ValidationFailed,
+            Event::Error { .. } => EventKind::Error,
+        }
+    }
+}
+
+/// An event that is not valid in the current state
+///
+/// Carries both so the caller can keep the unchanged state.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Invalid state transition: {state:?} cannot handle event {event:?}")]
+pub struct InvalidTransition {
+    pub state: State,
+    pub event: Event,
+}
+
 /// Actions to execute as side effects of transitions
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Action {
@@ -79,10 +156,10 @@ pub enum Action {
 /// This function is completely deterministic and has no side effects.
 ///
 /// # Invalid Transitions
-/// Any invalid transition results in a Failed state with descriptive error.
-/// This function never panics.
-pub fn transition(state: State, event: Event) -> (State, Vec<Action>) {
-    match (state, event) {
+/// An event not listed in [`State::valid_events`] is rejected with
+/// [`InvalidTransition`]. This function never panics.
+pub fn transition(state: State, event: Event) -> Result<(State, Vec<Action>), InvalidTransition> {
+    let next = match (state, event) {
         // From Idle state
         (State::Idle, Event::StartOrchestration { goal }) => {
             let actions = vec![
@@ -232,38 +309,10 @@ pub fn transition(state: State, event: Event) -> (State, Vec<Action>) {
             (State::Failed { error: message }, actions)
         }
 
-        // Terminal states - no valid transitions
-        (State::Complete { summary }, event) => (
-            State::Failed {
-                error: format!(
-                    "Invalid transition from Complete state (summary: {}) on event: {:?}",
-                    summary, event
-                ),
-            },
-            vec![],
-        ),
-
-        (State::Failed { error }, event) => (
-            State::Failed {
-                error: format!(
-                    "Invalid transition from Failed state (error: {}) on event: {:?}",
-                    error, event
-                ),
-            },
-            vec![],
-        ),
-
-        // All other invalid transitions
-        (state, event) => (
-            State::Failed {
-                error: format!(
-                    "Invalid state transition: {:?} cannot handle event {:?}",
-                    state, event
-                ),
-            },
-            vec![],
-        ),
-    }
+        // Terminal states accept nothing; everything else is invalid too
+        (state, event) => return Err(InvalidTransition { state, event }),
+    };
+    Ok(next)
 }
 
 #[cfg(test)]
@@ -278,29 +327,31 @@ mod tests {
             Event::StartOrchestration {
                 goal: "Build feature".to_string(),
             },
-        );
+        )
+        .unwrap();
         assert!(matches!(state, State::Planning { .. }));
         assert_eq!(actions.len(), 2);
         assert!(matches!(actions[0], Action::LogActivity { .. }));
         assert!(matches!(actions[1], Action::SpawnPlanningAgent { .. }));
 
         // Planning -> Executing
-        let (state, actions) = transition(state, Event::PlanningComplete { task_count: 3 });
+        let (state, actions) =
+            transition(state, Event::PlanningComplete { task_count: 3 }).unwrap();
         assert!(matches!(state, State::Executing { active_tasks: 3, .. }));
         assert_eq!(actions.len(), 2);
 
         // Executing -> Integrating
-        let (state, actions) = transition(state, Event::AllTasksComplete);
+        let (state, actions) = transition(state, Event::AllTasksComplete).unwrap();
         assert!(matches!(state, State::Integrating { .. }));
         assert_eq!(actions.len(), 1);
 
         // Integrating -> Validating (clean merge)
-        let (state, actions) = transition(state, Event::IntegrationClean);
+        let (state, actions) = transition(state, Event::IntegrationClean).unwrap();
         assert!(matches!(state, State::Validating { .. }));
         assert_eq!(actions.len(), 2);
 
         // Validating -> Complete
-        let (state, actions) = transition(state, Event::ValidationPassed);
+        let (state, actions) = transition(state, Event::ValidationPassed).unwrap();
         assert!(matches!(state, State::Complete { .. }));
         assert_eq!(actions.len(), 2);
         assert!(matches!(actions[1], Action::RecordPattern { .. }));
@@ -313,10 +364,12 @@ mod tests {
             Event::StartOrchestration {
                 goal: "Simple goal".to_string(),
             },
-        );
+        )
+        .unwrap();
 
         // Planning completes with 0 tasks -> directly to Complete
-        let (state, actions) = transition(state, Event::PlanningComplete { task_count: 0 });
+        let (state, actions) =
+            transition(state, Event::PlanningComplete { task_count: 0 }).unwrap();
         assert!(matches!(state, State::Complete { .. }));
         assert_eq!(actions.len(), 1);
     }
@@ -333,7 +386,8 @@ mod tests {
             Event::IntegrationConflict {
                 description: "File conflict in src/main.rs".to_string(),
             },
-        );
+        )
+        .unwrap();
         assert!(matches!(state, State::Integrating { .. }));
         assert_eq!(actions.len(), 2);
         assert!(matches!(actions[0], Action::LogActivity { .. }));
@@ -351,7 +405,8 @@ mod tests {
             Event::ValidationFailed {
                 reason: "Tests failed".to_string(),
             },
-        );
+        )
+        .unwrap();
         assert!(matches!(state, State::Failed { .. }));
         if let State::Failed { error } = state {
             assert!(error.contains("Tests failed"));
@@ -369,7 +424,8 @@ mod tests {
             Event::Error {
                 message: "Planning agent crashed".to_string(),
             },
-        );
+        )
+        .unwrap();
         assert!(matches!(state, State::Failed { .. }));
 
         // Error from Executing
@@ -381,69 +437,150 @@ mod tests {
             Event::Error {
                 message: "Agent timeout".to_string(),
             },
-        );
+        )
+        .unwrap();
         assert!(matches!(state, State::Failed { .. }));
     }
 
     #[test]
     fn test_invalid_transition_never_panics() {
         // Try to start orchestration from Planning state (invalid)
-        let (state, _) = transition(
-            State::Planning {
-                goal: "Existing goal".to_string(),
-            },
+        let planning = State::Planning {
+            goal: "Existing goal".to_string(),
+        };
+        let err = transition(
+            planning.clone(),
             Event::StartOrchestration {
                 goal: "New goal".to_string(),
             },
-        );
-        assert!(matches!(state, State::Failed { .. }));
+        )
+        .unwrap_err();
+        // The error hands back the unchanged state
+        assert_eq!(err.state, planning);
+        assert!(err
+            .to_string()
+            .contains("cannot handle event StartOrchestration"));
 
         // Try to plan complete from Executing (invalid)
-        let (state, _) = transition(
+        assert!(transition(
             State::Executing {
                 phase_name: "test".to_string(),
                 active_tasks: 2,
             },
             Event::PlanningComplete { task_count: 5 },
-        );
-        assert!(matches!(state, State::Failed { .. }));
+        )
+        .is_err());
 
         // Try to validate from Idle (invalid)
-        let (state, _) = transition(State::Idle, Event::ValidationPassed);
-        assert!(matches!(state, State::Failed { .. }));
+        assert!(transition(State::Idle, Event::ValidationPassed).is_err());
     }
 
     #[test]
     fn test_terminal_states_reject_all_events() {
-        // Complete state rejects all events
         let complete = State::Complete {
             summary: "Done".to_string(),
         };
-        let (state, _) = transition(complete.clone(), Event::ValidationPassed);
-        assert!(matches!(state, State::Failed { .. }));
-
-        let (state, _) = transition(
-            complete,
-            Event::StartOrchestration {
-                goal: "New".to_string(),
-            },
-        );
-        assert!(matches!(state, State::Failed { .. }));
-
-        // Failed state rejects all events
         let failed = State::Failed {
             error: "Original error".to_string(),
         };
-        let (state, _) = transition(failed.clone(), Event::IntegrationClean);
-        assert!(matches!(state, State::Failed { .. }));
 
-        let (state, _) = transition(
-            failed,
+        for state in [complete, failed] {
+            assert!(state.valid_events().is_empty());
+            for event in sample_events() {
+                assert!(transition(state.clone(), event).is_err());
+            }
+        }
+    }
+
+    fn sample_states() -> Vec<State> {
+        vec![
+            State::Idle,
+            State::Planning {
+                goal: "goal".to_string(),
+            },
+            State::Executing {
+                phase_name: "impl".to_string(),
+                active_tasks: 2,
+            },
+            State::Integrating {
+                merge_description: "merge".to_string(),
+            },
+            State::Validating {
+                validation_id: "val-1".to_string(),
+            },
+            State::Complete {
+                summary: "done".to_string(),
+            },
+            State::Failed {
+                error: "boom".to_string(),
+            },
+        ]
+    }
+
+    fn sample_events() -> Vec<Event> {
+        vec![
+            Event::StartOrchestration {
+                goal: "goal".to_string(),
+            },
+            Event::PlanningComplete { task_count: 2 },
+            Event::PhaseComplete,
+            Event::AllTasksComplete,
+            Event::IntegrationConflict {
+                description: "conflict".to_string(),
+            },
+            Event::IntegrationClean,
+            Event::ValidationPassed,
+            Event::ValidationFailed {
+                reason: "tests".to_string(),
+            },
             Event::Error {
-                message: "Another error".to_string(),
+                message: "boom".to_string(),
             },
-        );
-        assert!(matches!(state, State::Failed { .. }));
+        ]
+    }
+
+    #[test]
+    fn test_transition_table() {
+        use EventKind::*;
+
+        // Every (state, event) pair: valid ones transition, all others are rejected
+        let valid: [(&str, &[EventKind]); 7] = [
+            ("Idle", &[StartOrchestration, Error]),
+            ("Planning", &[PlanningComplete, Error]),
+            ("Executing", &[PhaseComplete, AllTasksComplete, Error]),
+            (
+                "Integrating",
+                &[IntegrationConflict, IntegrationClean, Error],
+            ),
+            ("Validating", &[ValidationPassed, ValidationFailed, Error]),
+            ("Complete", &[]),
+            ("Failed", &[]),
+        ];
+
+        for (state, (name, expected)) in sample_states().into_iter().zip(valid) {
+            assert!(format!("{:?}", state).starts_with(name));
+            assert_eq!(state.valid_events(), expected, "{}", name);
+
+            for event in sample_events() {
+                let kind = event.kind();
+                let is_valid = expected.contains(&kind);
+                assert_eq!(state.accepts(&event), is_valid, "{} on {:?}", name, kind);
+
+                match transition(state.clone(), event.clone()) {
+                    Ok(_) => assert!(is_valid, "{} accepted {:?}", name, kind),
+                    Err(err) => {
+                        assert!(!is_valid, "{} rejected {:?}", name, kind);
+                        assert_eq!(
+                            err,
+                            InvalidTransition {
+                                state: state.clone(),
+                                event
+                            }
+                        );
+                    }
+                }
+            }
+        }
     }
 
     #[test]
@@ -454,7 +591,8 @@ mod tests {
             Event::StartOrchestration {
                 goal: "Test".to_string(),
             },
-        );
+        )
+        .unwrap();
         assert!(actions.iter().any(|a| matches!(a, Action::SpawnPlanningAgent { .. })));
         assert!(actions.iter().any(|a| matches!(a, Action::LogActivity { .. })));
 
@@ -464,7 +602,8 @@ mod tests {
                 goal: "Test".to_string(),
             },
             Event::PlanningComplete { task_count: 4 },
-        );
+        )
+        .unwrap();
         assert!(actions.iter().any(|a| matches!(a, Action::SpawnTaskAgents { count: 4 })));
     }
 
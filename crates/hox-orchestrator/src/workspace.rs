@@ -2,23 +2,110 @@
 
 use hox_core::{HoxError, Result};
 use std::collections::HashMap;
-use std::path::PathBuf;
-use tracing::{debug, info};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tracing::{debug, info, warn};
 
 use hox_jj::JjExecutor;
 
+/// Name of the repository's main workspace, which is never pruned
+const DEFAULT_WORKSPACE: &str = "default";
+
+/// Directory, next to the repository root, holding the workspaces hox creates
+const AGENT_WORKSPACES_DIR: &str = ".hox-workspaces";
+
+/// One line per workspace: name, working-copy change, and whether it holds work
+const WORKSPACE_LIST_TEMPLATE: &str = r#"name ++ "\t" ++ target.change_id().short() ++ "\t" ++ if(target.empty() && !target.description(), "idle", "busy") ++ "\n""#;
+
 /// Information about a workspace
 #[derive(Debug, Clone)]
 pub struct WorkspaceInfo {
     pub name: String,
+    /// Workspace root as reported by jj (empty if jj cannot report it)
     pub path: PathBuf,
     pub active: bool,
+    /// Working-copy change of the workspace
+    pub change_id: Option<String>,
+    /// Whether the working copy holds no work (empty and undescribed),
+    /// e.g. because the agent's change was abandoned
+    pub idle: bool,
+    /// Whether the workspace directory exists under `.hox-workspaces`, i.e.
+    /// hox created it; only these are ever pruned or evicted
+    pub managed: bool,
+    /// Most recent modification of any file in the workspace (`None` if the
+    /// directory is missing)
+    pub last_modified: Option<SystemTime>,
+}
+
+impl WorkspaceInfo {
+    /// Whether the workspace can be pruned
+    ///
+    /// Only workspaces hox created are considered. Such a workspace is stale
+    /// if its change was abandoned, or if nothing in it has been modified
+    /// within `older_than`.
+    pub fn is_stale(&self, older_than: Duration, now: SystemTime) -> bool {
+        if !self.managed || self.name == DEFAULT_WORKSPACE {
+            return false;
+        }
+
+        match self.last_modified {
+            None => false,
+            Some(modified) => {
+                self.idle
+                    || now
+                        .duration_since(modified)
+                        .is_ok_and(|age| age > older_than)
+            }
+        }
+    }
+}
+
+/// Directory holding the workspaces created for agents, next to the repository root
+fn agent_workspaces_dir(repo_root: &Path) -> PathBuf {
+    repo_root
+        .parent()
+        .unwrap_or(repo_root)
+        .join(AGENT_WORKSPACES_DIR)
+}
+
+/// Path of the workspace created for an agent
+pub(crate) fn agent_workspace_path(repo_root: &Path, name: &str) -> PathBuf {
+    agent_workspaces_dir(repo_root).join(name)
+}
+
+/// Whether `path` is an existing directory inside the agent workspaces directory
+fn is_managed_workspace(repo_root: &Path, path: &Path) -> bool {
+    let (Ok(dir), Ok(path)) = (
+        agent_workspaces_dir(repo_root).canonicalize(),
+        path.canonicalize(),
+    ) else {
+        return false;
+    };
+    path.is_dir() && path != dir && path.starts_with(&dir)
+}
+
+/// Newest modification time of `path` and everything below it
+///
+/// Symlinks are not followed. `None` if `path` does not exist.
+fn newest_mtime(path: &Path) -> Option<SystemTime> {
+    let meta = std::fs::symlink_metadata(path).ok()?;
+    let mut newest = meta.modified().ok();
+    if meta.is_dir() {
+        if let Ok(entries) = std::fs::read_dir(path) {
+            for entry in entries.flatten() {
+                newest = newest.max(newest_mtime(&entry.path()));
+            }
+        }
+    }
+    newest
 }
 
 /// Manages JJ workspaces for agent isolation
 pub struct WorkspaceManager<E: JjExecutor> {
     executor: E,
     workspaces: HashMap<String, WorkspaceInfo>,
+    /// Maximum number of agent workspaces (excluding the default one)
+    max_workspaces: Option<usize>,
 }
 
 impl<E: JjExecutor> WorkspaceManager<E> {
@@ -26,17 +113,26 @@ impl<E: JjExecutor> WorkspaceManager<E> {
         Self {
             executor,
             workspaces: HashMap::new(),
+            max_workspaces: None,
         }
     }
 
+    /// Cap the number of agent workspaces
+    ///
+    /// When the limit is reached, `create_workspace` evicts the oldest idle
+    /// workspace, or fails if every workspace still holds work.
+    pub fn with_max_workspaces(mut self, max_workspaces: usize) -> Self {
+        self.max_workspaces = Some(max_workspaces);
+        self
+    }
+
     /// Create a new workspace for an agent
     pub async fn create_workspace(&mut self, name: &str) -> Result<PathBuf> {
-        let workspace_path = self
-            .executor
-            .repo_root()
-            .parent()
-            .unwrap_or(self.executor.repo_root())
-            .join(format!(".hox-workspaces/{}", name));
+        if let Some(max) = self.max_workspaces {
+            self.enforce_limit(name, max).await?;
+        }
+
+        let workspace_path = agent_workspace_path(self.executor.repo_root(), name);
 
         info!("Creating workspace {} at {:?}", name, workspace_path);
 
@@ -70,14 +166,56 @@ impl<E: JjExecutor> WorkspaceManager<E> {
             name: name.to_string(),
             path: workspace_path.clone(),
             active: true,
+            change_id: None,
+            idle: true,
+            managed: true,
+            last_modified: Some(SystemTime::now()),
         };
 
         self.workspaces.insert(name.to_string(), info);
         Ok(workspace_path)
     }
 
+    /// Make room for a new workspace, evicting the oldest idle one if needed
+    async fn enforce_limit(&mut self, name: &str, max: usize) -> Result<()> {
+        let existing: Vec<WorkspaceInfo> = self
+            .list_workspaces()
+            .await?
+            .into_iter()
+            .filter(|ws| ws.managed)
+            .collect();
+
+        if existing.len() < max || existing.iter().any(|ws| ws.name == name) {
+            return Ok(());
+        }
+
+        let oldest_idle = existing
+            .iter()
+            .filter(|ws| ws.idle)
+            .min_by_key(|ws| ws.last_modified)
+            .ok_or_else(|| {
+                HoxError::JjWorkspace(format!(
+                    "Workspace limit of {} reached and no workspace is idle",
+                    max
+                ))
+            })?;
+
+        info!(
+            "Workspace limit of {} reached, evicting idle workspace {}",
+            max, oldest_idle.name
+        );
+        let evicted = oldest_idle.clone();
+        self.remove_workspace_at(&evicted.name, &evicted.path).await
+    }
+
     /// Remove a workspace
     pub async fn remove_workspace(&mut self, name: &str) -> Result<()> {
+        let path = agent_workspace_path(self.executor.repo_root(), name);
+        self.remove_workspace_at(name, &path).await
+    }
+
+    /// Forget a workspace in jj and delete its directory
+    async fn remove_workspace_at(&mut self, name: &str, path: &Path) -> Result<()> {
         info!("Removing workspace {}", name);
 
         let output = self.executor.exec(&["workspace", "forget", name]).await?;
@@ -87,48 +225,101 @@ impl<E: JjExecutor> WorkspaceManager<E> {
         }
 
         // Remove from tracking
-        if let Some(info) = self.workspaces.remove(name) {
-            // Optionally clean up directory
-            if info.path.exists() {
-                std::fs::remove_dir_all(&info.path).ok();
+        let path = self
+            .workspaces
+            .remove(name)
+            .map_or_else(|| path.to_path_buf(), |info| info.path);
+        if path.exists() {
+            if let Err(e) = std::fs::remove_dir_all(&path) {
+                warn!("Failed to remove workspace directory {:?}: {}", path, e);
             }
         }
 
         Ok(())
     }
 
-    /// List all workspaces
+    /// List all workspaces known to jj, including ones from previous runs
     pub async fn list_workspaces(&self) -> Result<Vec<WorkspaceInfo>> {
-        let output = self.executor.exec(&["workspace", "list"]).await?;
+        let output = self
+            .executor
+            .exec(&["workspace", "list", "-T", WORKSPACE_LIST_TEMPLATE])
+            .await?;
 
         if !output.success {
             return Err(HoxError::JjWorkspace(output.stderr));
         }
 
-        // Parse workspace list output
-        // Format: "name: /path/to/workspace"
+        let repo_root = self.executor.repo_root();
         let mut workspaces = Vec::new();
-
         for line in output.stdout.lines() {
-            let line = line.trim();
-            if line.is_empty() {
-                continue;
-            }
+            let mut fields = line.split('\t');
+            let name = match fields.next().map(str::trim) {
+                Some(name) if !name.is_empty() => name,
+                _ => continue,
+            };
+            let change_id = fields.next().map(str::trim).filter(|id| !id.is_empty());
+            let idle = fields.next().map(str::trim) == Some("idle");
+
+            let path = if name == DEFAULT_WORKSPACE {
+                repo_root.to_path_buf()
+            } else {
+                self.workspace_root(name).await.unwrap_or_default()
+            };
+            let managed = name != DEFAULT_WORKSPACE && is_managed_workspace(repo_root, &path);
+            let last_modified = if managed {
+                newest_mtime(&path)
+            } else {
+                std::fs::metadata(&path)
+                    .and_then(|meta| meta.modified())
+                    .ok()
+            };
+
+            workspaces.push(WorkspaceInfo {
+                name: name.to_string(),
+                active: self.workspaces.contains_key(name),
+                path,
+                change_id: change_id.map(String::from),
+                idle,
+                managed,
+                last_modified,
+            });
+        }
 
-            let parts: Vec<&str> = line.splitn(2, ':').collect();
-            if parts.len() >= 2 {
-                let name = parts[0].trim().to_string();
-                let path = PathBuf::from(parts[1].trim());
+        Ok(workspaces)
+    }
 
-                workspaces.push(WorkspaceInfo {
-                    name,
-                    path,
-                    active: true,
-                });
-            }
+    /// Root directory jj recorded for a workspace
+    async fn workspace_root(&self, name: &str) -> Option<PathBuf> {
+        let output = self
+            .executor
+            .exec(&["workspace", "root", "--name", name])
+            .await
+            .ok()
+            .filter(|output| output.success)?;
+        let root = output.stdout.trim();
+        (!root.is_empty()).then(|| PathBuf::from(root))
+    }
+
+    /// Remove stale workspaces (see [`WorkspaceInfo::is_stale`])
+    ///
+    /// Returns the names of the removed workspaces.
+    pub async fn prune_stale(&mut self, older_than: Duration) -> Result<Vec<String>> {
+        let now = SystemTime::now();
+        let stale: Vec<WorkspaceInfo> = self
+            .list_workspaces()
+            .await?
+            .into_iter()
+            .filter(|ws| ws.is_stale(older_than, now))
+            .collect();
+
+        let mut pruned = Vec::with_capacity(stale.len());
+        for ws in stale {
+            self.remove_workspace_at(&ws.name, &ws.path).await?;
+            pruned.push(ws.name);
         }
 
-        Ok(workspaces)
+        info!("Pruned {} stale workspaces", pruned.len());
+        Ok(pruned)
     }
 
     /// Get workspace info
@@ -166,10 +357,48 @@ impl<E: JjExecutor> WorkspaceManager<E> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use hox_jj::{JjOutput, MockJjExecutor};
+
+    fn workspace(name: &str, idle: bool, last_modified: Option<SystemTime>) -> WorkspaceInfo {
+        WorkspaceInfo {
+            name: name.to_string(),
+            path: PathBuf::from(format!("/tmp/{}", name)),
+            active: false,
+            change_id: None,
+            idle,
+            managed: true,
+            last_modified,
+        }
+    }
+
+    fn ok(stdout: &str) -> JjOutput {
+        JjOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            success: true,
+            code: Some(0),
+        }
+    }
+
+    fn list_command() -> String {
+        format!("workspace list -T {}", WORKSPACE_LIST_TEMPLATE)
+    }
 
-    // Note: Integration tests for WorkspaceManager require a real JJ repository.
-    // The JjExecutor trait mocking is available within hox-jj crate tests.
-    // For unit tests here, we test the non-async logic.
+    /// Answer `jj workspace root --name {name}` with `path`
+    fn with_root(executor: MockJjExecutor, name: &str, path: &Path) -> MockJjExecutor {
+        executor.with_response(
+            &format!("workspace root --name {}", name),
+            ok(&format!("{}\n", path.display())),
+        )
+    }
+
+    /// Set the modification time of `path` to `age` ago
+    fn age(path: &Path, age: Duration) {
+        std::fs::File::open(path)
+            .unwrap()
+            .set_modified(SystemTime::now() - age)
+            .unwrap();
+    }
 
     #[test]
     fn test_workspace_info() {
@@ -177,9 +406,160 @@ mod tests {
             name: "test-agent".to_string(),
             path: PathBuf::from("/tmp/test-workspace"),
             active: true,
+            change_id: Some("abc123".to_string()),
+            idle: false,
+            managed: true,
+            last_modified: None,
         };
 
         assert_eq!(info.name, "test-agent");
         assert!(info.active);
     }
+
+    #[test]
+    fn test_is_stale() {
+        let now = SystemTime::now();
+        let hour = Duration::from_secs(3600);
+        let recent = Some(now - Duration::from_secs(60));
+        let old = Some(now - 2 * hour);
+
+        assert!(!workspace("agent-1", false, recent).is_stale(hour, now));
+        assert!(workspace("agent-1", false, old).is_stale(hour, now));
+        assert!(workspace("agent-1", true, recent).is_stale(hour, now));
+        // A missing directory is never pruned
+        assert!(!workspace("agent-1", true, None).is_stale(hour, now));
+        assert!(!workspace(DEFAULT_WORKSPACE, true, recent).is_stale(hour, now));
+
+        // Workspaces hox did not create are never stale
+        let mut user = workspace("mine", true, old);
+        user.managed = false;
+        assert!(!user.is_stale(hour, now));
+    }
+
+    #[tokio::test]
+    async fn test_list_workspaces() {
+        let executor = MockJjExecutor::new().with_response(
+            &list_command(),
+            ok("default\tqpvuntsm\tbusy\nagent-1\tzzsxqkwp\tidle\n"),
+        );
+
+        let manager = WorkspaceManager::new(executor);
+        let workspaces = manager.list_workspaces().await.unwrap();
+
+        assert_eq!(workspaces.len(), 2);
+        assert_eq!(workspaces[0].path, PathBuf::from("/mock/repo"));
+        assert!(!workspaces[0].idle);
+        assert_eq!(workspaces[1].name, "agent-1");
+        assert_eq!(workspaces[1].change_id.as_deref(), Some("zzsxqkwp"));
+        assert!(workspaces[1].idle);
+        // jj reported no root, so the workspace is not treated as hox's own
+        assert!(!workspaces[1].managed);
+        assert!(workspaces[1].last_modified.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prune_stale_only_removes_managed_inactive_workspaces() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo_root = dir.path().join("repo");
+        std::fs::create_dir_all(&repo_root).unwrap();
+        let hour = Duration::from_secs(3600);
+
+        // Idle agent workspace: stale
+        let idle = agent_workspace_path(&repo_root, "agent-idle");
+        std::fs::create_dir_all(&idle).unwrap();
+        // Busy, with an old top-level directory but a freshly edited file: kept
+        let active = agent_workspace_path(&repo_root, "agent-active");
+        std::fs::create_dir_all(active.join("src")).unwrap();
+        std::fs::write(active.join("src/lib.rs"), "fn wip() {}").unwrap();
+        age(&active, 3 * hour);
+        // Busy and untouched for longer than the threshold: stale
+        let dormant = agent_workspace_path(&repo_root, "agent-dormant");
+        std::fs::create_dir_all(&dormant).unwrap();
+        std::fs::write(dormant.join("notes.md"), "old").unwrap();
+        age(&dormant.join("notes.md"), 3 * hour);
+        age(&dormant, 3 * hour);
+        // Idle workspaces hox did not create: kept
+        let user = dir.path().join("my-checkout");
+        std::fs::create_dir_all(&user).unwrap();
+        let missing = agent_workspace_path(&repo_root, "agent-missing");
+
+        let mut executor = MockJjExecutor::new()
+            .with_repo_root(repo_root)
+            .with_response(
+                &list_command(),
+                ok("default\tqpvuntsm\tidle\n\
+                    agent-idle\ta\tidle\n\
+                    agent-active\tb\tbusy\n\
+                    agent-dormant\tc\tbusy\n\
+                    mine\td\tidle\n\
+                    agent-missing\te\tidle\n\
+                    unknown\tf\tidle\n"),
+            )
+            .with_response("workspace forget agent-idle", ok(""))
+            .with_response("workspace forget agent-dormant", ok(""));
+        for (name, path) in [
+            ("agent-idle", &idle),
+            ("agent-active", &active),
+            ("agent-dormant", &dormant),
+            ("mine", &user),
+            ("agent-missing", &missing),
+        ] {
+            executor = with_root(executor, name, path);
+        }
+
+        let mut manager = WorkspaceManager::new(executor);
+        let mut pruned = manager.prune_stale(hour).await.unwrap();
+        pruned.sort();
+
+        assert_eq!(pruned, vec!["agent-dormant", "agent-idle"]);
+        assert!(!idle.exists());
+        assert!(!dormant.exists());
+        assert!(active.join("src/lib.rs").exists());
+        assert!(user.exists());
+    }
+
+    #[tokio::test]
+    async fn test_create_workspace_limit_without_idle_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo_root = dir.path().join("repo");
+        let busy_path = agent_workspace_path(&repo_root, "agent-1");
+        std::fs::create_dir_all(&busy_path).unwrap();
+        let executor = MockJjExecutor::new()
+            .with_repo_root(repo_root)
+            .with_response(
+                &list_command(),
+                ok("default\tqpvuntsm\tbusy\nagent-1\tzzsxqkwp\tbusy\n"),
+            );
+        let executor = with_root(executor, "agent-1", &busy_path);
+
+        let mut manager = WorkspaceManager::new(executor).with_max_workspaces(1);
+        let err = manager.create_workspace("agent-2").await.unwrap_err();
+
+        assert!(err.to_string().contains("limit of 1"));
+    }
+
+    #[tokio::test]
+    async fn test_create_workspace_evicts_oldest_idle() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo_root = dir.path().join("repo");
+        let idle_path = agent_workspace_path(&repo_root, "agent-1");
+        std::fs::create_dir_all(&idle_path).unwrap();
+        let new_path = agent_workspace_path(&repo_root, "agent-2");
+        let executor = MockJjExecutor::new()
+            .with_repo_root(repo_root)
+            .with_response(&list_command(), ok("agent-1\tzzsxqkwp\tidle\n"))
+            .with_response("workspace forget agent-1", ok(""))
+            .with_response(
+                &format!("workspace add --name agent-2 {}", new_path.display()),
+                ok(""),
+            );
+        let executor = with_root(executor, "agent-1", &idle_path);
+
+        let mut manager = WorkspaceManager::new(executor).with_max_workspaces(1);
+        let path = manager.create_workspace("agent-2").await.unwrap();
+
+        assert_eq!(path, new_path);
+        assert!(!idle_path.exists());
+        assert!(new_path.exists());
+    }
 }
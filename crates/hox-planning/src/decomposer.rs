@@ -1,7 +1,8 @@
 //! PRD decomposition into phases and tasks
 
-use crate::prd::ProjectRequirementsDocument;
+use crate::prd::{Epic, ProjectRequirementsDocument, Story};
 use hox_core::{Phase, Priority, TaskStatus};
+use std::collections::{HashMap, HashSet};
 
 /// Decomposes a PRD into executable phases and task descriptions
 pub struct PrdDecomposer;
@@ -11,10 +12,20 @@ impl PrdDecomposer {
     ///
     /// Returns a tuple of (phases, tasks) where:
     /// - phases: Ordered execution phases (Phase 0 = contracts, final phases = integration/validation)
-    /// - tasks: Task descriptions that can be converted to JJ changes
+    /// - tasks: Task descriptions that can be converted to JJ changes, in
+    ///   dependency order (every task comes after the tasks it depends on)
+    ///
+    /// Dependencies come from each story's `depends_on` and from references in
+    /// its text to other epics ("Epic-1"), task IDs ("Epic-1-1.1") or stories
+    /// ("story 1.1"). A reference that would create a cycle, between tasks or
+    /// between epics, is dropped.
+    ///
+    /// Each epic gets one phase, ordered so that an epic comes after the epics
+    /// its tasks depend on; no task depends on a task in a later phase.
     pub fn decompose(prd: &ProjectRequirementsDocument) -> (Vec<Phase>, Vec<TaskDescription>) {
         let mut phases = Vec::new();
         let mut tasks = Vec::new();
+        let mut task_epics = Vec::new();
 
         // Phase 0: Planning/Contracts
         phases.push(Phase::contracts(format!(
@@ -22,19 +33,10 @@ impl PrdDecomposer {
             prd.project_name
         )));
 
-        // One phase per epic
+        // One task per story
         for (idx, epic) in prd.epics.iter().enumerate() {
-            let phase = Phase {
-                number: (idx + 1) as u32,
-                name: format!("epic-{}", epic.id.to_lowercase().replace(' ', "-")),
-                description: epic.description.clone(),
-                blocking: false,
-                tasks: Vec::new(),
-            };
-            phases.push(phase);
-
-            // One task per story
             for story in &epic.stories {
+                task_epics.push(idx);
                 tasks.push(TaskDescription {
                     id: format!("{}-{}", epic.id, story.id),
                     title: story.title.clone(),
@@ -51,12 +53,35 @@ impl PrdDecomposer {
                             .join("\n")
                     ),
                     priority: epic.priority,
-                    phase: (idx + 1) as u32,
+                    phase: 0,
                     status: TaskStatus::Open,
+                    dependencies: Vec::new(),
                 });
             }
         }
 
+        let epic_deps = Self::infer_dependencies(prd, &mut tasks, &task_epics);
+
+        // One phase per epic, in epic dependency order
+        let mut epic_phase = vec![0u32; prd.epics.len()];
+        for (position, idx) in epic_order(&epic_deps).into_iter().enumerate() {
+            let epic = &prd.epics[idx];
+            epic_phase[idx] = (position + 1) as u32;
+            phases.push(Phase {
+                number: epic_phase[idx],
+                name: format!("epic-{}", epic.id.to_lowercase().replace(' ', "-")),
+                description: epic.description.clone(),
+                blocking: false,
+                tasks: Vec::new(),
+            });
+        }
+        for (task, &idx) in tasks.iter_mut().zip(&task_epics) {
+            task.phase = epic_phase[idx];
+        }
+
+        tasks.sort_by_key(|t| t.phase);
+        let tasks = Self::topological_order(tasks);
+
         // Final phases
         let integration_phase = prd.epics.len() + 1;
         let validation_phase = prd.epics.len() + 2;
@@ -74,11 +99,182 @@ impl PrdDecomposer {
         (phases, tasks)
     }
 
+    /// Resolve a story reference to task IDs
+    ///
+    /// `reference` may be a task ID, an epic ID (all of its stories), or a
+    /// story ID, looked up in `epic` first and then across the PRD if unique.
+    pub(crate) fn resolve_reference(
+        prd: &ProjectRequirementsDocument,
+        epic: &Epic,
+        reference: &str,
+    ) -> Vec<String> {
+        let reference = reference.trim();
+
+        for e in &prd.epics {
+            if e.id == reference {
+                return e.stories.iter().map(|s| task_id(e, s)).collect();
+            }
+            if let Some(story) = e.stories.iter().find(|s| task_id(e, s) == reference) {
+                return vec![task_id(e, story)];
+            }
+        }
+
+        if let Some(story) = epic.stories.iter().find(|s| s.id == reference) {
+            return vec![task_id(epic, story)];
+        }
+
+        let matches: Vec<String> = prd
+            .epics
+            .iter()
+            .flat_map(|e| {
+                e.stories
+                    .iter()
+                    .filter(|s| s.id == reference)
+                    .map(move |s| task_id(e, s))
+            })
+            .collect();
+        if matches.len() == 1 {
+            matches
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// References to other stories mentioned in a story's text
+    fn text_references(prd: &ProjectRequirementsDocument, story: &Story) -> Vec<String> {
+        let text = std::iter::once(story.title.as_str())
+            .chain([story.i_want.as_str(), story.so_that.as_str()])
+            .chain(story.acceptance_criteria.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let lower = text.to_lowercase();
+
+        let mut references = Vec::new();
+        for e in &prd.epics {
+            if mentions(&text, &e.id) {
+                references.push(e.id.clone());
+            }
+            for s in &e.stories {
+                let id = task_id(e, s);
+                if mentions(&text, &id) || mentions(&lower, &format!("story {}", s.id)) {
+                    references.push(if mentions(&text, &id) {
+                        id
+                    } else {
+                        s.id.clone()
+                    });
+                }
+            }
+        }
+        references
+    }
+
+    /// Fill in `dependencies` for each task, skipping references that would form a cycle
+    ///
+    /// `task_epics` holds the epic index of each task. Returns, per epic, the
+    /// epics its tasks depend on; a reference that would make two epics depend
+    /// on each other is skipped too, so that graph is acyclic.
+    fn infer_dependencies(
+        prd: &ProjectRequirementsDocument,
+        tasks: &mut [TaskDescription],
+        task_epics: &[usize],
+    ) -> Vec<HashSet<usize>> {
+        let mut epic_deps = vec![HashSet::new(); prd.epics.len()];
+        let index: HashMap<String, usize> = tasks
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (t.id.clone(), i))
+            .collect();
+
+        for epic in &prd.epics {
+            for story in &epic.stories {
+                let Some(&from) = index.get(&task_id(epic, story)) else {
+                    continue;
+                };
+
+                let references = story
+                    .depends_on
+                    .iter()
+                    .cloned()
+                    .chain(Self::text_references(prd, story));
+                for reference in references {
+                    for target in Self::resolve_reference(prd, epic, &reference) {
+                        let Some(&to) = index.get(&target) else {
+                            continue;
+                        };
+                        let (from_epic, to_epic) = (task_epics[from], task_epics[to]);
+                        if to == from
+                            || tasks[from].dependencies.contains(&target)
+                            || depends_on(tasks, &index, to, &tasks[from].id)
+                            || (from_epic != to_epic
+                                && epic_depends_on(&epic_deps, to_epic, from_epic))
+                        {
+                            continue;
+                        }
+                        tasks[from].dependencies.push(target);
+                        if from_epic != to_epic {
+                            epic_deps[from_epic].insert(to_epic);
+                        }
+                    }
+                }
+            }
+        }
+
+        epic_deps
+    }
+
+    /// Stable topological sort: tasks keep PRD order unless a dependency forces otherwise
+    fn topological_order(tasks: Vec<TaskDescription>) -> Vec<TaskDescription> {
+        let mut placed: HashSet<String> = HashSet::new();
+        let mut remaining: Vec<Option<TaskDescription>> = tasks.into_iter().map(Some).collect();
+        let mut ordered = Vec::with_capacity(remaining.len());
+
+        while ordered.len() < remaining.len() {
+            let next = remaining.iter().position(|slot| {
+                slot.as_ref()
+                    .is_some_and(|t| t.dependencies.iter().all(|d| placed.contains(d)))
+            });
+            // Dependencies are acyclic by construction, so there is always a ready task
+            let Some(next) = next else { break };
+            let task = remaining[next].take().expect("slot is occupied");
+            placed.insert(task.id.clone());
+            ordered.push(task);
+        }
+
+        ordered
+    }
+
+    /// Group tasks into waves that can run in parallel
+    ///
+    /// Each group only depends on tasks in earlier groups. Expects tasks in
+    /// dependency order, as returned by [`Self::decompose`].
+    pub fn parallel_groups(tasks: &[TaskDescription]) -> Vec<Vec<String>> {
+        let mut level: HashMap<&str, usize> = HashMap::new();
+        let mut groups: Vec<Vec<String>> = Vec::new();
+
+        for task in tasks {
+            let depth = task
+                .dependencies
+                .iter()
+                .filter_map(|d| level.get(d.as_str()))
+                .map(|l| l + 1)
+                .max()
+                .unwrap_or(0);
+            level.insert(&task.id, depth);
+            if groups.len() <= depth {
+                groups.resize_with(depth + 1, Vec::new);
+            }
+            groups[depth].push(task.id.clone());
+        }
+
+        groups
+    }
+
     /// Generate a task summary for reporting
     pub fn summarize(prd: &ProjectRequirementsDocument) -> DecompositionSummary {
         let (phases, tasks) = Self::decompose(prd);
 
         let total_stories: usize = prd.epics.iter().map(|e| e.stories.len()).sum();
+        let groups = Self::parallel_groups(&tasks);
 
         DecompositionSummary {
             project_name: prd.project_name.clone(),
@@ -86,6 +282,8 @@ impl PrdDecomposer {
             total_stories,
             total_phases: phases.len(),
             total_tasks: tasks.len(),
+            parallel_groups: groups.len(),
+            max_parallel_tasks: groups.iter().map(Vec::len).max().unwrap_or(0),
             phases: phases
                 .iter()
                 .map(|p| PhaseInfo {
@@ -98,6 +296,86 @@ impl PrdDecomposer {
     }
 }
 
+/// Task ID for a story
+fn task_id(epic: &Epic, story: &Story) -> String {
+    format!("{}-{}", epic.id, story.id)
+}
+
+/// Whether `text` mentions `id` as a whole token
+fn mentions(text: &str, id: &str) -> bool {
+    let is_id_char = |c: char| c.is_alphanumeric() || c == '-' || c == '_';
+    text.match_indices(id).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let mut after = text[start + id.len()..].chars();
+        let next = after.next();
+        let boundary_before = before.is_none_or(|c| !is_id_char(c) && c != '.');
+        let boundary_after = match next {
+            None => true,
+            // A trailing period ends a sentence unless a digit follows ("1.1")
+            Some('.') => after.next().is_none_or(|c| !c.is_alphanumeric()),
+            Some(c) => !is_id_char(c),
+        };
+        boundary_before && boundary_after
+    })
+}
+
+/// Whether task `from` (transitively) depends on `target`
+fn depends_on(
+    tasks: &[TaskDescription],
+    index: &HashMap<String, usize>,
+    from: usize,
+    target: &str,
+) -> bool {
+    let mut stack = vec![from];
+    let mut seen = HashSet::new();
+    while let Some(i) = stack.pop() {
+        if !seen.insert(i) {
+            continue;
+        }
+        for dep in &tasks[i].dependencies {
+            if dep == target {
+                return true;
+            }
+            if let Some(&j) = index.get(dep) {
+                stack.push(j);
+            }
+        }
+    }
+    false
+}
+
+/// Whether epic `from` (transitively) depends on epic `target`
+fn epic_depends_on(epic_deps: &[HashSet<usize>], from: usize, target: usize) -> bool {
+    let mut stack = vec![from];
+    let mut seen = HashSet::new();
+    while let Some(epic) = stack.pop() {
+        if epic == target {
+            return true;
+        }
+        if seen.insert(epic) {
+            stack.extend(epic_deps[epic].iter().copied());
+        }
+    }
+    false
+}
+
+/// Stable topological order of epics: PRD order unless a dependency forces otherwise
+fn epic_order(epic_deps: &[HashSet<usize>]) -> Vec<usize> {
+    let mut placed = vec![false; epic_deps.len()];
+    let mut order = Vec::with_capacity(epic_deps.len());
+
+    while order.len() < epic_deps.len() {
+        let next =
+            (0..epic_deps.len()).find(|&e| !placed[e] && epic_deps[e].iter().all(|&d| placed[d]));
+        // The epic graph is acyclic by construction, so there is always a ready epic
+        let Some(next) = next else { break };
+        placed[next] = true;
+        order.push(next);
+    }
+
+    order
+}
+
 /// A task description ready to be converted into a JJ change
 #[derive(Debug, Clone)]
 pub struct TaskDescription {
@@ -107,14 +385,21 @@ pub struct TaskDescription {
     pub priority: Priority,
     pub phase: u32,
     pub status: TaskStatus,
+    /// IDs of tasks that must complete before this one starts
+    pub dependencies: Vec<String>,
 }
 
 impl TaskDescription {
     /// Format as a JJ change description with structured metadata
     pub fn to_change_description(&self) -> String {
+        let depends = if self.dependencies.is_empty() {
+            String::new()
+        } else {
+            format!("\nDepends-On: {}", self.dependencies.join(", "))
+        };
         format!(
-            "Task: {}\nPriority: {}\nStatus: {}\nPhase: {}\n\n{}",
-            self.title, self.priority as u32, self.status, self.phase, self.description
+            "Task: {}\nPriority: {}\nStatus: {}\nPhase: {}{}\n\n{}",
+            self.title, self.priority as u32, self.status, self.phase, depends, self.description
         )
     }
 }
@@ -127,6 +412,10 @@ pub struct DecompositionSummary {
     pub total_stories: usize,
     pub total_phases: usize,
     pub total_tasks: usize,
+    /// Waves of tasks that can run in parallel
+    pub parallel_groups: usize,
+    /// Size of the largest wave
+    pub max_parallel_tasks: usize,
     pub phases: Vec<PhaseInfo>,
 }
 
@@ -145,6 +434,11 @@ impl std::fmt::Display for DecompositionSummary {
         writeln!(f, "  Stories: {}", self.total_stories)?;
         writeln!(f, "  Phases: {}", self.total_phases)?;
         writeln!(f, "  Tasks: {}", self.total_tasks)?;
+        writeln!(
+            f,
+            "  Parallel groups: {} (up to {} tasks at once)",
+            self.parallel_groups, self.max_parallel_tasks
+        )?;
         writeln!(f)?;
         writeln!(f, "Phase Breakdown:")?;
         for phase in &self.phases {
@@ -162,6 +456,7 @@ impl std::fmt::Display for DecompositionSummary {
 mod tests {
     use super::*;
     use crate::templates::example_prd;
+    use hox_core::Priority;
 
     #[test]
     fn test_decompose_example_prd() {
@@ -191,4 +486,141 @@ mod tests {
         assert_eq!(summary.total_epics, 2);
         assert_eq!(summary.total_phases, 5);
     }
+
+    fn story(id: &str, title: &str, i_want: &str) -> Story {
+        Story {
+            id: id.to_string(),
+            title: title.to_string(),
+            as_a: "user".to_string(),
+            i_want: i_want.to_string(),
+            so_that: "it works".to_string(),
+            acceptance_criteria: Vec::new(),
+            depends_on: Vec::new(),
+        }
+    }
+
+    fn epic(id: &str, stories: Vec<Story>) -> Epic {
+        Epic {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            priority: Priority::Medium,
+            stories,
+        }
+    }
+
+    #[test]
+    fn test_feature_reference_becomes_dependency() {
+        let mut prd = ProjectRequirementsDocument::new("Deps");
+        // B is listed first but builds on A
+        prd.epics.push(epic(
+            "Epic-B",
+            vec![story("1", "Export reports", "to export what Epic-A stores")],
+        ));
+        prd.epics
+            .push(epic("Epic-A", vec![story("1", "Store data", "storage")]));
+
+        let (phases, tasks) = PrdDecomposer::decompose(&prd);
+        let ids: Vec<&str> = tasks.iter().map(|t| t.id.as_str()).collect();
+
+        assert_eq!(ids, vec!["Epic-A-1", "Epic-B-1"]);
+        assert_eq!(tasks[1].dependencies, vec!["Epic-A-1"]);
+        assert!(tasks[1]
+            .to_change_description()
+            .contains("Depends-On: Epic-A-1"));
+
+        // Epic-A's phase now runs first
+        assert_eq!(phases[1].name, "epic-epic-a");
+        assert_eq!(phases[2].name, "epic-epic-b");
+        assert_eq!((tasks[0].phase, tasks[1].phase), (1, 2));
+    }
+
+    #[test]
+    fn test_cross_epic_cycles_are_broken() {
+        let mut prd = ProjectRequirementsDocument::new("Cycle");
+        let mut ui = story("1", "UI", "a UI");
+        ui.depends_on.push("Epic-2-1".to_string());
+        let mut api = story("2", "API", "an API");
+        api.depends_on.push("Epic-1-2".to_string());
+        prd.epics
+            .push(epic("Epic-1", vec![ui, story("2", "Schema", "a schema")]));
+        prd.epics
+            .push(epic("Epic-2", vec![story("1", "Store", "storage"), api]));
+
+        let (phases, tasks) = PrdDecomposer::decompose(&prd);
+        let phase_of: HashMap<&str, u32> = tasks.iter().map(|t| (t.id.as_str(), t.phase)).collect();
+
+        // Epic-2 would also depend on Epic-1, so that reference is dropped
+        let api = tasks.iter().find(|t| t.id == "Epic-2-2").unwrap();
+        assert!(api.dependencies.is_empty());
+        assert_eq!(phases[1].name, "epic-epic-2");
+
+        // Phases never go backwards along a dependency
+        for task in &tasks {
+            for dep in &task.dependencies {
+                assert!(
+                    phase_of[dep.as_str()] <= task.phase,
+                    "{} -> {}",
+                    task.id,
+                    dep
+                );
+            }
+        }
+        let task_phases: Vec<u32> = tasks.iter().map(|t| t.phase).collect();
+        assert!(task_phases.is_sorted());
+    }
+
+    #[test]
+    fn test_story_references_and_parallel_groups() {
+        let mut prd = ProjectRequirementsDocument::new("Deps");
+        let mut api = story("2", "API", "an API over story 1");
+        api.depends_on.push("Epic-1-1".to_string());
+        let mut cli = story("3", "CLI", "a CLI");
+        cli.depends_on.push("1".to_string());
+        prd.epics.push(epic(
+            "Epic-1",
+            vec![story("1", "Core", "a core library"), api, cli],
+        ));
+        prd.epics
+            .push(epic("Epic-2", vec![story("1", "Docs", "docs")]));
+
+        let (_, tasks) = PrdDecomposer::decompose(&prd);
+        let api = tasks.iter().find(|t| t.id == "Epic-1-2").unwrap();
+        // Text reference and explicit reference to the same task count once
+        assert_eq!(api.dependencies, vec!["Epic-1-1"]);
+
+        let groups = PrdDecomposer::parallel_groups(&tasks);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0], vec!["Epic-1-1", "Epic-2-1"]);
+        assert_eq!(groups[1], vec!["Epic-1-2", "Epic-1-3"]);
+
+        let summary = PrdDecomposer::summarize(&prd);
+        assert_eq!(summary.parallel_groups, 2);
+        assert_eq!(summary.max_parallel_tasks, 2);
+    }
+
+    #[test]
+    fn test_cyclic_references_are_broken() {
+        let mut prd = ProjectRequirementsDocument::new("Cycle");
+        let mut a = story("1", "A", "a");
+        a.depends_on.push("2".to_string());
+        let mut b = story("2", "B", "b");
+        b.depends_on.push("1".to_string());
+        prd.epics.push(epic("Epic-1", vec![a, b]));
+
+        let (_, tasks) = PrdDecomposer::decompose(&prd);
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].id, "Epic-1-2");
+        assert_eq!(tasks[1].dependencies, vec!["Epic-1-2"]);
+        assert!(tasks[0].dependencies.is_empty());
+    }
+
+    #[test]
+    fn test_mentions_boundaries() {
+        assert!(mentions("builds on Epic-1.", "Epic-1"));
+        assert!(!mentions("see Epic-10", "Epic-1"));
+        assert!(!mentions("see Epic-1-2", "Epic-1"));
+        assert!(mentions("after story 1.1, then", "story 1.1"));
+        assert!(!mentions("after story 1.12", "story 1.1"));
+    }
 }
@@ -8,5 +8,5 @@ pub mod prd;
 pub mod templates;
 
 pub use decomposer::{DecompositionSummary, PrdDecomposer, TaskDescription};
-pub use prd::ProjectRequirementsDocument;
+pub use prd::{PrdValidationError, ProjectRequirementsDocument};
 pub use templates::{cli_tool_prd, example_prd, minimal_prd};
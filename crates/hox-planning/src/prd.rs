@@ -1,7 +1,9 @@
 //! Product Requirements Document (PRD) data structures
 
+use crate::decomposer::PrdDecomposer;
 use hox_core::Priority;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 /// A complete Product Requirements Document
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,6 +59,42 @@ pub struct Story {
     pub i_want: String,
     pub so_that: String,
     pub acceptance_criteria: Vec<String>,
+    /// Stories this one builds on: a story ID in the same epic ("1.1"),
+    /// a task ID ("Epic-1-1.1"), or an epic ID ("Epic-1") for all its stories
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+/// A problem that would make a PRD decompose into a broken or empty plan
+///
+/// Each error names the offending field, e.g. `epics[0].stories[1].title`.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PrdValidationError {
+    #[error("goals.goals: at least one goal is required")]
+    NoGoals,
+
+    #[error("goals.goals[{index}]: goal is empty")]
+    EmptyGoal { index: usize },
+
+    #[error("epics: at least one epic with a story is required")]
+    NoFeatures,
+
+    #[error("epics[{epic}].name: epic name is empty")]
+    EmptyEpicName { epic: usize },
+
+    #[error("epics[{epic}].stories[{story}].title: story title is empty")]
+    EmptyStoryTitle { epic: usize, story: usize },
+
+    #[error(
+        "epics[{epic}].stories[{story}].depends_on[{dependency}]: \
+         '{reference}' does not match an epic, task, or unique story ID"
+    )]
+    UnresolvedDependency {
+        epic: usize,
+        story: usize,
+        dependency: usize,
+        reference: String,
+    },
 }
 
 impl ProjectRequirementsDocument {
@@ -80,6 +118,56 @@ impl ProjectRequirementsDocument {
         }
     }
 
+    /// Check the PRD can be decomposed into a meaningful plan
+    ///
+    /// Returns every problem found rather than stopping at the first.
+    pub fn validate(&self) -> Result<(), Vec<PrdValidationError>> {
+        let mut errors = Vec::new();
+
+        if self.goals.goals.is_empty() {
+            errors.push(PrdValidationError::NoGoals);
+        }
+        for (index, goal) in self.goals.goals.iter().enumerate() {
+            if goal.trim().is_empty() {
+                errors.push(PrdValidationError::EmptyGoal { index });
+            }
+        }
+
+        if self.epics.iter().all(|e| e.stories.is_empty()) {
+            errors.push(PrdValidationError::NoFeatures);
+        }
+
+        for (epic_idx, epic) in self.epics.iter().enumerate() {
+            if epic.name.trim().is_empty() {
+                errors.push(PrdValidationError::EmptyEpicName { epic: epic_idx });
+            }
+            for (story_idx, story) in epic.stories.iter().enumerate() {
+                if story.title.trim().is_empty() {
+                    errors.push(PrdValidationError::EmptyStoryTitle {
+                        epic: epic_idx,
+                        story: story_idx,
+                    });
+                }
+                for (dependency, reference) in story.depends_on.iter().enumerate() {
+                    if PrdDecomposer::resolve_reference(self, epic, reference).is_empty() {
+                        errors.push(PrdValidationError::UnresolvedDependency {
+                            epic: epic_idx,
+                            story: story_idx,
+                            dependency,
+                            reference: reference.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
     /// Update the last_updated timestamp
     pub fn touch(&mut self) {
         self.last_updated = chrono::Utc::now().to_rfc3339();
@@ -101,6 +189,7 @@ impl ProjectRequirementsDocument {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::templates::{example_prd, minimal_prd};
 
     #[test]
     fn test_new_prd() {
@@ -122,4 +211,81 @@ mod tests {
         prd.start();
         assert_eq!(prd.status, "in_progress");
     }
+
+    #[test]
+    fn test_validate_example_prd() {
+        assert_eq!(example_prd().validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_missing_goals_and_features() {
+        let errors = minimal_prd("Empty").validate().unwrap_err();
+        assert!(errors.contains(&PrdValidationError::NoGoals));
+        assert!(errors.contains(&PrdValidationError::NoFeatures));
+    }
+
+    #[test]
+    fn test_validate_empty_goal() {
+        let mut prd = example_prd();
+        prd.goals.goals.push("  ".to_string());
+        let index = prd.goals.goals.len() - 1;
+
+        let errors = prd.validate().unwrap_err();
+        assert_eq!(errors, vec![PrdValidationError::EmptyGoal { index }]);
+        assert_eq!(
+            errors[0].to_string(),
+            format!("goals.goals[{}]: goal is empty", index)
+        );
+    }
+
+    #[test]
+    fn test_validate_epic_without_stories() {
+        let mut prd = example_prd();
+        for epic in &mut prd.epics {
+            epic.stories.clear();
+        }
+        assert_eq!(prd.validate(), Err(vec![PrdValidationError::NoFeatures]));
+    }
+
+    #[test]
+    fn test_validate_empty_titles() {
+        let mut prd = example_prd();
+        prd.epics[0].name = String::new();
+        prd.epics[0].stories[1].title = " ".to_string();
+
+        let errors = prd.validate().unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                PrdValidationError::EmptyEpicName { epic: 0 },
+                PrdValidationError::EmptyStoryTitle { epic: 0, story: 1 },
+            ]
+        );
+        assert_eq!(
+            errors[1].to_string(),
+            "epics[0].stories[1].title: story title is empty"
+        );
+    }
+
+    #[test]
+    fn test_validate_unresolved_dependency() {
+        let mut prd = example_prd();
+        prd.epics[0].stories[0]
+            .depends_on
+            .push("Epic-99".to_string());
+
+        let errors = prd.validate().unwrap_err();
+        assert_eq!(
+            errors,
+            vec![PrdValidationError::UnresolvedDependency {
+                epic: 0,
+                story: 0,
+                dependency: 0,
+                reference: "Epic-99".to_string(),
+            }]
+        );
+        assert!(errors[0]
+            .to_string()
+            .starts_with("epics[0].stories[0].depends_on[0]: 'Epic-99'"));
+    }
 }
@@ -63,6 +63,7 @@ pub fn example_prd() -> ProjectRequirementsDocument {
                             "All dependencies declared in Cargo.toml".to_string(),
                             "CI/CD pipeline configured".to_string(),
                         ],
+                        depends_on: Vec::new(),
                     },
                     Story {
                         id: "1.2".to_string(),
@@ -75,6 +76,7 @@ pub fn example_prd() -> ProjectRequirementsDocument {
                             "Serialization/deserialization working".to_string(),
                             "Comprehensive unit tests".to_string(),
                         ],
+                        depends_on: vec!["1.1".to_string()],
                     },
                 ],
             },
@@ -95,6 +97,7 @@ pub fn example_prd() -> ProjectRequirementsDocument {
                             "Integration test harness ready".to_string(),
                             "Test coverage reporting enabled".to_string(),
                         ],
+                        depends_on: Vec::new(),
                     },
                 ],
             },
@@ -171,6 +174,7 @@ pub fn cli_tool_prd(tool_name: impl Into<String>) -> ProjectRequirementsDocument
                     "Help text displays correctly".to_string(),
                     "Version flag works".to_string(),
                 ],
+                depends_on: Vec::new(),
             }],
         }],
     }
@@ -1,8 +1,25 @@
 //! Byzantine fault tolerant consensus implementation
-
+//!
+//! # Weighted votes
+//!
+//! Each [`Vote`] carries a weight (default 1.0, capped at
+//! [`ConsensusConfig::max_weight`]). Consensus is reached when the votes for
+//! Pass or Fail carry at least `threshold` of the total weight cast.
+//!
+//! Weights do not change the participant requirement: at least `3f + 1`
+//! validators must still vote, whatever their weights. They do change what
+//! "tolerating f faults" means. With equal weights, f faulty validators can
+//! never outvote the rest. With unequal weights, that only holds while the f
+//! heaviest validators together carry less than `1 - threshold` of the total
+//! weight. Keep `max_weight` low enough that no small group of validators can
+//! decide the outcome on its own.
+
+use futures::{Stream, StreamExt};
 use hox_core::ChangeId;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
+use tracing::{debug, warn};
 
 use crate::validator::{ValidationReport, ValidationResult};
 
@@ -14,6 +31,10 @@ pub struct ConsensusConfig {
     pub fault_tolerance: usize,
     /// Threshold for consensus (0.0 - 1.0)
     pub threshold: f32,
+    /// How long to wait for validators before giving up on a quorum
+    pub vote_timeout: Duration,
+    /// Upper bound on a single vote's weight
+    pub max_weight: f64,
 }
 
 impl Default for ConsensusConfig {
@@ -21,6 +42,8 @@ impl Default for ConsensusConfig {
         Self {
             fault_tolerance: 1, // Tolerate 1 faulty validator, need 4 total
             threshold: 0.75,    // 3/4 must agree
+            vote_timeout: Duration::from_secs(300),
+            max_weight: 1.0,
         }
     }
 }
@@ -35,6 +58,23 @@ impl ConsensusConfig {
     pub fn min_votes(&self, total_validators: usize) -> usize {
         ((total_validators as f32) * self.threshold).ceil() as usize
     }
+
+    /// Effective weight of a vote, clamped to `0.0..=max_weight`
+    pub fn effective_weight(&self, vote: &Vote) -> f64 {
+        if vote.weight.is_nan() {
+            return 0.0;
+        }
+        vote.weight.clamp(0.0, self.max_weight)
+    }
+
+    /// Whether `weight` out of `total_weight` meets the threshold
+    ///
+    /// Compared in `f32`, like [`Self::min_votes`]: with equal weights,
+    /// `count >= total * threshold` holds exactly when
+    /// `count >= ceil(total * threshold)`, so both agree on every vote count.
+    fn meets_threshold(&self, weight: f64, total_weight: f64) -> bool {
+        total_weight > 0.0 && weight as f32 >= total_weight as f32 * self.threshold
+    }
 }
 
 /// A vote from a validator
@@ -45,6 +85,13 @@ pub struct Vote {
     pub result: ValidationResult,
     pub score: f32,
     pub report: ValidationReport,
+    /// How much this vote counts toward the threshold
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+}
+
+fn default_weight() -> f64 {
+    1.0
 }
 
 /// Result of consensus
@@ -71,6 +118,198 @@ pub enum ConsensusResult {
         votes_against: usize,
         partial: usize,
     },
+    /// Timed out before a decisive quorum was reached
+    Inconclusive {
+        votes_for: usize,
+        votes_against: usize,
+        received: usize,
+        expected: usize,
+    },
+}
+
+/// Vote counts for one change
+struct Tally {
+    pass: usize,
+    fail: usize,
+    partial: usize,
+    pass_weight: f64,
+    fail_weight: f64,
+    total_weight: f64,
+    average_score: f32,
+    fail_reasons: Vec<String>,
+}
+
+impl Tally {
+    fn new(config: &ConsensusConfig, votes: &[Vote]) -> Self {
+        let mut tally = Self {
+            pass: 0,
+            fail: 0,
+            partial: 0,
+            pass_weight: 0.0,
+            fail_weight: 0.0,
+            total_weight: 0.0,
+            average_score: 0.0,
+            fail_reasons: Vec::new(),
+        };
+        let mut total_score = 0.0;
+
+        for vote in votes {
+            total_score += vote.score;
+            let weight = config.effective_weight(vote);
+            tally.total_weight += weight;
+            match vote.result {
+                ValidationResult::Pass => {
+                    tally.pass += 1;
+                    tally.pass_weight += weight;
+                }
+                ValidationResult::Fail => {
+                    tally.fail += 1;
+                    tally.fail_weight += weight;
+                    // Collect failure reasons from the report
+                    for check in &vote.report.checks {
+                        if !check.passed {
+                            tally.fail_reasons.push(format!(
+                                "{}: {:?} - {}",
+                                vote.validator_id, check.check, check.details
+                            ));
+                        }
+                    }
+                }
+                ValidationResult::Partial => tally.partial += 1,
+            }
+        }
+
+        if !votes.is_empty() {
+            tally.average_score = total_score / votes.len() as f32;
+        }
+        tally
+    }
+
+    fn pass(&self) -> ConsensusResult {
+        ConsensusResult::Pass {
+            votes_for: self.pass,
+            votes_against: self.fail,
+            average_score: self.average_score,
+        }
+    }
+
+    fn fail(self) -> ConsensusResult {
+        ConsensusResult::Fail {
+            votes_for: self.pass,
+            votes_against: self.fail,
+            average_score: self.average_score,
+            reasons: self.fail_reasons,
+        }
+    }
+
+    fn no_consensus(&self) -> ConsensusResult {
+        ConsensusResult::NoConsensus {
+            votes_for: self.pass,
+            votes_against: self.fail,
+            partial: self.partial,
+        }
+    }
+}
+
+/// Breakdown of why a set of votes did or did not reach consensus
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsensusExplanation {
+    pub total: usize,
+    pub pass_votes: usize,
+    pub fail_votes: usize,
+    pub partial_votes: usize,
+    /// Total weight of all votes
+    pub total_weight: f64,
+    /// Result with the most weight (ties favor Pass, then Fail)
+    pub leading: ValidationResult,
+    /// Fraction of the total weight cast for the leading result
+    pub agreement: f32,
+    /// Weight cast for the leading result
+    pub leading_weight: f64,
+    /// Required fraction of the total weight
+    pub threshold: f32,
+    /// Weight the leading result needs: `threshold` of the total weight
+    pub weight_needed: f64,
+    /// Votes the leading result would need if all weights were equal
+    pub votes_needed: usize,
+    /// Faulty validators tolerated (f)
+    pub fault_tolerance: usize,
+    /// Minimum validators for the fault tolerance (3f + 1)
+    pub quorum: usize,
+    pub quorum_met: bool,
+    /// Whether consensus was reached
+    pub reached: bool,
+    /// Validators that voted against the leading result
+    pub outliers: Vec<String>,
+}
+
+/// Explain the consensus outcome of a set of votes
+///
+/// Mirrors the rules of [`ByzantineConsensus::reach_consensus`]: the quorum
+/// must be met and Pass or Fail must carry at least `threshold` of the
+/// total vote weight.
+pub fn explain_consensus(config: &ConsensusConfig, votes: &[Vote]) -> ConsensusExplanation {
+    let total = votes.len();
+    let count = |result: ValidationResult| votes.iter().filter(|v| v.result == result).count();
+    let weight = |result: ValidationResult| -> f64 {
+        votes
+            .iter()
+            .filter(|v| v.result == result)
+            .map(|v| config.effective_weight(v))
+            .sum()
+    };
+    let pass_votes = count(ValidationResult::Pass);
+    let fail_votes = count(ValidationResult::Fail);
+    let partial_votes = count(ValidationResult::Partial);
+    let pass_weight = weight(ValidationResult::Pass);
+    let fail_weight = weight(ValidationResult::Fail);
+    let partial_weight = weight(ValidationResult::Partial);
+    let total_weight = pass_weight + fail_weight + partial_weight;
+
+    let (leading, leading_weight) = if pass_weight >= fail_weight && pass_weight >= partial_weight {
+        (ValidationResult::Pass, pass_weight)
+    } else if fail_weight >= partial_weight {
+        (ValidationResult::Fail, fail_weight)
+    } else {
+        (ValidationResult::Partial, partial_weight)
+    };
+
+    let agreement = if total_weight > 0.0 {
+        (leading_weight / total_weight) as f32
+    } else {
+        0.0
+    };
+    let votes_needed = config.min_votes(total);
+    let quorum = config.min_validators();
+    let quorum_met = total >= quorum;
+    let reached = quorum_met
+        && leading != ValidationResult::Partial
+        && config.meets_threshold(leading_weight, total_weight);
+
+    let outliers = votes
+        .iter()
+        .filter(|v| v.result != leading)
+        .map(|v| v.validator_id.clone())
+        .collect();
+
+    ConsensusExplanation {
+        total,
+        pass_votes,
+        fail_votes,
+        partial_votes,
+        total_weight,
+        leading,
+        agreement,
+        leading_weight,
+        threshold: config.threshold,
+        weight_needed: (total_weight as f32 * config.threshold) as f64,
+        votes_needed,
+        fault_tolerance: config.fault_tolerance,
+        quorum,
+        quorum_met,
+        reached,
+        outliers,
+    }
 }
 
 /// Byzantine consensus implementation
@@ -123,59 +362,117 @@ impl ByzantineConsensus {
             };
         }
 
-        // Count votes
-        let mut pass_votes = 0;
-        let mut fail_votes = 0;
-        let mut partial_votes = 0;
-        let mut total_score = 0.0;
-        let mut fail_reasons = Vec::new();
+        let tally = Tally::new(&self.config, votes);
 
-        for vote in votes {
-            total_score += vote.score;
-            match vote.result {
-                ValidationResult::Pass => pass_votes += 1,
-                ValidationResult::Fail => {
-                    fail_votes += 1;
-                    // Collect failure reasons from the report
-                    for check in &vote.report.checks {
-                        if !check.passed {
-                            fail_reasons.push(format!(
-                                "{}: {:?} - {}",
-                                vote.validator_id, check.check, check.details
-                            ));
-                        }
-                    }
-                }
-                ValidationResult::Partial => partial_votes += 1,
-            }
+        // Check for consensus
+        if self
+            .config
+            .meets_threshold(tally.pass_weight, tally.total_weight)
+        {
+            tally.pass()
+        } else if self
+            .config
+            .meets_threshold(tally.fail_weight, tally.total_weight)
+        {
+            tally.fail()
+        } else {
+            tally.no_consensus()
         }
+    }
 
-        let average_score = total_score / total as f32;
-        let min_votes = self.config.min_votes(total);
+    /// Decide early if the votes so far settle the outcome for `expected` validators
+    ///
+    /// Outstanding validators are assumed to vote with up to `max_weight`.
+    /// Returns Pass or Fail once no outstanding votes could pull it below the
+    /// threshold, and NoConsensus once neither could reach it even if every
+    /// outstanding validator agreed. Returns `None` while the outcome is still
+    /// open, or if `expected` is below the `3f + 1` quorum.
+    pub fn try_early_decision(
+        &self,
+        change_id: &ChangeId,
+        expected: usize,
+    ) -> Option<ConsensusResult> {
+        let votes = self.votes.get(change_id).map(Vec::as_slice).unwrap_or(&[]);
+        let expected = expected.max(votes.len());
+        if expected < self.config.min_validators() {
+            return None;
+        }
 
-        // Check for consensus
-        if pass_votes >= min_votes {
-            ConsensusResult::Pass {
-                votes_for: pass_votes,
-                votes_against: fail_votes,
-                average_score,
-            }
-        } else if fail_votes >= min_votes {
-            ConsensusResult::Fail {
-                votes_for: pass_votes,
-                votes_against: fail_votes,
-                average_score,
-                reasons: fail_reasons,
-            }
+        let tally = Tally::new(&self.config, votes);
+        let outstanding = (expected - votes.len()) as f64 * self.config.max_weight;
+        let worst_total = tally.total_weight + outstanding;
+        let can_reach = |weight: f64| {
+            self.config
+                .meets_threshold(weight + outstanding, worst_total)
+        };
+
+        if self.config.meets_threshold(tally.pass_weight, worst_total) {
+            Some(tally.pass())
+        } else if self.config.meets_threshold(tally.fail_weight, worst_total) {
+            Some(tally.fail())
+        } else if !can_reach(tally.pass_weight) && !can_reach(tally.fail_weight) {
+            Some(tally.no_consensus())
         } else {
-            ConsensusResult::NoConsensus {
-                votes_for: pass_votes,
-                votes_against: fail_votes,
-                partial: partial_votes,
+            None
+        }
+    }
+
+    /// Collect votes from a stream, deciding as soon as the outcome is settled
+    ///
+    /// Stragglers are not waited for once a decisive quorum exists. If
+    /// `timeout` elapses first the result is `Inconclusive`; if the stream
+    /// ends first the collected votes are judged by [`Self::reach_consensus`].
+    pub async fn collect_votes<S>(
+        &mut self,
+        change_id: &ChangeId,
+        expected: usize,
+        votes: S,
+        timeout: Duration,
+    ) -> ConsensusResult
+    where
+        S: Stream<Item = Vote>,
+    {
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut votes = std::pin::pin!(votes);
+
+        loop {
+            if let Some(result) = self.try_early_decision(change_id, expected) {
+                return result;
+            }
+
+            match tokio::time::timeout_at(deadline, votes.next()).await {
+                Ok(Some(vote)) => {
+                    if &vote.change_id != change_id {
+                        warn!(
+                            "Ignoring vote from {} for {} while collecting {}",
+                            vote.validator_id, vote.change_id, change_id
+                        );
+                        continue;
+                    }
+                    debug!("Vote from {}: {:?}", vote.validator_id, vote.result);
+                    self.add_vote(vote);
+                }
+                Ok(None) => return self.reach_consensus(change_id),
+                Err(_) => {
+                    let collected = self.votes.get(change_id).map(Vec::as_slice).unwrap_or(&[]);
+                    let tally = Tally::new(&self.config, collected);
+                    return ConsensusResult::Inconclusive {
+                        votes_for: tally.pass,
+                        votes_against: tally.fail,
+                        received: tally.pass + tally.fail + tally.partial,
+                        expected,
+                    };
+                }
             }
         }
     }
 
+    /// Explain the consensus outcome for a change
+    pub fn explain(&self, change_id: &ChangeId) -> ConsensusExplanation {
+        let votes = self.votes.get(change_id).map(Vec::as_slice).unwrap_or(&[]);
+        explain_consensus(&self.config, votes)
+    }
+
     /// Get all votes for a change
     pub fn get_votes(&self, change_id: &ChangeId) -> Option<&Vec<Vote>> {
         self.votes.get(change_id)
@@ -220,6 +517,7 @@ mod tests {
                 report.score = score;
                 report
             },
+            weight: 1.0,
         }
     }
 
@@ -266,4 +564,259 @@ mod tests {
             _ => panic!("Expected InsufficientValidators"),
         }
     }
+
+    #[test]
+    fn test_explain_barely_passing() {
+        let config = ConsensusConfig::default();
+        // 5 validators need ceil(5 * 0.75) = 4 agreeing votes
+        let votes = vec![
+            make_vote("v1", "c", ValidationResult::Pass, 0.9),
+            make_vote("v2", "c", ValidationResult::Pass, 0.8),
+            make_vote("v3", "c", ValidationResult::Pass, 0.85),
+            make_vote("v4", "c", ValidationResult::Pass, 0.9),
+            make_vote("v5", "c", ValidationResult::Fail, 0.2),
+        ];
+
+        let explanation = explain_consensus(&config, &votes);
+
+        assert_eq!(explanation.total, 5);
+        assert_eq!(explanation.pass_votes, 4);
+        assert_eq!(explanation.fail_votes, 1);
+        assert_eq!(explanation.leading, ValidationResult::Pass);
+        assert!((explanation.agreement - 0.8).abs() < f32::EPSILON);
+        assert_eq!(explanation.votes_needed, 4);
+        assert_eq!(explanation.quorum, 4);
+        assert!(explanation.quorum_met);
+        assert!(explanation.reached);
+        assert_eq!(explanation.outliers, vec!["v5"]);
+    }
+
+    #[test]
+    fn test_equal_weights_match_vote_counts() {
+        // (threshold, validators, agreeing votes): exactly ceil(n * threshold)
+        for (threshold, total, agreeing) in [(0.8, 5, 4), (0.6, 5, 3), (0.8, 10, 8), (0.6, 10, 6)] {
+            let config = ConsensusConfig {
+                threshold,
+                ..Default::default()
+            };
+            let votes: Vec<Vote> = (0..total)
+                .map(|i| {
+                    let result = if i < agreeing {
+                        ValidationResult::Pass
+                    } else {
+                        ValidationResult::Fail
+                    };
+                    make_vote(&format!("v{}", i), "c", result, 0.5)
+                })
+                .collect();
+
+            let explanation = explain_consensus(&config, &votes);
+            assert_eq!(explanation.votes_needed, agreeing);
+            assert!(
+                explanation.reached,
+                "{} of {} at {}",
+                agreeing, total, threshold
+            );
+
+            // One vote fewer falls short
+            let short = explain_consensus(&config, &votes[1..]);
+            let needed = config.min_votes(total - 1);
+            assert_eq!(short.reached, agreeing > needed);
+        }
+    }
+
+    #[test]
+    fn test_explain_barely_failing() {
+        let config = ConsensusConfig::default();
+        let votes = vec![
+            make_vote("v1", "c", ValidationResult::Pass, 0.9),
+            make_vote("v2", "c", ValidationResult::Pass, 0.8),
+            make_vote("v3", "c", ValidationResult::Pass, 0.85),
+            make_vote("v4", "c", ValidationResult::Partial, 0.6),
+            make_vote("v5", "c", ValidationResult::Fail, 0.2),
+        ];
+
+        let explanation = explain_consensus(&config, &votes);
+
+        assert_eq!(explanation.leading, ValidationResult::Pass);
+        assert!((explanation.agreement - 0.6).abs() < f32::EPSILON);
+        assert_eq!(explanation.votes_needed, 4);
+        assert!(explanation.quorum_met);
+        assert!(!explanation.reached);
+        assert_eq!(explanation.outliers, vec!["v4", "v5"]);
+
+        // Agrees with the consensus engine itself
+        let mut consensus = ByzantineConsensus::new(config);
+        for vote in votes {
+            consensus.add_vote(vote);
+        }
+        assert!(matches!(
+            consensus.reach_consensus(&"c".to_string()),
+            ConsensusResult::NoConsensus { .. }
+        ));
+    }
+
+    #[test]
+    fn test_explain_below_quorum() {
+        let votes = vec![
+            make_vote("v1", "c", ValidationResult::Pass, 0.9),
+            make_vote("v2", "c", ValidationResult::Pass, 0.9),
+        ];
+
+        let explanation = explain_consensus(&ConsensusConfig::default(), &votes);
+
+        assert!((explanation.agreement - 1.0).abs() < f32::EPSILON);
+        assert!(!explanation.quorum_met);
+        assert!(!explanation.reached);
+    }
+
+    fn vote_stream(results: Vec<ValidationResult>) -> impl futures::Stream<Item = Vote> {
+        let votes: Vec<Vote> = results
+            .into_iter()
+            .enumerate()
+            .map(|(i, r)| make_vote(&format!("v{}", i + 1), "c", r, 0.8))
+            .collect();
+        // Remaining validators never answer
+        futures::stream::iter(votes).chain(futures::stream::pending())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_collect_votes_early_accept() {
+        let mut consensus = ByzantineConsensus::default();
+        let votes = vote_stream(vec![ValidationResult::Pass; 3]);
+
+        let result = consensus
+            .collect_votes(&"c".to_string(), 4, votes, Duration::from_secs(60))
+            .await;
+
+        assert!(matches!(result, ConsensusResult::Pass { votes_for: 3, .. }));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_collect_votes_early_reject() {
+        let mut consensus = ByzantineConsensus::default();
+        let votes = vote_stream(vec![
+            ValidationResult::Fail,
+            ValidationResult::Pass,
+            ValidationResult::Fail,
+            ValidationResult::Fail,
+        ]);
+
+        let result = consensus
+            .collect_votes(&"c".to_string(), 4, votes, Duration::from_secs(60))
+            .await;
+
+        assert!(matches!(
+            result,
+            ConsensusResult::Fail {
+                votes_for: 1,
+                votes_against: 3,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_collect_votes_timeout() {
+        let mut consensus = ByzantineConsensus::default();
+        let votes = vote_stream(vec![ValidationResult::Pass, ValidationResult::Pass]);
+
+        let started = tokio::time::Instant::now();
+        let result = consensus
+            .collect_votes(&"c".to_string(), 4, votes, Duration::from_secs(30))
+            .await;
+
+        assert!(started.elapsed() >= Duration::from_secs(30));
+        assert!(matches!(
+            result,
+            ConsensusResult::Inconclusive {
+                votes_for: 2,
+                received: 2,
+                expected: 4,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_early_no_consensus_when_split() {
+        let mut consensus = ByzantineConsensus::default();
+        consensus.add_vote(make_vote("v1", "c", ValidationResult::Pass, 0.9));
+        consensus.add_vote(make_vote("v2", "c", ValidationResult::Fail, 0.2));
+        let change = "c".to_string();
+
+        // 4 expected: either side can still reach 3
+        assert!(consensus.try_early_decision(&change, 4).is_none());
+
+        consensus.add_vote(make_vote("v3", "c", ValidationResult::Partial, 0.5));
+        assert!(matches!(
+            consensus.try_early_decision(&change, 4),
+            Some(ConsensusResult::NoConsensus { .. })
+        ));
+    }
+
+    #[test]
+    fn test_weighted_honest_minority_outvotes_faulty_majority() {
+        let config = ConsensusConfig {
+            max_weight: 5.0,
+            ..Default::default()
+        };
+        let mut consensus = ByzantineConsensus::new(config.clone());
+        let weighted = |id: &str, result: ValidationResult, weight: f64| Vote {
+            weight,
+            ..make_vote(id, "c", result, 0.5)
+        };
+
+        // Two trusted validators pass; three low-weight validators fail
+        consensus.add_vote(weighted("trusted-1", ValidationResult::Pass, 5.0));
+        consensus.add_vote(weighted("trusted-2", ValidationResult::Pass, 5.0));
+        consensus.add_vote(weighted("v3", ValidationResult::Fail, 1.0));
+        consensus.add_vote(weighted("v4", ValidationResult::Fail, 1.0));
+        consensus.add_vote(weighted("v5", ValidationResult::Fail, 1.0));
+
+        // 10 of 13 weight passes the 0.75 threshold despite a 2-3 headcount
+        assert!(matches!(
+            consensus.reach_consensus(&"c".to_string()),
+            ConsensusResult::Pass {
+                votes_for: 2,
+                votes_against: 3,
+                ..
+            }
+        ));
+        let explanation = consensus.explain(&"c".to_string());
+        assert!(explanation.reached);
+        assert_eq!(explanation.total_weight, 13.0);
+        assert_eq!(explanation.leading_weight, 10.0);
+        assert_eq!(explanation.weight_needed, 9.75);
+
+        // Weights above max_weight are capped, so one validator can't dominate
+        let mut capped = ByzantineConsensus::new(config);
+        capped.add_vote(weighted("loud", ValidationResult::Pass, 100.0));
+        for id in ["v2", "v3", "v4"] {
+            capped.add_vote(weighted(id, ValidationResult::Fail, 1.0));
+        }
+        assert!(matches!(
+            capped.reach_consensus(&"c".to_string()),
+            ConsensusResult::NoConsensus { .. }
+        ));
+    }
+
+    #[test]
+    fn test_weights_do_not_bypass_quorum() {
+        let mut consensus = ByzantineConsensus::new(ConsensusConfig {
+            max_weight: 10.0,
+            ..Default::default()
+        });
+        for id in ["v1", "v2", "v3"] {
+            consensus.add_vote(Vote {
+                weight: 10.0,
+                ..make_vote(id, "c", ValidationResult::Pass, 0.9)
+            });
+        }
+
+        assert!(matches!(
+            consensus.reach_consensus(&"c".to_string()),
+            ConsensusResult::InsufficientValidators { have: 3, need: 4 }
+        ));
+    }
 }
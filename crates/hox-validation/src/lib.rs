@@ -12,5 +12,11 @@
 mod consensus;
 mod validator;
 
-pub use consensus::{ByzantineConsensus, ConsensusConfig, ConsensusResult, Vote};
-pub use validator::{ValidationReport, ValidationResult, Validator, ValidatorConfig};
+pub use consensus::{
+    explain_consensus, ByzantineConsensus, ConsensusConfig, ConsensusExplanation, ConsensusResult,
+    Vote,
+};
+pub use validator::{
+    default_dimension_weights, ValidationCheck, ValidationReport, ValidationResult, Validator,
+    ValidatorConfig,
+};
@@ -2,6 +2,7 @@
 
 use hox_core::{ChangeId, Result, ScoringWeights};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// Configuration for a validator
@@ -13,6 +14,24 @@ pub struct ValidatorConfig {
     pub checks: Vec<ValidationCheck>,
     /// Scoring weights
     pub weights: ScoringWeights,
+    /// Weight of each dimension in the aggregate score
+    ///
+    /// When empty, or when no dimension in a report has a weight, the
+    /// aggregate falls back to `weights`.
+    pub dimension_weights: HashMap<String, f64>,
+}
+
+/// Default dimension weights: correctness 0.4, tests 0.3, style 0.2, docs 0.1
+pub fn default_dimension_weights() -> HashMap<String, f64> {
+    [
+        ("correctness", 0.4),
+        ("tests", 0.3),
+        ("style", 0.2),
+        ("docs", 0.1),
+    ]
+    .into_iter()
+    .map(|(name, weight)| (name.to_string(), weight))
+    .collect()
 }
 
 impl Default for ValidatorConfig {
@@ -25,6 +44,7 @@ impl Default for ValidatorConfig {
                 ValidationCheck::MutationCompliance,
             ],
             weights: ScoringWeights::default(),
+            dimension_weights: default_dimension_weights(),
         }
     }
 }
@@ -46,6 +66,21 @@ pub enum ValidationCheck {
     Custom(String),
 }
 
+impl ValidationCheck {
+    /// Scoring dimension this check contributes to
+    ///
+    /// Custom checks report under their own name (e.g. `Custom("docs")`
+    /// scores the `docs` dimension).
+    pub fn dimension(&self) -> &str {
+        match self {
+            Self::Compilation | Self::MutationCompliance | Self::ContractAdherence => "correctness",
+            Self::Tests => "tests",
+            Self::CodeQuality => "style",
+            Self::Custom(name) => name,
+        }
+    }
+}
+
 /// Artifact metadata for validation results
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationArtifactRef {
@@ -98,6 +133,9 @@ pub struct ValidationReport {
     pub time: f32,
     /// Efficiency score
     pub efficiency: f32,
+    /// Per-dimension scores (0.0 - 1.0), e.g. correctness, tests, style, docs
+    #[serde(default)]
+    pub dimensions: HashMap<String, f64>,
     /// Additional notes
     pub notes: Vec<String>,
 }
@@ -114,6 +152,7 @@ impl ValidationReport {
             completeness: 0.0,
             time: 0.0,
             efficiency: 0.0,
+            dimensions: HashMap::new(),
             notes: Vec::new(),
         }
     }
@@ -137,6 +176,41 @@ impl ValidationReport {
             ValidationResult::Fail
         };
     }
+
+    /// Fill `dimensions` with the mean score of the checks in each dimension
+    pub fn calculate_dimensions(&mut self) {
+        let mut totals: HashMap<String, (f64, usize)> = HashMap::new();
+        for check in &self.checks {
+            let entry = totals
+                .entry(check.check.dimension().to_string())
+                .or_default();
+            entry.0 += check.score as f64;
+            entry.1 += 1;
+        }
+        self.dimensions = totals
+            .into_iter()
+            .map(|(name, (sum, count))| (name, sum / count as f64))
+            .collect();
+    }
+
+    /// Replace the aggregate score with the weighted mean of the dimensions
+    ///
+    /// Only dimensions that were scored and have a weight count. Leaves the
+    /// score unchanged if none do.
+    pub fn apply_dimension_weights(&mut self, weights: &HashMap<String, f64>) {
+        let (weighted, total) = self
+            .dimensions
+            .iter()
+            .filter_map(|(name, score)| weights.get(name).map(|w| (score, *w)))
+            .filter(|(_, weight)| *weight > 0.0)
+            .fold((0.0, 0.0), |(weighted, total), (score, weight)| {
+                (weighted + score * weight, total + weight)
+            });
+
+        if total > 0.0 {
+            self.score = (weighted / total) as f32;
+        }
+    }
 }
 
 /// Validator agent
@@ -170,6 +244,8 @@ impl Validator {
         report.efficiency = self.calculate_efficiency_score(&report);
 
         report.calculate_score(&self.config.weights);
+        report.calculate_dimensions();
+        report.apply_dimension_weights(&self.config.dimension_weights);
 
         Ok(report)
     }
@@ -325,4 +401,48 @@ mod tests {
         // Score should be: 0.35*0.8 + 0.30*1.0 + 0.20*0.9 + 0.15*0.7 = 0.865
         assert!((report.score - 0.865).abs() < 0.001);
     }
+
+    #[test]
+    fn test_dimension_scores_and_weighted_aggregate() {
+        let mut report = ValidationReport::new("validator-1", "change-1");
+        for (check, score) in [
+            (ValidationCheck::Compilation, 1.0),
+            (ValidationCheck::MutationCompliance, 0.6),
+            (ValidationCheck::Tests, 0.5),
+            (ValidationCheck::CodeQuality, 1.0),
+        ] {
+            report.add_check(CheckResult {
+                check,
+                passed: score > 0.5,
+                score,
+                details: String::new(),
+                artifacts: Vec::new(),
+            });
+        }
+
+        report.calculate_dimensions();
+        assert!((report.dimensions["correctness"] - 0.8).abs() < 1e-6);
+        assert!((report.dimensions["tests"] - 0.5).abs() < 1e-6);
+        assert!((report.dimensions["style"] - 1.0).abs() < 1e-6);
+        assert!(!report.dimensions.contains_key("docs"));
+
+        // docs wasn't scored, so only 0.4 + 0.3 + 0.2 of weight counts
+        report.apply_dimension_weights(&default_dimension_weights());
+        let expected = (0.4 * 0.8 + 0.3 * 0.5 + 0.2 * 1.0) / 0.9;
+        assert!((report.score as f64 - expected).abs() < 1e-6);
+
+        // No matching weights leaves the aggregate alone
+        report.score = 0.42;
+        report.apply_dimension_weights(&HashMap::new());
+        assert_eq!(report.score, 0.42);
+    }
+
+    #[test]
+    fn test_report_without_dimensions_deserializes() {
+        let mut value = serde_json::to_value(ValidationReport::new("v1", "c1")).unwrap();
+        value.as_object_mut().unwrap().remove("dimensions");
+
+        let report: ValidationReport = serde_json::from_value(value).unwrap();
+        assert!(report.dimensions.is_empty());
+    }
 }
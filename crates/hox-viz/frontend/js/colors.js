@@ -8,6 +8,9 @@ export const STATUS_COLORS = {
     active:    '#00ffff',  // Cyan (for phases)
 };
 
+// Color for nodes outside the focus filter
+export const DIMMED_COLOR = '#2a2a33';
+
 // Link type colors
 export const LINK_COLORS = {
     working_on: '#00ffff',
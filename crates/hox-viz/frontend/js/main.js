@@ -5,13 +5,35 @@ import { UI } from './colors.js';
 let currentState = null;
 let eventSource = null;
 let reconnectDelay = 1000;
+let lastEventId = null;
+
+// Focus filter from the page URL (e.g. /?agent=agent-3 or /?phase=2)
+function filterParams() {
+    const page = new URLSearchParams(window.location.search);
+    const params = new URLSearchParams();
+    for (const key of ['agent', 'phase']) {
+        if (page.get(key)) params.set(key, page.get(key));
+    }
+    return params;
+}
 
 function connect() {
     setConnectionStatus('connecting');
-    eventSource = new EventSource('/api/events');
+    const params = filterParams();
+    // The server only resends a full snapshot if we missed events
+    if (lastEventId !== null && currentState) {
+        params.set('last_event_id', lastEventId);
+    }
+    const query = params.toString();
+    eventSource = new EventSource(query ? `/api/events?${query}` : '/api/events');
+
+    eventSource.onopen = () => {
+        if (currentState) setConnectionStatus('connected');
+    };
 
     eventSource.addEventListener('state', (e) => {
         currentState = JSON.parse(e.data);
+        lastEventId = e.lastEventId || lastEventId;
         updateGraph(currentState);
         updateAll(currentState);
         setConnectionStatus('connected');
@@ -20,6 +42,7 @@ function connect() {
 
     eventSource.addEventListener('update', (e) => {
         const delta = JSON.parse(e.data);
+        lastEventId = e.lastEventId || lastEventId;
         if (currentState) {
             applyDeltaToState(currentState, delta);
         }
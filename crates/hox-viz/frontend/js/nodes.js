@@ -1,6 +1,6 @@
 // hox-viz custom Three.js node geometry factories
 // THREE loaded via UMD script tag (global)
-import { colorForStatus } from './colors.js';
+import { colorForStatus, DIMMED_COLOR } from './colors.js';
 
 /**
  * Create a text sprite label for a node.
@@ -182,6 +182,10 @@ function createTaskNode(node) {
  * @returns {THREE.Object3D}
  */
 export function createNodeObject(node) {
+    // Nodes outside the ?agent= / ?phase= focus are drawn muted
+    if (node.dimmed) {
+        node = { ...node, color: DIMMED_COLOR, glow_intensity: 0.05 };
+    }
     switch (node.node_type) {
         case 'agent':
             return createAgentNode(node);
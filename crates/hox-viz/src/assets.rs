@@ -10,12 +10,20 @@ use rust_embed::Embed;
 #[folder = "frontend/"]
 struct Asset;
 
+/// Entry point of the frontend, also served for unknown non-API paths
+pub const INDEX: &str = "index.html";
+
+/// Whether `path` is embedded
+pub fn exists(path: &str) -> bool {
+    Asset::get(path).is_some()
+}
+
 /// Fallback handler that serves embedded static files
 pub async fn static_handler(req: Request<Body>) -> Response<Body> {
     let path = req.uri().path().trim_start_matches('/');
 
     // Default to index.html for root
-    let path = if path.is_empty() { "index.html" } else { path };
+    let path = if path.is_empty() { INDEX } else { path };
 
     match Asset::get(path) {
         Some(content) => {
@@ -33,7 +41,7 @@ pub async fn static_handler(req: Request<Body>) -> Response<Body> {
         None => {
             // SPA fallback: serve index.html for any non-API, non-file path
             if !path.contains('.') && !path.starts_with("api/") {
-                if let Some(content) = Asset::get("index.html") {
+                if let Some(content) = Asset::get(INDEX) {
                     return Response::builder()
                         .status(StatusCode::OK)
                         .header(header::CONTENT_TYPE, "text/html")
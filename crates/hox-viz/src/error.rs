@@ -0,0 +1,35 @@
+//! Errors surfaced by the visualization server
+
+use std::io;
+use thiserror::Error;
+
+/// Why the visualization server could not start or stopped serving
+#[derive(Error, Debug)]
+pub enum VizError {
+    /// The listen address could not be bound, e.g. the port is in use
+    #[error("Failed to bind visualization server: {0}")]
+    BindFailed(#[source] io::Error),
+
+    /// A configured host is not an IP address or valid hostname
+    #[error("Invalid {field} '{host}': {reason}")]
+    InvalidHost {
+        field: &'static str,
+        host: String,
+        reason: String,
+    },
+
+    /// The `jj` binary could not be run
+    #[error("jj is not available: {0}")]
+    JjUnavailable(#[source] io::Error),
+
+    /// A frontend asset was not embedded in the binary
+    #[error("Embedded asset missing: {0}")]
+    AssetMissing(String),
+
+    /// The server failed while serving requests
+    #[error("Visualization server failed: {0}")]
+    Serve(#[source] io::Error),
+}
+
+/// Result type for hox-viz operations
+pub type Result<T> = std::result::Result<T, VizError>;
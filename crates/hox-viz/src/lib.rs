@@ -4,19 +4,31 @@
 //! Serves a Three.js-based force-directed graph via an embedded Axum web server.
 
 mod assets;
+mod error;
 mod server;
 mod sse;
 mod state;
 
+pub use error::{Result, VizError};
+pub use server::VizServer;
 pub use state::{VizDelta, VizLink, VizNode, VizState, LinkType, NodeType};
 
+use std::net::IpAddr;
 use tracing::info;
 
 /// Configuration for the visualization server
+///
+/// With `open_browser: false` and `print_url_host` set, the server runs as a
+/// plain backend for remote clients or an embedding iframe.
 #[derive(Debug, Clone)]
 pub struct VizConfig {
-    /// Port to serve on
+    /// Host or IP address to bind to
+    pub bind_host: String,
+    /// Port to serve on; 0 picks a free port
     pub port: u16,
+    /// Host shown in the printed and opened URL, for when clients reach the
+    /// server under a different name than `bind_host`
+    pub print_url_host: Option<String>,
     /// Refresh interval in milliseconds for SSE updates
     pub refresh_ms: u64,
     /// Maximum oplog entries to track
@@ -28,7 +40,9 @@ pub struct VizConfig {
 impl Default for VizConfig {
     fn default() -> Self {
         Self {
+            bind_host: "0.0.0.0".to_string(),
             port: 7070,
+            print_url_host: None,
             refresh_ms: 500,
             max_oplog: 100,
             open_browser: true,
@@ -36,14 +50,80 @@ impl Default for VizConfig {
     }
 }
 
-/// Run the visualization server
-pub async fn run(config: VizConfig) -> anyhow::Result<()> {
-    let addr = format!("0.0.0.0:{}", config.port);
-    let url = format!("http://localhost:{}", config.port);
+impl VizConfig {
+    /// Check that `bind_host` and `print_url_host` are valid hosts
+    pub fn validate(&self) -> Result<()> {
+        validate_host("bind_host", &self.bind_host)?;
+        if let Some(host) = &self.print_url_host {
+            validate_host("print_url_host", host)?;
+        }
+        Ok(())
+    }
+
+    /// Host to show in URLs: the override, else `bind_host` unless it is a wildcard
+    fn url_host(&self) -> &str {
+        match &self.print_url_host {
+            Some(host) => host,
+            None => match self.bind_host.parse::<IpAddr>() {
+                Ok(ip) if ip.is_unspecified() => "localhost",
+                _ => &self.bind_host,
+            },
+        }
+    }
+}
+
+/// Accept an IP address or an RFC 1123 hostname
+fn validate_host(field: &'static str, host: &str) -> Result<()> {
+    let invalid = |reason: &str| VizError::InvalidHost {
+        field,
+        host: host.to_string(),
+        reason: reason.to_string(),
+    };
+
+    if host.parse::<IpAddr>().is_ok() {
+        return Ok(());
+    }
+    if host.is_empty() {
+        return Err(invalid("host cannot be empty"));
+    }
+    if host.len() > 253 {
+        return Err(invalid("hostname is longer than 253 characters"));
+    }
+    for label in host.split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(invalid("each hostname label must be 1-63 characters"));
+        }
+        if !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(invalid(
+                "expected an IP address or a hostname of letters, digits, '-' and '.'",
+            ));
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return Err(invalid("hostname labels cannot start or end with '-'"));
+        }
+    }
+    Ok(())
+}
 
-    info!("Starting hox-viz server on {}", addr);
+/// `host:port`, bracketing IPv6 literals
+fn host_port(host: &str, port: u16) -> String {
+    match host.parse::<IpAddr>() {
+        Ok(IpAddr::V6(ip)) => format!("[{}]:{}", ip, port),
+        _ => format!("{}:{}", host, port),
+    }
+}
 
+/// Run the visualization server
+///
+/// With `port: 0` the server binds to a free port; the printed URL and the
+/// browser use the port actually bound. Use [`VizServer`] directly to learn
+/// the port before serving.
+pub async fn run(config: VizConfig) -> Result<()> {
     let open_browser = config.open_browser;
+    let server = VizServer::bind(config).await?;
+    let url = server.url();
+
+    info!("Starting hox-viz server on {}", server.local_addr());
 
     // Spawn browser opener
     if open_browser {
@@ -59,5 +139,75 @@ pub async fn run(config: VizConfig) -> anyhow::Result<()> {
     println!("Hox Viz running at {}", url);
     println!("Press Ctrl+C to stop");
 
-    server::serve(config, &addr).await
+    server.serve().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(bind_host: &str, print_url_host: Option<&str>) -> VizConfig {
+        VizConfig {
+            bind_host: bind_host.to_string(),
+            print_url_host: print_url_host.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_accepts_ips_and_hostnames() {
+        for host in [
+            "0.0.0.0",
+            "127.0.0.1",
+            "::",
+            "::1",
+            "localhost",
+            "viz.example-1.com",
+        ] {
+            assert!(config(host, None).validate().is_ok(), "{}", host);
+        }
+        assert!(config("0.0.0.0", Some("viz.internal")).validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_hosts() {
+        for host in [
+            "",
+            "http://host",
+            "host:7070",
+            "a..b",
+            "-host",
+            "has space",
+            "[::1]",
+        ] {
+            let err = config(host, None).validate().unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    VizError::InvalidHost {
+                        field: "bind_host",
+                        ..
+                    }
+                ),
+                "{}: {:?}",
+                host,
+                err
+            );
+        }
+        let err = config("0.0.0.0", Some("bad/host")).validate().unwrap_err();
+        assert!(err.to_string().contains("print_url_host"), "{}", err);
+    }
+
+    #[test]
+    fn test_url_host() {
+        assert_eq!(config("0.0.0.0", None).url_host(), "localhost");
+        assert_eq!(config("::", None).url_host(), "localhost");
+        assert_eq!(config("10.0.0.5", None).url_host(), "10.0.0.5");
+        assert_eq!(
+            config("0.0.0.0", Some("viz.example.com")).url_host(),
+            "viz.example.com"
+        );
+        assert_eq!(host_port("::1", 7070), "[::1]:7070");
+        assert_eq!(host_port("localhost", 7070), "localhost:7070");
+    }
 }
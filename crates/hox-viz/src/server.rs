@@ -1,70 +1,267 @@
 //! Axum web server for the visualization
 
-use crate::{sse, state, VizConfig};
+use crate::{sse, state, VizConfig, VizError};
 use axum::{
-    extract::State,
+    extract::{Query, State},
     http::StatusCode,
     response::Json,
     routing::get,
     Router,
 };
+use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Instant;
+use tokio::net::TcpListener;
 use tower_http::cors::CorsLayer;
 
 /// Shared application state
 pub struct AppState {
     pub config: VizConfig,
-    pub current_state: RwLock<Option<state::VizState>>,
     pub data_source: hox_dashboard::JjDataSource,
+    /// Single source of truth for both `/api/state` and the SSE stream
+    pub hub: Arc<sse::VizHub>,
+    pub started_at: Instant,
 }
 
 pub type SharedState = Arc<AppState>;
 
-/// Serve the visualization
-pub async fn serve(config: VizConfig, addr: &str) -> anyhow::Result<()> {
-    let dashboard_config = hox_dashboard::DashboardConfig {
-        refresh_ms: config.refresh_ms,
-        max_oplog_entries: config.max_oplog,
-        local_time: true,
-        metrics_path: None,
-    };
-
-    let app_state = Arc::new(AppState {
-        config,
-        current_state: RwLock::new(None),
-        data_source: hox_dashboard::JjDataSource::new(dashboard_config),
-    });
-
-    let app = Router::new()
+impl AppState {
+    pub fn new(config: VizConfig) -> SharedState {
+        let dashboard_config = hox_dashboard::DashboardConfig {
+            refresh_ms: config.refresh_ms,
+            max_oplog_entries: config.max_oplog,
+            local_time: true,
+            metrics_path: None,
+        };
+
+        Arc::new(Self {
+            config,
+            data_source: hox_dashboard::JjDataSource::new(dashboard_config),
+            hub: Arc::new(sse::VizHub::new()),
+            started_at: Instant::now(),
+        })
+    }
+}
+
+/// Build the HTTP router
+pub fn router(app_state: SharedState) -> Router {
+    Router::new()
         .route("/api/state", get(get_state))
         .route("/api/events", get(sse::sse_handler))
         .route("/api/health", get(health))
         .fallback(crate::assets::static_handler)
         .layer(CorsLayer::permissive())
-        .with_state(app_state);
+        .with_state(app_state)
+}
 
-    let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
-    Ok(())
+/// A visualization server bound to its listen address but not yet serving
+///
+/// Binding first lets callers learn the actual port before serving, which
+/// matters when `VizConfig::port` is 0 and the OS picks a free one.
+pub struct VizServer {
+    config: VizConfig,
+    listener: TcpListener,
+    local_addr: SocketAddr,
 }
 
-/// GET /api/state - Returns full current state
-async fn get_state(State(app): State<SharedState>) -> Result<Json<state::VizState>, StatusCode> {
-    match app.data_source.fetch_state().await {
-        Ok(dashboard_state) => {
-            let viz_state = state::translate(&dashboard_state);
-            *app.current_state.write().await = Some(viz_state.clone());
-            Ok(Json(viz_state))
+impl VizServer {
+    /// Bind to `config.bind_host` and `config.port`; port 0 binds to a free port
+    ///
+    /// Fails with [`VizError::InvalidHost`] if the configured hosts are invalid.
+    pub async fn bind(config: VizConfig) -> crate::Result<Self> {
+        config.validate()?;
+        let addr = crate::host_port(&config.bind_host, config.port);
+        Self::bind_addr(config, &addr).await
+    }
+
+    /// Bind to `addr`, failing if the frontend was not embedded
+    pub async fn bind_addr(config: VizConfig, addr: &str) -> crate::Result<Self> {
+        if !crate::assets::exists(crate::assets::INDEX) {
+            return Err(VizError::AssetMissing(crate::assets::INDEX.to_string()));
         }
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(VizError::BindFailed)?;
+        let local_addr = listener.local_addr().map_err(VizError::BindFailed)?;
+
+        Ok(Self {
+            config,
+            listener,
+            local_addr,
+        })
+    }
+
+    /// Address the server is listening on
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Port the server is listening on
+    pub fn port(&self) -> u16 {
+        self.local_addr.port()
+    }
+
+    /// URL clients should use, built from `print_url_host` when set
+    pub fn url(&self) -> String {
+        format!(
+            "http://{}",
+            crate::host_port(self.config.url_host(), self.port())
+        )
+    }
+
+    /// Serve until the server fails; fails first if `jj` cannot be run
+    pub async fn serve(self) -> crate::Result<()> {
+        check_jj().await?;
+
+        let app_state = AppState::new(self.config);
+        tokio::spawn(sse::run_poller(app_state.clone()));
+
+        let app = router(app_state);
+        axum::serve(self.listener, app)
+            .await
+            .map_err(VizError::Serve)
+    }
+}
+
+/// Make sure the `jj` binary the data source shells out to can be run
+async fn check_jj() -> crate::Result<()> {
+    tokio::process::Command::new("jj")
+        .arg("--version")
+        .output()
+        .await
+        .map(|_| ())
+        .map_err(VizError::JjUnavailable)
+}
+
+/// GET /api/state - Returns the latest published state, narrowed by `?agent=` / `?phase=`
+///
+/// Reads the same snapshot the SSE stream resyncs from, so the two never
+/// diverge. Returns 503 until the poller has published once.
+async fn get_state(
+    State(app): State<SharedState>,
+    Query(filter): Query<state::NodeFilter>,
+) -> Result<Json<state::VizState>, StatusCode> {
+    match app.hub.snapshot() {
+        Some((_, viz_state)) => Ok(Json(filter.apply(&viz_state))),
+        None => Err(StatusCode::SERVICE_UNAVAILABLE),
     }
 }
 
-/// GET /api/health
-async fn health() -> Json<serde_json::Value> {
+/// GET /api/health - Server status and uptime
+async fn health(State(app): State<SharedState>) -> Json<serde_json::Value> {
+    let last_event_id = app.hub.snapshot().map(|(id, _)| id);
     Json(serde_json::json!({
-        "status": "ok",
-        "service": "hox-viz"
+        "status": if last_event_id.is_some() { "ok" } else { "starting" },
+        "service": "hox-viz",
+        "uptime_ms": app.started_at.elapsed().as_millis() as u64,
+        "last_event_id": last_event_id,
+        "subscribers": app.hub.subscriber_count()
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{NodeFilter, VizMetrics, VizSession, VizState};
+
+    fn viz_state(total_tool_calls: u32) -> VizState {
+        VizState {
+            session: VizSession::default(),
+            metrics: VizMetrics {
+                total_tool_calls,
+                ..Default::default()
+            },
+            nodes: vec![],
+            links: vec![],
+            phases: vec![],
+            oplog: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_state_unavailable_before_first_poll() {
+        let app = AppState::new(VizConfig::default());
+        let result = get_state(State(app), Query(NodeFilter::default())).await;
+        assert_eq!(result.unwrap_err(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_state_matches_published_snapshot() {
+        let app = AppState::new(VizConfig::default());
+        app.hub.publish(viz_state(3), None, true);
+        app.hub.publish(viz_state(7), Some(&viz_state(3)), false);
+
+        let Json(state) = get_state(State(app.clone()), Query(NodeFilter::default()))
+            .await
+            .unwrap();
+        assert_eq!(state.metrics.total_tool_calls, 7);
+    }
+
+    #[tokio::test]
+    async fn test_health_reports_uptime_and_last_event() {
+        let app = AppState::new(VizConfig::default());
+        let Json(body) = health(State(app.clone())).await;
+        assert_eq!(body["status"], "starting");
+        assert!(body["last_event_id"].is_null());
+        assert!(body["uptime_ms"].is_u64());
+
+        app.hub.publish(viz_state(0), None, true);
+        let Json(body) = health(State(app)).await;
+        assert_eq!(body["status"], "ok");
+        assert_eq!(body["last_event_id"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_bind_reports_bind_failure() {
+        let occupied = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = occupied.local_addr().unwrap().to_string();
+
+        let err = VizServer::bind_addr(VizConfig::default(), &addr)
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(err, VizError::BindFailed(_)), "{:?}", err);
+    }
+
+    #[tokio::test]
+    async fn test_auto_port_binds_distinct_free_ports() {
+        let config = VizConfig {
+            bind_host: "127.0.0.1".to_string(),
+            port: 0,
+            ..Default::default()
+        };
+
+        let first = VizServer::bind(config.clone()).await.unwrap();
+        let second = VizServer::bind(config).await.unwrap();
+
+        assert_ne!(first.port(), 0);
+        assert_ne!(second.port(), 0);
+        assert_ne!(first.port(), second.port());
+        assert_eq!(first.url(), format!("http://127.0.0.1:{}", first.port()));
+    }
+
+    #[tokio::test]
+    async fn test_url_uses_print_host_and_rejects_bad_bind_host() {
+        let server = VizServer::bind(VizConfig {
+            bind_host: "127.0.0.1".to_string(),
+            port: 0,
+            print_url_host: Some("viz.example.com".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            server.url(),
+            format!("http://viz.example.com:{}", server.port())
+        );
+
+        let err = VizServer::bind(VizConfig {
+            bind_host: "not a host".to_string(),
+            ..Default::default()
+        })
+        .await
+        .err()
+        .unwrap();
+        assert!(matches!(err, VizError::InvalidHost { .. }), "{:?}", err);
+    }
+}
@@ -1,83 +1,270 @@
 //! Server-Sent Events endpoint for real-time updates
+//!
+//! A single poller publishes state through a [`VizHub`]. Each SSE subscriber
+//! gets its own bounded buffer: when a slow client fills it, further deltas
+//! are dropped and the client is sent a full snapshot once it catches up.
+//! Every event carries a monotonically increasing id, so a reconnecting
+//! client can send `Last-Event-ID` and is only resynced if it missed updates.
 
 use crate::{
     server::SharedState,
-    state::{self, VizState},
+    state::{self, NodeFilter, VizDelta, VizState},
 };
 use axum::{
-    extract::State,
+    extract::{Query, State},
+    http::HeaderMap,
     response::sse::{Event, KeepAlive, Sse},
 };
-use futures::stream::Stream;
+use futures::stream::{self, Stream, StreamExt};
+use serde::Deserialize;
 use std::convert::Infallible;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
 
-/// SSE handler - streams state updates to the frontend
-pub async fn sse_handler(
-    State(app): State<SharedState>,
-) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
-    let refresh_ms = app.config.refresh_ms;
-
-    let stream = async_stream::stream! {
-        let mut last_state: Option<VizState> = None;
-        let mut tick_count: u64 = 0;
-        let resync_interval = 5000 / refresh_ms.max(1); // Full resync every ~5s
-
-        loop {
-            let dashboard_result = app.data_source.fetch_state().await;
-
-            match dashboard_result {
-                Ok(dashboard_state) => {
-                    let viz_state = state::translate(&dashboard_state);
-
-                    // Full snapshot on first connect or every resync_interval
-                    if last_state.is_none() || tick_count % resync_interval == 0 {
-                        if let Ok(json) = serde_json::to_string(&viz_state) {
-                            yield Ok(Event::default().event("state").data(json));
-                        }
-                    } else if let Some(ref old) = last_state {
-                        // Send delta
-                        let delta = state::compute_delta(old, &viz_state);
-                        if !delta.changed_nodes.is_empty()
-                            || !delta.new_oplog.is_empty()
-                            || !delta.changed_phases.is_empty()
-                        {
-                            if let Ok(json) = serde_json::to_string(&delta) {
-                                yield Ok(Event::default().event("update").data(json));
-                            }
-                        }
-
-                        // Send individual oplog entries for immediate effects
-                        for entry in &delta.new_oplog {
-                            if let Ok(json) = serde_json::to_string(entry) {
-                                yield Ok(Event::default().event("oplog").data(json));
-                            }
-                        }
-                    }
+/// Messages buffered per subscriber before deltas start being dropped
+const SUBSCRIBER_BUFFER: usize = 32;
+
+/// Full resync interval, in milliseconds
+const RESYNC_INTERVAL_MS: u64 = 5000;
+
+/// A message delivered to one subscriber
+#[derive(Debug, Clone)]
+pub enum VizMessage {
+    /// Full state as of event `id`
+    Snapshot { id: u64, state: VizState },
+    /// Incremental update (its `event_id` is the event id)
+    Delta(VizDelta),
+}
+
+impl VizMessage {
+    /// Event id of the message
+    pub fn id(&self) -> u64 {
+        match self {
+            Self::Snapshot { id, .. } => *id,
+            Self::Delta(delta) => delta.event_id,
+        }
+    }
+}
+
+struct Subscriber {
+    tx: mpsc::Sender<VizMessage>,
+    lagged: Arc<AtomicBool>,
+}
+
+#[derive(Default)]
+struct HubInner {
+    last_id: u64,
+    latest: Option<VizState>,
+    subscribers: Vec<Subscriber>,
+}
+
+/// Fans state updates out to SSE subscribers
+#[derive(Default)]
+pub struct VizHub {
+    inner: Mutex<HubInner>,
+}
+
+/// A subscriber's view of the hub
+pub struct Subscription {
+    rx: mpsc::Receiver<VizMessage>,
+    lagged: Arc<AtomicBool>,
+}
+
+impl VizHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Latest published state and its event id
+    pub fn snapshot(&self) -> Option<(u64, VizState)> {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.latest.clone().map(|state| (inner.last_id, state))
+    }
+
+    /// Register a subscriber
+    ///
+    /// A client reconnecting with the id of the latest event gets no snapshot;
+    /// anyone else (new, or behind) starts with one.
+    pub fn subscribe(&self, last_event_id: Option<u64>) -> Subscription {
+        let (tx, rx) = mpsc::channel(SUBSCRIBER_BUFFER);
+        let lagged = Arc::new(AtomicBool::new(false));
 
-                    *app.current_state.write().await = Some(viz_state.clone());
-                    last_state = Some(viz_state);
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(state) = &inner.latest {
+            if last_event_id != Some(inner.last_id) {
+                let snapshot = VizMessage::Snapshot {
+                    id: inner.last_id,
+                    state: state.clone(),
+                };
+                // Fresh channel, so this cannot be full
+                let _ = tx.try_send(snapshot);
+            }
+        }
+        inner.subscribers.push(Subscriber {
+            tx,
+            lagged: lagged.clone(),
+        });
+
+        Subscription { rx, lagged }
+    }
+
+    /// Publish a new state, sending each subscriber a delta from `previous`
+    ///
+    /// With no previous state (or when `resync` is set) a full snapshot is
+    /// sent instead. Returns the event id assigned.
+    pub fn publish(&self, state: VizState, previous: Option<&VizState>, resync: bool) -> u64 {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.last_id += 1;
+        let id = inner.last_id;
+
+        let message = match previous {
+            Some(previous) if !resync => {
+                let mut delta = state::compute_delta(previous, &state);
+                delta.event_id = id;
+                VizMessage::Delta(delta)
+            }
+            _ => VizMessage::Snapshot {
+                id,
+                state: state.clone(),
+            },
+        };
+        inner.latest = Some(state);
+
+        inner.subscribers.retain(|sub| {
+            match sub.tx.try_send(message.clone()) {
+                Ok(()) => true,
+                Err(mpsc::error::TrySendError::Full(_)) => {
+                    // Drop this update; the subscriber resyncs from a snapshot
+                    sub.lagged.store(true, Ordering::Release);
+                    true
                 }
-                Err(_) => {
-                    // Send empty state on error
-                    let empty = VizState {
-                        session: Default::default(),
-                        metrics: Default::default(),
-                        nodes: vec![],
-                        links: vec![],
-                        phases: vec![],
-                        oplog: vec![],
-                    };
-                    if let Ok(json) = serde_json::to_string(&empty) {
-                        yield Ok(Event::default().event("state").data(json));
+                Err(mpsc::error::TrySendError::Closed(_)) => false,
+            }
+        });
+
+        id
+    }
+
+    /// Number of connected subscribers
+    pub fn subscriber_count(&self) -> usize {
+        self.inner
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .subscribers
+            .len()
+    }
+}
+
+/// Stream a subscription's messages, replacing dropped deltas with a snapshot
+pub fn subscription_stream(
+    hub: Arc<VizHub>,
+    subscription: Subscription,
+) -> impl Stream<Item = VizMessage> {
+    let Subscription { rx, lagged } = subscription;
+
+    stream::unfold((rx, 0u64), move |(mut rx, mut last_sent)| {
+        let hub = hub.clone();
+        let lagged = lagged.clone();
+        async move {
+            loop {
+                let message = rx.recv().await?;
+
+                if lagged.swap(false, Ordering::AcqRel) {
+                    // Everything buffered is stale; jump straight to the latest state
+                    while rx.try_recv().is_ok() {}
+                    if let Some((id, state)) = hub.snapshot() {
+                        debug!("SSE subscriber fell behind, resyncing at event {}", id);
+                        last_sent = id;
+                        return Some((VizMessage::Snapshot { id, state }, (rx, last_sent)));
                     }
                 }
+
+                // Skip anything already covered by a snapshot we sent
+                if message.id() <= last_sent && last_sent != 0 {
+                    continue;
+                }
+                last_sent = message.id();
+                return Some((message, (rx, last_sent)));
+            }
+        }
+    })
+}
+
+/// Poll the data source and publish to the hub until the process exits
+pub async fn run_poller(app: SharedState) {
+    let refresh_ms = app.config.refresh_ms.max(1);
+    let resync_every = (RESYNC_INTERVAL_MS / refresh_ms).max(1);
+    let mut previous: Option<VizState> = None;
+    let mut tick: u64 = 0;
+
+    loop {
+        let viz_state = match app.data_source.fetch_state().await {
+            Ok(dashboard_state) => state::translate(&dashboard_state),
+            Err(e) => {
+                warn!("Failed to fetch dashboard state: {}", e);
+                // Send empty state on error
+                VizState {
+                    session: Default::default(),
+                    metrics: Default::default(),
+                    nodes: vec![],
+                    links: vec![],
+                    phases: vec![],
+                    oplog: vec![],
+                }
             }
+        };
 
-            tick_count += 1;
-            tokio::time::sleep(Duration::from_millis(refresh_ms)).await;
+        let resync = tick.is_multiple_of(resync_every);
+        app.hub
+            .publish(viz_state.clone(), previous.as_ref(), resync);
+        previous = Some(viz_state);
+
+        tick += 1;
+        tokio::time::sleep(Duration::from_millis(refresh_ms)).await;
+    }
+}
+
+/// Query parameters for `/api/events`
+#[derive(Debug, Default, Deserialize)]
+pub struct EventsQuery {
+    /// Resume point for clients that cannot set `Last-Event-ID`
+    pub last_event_id: Option<u64>,
+    /// Focus on a single agent
+    pub agent: Option<String>,
+    /// Focus on a single phase
+    pub phase: Option<usize>,
+}
+
+impl EventsQuery {
+    /// Node filter requested by the client
+    pub fn filter(&self) -> NodeFilter {
+        NodeFilter {
+            agent: self.agent.clone(),
+            phase: self.phase,
         }
-    };
+    }
+}
+
+/// SSE handler - streams state updates to the frontend
+pub async fn sse_handler(
+    State(app): State<SharedState>,
+    Query(query): Query<EventsQuery>,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+        .or(query.last_event_id);
+    let filter = query.filter();
+
+    let subscription = app.hub.subscribe(last_event_id);
+    let stream = subscription_stream(app.hub.clone(), subscription)
+        .map(move |message| filter_message(&filter, message))
+        .flat_map(|message| stream::iter(to_events(message).into_iter().map(Ok)));
 
     Sse::new(stream).keep_alive(
         KeepAlive::new()
@@ -85,3 +272,140 @@ pub async fn sse_handler(
             .text("ping"),
     )
 }
+
+/// Apply a client's node filter to a hub message
+fn filter_message(filter: &NodeFilter, message: VizMessage) -> VizMessage {
+    match message {
+        VizMessage::Snapshot { id, state } => VizMessage::Snapshot {
+            id,
+            state: filter.apply(&state),
+        },
+        VizMessage::Delta(mut delta) => {
+            filter.apply_to_delta(&mut delta);
+            VizMessage::Delta(delta)
+        }
+    }
+}
+
+/// Convert a hub message to SSE events
+fn to_events(message: VizMessage) -> Vec<Event> {
+    let id = message.id().to_string();
+    match message {
+        VizMessage::Snapshot { state, .. } => serde_json::to_string(&state)
+            .map(|json| vec![Event::default().event("state").id(id).data(json)])
+            .unwrap_or_default(),
+        VizMessage::Delta(delta) => {
+            let mut events = Vec::new();
+            if !delta.changed_nodes.is_empty()
+                || !delta.new_oplog.is_empty()
+                || !delta.changed_phases.is_empty()
+            {
+                if let Ok(json) = serde_json::to_string(&delta) {
+                    events.push(Event::default().event("update").id(id.clone()).data(json));
+                }
+            }
+
+            // Send individual oplog entries for immediate effects
+            for entry in &delta.new_oplog {
+                if let Ok(json) = serde_json::to_string(entry) {
+                    events.push(Event::default().event("oplog").data(json));
+                }
+            }
+            events
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{VizMetrics, VizSession};
+
+    fn viz_state(total_tool_calls: u32) -> VizState {
+        VizState {
+            session: VizSession::default(),
+            metrics: VizMetrics {
+                total_tool_calls,
+                ..Default::default()
+            },
+            nodes: vec![],
+            links: vec![],
+            phases: vec![],
+            oplog: vec![],
+        }
+    }
+
+    fn publish_n(hub: &VizHub, from: u32, count: u32) {
+        for i in from..from + count {
+            let previous = viz_state(i.saturating_sub(1));
+            hub.publish(viz_state(i), (i > 0).then_some(&previous), false);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_event_ids_increase() {
+        let hub = Arc::new(VizHub::new());
+        let subscription = hub.subscribe(None);
+        publish_n(&hub, 0, 3);
+
+        let ids: Vec<u64> = subscription_stream(hub, subscription)
+            .take(3)
+            .map(|m| m.id())
+            .collect()
+            .await;
+
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_slow_consumer_resyncs_with_snapshot() {
+        let hub = Arc::new(VizHub::new());
+        let subscription = hub.subscribe(None);
+
+        // Overflow the subscriber's buffer without consuming anything
+        let total = SUBSCRIBER_BUFFER as u32 + 10;
+        publish_n(&hub, 0, total);
+        assert_eq!(hub.subscriber_count(), 1);
+
+        let mut stream = Box::pin(subscription_stream(hub.clone(), subscription));
+        match stream.next().await.unwrap() {
+            VizMessage::Snapshot { id, state } => {
+                assert_eq!(id, total as u64);
+                assert_eq!(state.metrics.total_tool_calls, total - 1);
+            }
+            other => panic!("expected snapshot, got {:?}", other),
+        }
+
+        // Back to deltas once caught up
+        publish_n(&hub, total, 1);
+        match stream.next().await.unwrap() {
+            VizMessage::Delta(delta) => assert_eq!(delta.event_id, total as u64 + 1),
+            other => panic!("expected delta, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_with_last_event_id() {
+        let hub = Arc::new(VizHub::new());
+        publish_n(&hub, 0, 2);
+
+        // Up to date: no snapshot
+        let mut current = hub.subscribe(Some(2));
+        assert!(current.rx.try_recv().is_err());
+
+        // Missed updates: snapshot at the latest id
+        let mut behind = hub.subscribe(Some(1));
+        assert!(matches!(
+            behind.rx.try_recv(),
+            Ok(VizMessage::Snapshot { id: 2, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_closed_subscribers_removed() {
+        let hub = VizHub::new();
+        drop(hub.subscribe(None));
+        publish_n(&hub, 0, 1);
+        assert_eq!(hub.subscriber_count(), 0);
+    }
+}
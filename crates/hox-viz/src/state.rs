@@ -38,9 +38,15 @@ pub struct VizNode {
     pub status: String,
     pub progress: f32,
     pub phase: Option<usize>,
+    /// Owning agent id (set on agent nodes)
+    #[serde(default)]
+    pub agent: Option<String>,
     pub color: String,
     pub glow_intensity: f32,
     pub details: serde_json::Value,
+    /// Outside the client's focus filter; rendered muted
+    #[serde(default)]
+    pub dimmed: bool,
 }
 
 /// A link between nodes
@@ -109,12 +115,98 @@ pub struct VizState {
 /// Delta update (sent between full syncs)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VizDelta {
+    /// Monotonically increasing event id, assigned when the delta is published
+    #[serde(default)]
+    pub event_id: u64,
     pub changed_nodes: Vec<VizNode>,
     pub new_oplog: Vec<VizOplogEntry>,
     pub metrics: VizMetrics,
     pub changed_phases: Vec<VizPhase>,
 }
 
+/// Focus filter from the page query string (`?agent=agent-3`, `?phase=2`)
+///
+/// Snapshots are narrowed to the matching nodes and their immediate
+/// neighbors; anything outside the match is marked `dimmed`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct NodeFilter {
+    pub agent: Option<String>,
+    pub phase: Option<usize>,
+}
+
+impl NodeFilter {
+    /// True if the filter matches everything
+    pub fn is_empty(&self) -> bool {
+        self.agent.is_none() && self.phase.is_none()
+    }
+
+    /// Whether a node is in focus
+    pub fn matches(&self, node: &VizNode) -> bool {
+        let agent_ok = self
+            .agent
+            .as_ref()
+            .is_none_or(|agent| node.agent.as_ref() == Some(agent));
+        let phase_ok = self.phase.is_none_or(|phase| node.phase == Some(phase));
+        agent_ok && phase_ok
+    }
+
+    /// Restrict a state to matching nodes plus their immediate neighbors
+    pub fn apply(&self, state: &VizState) -> VizState {
+        if self.is_empty() {
+            return state.clone();
+        }
+
+        let matched: HashSet<&str> = state
+            .nodes
+            .iter()
+            .filter(|n| self.matches(n))
+            .map(|n| n.id.as_str())
+            .collect();
+
+        let mut visible = matched.clone();
+        for link in &state.links {
+            if matched.contains(link.source.as_str()) {
+                visible.insert(link.target.as_str());
+            }
+            if matched.contains(link.target.as_str()) {
+                visible.insert(link.source.as_str());
+            }
+        }
+
+        let nodes = state
+            .nodes
+            .iter()
+            .filter(|n| visible.contains(n.id.as_str()))
+            .map(|n| VizNode {
+                dimmed: !matched.contains(n.id.as_str()),
+                ..n.clone()
+            })
+            .collect();
+        let links = state
+            .links
+            .iter()
+            .filter(|l| visible.contains(l.source.as_str()) && visible.contains(l.target.as_str()))
+            .cloned()
+            .collect();
+
+        VizState {
+            nodes,
+            links,
+            ..state.clone()
+        }
+    }
+
+    /// Mark nodes outside the filter as dimmed
+    pub fn apply_to_delta(&self, delta: &mut VizDelta) {
+        if self.is_empty() {
+            return;
+        }
+        for node in &mut delta.changed_nodes {
+            node.dimmed = !self.matches(node);
+        }
+    }
+}
+
 /// Convert AgentStatus to CSS color hex
 fn status_color(status: &AgentStatus) -> &'static str {
     match status {
@@ -196,6 +288,7 @@ pub fn translate(dashboard: &DashboardState) -> VizState {
             status: phase_status_str(&phase.status).to_string(),
             progress: phase.progress,
             phase: Some(phase.number),
+            agent: None,
             color: phase_color(&phase.status).to_string(),
             glow_intensity: if phase.status == PhaseStatus::Active {
                 0.6
@@ -206,6 +299,7 @@ pub fn translate(dashboard: &DashboardState) -> VizState {
                 "agent_count": phase.agent_ids.len(),
                 "blocking": phase.blocking,
             }),
+            dimmed: false,
         });
     }
 
@@ -220,6 +314,7 @@ pub fn translate(dashboard: &DashboardState) -> VizState {
             status: agent_status_str(&agent.status).to_string(),
             progress: agent.progress,
             phase: Some(agent.phase),
+            agent: Some(agent.id.clone()),
             color: color.clone(),
             glow_intensity: status_glow(&agent.status),
             details: serde_json::json!({
@@ -229,6 +324,7 @@ pub fn translate(dashboard: &DashboardState) -> VizState {
                 "task": agent.task,
                 "change_id": agent.change_id,
             }),
+            dimmed: false,
         });
 
         // Link agent to its phase
@@ -378,6 +474,7 @@ pub fn compute_delta(old: &VizState, new: &VizState) -> VizDelta {
         .collect();
 
     VizDelta {
+        event_id: 0,
         changed_nodes,
         new_oplog,
         metrics: new.metrics.clone(),
@@ -448,9 +545,11 @@ mod tests {
                 status: "running".into(),
                 progress: 0.5,
                 phase: Some(1),
+                agent: Some("a1".into()),
                 color: "#00ffff".into(),
                 glow_intensity: 0.8,
                 details: serde_json::json!({}),
+                dimmed: false,
             }],
             links: vec![],
             phases: vec![],
@@ -464,4 +563,87 @@ mod tests {
         assert_eq!(delta.changed_nodes.len(), 1);
         assert_eq!(delta.changed_nodes[0].progress, 0.8);
     }
+
+    fn filter_fixture() -> VizState {
+        let mut state = DashboardState::default();
+        state.agents.push(AgentNode::new("agent-1", "Builder", 1));
+        state.agents.push(AgentNode::new("agent-2", "Tester", 1));
+        state.agents.push(AgentNode::new("agent-3", "Reviewer", 2));
+        for number in [1, 2] {
+            state.phases.push(hox_dashboard::PhaseProgress {
+                number,
+                name: format!("Phase {}", number),
+                blocking: false,
+                status: PhaseStatus::Active,
+                progress: 0.0,
+                agent_ids: vec![],
+            });
+        }
+        translate(&state)
+    }
+
+    fn ids(state: &VizState) -> Vec<&str> {
+        let mut ids: Vec<&str> = state.nodes.iter().map(|n| n.id.as_str()).collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_filter_by_agent_keeps_neighbors() {
+        let filter = NodeFilter {
+            agent: Some("agent-3".into()),
+            phase: None,
+        };
+        let viz = filter.apply(&filter_fixture());
+
+        assert_eq!(ids(&viz), vec!["agent-3", "phase-2"]);
+        assert_eq!(viz.links.len(), 1);
+        let phase = viz.nodes.iter().find(|n| n.id == "phase-2").unwrap();
+        assert!(phase.dimmed);
+        let agent = viz.nodes.iter().find(|n| n.id == "agent-3").unwrap();
+        assert!(!agent.dimmed);
+    }
+
+    #[test]
+    fn test_filter_by_phase() {
+        let filter = NodeFilter {
+            agent: None,
+            phase: Some(1),
+        };
+        let viz = filter.apply(&filter_fixture());
+
+        // Phase 1, its agents, and phase 2 via the dependency link
+        assert_eq!(ids(&viz), vec!["agent-1", "agent-2", "phase-1", "phase-2"]);
+        assert!(viz
+            .nodes
+            .iter()
+            .filter(|n| n.dimmed)
+            .all(|n| n.id == "phase-2"));
+    }
+
+    #[test]
+    fn test_filter_dims_delta_nodes() {
+        let full = filter_fixture();
+        let filter = NodeFilter {
+            agent: Some("agent-1".into()),
+            phase: None,
+        };
+        let mut delta = compute_delta(
+            &VizState {
+                nodes: vec![],
+                ..full.clone()
+            },
+            &full,
+        );
+        filter.apply_to_delta(&mut delta);
+
+        for node in &delta.changed_nodes {
+            assert_eq!(node.dimmed, node.id != "agent-1", "{}", node.id);
+        }
+        assert!(NodeFilter::default()
+            .apply(&full)
+            .nodes
+            .iter()
+            .all(|n| !n.dimmed));
+    }
 }